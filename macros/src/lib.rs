@@ -0,0 +1,113 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn, LitStr, parse_macro_input};
+
+/// Turns a function into a test that runs in a fresh [`TestWorkspace`], with kernel-error-aware panic diagnostics
+/// and an optional timeout.  See `attestify_test_framework::nape_test` for details.
+///
+/// [`TestWorkspace`]: https://docs.rs/attestify_test_framework/latest/attestify_test_framework/workspace/struct.TestWorkspace.html
+#[proc_macro_attribute]
+pub fn nape_test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut timeout: Option<LitStr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("timeout") {
+            timeout = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported nape_test argument, expected `timeout = \"5s\"`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let function = parse_macro_input!(item as ItemFn);
+    match expand(function, timeout) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(function: ItemFn, timeout: Option<LitStr>) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "nape_test does not support async functions",
+        ));
+    }
+    if sig.inputs.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "a nape_test takes no arguments, or only a `&TestWorkspace`",
+        ));
+    }
+
+    let workspace = match sig.inputs.first() {
+        Some(FnArg::Typed(argument)) => {
+            let pattern = &argument.pat;
+            let ty = &argument.ty;
+            quote!(#pattern: #ty)
+        }
+        Some(receiver) => {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "a nape_test cannot take `self`",
+            ));
+        }
+        None => quote!(_: &::attestify_test_framework::workspace::TestWorkspace),
+    };
+
+    let name = &sig.ident;
+    let name_text = name.to_string();
+    let output = &sig.output;
+
+    let run = match timeout {
+        Some(timeout) => {
+            let millis = parse_duration_millis(&timeout.value()).ok_or_else(|| {
+                syn::Error::new(
+                    timeout.span(),
+                    "expected a duration such as \"500ms\", \"5s\", or \"2m\"",
+                )
+            })?;
+            quote! {
+                ::attestify_test_framework::nape_test::run_within(
+                    #name_text,
+                    ::std::time::Duration::from_millis(#millis),
+                    move |#workspace| #block,
+                )
+            }
+        }
+        None => quote! {
+            ::attestify_test_framework::nape_test::run(#name_text, move |#workspace| #block)
+        },
+    };
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #output {
+            #run
+        }
+    })
+}
+
+/// Parses a duration made of a whole number and a unit: `ms`, `s`, `m`, or `h`.
+fn parse_duration_millis(duration: &str) -> Option<u64> {
+    let duration = duration.trim();
+    let split = duration.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = duration.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let factor = match unit.trim() {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        _ => return None,
+    };
+    amount.checked_mul(factor)
+}
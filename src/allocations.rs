@@ -0,0 +1,170 @@
+//! Assertions on the allocation volume of a routine, such as report rendering, so a refactoring that makes it
+//! allocate far more fails a test.
+//!
+//! Allocations are counted by [`CountingAllocator`], which every test binary that uses the assertions installs as
+//! its global allocator once, with [`install_counting_allocator`](crate::install_counting_allocator):
+//!
+//! ```no_run
+//! use attestify_test_framework::{assert_allocates_less_than, install_counting_allocator};
+//! # fn render_report(activities: usize) -> String { "x".repeat(activities) }
+//!
+//! install_counting_allocator!();
+//!
+//! #[test]
+//! fn rendering_a_report_allocates_less_than_64_kib() {
+//!     let report = assert_allocates_less_than!(64 * 1024, || render_report(100));
+//!     assert!(!report.is_empty());
+//! }
+//! ```
+
+use crate::assertions::report::{self, MismatchReport};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the first allocation through a [`CountingAllocator`], so [`measure`] can tell it is not installed.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// The allocations of the measurement running on this thread, if any.
+    static MEASURED: Cell<Option<Allocations>> = const { Cell::new(None) };
+}
+
+/// The allocations made during a measurement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// The number of allocations, including reallocations.
+    pub count: usize,
+    /// The bytes allocated.  A reallocation counts the bytes it grew by, and freed bytes are not subtracted.
+    pub bytes: usize,
+}
+
+/// A global allocator that forwards to the [`System`] allocator and counts the allocations of the threads running
+/// a [`measure`].  Install it with [`install_counting_allocator`](crate::install_counting_allocator).
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(bytes: usize) {
+        let _ = MEASURED.try_with(|measured| {
+            if let Some(allocations) = measured.get() {
+                measured.set(Some(Allocations {
+                    count: allocations.count + 1,
+                    bytes: allocations.bytes + bytes,
+                }));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        Self::record(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        Self::record(layout.size());
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size.saturating_sub(layout.size()));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Runs the closure and returns its value with the allocations it made on the current thread.  Allocations of
+/// other threads, including those the closure spawns, are not counted.  Measurements can be nested, and the outer
+/// one includes the allocations of the inner one.
+///
+/// The test panics when no [`CountingAllocator`] is installed.
+pub fn measure<R>(work: impl FnOnce() -> R) -> (R, Allocations) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        drop(std::hint::black_box(Box::new(0u8)));
+    }
+    assert!(
+        INSTALLED.load(Ordering::Relaxed),
+        "The CountingAllocator is not the global allocator; add install_counting_allocator!() to the test binary."
+    );
+
+    let outer = MEASURED.replace(Some(Allocations::default()));
+    let value = work();
+    let measured = MEASURED.replace(outer).unwrap_or_default();
+    if let Some(outer) = outer {
+        MEASURED.set(Some(Allocations {
+            count: outer.count + measured.count,
+            bytes: outer.bytes + measured.bytes,
+        }));
+    }
+    (value, measured)
+}
+
+/// Checks the closure allocates fewer than `bytes` bytes, returning its value.  See
+/// [`check_allocates_less_than`](crate::check_allocates_less_than).
+pub fn check_allocates_less_than<R>(bytes: usize, work: impl FnOnce() -> R) -> Result<R, MismatchReport> {
+    let (value, allocations) = measure(work);
+    if allocations.bytes < bytes {
+        return Ok(value);
+    }
+    Err(
+        MismatchReport::new("allocations", "The expression allocated more than its allocation budget.")
+            .expected(format!("less than {} bytes", bytes))
+            .actual(format!("{} bytes in {} allocations", allocations.bytes, allocations.count)),
+    )
+}
+
+/// Asserts the closure allocates fewer than `bytes` bytes, returning its value.  See
+/// [`assert_allocates_less_than`](crate::assert_allocates_less_than).
+pub fn assert_allocates_less_than<R>(bytes: usize, work: impl FnOnce() -> R) -> R {
+    report::enforce(check_allocates_less_than(bytes, work))
+}
+
+/// Installs a [`CountingAllocator`](crate::allocations::CountingAllocator) as the global allocator of the test
+/// binary, which the allocation assertions require.  Use it once per binary, at the top level of a test file.
+#[macro_export]
+macro_rules! install_counting_allocator {
+    () => {
+        #[global_allocator]
+        static ATTESTIFY_COUNTING_ALLOCATOR: $crate::allocations::CountingAllocator =
+            $crate::allocations::CountingAllocator;
+    };
+}
+
+/// Asserts that a closure allocates fewer than a number of bytes on the current thread, and returns its value.
+///
+/// Requires the `allocations` feature, and a [`CountingAllocator`](crate::allocations::CountingAllocator)
+/// installed with [`install_counting_allocator`](crate::install_counting_allocator).
+///
+/// # Arguments
+///
+/// * `$bytes` - The allocation budget, which the allocated bytes must stay below.
+/// * `$closure` - The routine under test, e.g. `|| render_report(&procedure)`.
+///
+#[macro_export]
+macro_rules! assert_allocates_less_than {
+    ($bytes:expr, $closure:expr $(,)?) => {
+        $crate::allocations::assert_allocates_less_than($bytes, $closure)
+    };
+}
+
+/// Checks that a closure allocates fewer than a number of bytes, like
+/// [`assert_allocates_less_than`](crate::assert_allocates_less_than), returning its value, or a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$bytes` - The allocation budget, which the allocated bytes must stay below.
+/// * `$closure` - The routine under test.
+///
+#[macro_export]
+macro_rules! check_allocates_less_than {
+    ($bytes:expr, $closure:expr $(,)?) => {
+        $crate::allocations::check_allocates_less_than($bytes, $closure)
+    };
+}
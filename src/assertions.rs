@@ -1,3 +1,36 @@
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod channels;
+pub mod collections;
+pub mod config;
+pub mod coverage;
+pub mod digest;
+pub mod directory;
+pub mod futures;
+pub mod hygiene;
+pub mod json;
+pub mod markdown;
+pub mod messages;
+pub mod nrn;
+pub mod numeric;
+pub mod path;
+pub mod permissions;
+pub mod report;
+pub mod roundtrip;
+pub mod schema;
+pub mod sequences;
+pub mod structs;
+pub mod text;
+pub mod yaml;
+
+pub use config::{OutputConfig, config, reset_config, set_config};
+
+/// Applies the predicate to the value, so a closure passed to a macro has its argument type inferred.
+#[doc(hidden)]
+pub fn __satisfies<T>(value: &T, predicate: impl FnOnce(&T) -> bool) -> bool {
+    predicate(value)
+}
+
 /// Asserts that an [`nape_kernel::error::Error`] matches the expected kind, audience, and message.
 ///
 /// # Arguments
@@ -10,38 +43,9 @@
 #[macro_export]
 macro_rules! kernel_error_eq {
     ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
-        match $result {
-            Ok(val) => panic!(
-                "An Error was expected, although one was not returned:\n\t{:?}",
-                val
-            ),
-            Err(e) => {
-                assert_eq!(
-                    e.kind,
-                    $expected_kind,
-                    "{}",
-                    format!(
-                        "Kind does not match.\n\tExpected: {:?},\n\tActual: {:?}\n",
-                        $expected_kind, e.kind
-                    )
-                );
-                assert_eq!(
-                    e.audience,
-                    $expected_audience,
-                    "{}",
-                    format!(
-                        "Audience does not match.\n\tExpected: {:?}\n\tActual: {:?}\n",
-                        $expected_audience, e.audience
-                    )
-                );
-                if e.message != $expected_message {
-                    panic!(
-                        "The Error Message does not match.\n\tExpected:\t{:?},\n Actual:\t{:?}\n",
-                        $expected_message, e.message
-                    );
-                }
-            }
-        }
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_eq!($result, $expected_kind, $expected_audience, $expected_message)
+        )
     };
 }
 
@@ -62,37 +66,9 @@ macro_rules! kernel_error_eq {
 #[macro_export]
 macro_rules! kernel_error_has_message {
     ($result:expr, $expected_kind:expr, $expected_audience:expr) => {
-        match $result {
-            Ok(val) => panic!(
-                "An Error was expected, although one was not returned:\n\t{:?}",
-                val
-            ),
-            Err(e) => {
-                assert_eq!(
-                    e.kind,
-                    $expected_kind,
-                    "{}",
-                    format!(
-                        "Kind does not match.\n\tExpected: {:?},\n\tActual: {:?}\n",
-                        $expected_kind, e.kind
-                    )
-                );
-                assert_eq!(
-                    e.audience,
-                    $expected_audience,
-                    "{}",
-                    format!(
-                        "Audience does not match.\n\tExpected: {:?}\n\tActual: {:?}\n",
-                        $expected_audience, e.audience
-                    )
-                );
-                if e.message.len() == 0 {
-                    panic!(
-                        "The error message is empty.  A populated error message is expected.\n"
-                    );
-                }
-            }
-        }
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_has_message!($result, $expected_kind, $expected_audience)
+        )
     };
 }
 
@@ -107,17 +83,10 @@ macro_rules! kernel_error_has_message {
 ///
 #[macro_export]
 macro_rules! kernel_error_starts_with {
-    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr)=> {
-        match $result {
-            Ok(val) =>   panic!("An Error was expected, although one was not retured:\n\t{:?}", val),
-            Err(e) => {
-                assert_eq!(e.kind, $expected_kind,  "{}", format!("Kind does not match.\n\tExpected:\t{:?}\n\tActual:\t{:?}\n", $expected_kind, e.kind));
-                assert_eq!(e.audience, $expected_audience,  "{}", format!("Audience does not match.\n\tExpected:\t{:?}\n\tActual:\t{:?}\n ", $expected_audience, e.audience));
-                if !e.message.starts_with($expected_message) {
-                    panic!("The Error Message does not start with the expected phrase.\n\tExpected:\t{:?}\n\tActual:\t{:?}\n", $expected_message, e.message);
-                }
-            }
-        }
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_starts_with!($result, $expected_kind, $expected_audience, $expected_message)
+        )
     };
 }
 
@@ -132,17 +101,31 @@ macro_rules! kernel_error_starts_with {
 ///
 #[macro_export]
 macro_rules! kernel_error_contains {
-    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr)=> {
-        match $result {
-            Ok(val) =>   panic!("An Error was expected, although one was not retured:\n\t{:?}", val),
-            Err(e) => {
-                assert_eq!(e.kind, $expected_kind,  "{}", format!("Kind does not match.\n\tExpected: {:?},\n\tActual: {:?}\n", $expected_kind, e.kind));
-                assert_eq!(e.audience, $expected_audience,  "{}", format!("Audience does not match.\n\tExpected: {:?}\n\tActual: {:?}\n", $expected_audience, e.audience));
-                if !e.message.contains($expected_message) {
-                    panic!("The Error Message does not contains the expected phrase.\n\tExpected:\t{:?}\n\tActual:\t{:?}\n", $expected_message, e.message);
-                }
-            }
-        }
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_contains!($result, $expected_kind, $expected_audience, $expected_message)
+        )
+    };
+}
+
+/// Asserts that an [`nape_kernel::error::Error`] has the expected kind, audience, and the message is the localized
+/// message for a key in the installed [message catalog](crate::assertions::messages), instead of hard-coded text.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$key` - The key of the expected message, e.g. `"errors.evidence.missing"`.
+/// * `$name = $value` - Optional values of the message's `{name}` placeholders.  Placeholders with no value match
+///   any text.
+///
+#[macro_export]
+macro_rules! kernel_error_msg_key {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_msg_key!($result, $expected_kind, $expected_audience, $key $(, $name = $value)*)
+        )
     };
 }
 
@@ -156,13 +139,7 @@ macro_rules! kernel_error_contains {
 #[macro_export]
 macro_rules! is_ok {
     ($result:expr) => {
-        match $result {
-            Ok(val) => val,
-            Err(e) => panic!(
-                "An Ok was expected, although an Error was returned:\n\t{:?}",
-                e
-            ),
-        }
+        $crate::assertions::report::enforce($crate::check_is_ok!($result))
     };
 }
 
@@ -175,10 +152,762 @@ macro_rules! is_ok {
 ///
 #[macro_export]
 macro_rules! is_error {
+    ($result:expr) => {
+        $crate::assertions::report::enforce($crate::check_is_error!($result))
+    };
+}
+
+/// Asserts that a [`Result`] is an [`Ok`] whose value equals the expected value, and returns the value.
+/// If the result is an [`Err`], or the values differ, the test will panic with a diff of their `Debug` output.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Ok`].
+/// * `$expected` - The expected value, comparable to the [`Ok`] value with `==`.
+///
+#[macro_export]
+macro_rules! is_ok_eq {
+    ($result:expr, $expected:expr $(,)?) => {
+        $crate::assertions::report::enforce($crate::check_is_ok_eq!($result, $expected))
+    };
+}
+
+/// Asserts that a [`Result`] is an [`Ok`] whose value satisfies the predicate, and returns the value.
+/// If the result is an [`Err`], or the predicate returns false, the test will panic with the message and the
+/// value's `Debug` output.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Ok`].
+/// * `$predicate` - A closure given a reference to the value, returning whether it is as expected.
+/// * `$message` - Optional. What was expected of the value, with optional format arguments.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_ok_and;
+/// # fn list_activities() -> Result<Vec<&'static str>, String> { Ok(vec!["a", "b", "c"]) }
+///
+/// let activities = assert_ok_and!(list_activities(), |val| val.len() == 3, "expected 3 activities");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_ok_and {
+    ($result:expr, $predicate:expr $(,)?) => {
+        $crate::assertions::report::enforce($crate::check_ok_and!($result, $predicate))
+    };
+    ($result:expr, $predicate:expr, $($message:tt)+) => {
+        $crate::assertions::report::enforce($crate::check_ok_and!($result, $predicate, $($message)+))
+    };
+}
+
+/// Asserts that an [`nape_kernel::error::Error`] has the expected kind, audience, and the message matches a regular expression.
+///
+/// This is useful when the message embeds dynamic values such as timestamps, UUIDs, or file paths.  The pattern is
+/// not anchored; use `^` and `$` to match the whole message.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_pattern` - The regular expression the message must match. Should be a `&str`.
+///
+#[macro_export]
+macro_rules! kernel_error_matches {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_pattern:expr) => {
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_matches!($result, $expected_kind, $expected_audience, $expected_pattern)
+        )
+    };
+}
+
+/// Asserts that some cause of an [`nape_kernel::error::Error`] has the expected kind, audience, and the message
+/// contains a specific phrase.  This verifies a re-thrown error preserves its root cause.
+///
+/// The causes are found by walking the [`std::error::Error::source`] chain of the error, see
+/// [`crate::matchers::error_chain`].  The `Result` must be passed by value.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected kind of the cause. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected audience of the cause. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase of the cause. Should be a `&str`.
+///
+#[macro_export]
+macro_rules! kernel_error_caused_by {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::assertions::report::enforce(
+            $crate::check_kernel_error_caused_by!($result, $expected_kind, $expected_audience, $expected_message)
+        )
+    };
+}
+
+/// Asserts that an [`nape_kernel::error::Error`] has the expected kind, regardless of its audience and message.
+///
+/// This is useful when the audience and message are not what the test is about, where
+/// [`kernel_error_has_message`] would assert more than is meant.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+///
+#[macro_export]
+macro_rules! assert_kind {
+    ($result:expr, $expected_kind:expr) => {
+        $crate::assertions::report::enforce($crate::check_kind!($result, $expected_kind))
+    };
+}
+
+/// Asserts that an [`nape_kernel::error::Error`] has the expected audience, regardless of its kind and message.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! assert_audience {
+    ($result:expr, $expected_audience:expr) => {
+        $crate::assertions::report::enforce($crate::check_audience!($result, $expected_audience))
+    };
+}
+
+/// Asserts that a `Result`, if it is an [`Err`], is not an [`nape_kernel::error::Error`] with the given kind and
+/// audience combination.  An [`Ok`] passes, as does an error with only one of the two.
+///
+/// This is useful for verifying an error is translated at a boundary, e.g. that no `Kind::GatewayError` reaches
+/// `Audience::User`.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression whose error, if any, should be an [`nape_kernel::error::Error`].
+/// * `$kind` - The kind that must not occur with the audience. Should be of type [`nape_kernel::error::Kind`].
+/// * `$audience` - The audience that must not occur with the kind. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! kernel_error_not {
+    ($result:expr, $kind:expr, $audience:expr) => {
+        $crate::assertions::report::enforce($crate::check_kernel_error_not!($result, $kind, $audience))
+    };
+}
+
+/// Expands a table of named cases into one `#[test]` function per case, so a failure identifies the exact case.
+///
+/// The tests are generated in a module with the table's name, which imports everything from the enclosing module.
+/// Each case calls the subject with its input and asserts the result with [`kernel_error_eq`].  Use a tuple as the
+/// input when the subject takes several arguments.
+///
+/// # Arguments
+///
+/// * `name` - The name of the generated module.
+/// * `subject` - A closure or function called with each case's input, returning a `Result`.
+/// * `cases` - Each case as `case_name => (input, expected_kind, expected_audience, expected_message)`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::table_test;
+/// # #[derive(Debug, Clone, PartialEq)] pub enum Kind { InvalidInput }
+/// # #[derive(Debug, Clone, PartialEq)] pub enum Audience { User }
+/// # #[derive(Debug)] pub struct Error { kind: Kind, audience: Audience, message: String }
+/// # pub fn parse(input: &str) -> Result<(), Error> { Ok(()) }
+///
+/// table_test! {
+///     name: parse_procedure,
+///     subject: |input: &str| parse(input),
+///     cases: {
+///         empty_input => ("", Kind::InvalidInput, Audience::User, "The procedure is empty."),
+///         missing_name => ("version: 1", Kind::InvalidInput, Audience::User, "The procedure has no name."),
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+#[macro_export]
+macro_rules! table_test {
+    (
+        name: $name:ident,
+        subject: $subject:expr,
+        cases: {
+            $($case:ident => ($input:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr)),* $(,)?
+        } $(,)?
+    ) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                #[test]
+                fn $case() {
+                    let subject = $subject;
+                    $crate::kernel_error_eq!(subject($input), $expected_kind, $expected_audience, $expected_message);
+                }
+            )*
+        }
+    };
+}
+
+/// Asserts that no [`nape_kernel::error::Error`] in a collection of `Result`s has the given kind.  On failure it
+/// lists every offending item with its index, audience, and message.
+///
+/// # Arguments
+///
+/// * `$results` - Anything that can be iterated into `Result`s, such as a `Vec<Result<_, Error>>` or a reference to one.
+/// * `$kind` - The kind that must not occur. Should be of type [`nape_kernel::error::Kind`].
+///
+#[macro_export]
+macro_rules! assert_no_error_of_kind {
+    ($results:expr, $kind:expr) => {
+        $crate::assertions::report::enforce($crate::check_no_error_of_kind!($results, $kind))
+    };
+}
+
+/// Deserializes a JSON string into an [`nape_kernel::error::Error`] and asserts it matches the expected kind,
+/// audience, and message, like [`kernel_error_eq`].  If the JSON cannot be deserialized the test fails with the
+/// deserialization error.
+///
+/// With only the JSON and the error type, it returns a fluent [`crate::matchers::KernelErrorAssert`] instead, so any
+/// of its checks can be chained.
+///
+/// # Arguments
+///
+/// * `$json` - The serialized error as a `&str`.
+/// * `$error_type` - The type to deserialize into, which must implement `serde::Deserialize`, typically [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected error message. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! assert_kernel_error_json {
+    ($json:expr, $error_type:ty) => {
+        $crate::assert_kernel_error!($crate::__deserialize_kernel_error_json!($json, $error_type))
+    };
+    ($json:expr, $error_type:ty, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::assertions::report::enforce($crate::check_kernel_error_json!(
+            $json,
+            $error_type,
+            $expected_kind,
+            $expected_audience,
+            $expected_message
+        ))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __deserialize_kernel_error_json {
+    ($json:expr, $error_type:ty) => {
+        match $crate::serde_json::from_str::<$error_type>($json) {
+            Ok(error) => Err::<(), $error_type>(error),
+            Err(err) => $crate::assertions::report::fail(
+                $crate::assertions::report::MismatchReport::new(
+                    "json",
+                    format!("The JSON could not be deserialized into a {}.", stringify!($error_type)),
+                )
+                .hint(format!("\t{}\n\tJSON:\t{}", err, $json)),
+            ),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_kind_and_audience {
+    ($error:expr, $expected_kind:expr, $expected_audience:expr) => {
+        if $crate::__observe_error!($error) && $error.kind != $expected_kind {
+            Err($crate::assertions::report::MismatchReport::new("kind", "Kind does not match.")
+                .expected(format!("{:?}", $expected_kind))
+                .actual(format!("{:?}", $error.kind)))
+        } else if $error.audience != $expected_audience {
+            Err($crate::assertions::report::MismatchReport::new("audience", "Audience does not match.")
+                .expected(format!("{:?}", $expected_audience))
+                .actual(format!("{:?}", $error.audience)))
+        } else {
+            Ok(())
+        }
+    };
+}
+
+/// Records the kind and audience of the error in the [coverage registry](crate::assertions::coverage), evaluating to
+/// `true` so it can lead a guard.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __observe_error {
+    ($error:expr) => {{
+        $crate::assertions::coverage::observe(&$error.kind, &$error.audience);
+        true
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check_no_error_returned {
+    ($value:expr) => {
+        Err($crate::assertions::report::MismatchReport::new(
+            "result",
+            "An Error was expected, although one was not returned.",
+        )
+        .actual(format!("Ok({:?})", $value)))
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] matches the expected kind, audience, and message, like
+/// [`kernel_error_eq`], returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of
+/// panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected error message. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_eq {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) if e.message != $expected_message => {
+                    Err($crate::assertions::report::MismatchReport::new("message", "The Error Message does not match.")
+                        .hint($crate::diff::render(&$expected_message.to_string(), &e.message.to_string())))
+                }
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] matches the expected kind and audience and has a message, like
+/// [`kernel_error_has_message`], returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead
+/// of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_has_message {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) if e.message.is_empty() => Err($crate::assertions::report::MismatchReport::new(
+                    "message",
+                    "The error message is empty.  A populated error message is expected.",
+                )),
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected kind, audience, and the message starts with a
+/// specific phrase, like [`kernel_error_starts_with`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_starts_with {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) if !e.message.starts_with($expected_message) => {
+                    Err($crate::assertions::report::MismatchReport::new(
+                        "message",
+                        "The Error Message does not start with the expected phrase.",
+                    )
+                    .expected(format!("{:?}", $expected_message))
+                    .actual(format!("{:?}", e.message))
+                    .hint($crate::diff::render(&$expected_message.to_string(), &e.message.to_string())))
+                }
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected kind, audience, and the message contains a
+/// specific phrase, like [`kernel_error_contains`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_contains {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) if !e.message.contains($expected_message) => {
+                    Err($crate::assertions::report::MismatchReport::new(
+                        "message",
+                        "The Error Message does not contains the expected phrase.",
+                    )
+                    .expected(format!("{:?}", $expected_message))
+                    .actual(format!("{:?}", e.message)))
+                }
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected kind, audience, and the localized message for a
+/// key, like [`kernel_error_msg_key`], returning a [`MismatchReport`](crate::assertions::report::MismatchReport)
+/// instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$key` - The key of the expected message in the installed message catalog.
+/// * `$name = $value` - Optional values of the message's `{name}` placeholders.
+///
+#[macro_export]
+macro_rules! check_kernel_error_msg_key {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) => $crate::assertions::messages::check_message(
+                    $key,
+                    &e.message.to_string(),
+                    &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+                ),
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected kind, audience, and the message matches a regular
+/// expression, like [`kernel_error_matches`], returning a [`MismatchReport`](crate::assertions::report::MismatchReport)
+/// instead of panicking.  An invalid pattern is also reported.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_pattern` - The regular expression the message must match. Should be a `&str`.
+///
+#[macro_export]
+macro_rules! check_kernel_error_matches {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_pattern:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => match $crate::__check_kind_and_audience!(e, $expected_kind, $expected_audience) {
+                Ok(()) => match $crate::regex::Regex::new($expected_pattern) {
+                    Err(err) => Err($crate::assertions::report::MismatchReport::new(
+                        "message",
+                        "The expected pattern is not a valid regular expression.",
+                    )
+                    .hint(format!("\t{}", err))),
+                    Ok(pattern) if !pattern.is_match(&e.message) => {
+                        Err($crate::assertions::report::MismatchReport::new(
+                            "message",
+                            "The Error Message does not match the expected pattern.",
+                        )
+                        .expected(format!("{:?}", $expected_pattern))
+                        .actual(format!("{:?}", e.message)))
+                    }
+                    Ok(_) => Ok(()),
+                },
+                checked => checked,
+            },
+        }
+    };
+}
+
+/// Checks that some cause of an [`nape_kernel::error::Error`] has the expected kind, audience, and the message
+/// contains a specific phrase, like [`kernel_error_caused_by`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected kind of the cause. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected audience of the cause. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase of the cause. Should be a `&str`.
+///
+#[macro_export]
+macro_rules! check_kernel_error_caused_by {
+    ($result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) => {
+                $crate::__observe_error!(e);
+                let chain = $crate::matchers::error_chain(&e);
+                let found = chain.iter().any(|cause| {
+                    cause.kind == $expected_kind
+                        && cause.audience == $expected_audience
+                        && cause.message.contains($expected_message)
+                });
+                if found {
+                    Ok(())
+                } else {
+                    let causes: Vec<_> = chain
+                        .iter()
+                        .map(|cause| format!("{:?}, {:?}, {:?}", cause.kind, cause.audience, cause.message))
+                        .collect();
+                    Err($crate::assertions::report::MismatchReport::new(
+                        "causes",
+                        "No cause of the Error matches the expected values.",
+                    )
+                    .expected(format!("{:?}, {:?}, {:?}", $expected_kind, $expected_audience, $expected_message))
+                    .actual(format!("{:?}", causes)))
+                }
+            }
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected kind, like [`assert_kind`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+///
+#[macro_export]
+macro_rules! check_kind {
+    ($result:expr, $expected_kind:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) if $crate::__observe_error!(e) && e.kind != $expected_kind => {
+                Err($crate::assertions::report::MismatchReport::new("kind", "Kind does not match.")
+                    .expected(format!("{:?}", $expected_kind))
+                    .actual(format!("{:?}", e.kind)))
+            }
+            Err(_) => Ok(()),
+        }
+    };
+}
+
+/// Checks that an [`nape_kernel::error::Error`] has the expected audience, like [`assert_audience`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! check_audience {
+    ($result:expr, $expected_audience:expr) => {
+        match $result {
+            Ok(val) => $crate::__check_no_error_returned!(val),
+            Err(e) if $crate::__observe_error!(e) && e.audience != $expected_audience => {
+                Err($crate::assertions::report::MismatchReport::new("audience", "Audience does not match.")
+                    .expected(format!("{:?}", $expected_audience))
+                    .actual(format!("{:?}", e.audience)))
+            }
+            Err(_) => Ok(()),
+        }
+    };
+}
+
+/// Checks that a `Result`, if it is an [`Err`], is not an [`nape_kernel::error::Error`] with the given kind and
+/// audience combination, like [`kernel_error_not`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression whose error, if any, should be an [`nape_kernel::error::Error`].
+/// * `$kind` - The kind that must not occur with the audience. Should be of type [`nape_kernel::error::Kind`].
+/// * `$audience` - The audience that must not occur with the kind. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_not {
+    ($result:expr, $kind:expr, $audience:expr) => {
+        match $result {
+            Err(e) if $crate::__observe_error!(e) && e.kind == $kind && e.audience == $audience => {
+                Err($crate::assertions::report::MismatchReport::new(
+                    "error",
+                    "The Error has a kind and audience combination that was not expected.",
+                )
+                .expected(format!("not {:?} for {:?}", $kind, $audience))
+                .actual(format!("{:?}, {:?}: {:?}", e.kind, e.audience, e.message)))
+            }
+            _ => Ok(()),
+        }
+    };
+}
+
+/// Checks that a [`Result`] is an [`Ok`], like [`is_ok`], returning the value, or a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Ok`].
+///
+#[macro_export]
+macro_rules! check_is_ok {
+    ($result:expr) => {
+        match $result {
+            Ok(val) => Ok(val),
+            Err(e) => Err($crate::assertions::report::MismatchReport::new(
+                "result",
+                "An Ok was expected, although an Error was returned.",
+            )
+            .actual(format!("Err({:?})", e))),
+        }
+    };
+}
+
+/// Checks that a [`Result`] is an [`Err`], like [`is_error`], returning the error value, or a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Err`].
+///
+#[macro_export]
+macro_rules! check_is_error {
     ($result:expr) => {
         match $result {
-            Ok(val) => panic!("An error was expected, although one was not returned."),
-            Err(e) => e,
+            Ok(_) => Err($crate::assertions::report::MismatchReport::new(
+                "result",
+                "An error was expected, although one was not returned.",
+            )),
+            Err(e) => Ok(e),
+        }
+    };
+}
+
+/// Checks that a [`Result`] is an [`Ok`] whose value equals the expected value, like [`is_ok_eq`], returning the
+/// value, or a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Ok`].
+/// * `$expected` - The expected value, comparable to the [`Ok`] value with `==`.
+///
+#[macro_export]
+macro_rules! check_is_ok_eq {
+    ($result:expr, $expected:expr $(,)?) => {
+        match ($crate::check_is_ok!($result), &$expected) {
+            (Ok(val), expected) if val == *expected => Ok(val),
+            (Ok(val), expected) => Err($crate::assertions::report::MismatchReport::new(
+                "value",
+                "The Ok value does not match.",
+            )
+            .expected(format!("{:?}", expected))
+            .actual(format!("{:?}", val))
+            .hint($crate::diff::render(&format!("{:#?}", expected), &format!("{:#?}", val)))),
+            (Err(report), _) => Err(report),
+        }
+    };
+}
+
+/// Checks that a [`Result`] is an [`Ok`] whose value satisfies the predicate, like [`assert_ok_and`], returning the
+/// value, or a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`Ok`].
+/// * `$predicate` - A closure given a reference to the value, returning whether it is as expected.
+/// * `$message` - Optional. What was expected of the value, with optional format arguments.
+///
+#[macro_export]
+macro_rules! check_ok_and {
+    ($result:expr, $predicate:expr $(,)?) => {
+        $crate::check_ok_and!($result, $predicate, "The value does not satisfy the predicate.")
+    };
+    ($result:expr, $predicate:expr, $($message:tt)+) => {
+        match $crate::check_is_ok!($result) {
+            Ok(val) if $crate::assertions::__satisfies(&val, $predicate) => Ok(val),
+            Ok(val) => Err($crate::assertions::report::MismatchReport::new("value", format!($($message)+))
+                .actual(format!("Ok({:?})", val))),
+            Err(report) => Err(report),
+        }
+    };
+}
+
+/// Checks that no [`nape_kernel::error::Error`] in a collection of `Result`s has the given kind, like
+/// [`assert_no_error_of_kind`], returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead
+/// of panicking.
+///
+/// # Arguments
+///
+/// * `$results` - Anything that can be iterated into `Result`s, such as a `Vec<Result<_, Error>>` or a reference to one.
+/// * `$kind` - The kind that must not occur. Should be of type [`nape_kernel::error::Kind`].
+///
+#[macro_export]
+macro_rules! check_no_error_of_kind {
+    ($results:expr, $kind:expr) => {{
+        let offending: Vec<String> = $results
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, result)| match result {
+                Err(e) if $crate::__observe_error!(e) && e.kind == $kind => Some(format!(
+                    "\t[{}] {:?}, {:?}: {:?}",
+                    index, e.kind, e.audience, e.message
+                )),
+                _ => None,
+            })
+            .collect();
+        if offending.is_empty() {
+            Ok(())
+        } else {
+            Err($crate::assertions::report::MismatchReport::new(
+                "kind",
+                format!("No Error of kind {:?} was expected, although {} were found:", $kind, offending.len()),
+            )
+            .hint(offending.join("\n")))
+        }
+    }};
+}
+
+/// Deserializes a JSON string into an [`nape_kernel::error::Error`] and checks it matches the expected kind,
+/// audience, and message, like [`assert_kernel_error_json`], returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.  A deserialization failure
+/// is also reported.
+///
+/// # Arguments
+///
+/// * `$json` - The serialized error as a `&str`.
+/// * `$error_type` - The type to deserialize into, which must implement `serde::Deserialize`, typically [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected error message. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! check_kernel_error_json {
+    ($json:expr, $error_type:ty, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $crate::serde_json::from_str::<$error_type>($json) {
+            Ok(error) => $crate::check_kernel_error_eq!(
+                Err::<(), $error_type>(error),
+                $expected_kind,
+                $expected_audience,
+                $expected_message
+            ),
+            Err(err) => Err($crate::assertions::report::MismatchReport::new(
+                "json",
+                format!("The JSON could not be deserialized into a {}.", stringify!($error_type)),
+            )
+            .hint(format!("\t{}\n\tJSON:\t{}", err, $json))),
         }
     };
 }
@@ -0,0 +1,215 @@
+use super::digest::{self, Algorithm};
+use super::report::{self, MismatchReport};
+use crate::fixtures::archive::read_archive;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// What [`assert_archive_contains`](crate::assert_archive_contains) expects of an entry besides its presence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryExpectation<'a> {
+    /// The entry exists.
+    Exists,
+    /// The SHA-256 digest of the entry equals the hex or base64 digest.
+    Sha256(&'a str),
+    /// The entry has the contents.
+    Contents(&'a [u8]),
+}
+
+fn read(path: &Path) -> Result<BTreeMap<String, Vec<u8>>, MismatchReport> {
+    read_archive(path).map_err(|err| {
+        MismatchReport::new(path.display().to_string(), "Could not read the archive.").hint(format!("\t{}", err))
+    })
+}
+
+fn entries_hint(files: &BTreeMap<String, Vec<u8>>) -> String {
+    if files.is_empty() {
+        return "\tEntries:\n\t\t<none>".to_string();
+    }
+    let entries: Vec<String> = files
+        .iter()
+        .map(|(name, contents)| format!("\t\t{} ({} bytes)", name, contents.len()))
+        .collect();
+    format!("\tEntries:\n{}", entries.join("\n"))
+}
+
+/// Checks the archive has the file, and that it meets the expectation.  See
+/// [`check_archive_contains`](crate::check_archive_contains).
+pub fn check_archive_contains(path: &Path, entry: &str, expectation: EntryExpectation) -> Result<(), MismatchReport> {
+    let files = read(path)?;
+    let field = format!("{}!{}", path.display(), entry);
+    let Some(contents) = files.get(entry) else {
+        return Err(MismatchReport::new(field, "The archive does not contain the entry.").hint(entries_hint(&files)));
+    };
+    match expectation {
+        EntryExpectation::Exists => Ok(()),
+        EntryExpectation::Sha256(expected) => {
+            digest::check_bytes_digest(Algorithm::Sha256, contents, expected).map_err(|mut report| {
+                report.field = field;
+                report.summary = report.summary.replace("the bytes", &format!("the entry '{}'", entry));
+                report
+            })
+        }
+        EntryExpectation::Contents(expected) if contents.as_slice() == expected => Ok(()),
+        EntryExpectation::Contents(expected) => Err(MismatchReport::new(field, "The contents of the entry do not match.")
+            .expected(String::from_utf8_lossy(expected))
+            .actual(String::from_utf8_lossy(contents))),
+    }
+}
+
+/// Asserts the archive has the file, and that it meets the expectation.  See
+/// [`assert_archive_contains`](crate::assert_archive_contains).
+pub fn assert_archive_contains(path: &Path, entry: &str, expectation: EntryExpectation) {
+    report::enforce(check_archive_contains(path, entry, expectation));
+}
+
+/// Checks the files of the archive are exactly the entries, in any order.  See
+/// [`check_archive_entries`](crate::check_archive_entries).
+pub fn check_archive_entries(path: &Path, entries: &[&str]) -> Result<(), MismatchReport> {
+    let files = read(path)?;
+    let missing: Vec<&str> = entries.iter().copied().filter(|entry| !files.contains_key(*entry)).collect();
+    let unexpected: Vec<&str> = files.keys().map(String::as_str).filter(|name| !entries.contains(name)).collect();
+    if missing.is_empty() && unexpected.is_empty() {
+        return Ok(());
+    }
+    let list = |names: &[&str]| {
+        if names.is_empty() {
+            "\t\t<none>".to_string()
+        } else {
+            names.iter().map(|name| format!("\t\t{}", name)).collect::<Vec<_>>().join("\n")
+        }
+    };
+    Err(
+        MismatchReport::new(path.display().to_string(), "The entries of the archive do not match.")
+            .hint(format!("\tMissing:\n{}\n\tUnexpected:\n{}", list(&missing), list(&unexpected))),
+    )
+}
+
+/// Asserts the files of the archive are exactly the entries, in any order.  See
+/// [`assert_archive_entries`](crate::assert_archive_entries).
+pub fn assert_archive_entries(path: &Path, entries: &[&str]) {
+    report::enforce(check_archive_entries(path, entries));
+}
+
+/// Asserts that a tar, tar.gz, or zip archive contains a file, and optionally its SHA-256 digest or contents,
+/// without unpacking it.  On failure it lists the entries of the archive.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the archive, anything that implements `AsRef<Path>`.
+/// * `$entry` - The path of the file in the archive, with `/` separators, e.g. `evidence/report.yaml`.
+/// * `sha256 = $digest` - Optional. The expected digest as a `&str`, in hex or base64.
+/// * `contents = $contents` - Optional. The expected contents, anything that implements `AsRef<[u8]>`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_archive_contains;
+///
+/// assert_archive_contains!(
+///     "target/bundle.tar.gz",
+///     "evidence/report.yaml",
+///     sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+/// );
+/// ```
+///
+#[macro_export]
+macro_rules! assert_archive_contains {
+    ($path:expr, $entry:expr $(,)?) => {
+        $crate::assertions::archive::assert_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Exists,
+        )
+    };
+    ($path:expr, $entry:expr, sha256 = $digest:expr $(,)?) => {
+        $crate::assertions::archive::assert_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Sha256(&$digest),
+        )
+    };
+    ($path:expr, $entry:expr, contents = $contents:expr $(,)?) => {
+        $crate::assertions::archive::assert_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Contents(::std::convert::AsRef::<[u8]>::as_ref(
+                &$contents,
+            )),
+        )
+    };
+}
+
+/// Checks that a tar, tar.gz, or zip archive contains a file, and optionally its SHA-256 digest or contents, like
+/// [`assert_archive_contains`](crate::assert_archive_contains), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the archive, anything that implements `AsRef<Path>`.
+/// * `$entry` - The path of the file in the archive, with `/` separators.
+/// * `sha256 = $digest` - Optional. The expected digest as a `&str`, in hex or base64.
+/// * `contents = $contents` - Optional. The expected contents, anything that implements `AsRef<[u8]>`.
+///
+#[macro_export]
+macro_rules! check_archive_contains {
+    ($path:expr, $entry:expr $(,)?) => {
+        $crate::assertions::archive::check_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Exists,
+        )
+    };
+    ($path:expr, $entry:expr, sha256 = $digest:expr $(,)?) => {
+        $crate::assertions::archive::check_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Sha256(&$digest),
+        )
+    };
+    ($path:expr, $entry:expr, contents = $contents:expr $(,)?) => {
+        $crate::assertions::archive::check_archive_contains(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$entry,
+            $crate::assertions::archive::EntryExpectation::Contents(::std::convert::AsRef::<[u8]>::as_ref(
+                &$contents,
+            )),
+        )
+    };
+}
+
+/// Asserts that the files of a tar, tar.gz, or zip archive are exactly the entries, in any order.  On failure it
+/// lists the missing and unexpected entries.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the archive, anything that implements `AsRef<Path>`.
+/// * `[$entry, ...]` - The paths of the files in the archive, with `/` separators.
+///
+#[macro_export]
+macro_rules! assert_archive_entries {
+    ($path:expr, [$($entry:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::archive::assert_archive_entries(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &[$(::std::convert::AsRef::<str>::as_ref(&$entry)),*],
+        )
+    };
+}
+
+/// Checks that the files of a tar, tar.gz, or zip archive are exactly the entries, like
+/// [`assert_archive_entries`](crate::assert_archive_entries), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the archive, anything that implements `AsRef<Path>`.
+/// * `[$entry, ...]` - The paths of the files in the archive, with `/` separators.
+///
+#[macro_export]
+macro_rules! check_archive_entries {
+    ($path:expr, [$($entry:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::archive::check_archive_entries(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &[$(::std::convert::AsRef::<str>::as_ref(&$entry)),*],
+        )
+    };
+}
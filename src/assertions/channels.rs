@@ -0,0 +1,270 @@
+use super::report::{self, MismatchReport};
+use crate::matchers::Matcher;
+use std::fmt::Debug;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long [`assert_received`](crate::assert_received) waits for a matching message when no timeout is given.
+pub const DEFAULT_RECEIVE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The outcome of waiting for a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Received<T> {
+    Message(T),
+    /// No message arrived in time.
+    Timeout,
+    /// Every sender was dropped, so no message will arrive.
+    Disconnected,
+}
+
+/// The receiving end of a channel that the channel assertions can wait on.
+///
+/// Implemented for [`std::sync::mpsc::Receiver`], and, with the `tokio` feature, for the receivers of tokio's
+/// `mpsc` and `broadcast` channels, which are polled without a runtime.
+pub trait MessageReceiver {
+    type Message;
+
+    /// Waits up to the timeout for the next message.  A zero timeout only takes a message that is already queued.
+    fn receive_within(&mut self, timeout: Duration) -> Received<Self::Message>;
+}
+
+impl<T> MessageReceiver for mpsc::Receiver<T> {
+    type Message = T;
+
+    fn receive_within(&mut self, timeout: Duration) -> Received<T> {
+        match self.recv_timeout(timeout) {
+            Ok(message) => Received::Message(message),
+            Err(mpsc::RecvTimeoutError::Timeout) => Received::Timeout,
+            Err(mpsc::RecvTimeoutError::Disconnected) => Received::Disconnected,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_receivers {
+    use super::{MessageReceiver, Received};
+    use std::time::{Duration, Instant};
+    use tokio::sync::{broadcast, mpsc};
+
+    /// Polls `try_receive` until it returns a message or the channel is disconnected, or the timeout passes.
+    fn poll<T>(timeout: Duration, mut try_receive: impl FnMut() -> Option<Received<T>>) -> Received<T> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(received) = try_receive() {
+                return received;
+            }
+            if Instant::now() >= deadline {
+                return Received::Timeout;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    impl<T> MessageReceiver for mpsc::Receiver<T> {
+        type Message = T;
+
+        fn receive_within(&mut self, timeout: Duration) -> Received<T> {
+            poll(timeout, || match self.try_recv() {
+                Ok(message) => Some(Received::Message(message)),
+                Err(mpsc::error::TryRecvError::Empty) => None,
+                Err(mpsc::error::TryRecvError::Disconnected) => Some(Received::Disconnected),
+            })
+        }
+    }
+
+    impl<T> MessageReceiver for mpsc::UnboundedReceiver<T> {
+        type Message = T;
+
+        fn receive_within(&mut self, timeout: Duration) -> Received<T> {
+            poll(timeout, || match self.try_recv() {
+                Ok(message) => Some(Received::Message(message)),
+                Err(mpsc::error::TryRecvError::Empty) => None,
+                Err(mpsc::error::TryRecvError::Disconnected) => Some(Received::Disconnected),
+            })
+        }
+    }
+
+    /// Messages a lagging receiver missed are skipped, and it continues with the oldest message still queued.
+    impl<T: Clone> MessageReceiver for broadcast::Receiver<T> {
+        type Message = T;
+
+        fn receive_within(&mut self, timeout: Duration) -> Received<T> {
+            poll(timeout, || match self.try_recv() {
+                Ok(message) => Some(Received::Message(message)),
+                Err(broadcast::error::TryRecvError::Empty | broadcast::error::TryRecvError::Lagged(_)) => None,
+                Err(broadcast::error::TryRecvError::Closed) => Some(Received::Disconnected),
+            })
+        }
+    }
+}
+
+/// Checks a message that matches arrives within the timeout, returning it.  See
+/// [`check_received`](crate::check_received).
+pub fn check_received<R, M>(receiver: &mut R, matcher: &M, timeout: Duration) -> Result<R::Message, MismatchReport>
+where
+    R: MessageReceiver + ?Sized,
+    R::Message: Debug,
+    M: Matcher<R::Message> + ?Sized,
+{
+    let deadline = Instant::now() + timeout;
+    let mut skipped = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let summary = match receiver.receive_within(remaining) {
+            Received::Message(message) if matcher.matched(&message) => return Ok(message),
+            Received::Message(message) => {
+                skipped.push(format!("\t\t{:?}: {}", message, matcher.describe_mismatch(&message)));
+                continue;
+            }
+            Received::Timeout => format!("No matching message was received within {:?}.", timeout),
+            Received::Disconnected => "The channel was disconnected before a matching message was received.".to_string(),
+        };
+        let hint = if skipped.is_empty() {
+            "\tReceived:\n\t\t<none>".to_string()
+        } else {
+            format!("\tReceived:\n{}", skipped.join("\n"))
+        };
+        return Err(MismatchReport::new("message", summary)
+            .expected(matcher.describe())
+            .hint(hint));
+    }
+}
+
+/// Asserts a message that matches arrives within the timeout, returning it.  See
+/// [`assert_received`](crate::assert_received).
+pub fn assert_received<R, M>(receiver: &mut R, matcher: &M, timeout: Duration) -> R::Message
+where
+    R: MessageReceiver + ?Sized,
+    R::Message: Debug,
+    M: Matcher<R::Message> + ?Sized,
+{
+    report::enforce(check_received(receiver, matcher, timeout))
+}
+
+/// Checks no message arrives within the timeout.  See [`check_no_more_messages`](crate::check_no_more_messages).
+pub fn check_no_more_messages<R>(receiver: &mut R, timeout: Duration) -> Result<(), MismatchReport>
+where
+    R: MessageReceiver + ?Sized,
+    R::Message: Debug,
+{
+    match receiver.receive_within(timeout) {
+        Received::Message(message) => Err(MismatchReport::new(
+            "message",
+            "A message was received, although no more were expected.",
+        )
+        .expected("no message")
+        .actual(format!("{:?}", message))),
+        Received::Timeout | Received::Disconnected => Ok(()),
+    }
+}
+
+/// Asserts no message arrives within the timeout.  See [`assert_no_more_messages`](crate::assert_no_more_messages).
+pub fn assert_no_more_messages<R>(receiver: &mut R, timeout: Duration)
+where
+    R: MessageReceiver + ?Sized,
+    R::Message: Debug,
+{
+    report::enforce(check_no_more_messages(receiver, timeout));
+}
+
+/// Asserts that a message matching a [`Matcher`](crate::matchers::Matcher) arrives on a channel within a timeout,
+/// and returns it.  Messages that do not match are consumed and skipped, and listed on failure.
+///
+/// # Arguments
+///
+/// * `$receiver` - A mutable [`MessageReceiver`](crate::assertions::channels::MessageReceiver), such as an
+///   [`std::sync::mpsc::Receiver`].
+/// * `$matcher` - A [`Matcher`](crate::matchers::Matcher) for the message type.
+/// * `within = $timeout` - Optional. How long to wait, a [`Duration`](std::time::Duration).  Defaults to
+///   [`DEFAULT_RECEIVE_TIMEOUT`](crate::assertions::channels::DEFAULT_RECEIVE_TIMEOUT).
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::{assert_no_more_messages, assert_received};
+/// use attestify_test_framework::matchers::predicate;
+/// use std::sync::mpsc;
+/// use std::time::Duration;
+///
+/// let (sender, mut receiver) = mpsc::channel();
+/// // run the usecase that publishes its events with the sender
+/// # sender.send("EvidenceCollected").unwrap();
+///
+/// assert_received!(receiver, predicate("an EvidenceCollected event", |e: &&str| *e == "EvidenceCollected"),
+///     within = Duration::from_millis(500));
+/// assert_no_more_messages!(receiver);
+/// ```
+#[macro_export]
+macro_rules! assert_received {
+    ($receiver:expr, $matcher:expr $(,)?) => {
+        $crate::assertions::channels::assert_received(
+            &mut $receiver,
+            &$matcher,
+            $crate::assertions::channels::DEFAULT_RECEIVE_TIMEOUT,
+        )
+    };
+    ($receiver:expr, $matcher:expr, within = $timeout:expr $(,)?) => {
+        $crate::assertions::channels::assert_received(&mut $receiver, &$matcher, $timeout)
+    };
+}
+
+/// Asserts that no message is queued on a channel, or, with a timeout, that none arrives within it.  A
+/// disconnected channel has no more messages.
+///
+/// # Arguments
+///
+/// * `$receiver` - A mutable [`MessageReceiver`](crate::assertions::channels::MessageReceiver).
+/// * `within = $timeout` - Optional. How long to wait for a message, a [`Duration`](std::time::Duration).
+///
+#[macro_export]
+macro_rules! assert_no_more_messages {
+    ($receiver:expr $(,)?) => {
+        $crate::assertions::channels::assert_no_more_messages(&mut $receiver, ::std::time::Duration::ZERO)
+    };
+    ($receiver:expr, within = $timeout:expr $(,)?) => {
+        $crate::assertions::channels::assert_no_more_messages(&mut $receiver, $timeout)
+    };
+}
+
+/// Checks that a message matching a [`Matcher`](crate::matchers::Matcher) arrives on a channel within a timeout,
+/// like [`assert_received`](crate::assert_received), returning the message, or a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$receiver` - A mutable [`MessageReceiver`](crate::assertions::channels::MessageReceiver).
+/// * `$matcher` - A [`Matcher`](crate::matchers::Matcher) for the message type.
+/// * `within = $timeout` - Optional. How long to wait, a [`Duration`](std::time::Duration).
+///
+#[macro_export]
+macro_rules! check_received {
+    ($receiver:expr, $matcher:expr $(,)?) => {
+        $crate::assertions::channels::check_received(
+            &mut $receiver,
+            &$matcher,
+            $crate::assertions::channels::DEFAULT_RECEIVE_TIMEOUT,
+        )
+    };
+    ($receiver:expr, $matcher:expr, within = $timeout:expr $(,)?) => {
+        $crate::assertions::channels::check_received(&mut $receiver, &$matcher, $timeout)
+    };
+}
+
+/// Checks that no message is queued on a channel, or arrives within a timeout, like
+/// [`assert_no_more_messages`](crate::assert_no_more_messages), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$receiver` - A mutable [`MessageReceiver`](crate::assertions::channels::MessageReceiver).
+/// * `within = $timeout` - Optional. How long to wait for a message, a [`Duration`](std::time::Duration).
+///
+#[macro_export]
+macro_rules! check_no_more_messages {
+    ($receiver:expr $(,)?) => {
+        $crate::assertions::channels::check_no_more_messages(&mut $receiver, ::std::time::Duration::ZERO)
+    };
+    ($receiver:expr, within = $timeout:expr $(,)?) => {
+        $crate::assertions::channels::check_no_more_messages(&mut $receiver, $timeout)
+    };
+}
@@ -0,0 +1,467 @@
+use super::report::{self, MismatchReport};
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// The elements two collections do not have in common, ignoring order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnorderedDifference<'a, T> {
+    /// The expected elements with no matching actual element.
+    pub missing: Vec<&'a T>,
+    /// The actual elements with no matching expected element.
+    pub unexpected: Vec<&'a T>,
+}
+
+impl<T> UnorderedDifference<'_, T> {
+    /// Returns true when both collections hold the same elements.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Compares the collections as multisets: every expected element is paired with one equal actual element, so
+/// duplicates must occur the same number of times in both.  Only [`PartialEq`] is required of the elements.
+///
+/// # Arguments
+///
+/// * `expected` - The expected elements, in any order.
+/// * `actual` - The actual elements, in any order.
+///
+pub fn unordered_difference<'a, T: PartialEq>(expected: &'a [T], actual: &'a [T]) -> UnorderedDifference<'a, T> {
+    let mut matched = vec![false; actual.len()];
+    let mut missing = Vec::new();
+
+    for item in expected {
+        let pair = actual
+            .iter()
+            .enumerate()
+            .position(|(index, candidate)| !matched[index] && candidate == item);
+        match pair {
+            Some(index) => matched[index] = true,
+            None => missing.push(item),
+        }
+    }
+
+    let unexpected = actual
+        .iter()
+        .zip(&matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(item, _)| item)
+        .collect();
+
+    UnorderedDifference { missing, unexpected }
+}
+
+/// Checks the collections hold the same elements in any order.  See
+/// [`check_eq_unordered`](crate::check_eq_unordered).
+pub fn check_eq_unordered<T: PartialEq + Debug>(expected: &[T], actual: &[T]) -> Result<(), MismatchReport> {
+    let difference = unordered_difference(expected, actual);
+
+    if difference.is_empty() {
+        return Ok(());
+    }
+    Err(
+        MismatchReport::new("elements", "The collections do not hold the same elements.").hint(format!(
+            "\tMissing:\n{}\n\tUnexpected:\n{}",
+            render(&difference.missing),
+            render(&difference.unexpected)
+        )),
+    )
+}
+
+/// Asserts the collections hold the same elements in any order.  See
+/// [`assert_eq_unordered`](crate::assert_eq_unordered).
+pub fn assert_eq_unordered<T: PartialEq + Debug>(expected: &[T], actual: &[T]) {
+    report::enforce(check_eq_unordered(expected, actual));
+}
+
+/// Returns each key that occurs more than once, with the positions it occurs at, in the order of its first
+/// occurrence.  Only [`PartialEq`] is required of the keys.
+pub fn duplicates<K: PartialEq>(keys: &[K]) -> Vec<(&K, Vec<usize>)> {
+    let mut duplicates: Vec<(&K, Vec<usize>)> = Vec::new();
+    for (position, key) in keys.iter().enumerate() {
+        if keys[..position].contains(key) {
+            continue;
+        }
+        let positions: Vec<usize> = keys
+            .iter()
+            .enumerate()
+            .skip(position)
+            .filter(|(_, other)| *other == key)
+            .map(|(index, _)| index)
+            .collect();
+        if positions.len() > 1 {
+            duplicates.push((key, positions));
+        }
+    }
+    duplicates
+}
+
+/// Checks no key occurs more than once.  See [`check_unique_by`](crate::check_unique_by).
+pub fn check_unique<K: PartialEq + Debug>(keys: &[K]) -> Result<(), MismatchReport> {
+    let duplicates = duplicates(keys);
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+    let lines: Vec<String> = duplicates
+        .iter()
+        .map(|(key, positions)| {
+            let positions: Vec<String> = positions.iter().map(usize::to_string).collect();
+            format!("\t\t{:?} at positions {}", key, positions.join(", "))
+        })
+        .collect();
+    Err(
+        MismatchReport::new("keys", "The collection has duplicated keys.")
+            .hint(format!("\tDuplicates:\n{}", lines.join("\n"))),
+    )
+}
+
+/// Asserts no key occurs more than once.  See [`assert_unique_by`](crate::assert_unique_by).
+pub fn assert_unique<K: PartialEq + Debug>(keys: &[K]) {
+    report::enforce(check_unique(keys));
+}
+
+/// Checks each key is ordered with the next one as `ordering` says, or equal to it.  See
+/// [`check_sorted_by`](crate::check_sorted_by).
+///
+/// # Arguments
+///
+/// * `keys` - The keys of the elements, in the order of the elements.
+/// * `ordering` - [`Ordering::Less`] for ascending keys, [`Ordering::Greater`] for descending keys, or
+///   [`Ordering::Equal`] for keys that are all equal.
+///
+pub fn check_sorted<K: PartialOrd + Debug>(keys: &[K], ordering: Ordering) -> Result<(), MismatchReport> {
+    let out_of_order: Vec<String> = keys
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| !matches!(pair[0].partial_cmp(&pair[1]), Some(found) if found == ordering || found.is_eq()))
+        .map(|(position, pair)| format!("\t\t[{}] {:?} before [{}] {:?}", position, pair[0], position + 1, pair[1]))
+        .collect();
+    if out_of_order.is_empty() {
+        return Ok(());
+    }
+    let order = match ordering {
+        Ordering::Less => "ascending",
+        Ordering::Greater => "descending",
+        Ordering::Equal => "equal",
+    };
+    Err(
+        MismatchReport::new("order", format!("The elements are not in {} order of their keys.", order))
+            .hint(format!("\tOut of order:\n{}", out_of_order.join("\n"))),
+    )
+}
+
+/// Asserts each key is ordered with the next one as `ordering` says, or equal to it.  See
+/// [`assert_sorted_by`](crate::assert_sorted_by).
+pub fn assert_sorted<K: PartialOrd + Debug>(keys: &[K], ordering: Ordering) {
+    report::enforce(check_sorted(keys, ordering));
+}
+
+/// Checks every element matching `first` comes before every element matching `second`, and that both match an
+/// element.  See [`check_precedes`](crate::check_precedes).
+///
+/// # Arguments
+///
+/// * `items` - The elements, in order.
+/// * `first` - The description and predicate of the elements that must come first.
+/// * `second` - The description and predicate of the elements that must come after them.
+///
+pub fn check_precedes<T: Debug>(
+    items: &[T],
+    first: (&str, impl Fn(&T) -> bool),
+    second: (&str, impl Fn(&T) -> bool),
+) -> Result<(), MismatchReport> {
+    let (first_description, first) = first;
+    let (second_description, second) = second;
+    let matching = |predicate: &dyn Fn(&T) -> bool| -> Vec<usize> {
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| predicate(item))
+            .map(|(position, _)| position)
+            .collect()
+    };
+    let (firsts, seconds) = (matching(&first), matching(&second));
+    let render_matches = |positions: &[usize]| -> String {
+        if positions.is_empty() {
+            return "\t\t<none>".to_string();
+        }
+        let lines: Vec<String> = positions
+            .iter()
+            .map(|position| format!("\t\t[{}] {:?}", position, items[*position]))
+            .collect();
+        lines.join("\n")
+    };
+    let hint = format!(
+        "\tMatching {}:\n{}\n\tMatching {}:\n{}",
+        first_description,
+        render_matches(&firsts),
+        second_description,
+        render_matches(&seconds)
+    );
+
+    let summary = match (firsts.last(), seconds.first()) {
+        (None, _) => format!("No element matches {}.", first_description),
+        (_, None) => format!("No element matches {}.", second_description),
+        (Some(last), Some(next)) if last > next => format!(
+            "An element matching {} comes after an element matching {}.",
+            first_description, second_description
+        ),
+        _ => return Ok(()),
+    };
+    Err(MismatchReport::new("order", summary).hint(hint))
+}
+
+/// Asserts every element matching `first` comes before every element matching `second`, and that both match an
+/// element.  See [`assert_precedes`](crate::assert_precedes).
+pub fn assert_precedes<T: Debug>(items: &[T], first: (&str, impl Fn(&T) -> bool), second: (&str, impl Fn(&T) -> bool)) {
+    report::enforce(check_precedes(items, first, second));
+}
+
+fn render<T: Debug>(items: &[&T]) -> String {
+    if items.is_empty() {
+        return "\t\t<none>".to_string();
+    }
+    items
+        .iter()
+        .map(|item| format!("\t\t{:?}", item))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Asserts that two collections hold the same elements, ignoring their order.  Duplicates must occur the same
+/// number of times in both.  On failure it lists the missing and the unexpected elements separately.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected elements, anything that can be iterated, such as a `Vec` or an array.
+/// * `$actual` - The actual elements, anything that can be iterated.
+///
+#[macro_export]
+macro_rules! assert_eq_unordered {
+    ($expected:expr, $actual:expr) => {
+        match ($expected, $actual) {
+            (expected, actual) => {
+                let expected: Vec<_> = ::std::iter::IntoIterator::into_iter(expected).collect();
+                let actual: Vec<_> = ::std::iter::IntoIterator::into_iter(actual).collect();
+                $crate::assertions::collections::assert_eq_unordered(&expected, &actual)
+            }
+        }
+    };
+}
+
+/// Checks that two collections hold the same elements, ignoring their order, like
+/// [`assert_eq_unordered`](crate::assert_eq_unordered), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected elements, anything that can be iterated, such as a `Vec` or an array.
+/// * `$actual` - The actual elements, anything that can be iterated.
+///
+#[macro_export]
+macro_rules! check_eq_unordered {
+    ($expected:expr, $actual:expr) => {
+        match ($expected, $actual) {
+            (expected, actual) => {
+                let expected: Vec<_> = ::std::iter::IntoIterator::into_iter(expected).collect();
+                let actual: Vec<_> = ::std::iter::IntoIterator::into_iter(actual).collect();
+                $crate::assertions::collections::check_eq_unordered(&expected, &actual)
+            }
+        }
+    };
+}
+
+/// Asserts that no element of a collection occurs more than once.  On failure it lists every duplicated element
+/// and the positions it occurs at.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+///
+#[macro_export]
+macro_rules! assert_unique {
+    ($collection:expr) => {
+        $crate::assert_unique_by!($collection, |item| item)
+    };
+}
+
+/// Asserts that no two elements of a collection have the same key, e.g. that generated evidence lists and activity
+/// ids contain no collisions.  On failure it lists every duplicated key and the positions it occurs at.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$key` - A closure returning the key of a reference to an element, e.g. `|item| item.id` or `|item| &item.name`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_unique_by;
+/// # struct Activity { id: u64 }
+///
+/// let activities = vec![Activity { id: 1 }, Activity { id: 2 }];
+/// assert_unique_by!(activities, |activity| activity.id);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_unique_by {
+    ($collection:expr, $key:expr) => {
+        match &$collection {
+            collection => {
+                let keys: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).map($key).collect();
+                $crate::assertions::collections::assert_unique(&keys)
+            }
+        }
+    };
+}
+
+/// Checks that no element of a collection occurs more than once, like [`assert_unique`](crate::assert_unique),
+/// returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+///
+#[macro_export]
+macro_rules! check_unique {
+    ($collection:expr) => {
+        $crate::check_unique_by!($collection, |item| item)
+    };
+}
+
+/// Checks that no two elements of a collection have the same key, like [`assert_unique_by`](crate::assert_unique_by),
+/// returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$key` - A closure returning the key of a reference to an element.
+///
+#[macro_export]
+macro_rules! check_unique_by {
+    ($collection:expr, $key:expr) => {
+        match &$collection {
+            collection => {
+                let keys: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).map($key).collect();
+                $crate::assertions::collections::check_unique(&keys)
+            }
+        }
+    };
+}
+
+/// Asserts that the elements of a collection are sorted by a key, e.g. that evidence is listed deterministically in
+/// a rendered report.  Equal keys may be next to each other.  On failure it lists every pair of neighbouring
+/// elements that is out of order.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$key` - A closure returning the key of a reference to an element, e.g. `|item| &item.name`.
+/// * `$ordering` - [`std::cmp::Ordering::Less`] for ascending keys, [`std::cmp::Ordering::Greater`] for descending
+///   keys, or [`std::cmp::Ordering::Equal`] for keys that are all equal.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_sorted_by;
+/// use std::cmp::Ordering;
+/// # struct Evidence { name: String }
+///
+/// let evidence = vec![Evidence { name: "build.log".to_string() }, Evidence { name: "sbom.json".to_string() }];
+/// assert_sorted_by!(evidence, |item| &item.name, Ordering::Less);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_sorted_by {
+    ($collection:expr, $key:expr, $ordering:expr) => {
+        match &$collection {
+            collection => {
+                let keys: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).map($key).collect();
+                $crate::assertions::collections::assert_sorted(&keys, $ordering)
+            }
+        }
+    };
+}
+
+/// Asserts that every element of a collection matching the first predicate comes before every element matching
+/// the second, and that both match an element, e.g. that activities appear in procedure order.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$first` - A closure returning true for a reference to an element that must come first.
+/// * `$second` - A closure returning true for a reference to an element that must come after them.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_precedes;
+/// # #[derive(Debug)] struct Activity { name: String }
+///
+/// let activities = vec![Activity { name: "build".to_string() }, Activity { name: "scan".to_string() }];
+/// assert_precedes!(activities, |activity| activity.name == "build", |activity| activity.name == "scan");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_precedes {
+    ($collection:expr, $first:expr, $second:expr) => {
+        match &$collection {
+            collection => {
+                let items: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).collect();
+                $crate::assertions::collections::assert_precedes(
+                    &items,
+                    (stringify!($first), $first),
+                    (stringify!($second), $second),
+                )
+            }
+        }
+    };
+}
+
+/// Checks that the elements of a collection are sorted by a key, like [`assert_sorted_by`](crate::assert_sorted_by),
+/// returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$key` - A closure returning the key of a reference to an element.
+/// * `$ordering` - [`std::cmp::Ordering::Less`] for ascending keys, [`std::cmp::Ordering::Greater`] for descending
+///   keys, or [`std::cmp::Ordering::Equal`] for keys that are all equal.
+///
+#[macro_export]
+macro_rules! check_sorted_by {
+    ($collection:expr, $key:expr, $ordering:expr) => {
+        match &$collection {
+            collection => {
+                let keys: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).map($key).collect();
+                $crate::assertions::collections::check_sorted(&keys, $ordering)
+            }
+        }
+    };
+}
+
+/// Checks that every element matching the first predicate comes before every element matching the second, like
+/// [`assert_precedes`](crate::assert_precedes), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$collection` - The elements, anything whose reference can be iterated, such as a `Vec` or an array.
+/// * `$first` - A closure returning true for a reference to an element that must come first.
+/// * `$second` - A closure returning true for a reference to an element that must come after them.
+///
+#[macro_export]
+macro_rules! check_precedes {
+    ($collection:expr, $first:expr, $second:expr) => {
+        match &$collection {
+            collection => {
+                let items: Vec<_> = ::std::iter::IntoIterator::into_iter(collection).collect();
+                $crate::assertions::collections::check_precedes(
+                    &items,
+                    (stringify!($first), $first),
+                    (stringify!($second), $second),
+                )
+            }
+        }
+    };
+}
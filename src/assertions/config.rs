@@ -0,0 +1,201 @@
+//! How assertion failures are rendered: ANSI color, unicode symbols, the length values are truncated to, and how much
+//! of a long diff is shown.
+//!
+//! Every failure is rendered by [`MismatchReport`](super::report::MismatchReport) and its diffs by
+//! [`crate::diff`], which read the configuration from [`config`].  It comes from environment variables, so CI logs
+//! stay plain while a local run gets colored diffs, e.g. `ATTESTIFY_COLOR=auto` colors output only on a terminal:
+//!
+//! | Variable                   | Values                                               | Default |
+//! |----------------------------|------------------------------------------------------|---------|
+//! | [`COLOR_ENV_VAR`]          | `1`, `true`, `always`, `auto`, `0`, `false`, `never` | `never` |
+//! | [`UNICODE_ENV_VAR`]        | `1`, `true`, `0`, `false`                            | `true`  |
+//! | [`MAX_LENGTH_ENV_VAR`]     | characters per value, `0` for no limit               | `0`     |
+//! | [`MAX_DIFF_LINES_ENV_VAR`] | lines per diff, `0` for no limit                     | `100`   |
+//! | [`DIFF_CONTEXT_ENV_VAR`]   | unchanged lines around a difference                  | `3`     |
+//!
+//! A test or harness can instead set the configuration for the process:
+//!
+//! ```no_run
+//! use attestify_test_framework::assertions::{self, OutputConfig};
+//!
+//! assertions::set_config(OutputConfig::plain().with_max_length(Some(500)));
+//! assert!(!assertions::config().color);
+//! ```
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+
+/// The environment variable that turns on ANSI coloring of failure output.  Coloring is enabled when it is set to
+/// `1`, `true`, or `always`, and on `auto` when standard error is a terminal and `NO_COLOR` is not set.
+pub const COLOR_ENV_VAR: &str = "ATTESTIFY_COLOR";
+
+/// The environment variable that replaces the unicode symbols in failure output with ASCII, when it is set to `0`
+/// or `false`.
+pub const UNICODE_ENV_VAR: &str = "ATTESTIFY_UNICODE";
+
+/// The environment variable that sets the number of characters an expected or actual value is truncated to in
+/// failure output.  `0`, or a value that is not a number, does not truncate.
+pub const MAX_LENGTH_ENV_VAR: &str = "ATTESTIFY_MAX_LENGTH";
+
+/// The environment variable that sets the number of lines a diff is rendered in full up to.  A longer diff shows only
+/// its first difference, with [`DIFF_CONTEXT_ENV_VAR`] lines of context, and a summary of the differences left out.
+/// `0` always renders the full diff.
+pub const MAX_DIFF_LINES_ENV_VAR: &str = "ATTESTIFY_MAX_DIFF_LINES";
+
+/// The environment variable that sets the number of unchanged lines shown around a difference in a diff that is
+/// longer than [`MAX_DIFF_LINES_ENV_VAR`].
+pub const DIFF_CONTEXT_ENV_VAR: &str = "ATTESTIFY_DIFF_CONTEXT";
+
+/// The number of lines a diff is rendered in full up to, unless set by [`MAX_DIFF_LINES_ENV_VAR`].
+pub const DEFAULT_MAX_DIFF_LINES: usize = 100;
+
+/// The number of unchanged lines shown around a difference, unless set by [`DIFF_CONTEXT_ENV_VAR`].
+pub const DEFAULT_DIFF_CONTEXT: usize = 3;
+
+/// The configuration set with [`set_config`], which takes precedence over the environment.
+static CONFIG: Mutex<Option<OutputConfig>> = Mutex::new(None);
+
+/// How assertion failures are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// Whether to color labels and diffs with ANSI escape codes.
+    pub color: bool,
+    /// Whether to use unicode symbols, such as `…`, `–`, and `±`, instead of their ASCII equivalents.
+    pub unicode: bool,
+    /// The number of characters an expected or actual value is truncated to, or [`None`] to show it in full.
+    pub max_length: Option<usize>,
+    /// The number of lines a diff is rendered in full up to, or [`None`] to always render it in full.
+    pub max_diff_lines: Option<usize>,
+    /// The number of unchanged lines shown around the difference of a diff longer than
+    /// [`OutputConfig::max_diff_lines`].
+    pub diff_context: usize,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+impl OutputConfig {
+    /// Returns the configuration for a log: no color, unicode symbols, no truncation of values, and the first
+    /// difference of diffs longer than [`DEFAULT_MAX_DIFF_LINES`].
+    pub fn plain() -> Self {
+        OutputConfig {
+            color: false,
+            unicode: true,
+            max_length: None,
+            max_diff_lines: Some(DEFAULT_MAX_DIFF_LINES),
+            diff_context: DEFAULT_DIFF_CONTEXT,
+        }
+    }
+
+    /// Returns the configuration set by [`COLOR_ENV_VAR`], [`UNICODE_ENV_VAR`], [`MAX_LENGTH_ENV_VAR`],
+    /// [`MAX_DIFF_LINES_ENV_VAR`], and [`DIFF_CONTEXT_ENV_VAR`].
+    pub fn from_env() -> Self {
+        let var = |name: &str| env::var(name).map(|value| value.to_lowercase()).unwrap_or_default();
+        let color = match var(COLOR_ENV_VAR).as_str() {
+            "1" | "true" | "always" => true,
+            "auto" => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            _ => false,
+        };
+        OutputConfig {
+            color,
+            unicode: !matches!(var(UNICODE_ENV_VAR).as_str(), "0" | "false"),
+            max_length: var(MAX_LENGTH_ENV_VAR).parse().ok().filter(|length| *length > 0),
+            max_diff_lines: match var(MAX_DIFF_LINES_ENV_VAR).parse() {
+                Ok(0) => None,
+                Ok(lines) => Some(lines),
+                Err(_) => Some(DEFAULT_MAX_DIFF_LINES),
+            },
+            diff_context: var(DIFF_CONTEXT_ENV_VAR).parse().unwrap_or(DEFAULT_DIFF_CONTEXT),
+        }
+    }
+
+    /// Sets whether to color output.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets whether to use unicode symbols.
+    pub fn with_unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Sets the number of characters values are truncated to, or [`None`] to show them in full.
+    pub fn with_max_length(mut self, max_length: Option<usize>) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Sets the number of lines diffs are rendered in full up to, or [`None`] to always render them in full.
+    pub fn with_max_diff_lines(mut self, max_diff_lines: Option<usize>) -> Self {
+        self.max_diff_lines = max_diff_lines;
+        self
+    }
+
+    /// Sets the number of unchanged lines shown around the difference of a long diff.
+    pub fn with_diff_context(mut self, diff_context: usize) -> Self {
+        self.diff_context = diff_context;
+        self
+    }
+
+    /// Returns the symbol marking elided text, `…` or `...`.
+    pub fn ellipsis(&self) -> &'static str {
+        if self.unicode { "…" } else { "..." }
+    }
+
+    /// Returns the symbol between the bounds of a range, `–` or `-`.
+    pub fn range_separator(&self) -> &'static str {
+        if self.unicode { "–" } else { "-" }
+    }
+
+    /// Returns the symbol between a value and its tolerance, `±` or `+/-`.
+    pub fn plus_minus(&self) -> &'static str {
+        if self.unicode { "±" } else { "+/-" }
+    }
+
+    /// Returns the value, truncated to [`OutputConfig::max_length`] characters followed by the number of characters
+    /// left out.
+    pub fn truncate(&self, value: &str) -> String {
+        match self.max_length {
+            Some(max_length) if value.chars().count() > max_length => {
+                let kept: String = value.chars().take(max_length).collect();
+                let omitted = value.chars().count() - max_length;
+                format!("{}{} ({} more characters)", kept, self.ellipsis(), omitted)
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Wraps the text in the ANSI escape code when color is enabled.
+    pub(crate) fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Returns the configuration failures are rendered with: the one set with [`set_config`], or else
+/// [`OutputConfig::from_env`].
+pub fn config() -> OutputConfig {
+    CONFIG
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .unwrap_or_else(OutputConfig::from_env)
+}
+
+/// Sets the configuration failures are rendered with, for every test in the process, instead of the environment.
+pub fn set_config(config: OutputConfig) {
+    *CONFIG.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(config);
+}
+
+/// Forgets the configuration set with [`set_config`], so it is read from the environment again.
+pub fn reset_config() {
+    *CONFIG.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
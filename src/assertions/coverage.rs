@@ -0,0 +1,141 @@
+//! A registry of the error classifications the kernel error assertions observed, to prove the error-path tests
+//! exercise every [`nape_kernel::error::Kind`] and [`nape_kernel::error::Audience`].
+//!
+//! Every assertion that is given a kernel error, such as `kernel_error_eq`, `assert_kind`, `assert_audience`, the
+//! fluent `assert_kernel_error`, the negative `kernel_error_not` and `assert_no_error_of_kind`, the soft
+//! `soft_kernel_error_eq`, and their `check_*` twins, records the kind and audience of the error.  At the end of the suite,
+//! [`assert_all_error_kinds_covered`](crate::assert_all_error_kinds_covered) fails with the classifications no
+//! assertion observed.  The registry is per process, and libtest runs tests in parallel with no end of suite, so the
+//! check belongs in a [`TestHarness`](crate::harness::TestHarness) suite assertion:
+//!
+//! ```no_run
+//! use attestify_test_framework::assert_all_error_kinds_covered;
+//! use attestify_test_framework::harness::TestHarness;
+//! # #[derive(Debug)] enum Kind { InvalidInput, GatewayError }
+//! # #[derive(Debug)] enum Audience { User, System }
+//!
+//! fn main() -> std::process::ExitCode {
+//!     TestHarness::new("error_paths", || ())
+//!         // .test(...) for every error path
+//!         .suite_assertion("error_kinds_covered", || {
+//!             assert_all_error_kinds_covered!(
+//!                 [Kind::InvalidInput, Kind::GatewayError],
+//!                 [Audience::User, Audience::System]
+//!             );
+//!         })
+//!         .run()
+//! }
+//! ```
+
+use super::report::{self, MismatchReport};
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// The `(kind, audience)` pairs observed, rendered with [`Debug`].
+static OBSERVED: Mutex<BTreeSet<(String, String)>> = Mutex::new(BTreeSet::new());
+
+/// Records that an assertion observed an error of the kind and audience.  Called by the kernel error assertions.
+pub fn observe(kind: &dyn Debug, audience: &dyn Debug) {
+    OBSERVED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert((format!("{:?}", kind), format!("{:?}", audience)));
+}
+
+/// Returns the `(kind, audience)` pairs observed so far, rendered with [`Debug`], in order.
+pub fn observed_error_kinds() -> BTreeSet<(String, String)> {
+    OBSERVED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Forgets the observed pairs, e.g. between suites run by one process.
+pub fn reset_observed_error_kinds() {
+    OBSERVED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+}
+
+/// Checks every kind was observed with every audience, or with any audience when none are given.  See
+/// [`check_all_error_kinds_covered`](crate::check_all_error_kinds_covered).
+pub fn check_error_kinds_covered(kinds: &[&dyn Debug], audiences: &[&dyn Debug]) -> Result<(), MismatchReport> {
+    let observed = observed_error_kinds();
+    let missing: Vec<String> = if audiences.is_empty() {
+        kinds
+            .iter()
+            .map(|kind| format!("{:?}", kind))
+            .filter(|kind| !observed.iter().any(|(observed, _)| observed == kind))
+            .collect()
+    } else {
+        kinds
+            .iter()
+            .flat_map(|kind| audiences.iter().map(move |audience| (format!("{:?}", kind), format!("{:?}", audience))))
+            .filter(|pair| !observed.contains(pair))
+            .map(|(kind, audience)| format!("{}, {}", kind, audience))
+            .collect()
+    };
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let observed: Vec<String> = observed
+        .iter()
+        .map(|(kind, audience)| format!("\t\t{}, {}", kind, audience))
+        .collect();
+    let observed = if observed.is_empty() {
+        "\t\t<none>".to_string()
+    } else {
+        observed.join("\n")
+    };
+    Err(MismatchReport::new(
+        "error kinds",
+        format!("{} error classifications were not observed by any assertion.", missing.len()),
+    )
+    .hint(format!(
+        "\tMissing:\n{}\n\tObserved:\n{}",
+        missing.iter().map(|missing| format!("\t\t{}", missing)).collect::<Vec<_>>().join("\n"),
+        observed
+    )))
+}
+
+/// Asserts every kind was observed with every audience, or with any audience when none are given.  See
+/// [`assert_all_error_kinds_covered`](crate::assert_all_error_kinds_covered).
+pub fn assert_error_kinds_covered(kinds: &[&dyn Debug], audiences: &[&dyn Debug]) {
+    report::enforce(check_error_kinds_covered(kinds, audiences));
+}
+
+/// Asserts that the kernel error assertions run so far observed every error kind, or every combination of kind and
+/// audience.  On failure it lists the missing and the observed classifications.
+///
+/// # Arguments
+///
+/// * `[$kind, ...]` - Every kind that must be observed. Should be of type [`nape_kernel::error::Kind`].
+/// * `[$audience, ...]` - Optional. Every audience each kind must be observed with. Should be of type
+///   [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! assert_all_error_kinds_covered {
+    ([$($kind:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::coverage::assert_error_kinds_covered(&[$(&$kind),*], &[])
+    };
+    ([$($kind:expr),* $(,)?], [$($audience:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::coverage::assert_error_kinds_covered(&[$(&$kind),*], &[$(&$audience),*])
+    };
+}
+
+/// Checks that the kernel error assertions run so far observed every error kind, or every combination of kind and
+/// audience, like [`assert_all_error_kinds_covered`](crate::assert_all_error_kinds_covered), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `[$kind, ...]` - Every kind that must be observed. Should be of type [`nape_kernel::error::Kind`].
+/// * `[$audience, ...]` - Optional. Every audience each kind must be observed with. Should be of type
+///   [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! check_all_error_kinds_covered {
+    ([$($kind:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::coverage::check_error_kinds_covered(&[$(&$kind),*], &[])
+    };
+    ([$($kind:expr),* $(,)?], [$($audience:expr),* $(,)?] $(,)?) => {
+        $crate::assertions::coverage::check_error_kinds_covered(&[$(&$kind),*], &[$(&$audience),*])
+    };
+}
@@ -0,0 +1,225 @@
+use super::report::{self, MismatchReport};
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A digest algorithm supported by the digest assertions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    /// Returns the digest of the bytes.
+    pub fn digest(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+            Algorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Algorithm::Sha256 => f.write_str("SHA-256"),
+            Algorithm::Sha512 => f.write_str("SHA-512"),
+        }
+    }
+}
+
+/// Returns the bytes as lowercase hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Returns the SHA-256 digest of the bytes as lowercase hex.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Algorithm::Sha256.digest(bytes))
+}
+
+/// Decodes an expected digest given as hex, in either case, or as base64, in the standard or URL-safe alphabet with
+/// or without padding.  Returns [`None`] when it is neither.
+///
+/// # Arguments
+///
+/// * `expected` - The encoded digest.
+/// * `length` - The length of the digest in bytes, used to tell hex from base64.
+///
+pub fn decode_expected(expected: &str, length: usize) -> Option<Vec<u8>> {
+    let expected = expected.trim();
+    if expected.len() == length * 2 && expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return (0..expected.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&expected[index..index + 2], 16).ok())
+            .collect();
+    }
+    [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(expected).ok())
+}
+
+/// Checks the digest of the bytes equals the expected hex or base64 digest.  See
+/// [`check_bytes_digest`](crate::check_bytes_digest).
+pub fn check_bytes_digest(algorithm: Algorithm, bytes: &[u8], expected: &str) -> Result<(), MismatchReport> {
+    let actual = algorithm.digest(bytes);
+    check(algorithm, &actual, expected, "bytes")
+}
+
+/// Asserts the digest of the bytes equals the expected hex or base64 digest.  See
+/// [`assert_bytes_digest`](crate::assert_bytes_digest).
+pub fn assert_bytes_digest(algorithm: Algorithm, bytes: &[u8], expected: &str) {
+    report::enforce(check_bytes_digest(algorithm, bytes, expected));
+}
+
+/// Checks the digest of the file contents equals the expected hex or base64 digest.  See
+/// [`check_file_sha256`](crate::check_file_sha256).
+pub fn check_file_digest(algorithm: Algorithm, path: &Path, expected: &str) -> Result<(), MismatchReport> {
+    let contents = fs::read(path).map_err(|err| {
+        MismatchReport::new("file", format!("Could not read the file '{}': {}", path.display(), err))
+    })?;
+    let actual = algorithm.digest(&contents);
+    check(algorithm, &actual, expected, &format!("file '{}'", path.display()))
+}
+
+/// Asserts the digest of the file contents equals the expected hex or base64 digest.  See
+/// [`assert_file_sha256`](crate::assert_file_sha256).
+pub fn assert_file_digest(algorithm: Algorithm, path: &Path, expected: &str) {
+    report::enforce(check_file_digest(algorithm, path, expected));
+}
+
+fn check(algorithm: Algorithm, actual: &[u8], expected: &str, subject: &str) -> Result<(), MismatchReport> {
+    let expected_bytes = decode_expected(expected, actual.len()).ok_or_else(|| {
+        MismatchReport::new("digest", format!("The expected {} digest is neither hex nor base64.", algorithm))
+            .expected(expected)
+    })?;
+
+    if expected_bytes != actual {
+        return Err(
+            MismatchReport::new("digest", format!("The {} digest of the {} does not match.", algorithm, subject))
+                .expected(expected.trim())
+                .actual(format!("{} (hex), {} (base64)", to_hex(actual), STANDARD.encode(actual))),
+        );
+    }
+    Ok(())
+}
+
+/// Asserts that the SHA-256 digest of a file's contents equals the expected digest.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the file, anything that implements `AsRef<Path>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! assert_file_sha256 {
+    ($path:expr, $expected:expr) => {{
+        $crate::assertions::digest::assert_file_digest(
+            $crate::assertions::digest::Algorithm::Sha256,
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$expected,
+        )
+    }};
+}
+
+/// Asserts that the SHA-512 digest of a file's contents equals the expected digest.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the file, anything that implements `AsRef<Path>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! assert_file_sha512 {
+    ($path:expr, $expected:expr) => {{
+        $crate::assertions::digest::assert_file_digest(
+            $crate::assertions::digest::Algorithm::Sha512,
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$expected,
+        )
+    }};
+}
+
+/// Asserts that the digest of some bytes equals the expected digest.
+///
+/// # Arguments
+///
+/// * `$algorithm` - The digest algorithm, `Sha256` or `Sha512`.
+/// * `$bytes` - The bytes, anything that implements `AsRef<[u8]>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! assert_bytes_digest {
+    ($algorithm:ident, $bytes:expr, $expected:expr) => {{
+        $crate::assertions::digest::assert_bytes_digest(
+            $crate::assertions::digest::Algorithm::$algorithm,
+            ::std::convert::AsRef::<[u8]>::as_ref(&$bytes),
+            &$expected,
+        )
+    }};
+}
+
+/// Checks that the SHA-256 digest of a file's contents equals the expected digest, like
+/// [`assert_file_sha256`](crate::assert_file_sha256), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the file, anything that implements `AsRef<Path>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! check_file_sha256 {
+    ($path:expr, $expected:expr) => {{
+        $crate::assertions::digest::check_file_digest(
+            $crate::assertions::digest::Algorithm::Sha256,
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$expected,
+        )
+    }};
+}
+
+/// Checks that the SHA-512 digest of a file's contents equals the expected digest, like
+/// [`assert_file_sha512`](crate::assert_file_sha512), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path to the file, anything that implements `AsRef<Path>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! check_file_sha512 {
+    ($path:expr, $expected:expr) => {{
+        $crate::assertions::digest::check_file_digest(
+            $crate::assertions::digest::Algorithm::Sha512,
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            &$expected,
+        )
+    }};
+}
+
+/// Checks that the digest of some bytes equals the expected digest, like
+/// [`assert_bytes_digest`](crate::assert_bytes_digest), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$algorithm` - The digest algorithm, `Sha256` or `Sha512`.
+/// * `$bytes` - The bytes, anything that implements `AsRef<[u8]>`.
+/// * `$expected` - The expected digest as a `&str`, in hex or base64.
+///
+#[macro_export]
+macro_rules! check_bytes_digest {
+    ($algorithm:ident, $bytes:expr, $expected:expr) => {{
+        $crate::assertions::digest::check_bytes_digest(
+            $crate::assertions::digest::Algorithm::$algorithm,
+            ::std::convert::AsRef::<[u8]>::as_ref(&$bytes),
+            &$expected,
+        )
+    }};
+}
@@ -0,0 +1,236 @@
+use super::report::{self, MismatchReport};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// How two directory trees differ.  Paths are relative to the compared directories and use `/` separators.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirDifference {
+    /// The paths present in the expected tree but not in the actual tree.
+    pub missing: Vec<String>,
+    /// The paths present in the actual tree but not in the expected tree.
+    pub extra: Vec<String>,
+    /// The paths present in both trees whose contents, or whose type, differ.
+    pub differing: Vec<String>,
+}
+
+impl DirDifference {
+    /// Returns true when the trees are equal.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.differing.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Entry {
+    Dir,
+    File(Vec<u8>),
+}
+
+/// Returns true when the relative path, or one of its parent directories, matches the glob pattern.
+///
+/// `*` matches any characters except `/`, `?` matches one character except `/`, and `**` matches any number of
+/// directories, e.g. `**/*.tmp` or `logs/**`.
+///
+/// # Arguments
+///
+/// * `pattern` - The glob pattern.
+/// * `path` - The relative path, with `/` separators.
+///
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut prefix = String::new();
+    for (index, segment) in path.split('/').enumerate() {
+        if index > 0 {
+            prefix.push('/');
+        }
+        prefix.push_str(segment);
+        if matches_at(&pattern, &prefix.chars().collect::<Vec<char>>()) {
+            return true;
+        }
+    }
+    false
+}
+
+fn matches_at(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            matches_at(rest, path)
+                || path
+                    .iter()
+                    .enumerate()
+                    .any(|(index, c)| *c == '/' && matches_at(rest, &path[index + 1..]))
+        }
+        ['*', '*', rest @ ..] => (0..=path.len()).any(|index| matches_at(rest, &path[index..])),
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|index| *index == 0 || path[index - 1] != '/')
+            .any(|index| matches_at(rest, &path[index..])),
+        ['?', rest @ ..] => matches!(path.first(), Some(c) if *c != '/') && matches_at(rest, &path[1..]),
+        [c, rest @ ..] => path.first() == Some(c) && matches_at(rest, &path[1..]),
+    }
+}
+
+/// Compares two directory trees by relative path and file content.
+///
+/// # Arguments
+///
+/// * `expected` - The expected directory.
+/// * `actual` - The actual directory.
+/// * `exclude` - Glob patterns of relative paths to skip in both trees, see [`glob_matches`].
+///
+pub fn compare_dirs(expected: &Path, actual: &Path, exclude: &[&str]) -> DirDifference {
+    let expected_entries = entries(expected, exclude);
+    let actual_entries = entries(actual, exclude);
+    let mut difference = DirDifference::default();
+
+    for (path, expected_entry) in &expected_entries {
+        match actual_entries.get(path) {
+            None => difference.missing.push(path.clone()),
+            Some(actual_entry) if actual_entry != expected_entry => difference.differing.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    difference.extra = actual_entries
+        .keys()
+        .filter(|path| !expected_entries.contains_key(*path))
+        .cloned()
+        .collect();
+
+    difference
+}
+
+fn entries(root: &Path, exclude: &[&str]) -> BTreeMap<String, Entry> {
+    if !root.is_dir() {
+        report::fail(
+            MismatchReport::new("directory", format!("The directory '{}' does not exist.", root.display()))
+                .expected("a directory")
+                .actual(if root.exists() { "a file" } else { "nothing" }),
+        );
+    }
+    let mut entries = BTreeMap::new();
+    collect(root, "", exclude, &mut entries);
+    entries
+}
+
+fn collect(dir: &Path, prefix: &str, exclude: &[&str], entries: &mut BTreeMap<String, Entry>) {
+    let children = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Could not read the directory '{}': {}", dir.display(), err));
+    for child in children {
+        let child = child.unwrap_or_else(|err| panic!("Could not read the directory '{}': {}", dir.display(), err));
+        let relative = format!("{}{}", prefix, child.file_name().to_string_lossy());
+        if exclude.iter().any(|pattern| glob_matches(pattern, &relative)) {
+            continue;
+        }
+        let path = child.path();
+        if path.is_dir() {
+            entries.insert(relative.clone(), Entry::Dir);
+            collect(&path, &format!("{}/", relative), exclude, entries);
+        } else {
+            let contents = fs::read(&path)
+                .unwrap_or_else(|err| panic!("Could not read the file '{}': {}", path.display(), err));
+            entries.insert(relative, Entry::File(contents));
+        }
+    }
+}
+
+/// Checks the directory trees are equal.  See [`check_dir_eq`](crate::check_dir_eq).
+pub fn check_dir_eq(expected: &Path, actual: &Path, exclude: &[&str]) -> Result<(), MismatchReport> {
+    for dir in [expected, actual] {
+        if !dir.is_dir() {
+            return Err(MismatchReport::new(
+                "directory",
+                format!("The directory '{}' does not exist.", dir.display()),
+            ));
+        }
+    }
+    let difference = compare_dirs(expected, actual, exclude);
+    if difference.is_empty() {
+        return Ok(());
+    }
+
+    let mut hint = String::new();
+    for path in &difference.missing {
+        hint.push_str(&format!("\tMissing:\t{}\n", path));
+    }
+    for path in &difference.extra {
+        hint.push_str(&format!("\tExtra:\t{}\n", path));
+    }
+    for path in &difference.differing {
+        hint.push_str(&format!("\tDiffering:\t{}\n", path));
+        let expected_text = fs::read_to_string(expected.join(path));
+        let actual_text = fs::read_to_string(actual.join(path));
+        if let (Ok(expected_text), Ok(actual_text)) = (expected_text, actual_text) {
+            hint.push_str(&crate::diff::render(&expected_text, &actual_text));
+        }
+    }
+    Err(MismatchReport::new(
+        "directory",
+        format!(
+            "The directory '{}' does not match the expected directory '{}'.",
+            actual.display(),
+            expected.display()
+        ),
+    )
+    .hint(hint))
+}
+
+/// Asserts the directory trees are equal.  See [`assert_dir_eq`](crate::assert_dir_eq).
+pub fn assert_dir_eq(expected: &Path, actual: &Path, exclude: &[&str]) {
+    report::enforce(check_dir_eq(expected, actual, exclude));
+}
+
+/// Asserts that two directory trees hold the same files and directories, with the same contents.  On failure it
+/// lists the missing, extra, and differing paths, with a diff for each differing text file.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected directory, anything that implements `AsRef<Path>`.
+/// * `$actual` - The actual directory, anything that implements `AsRef<Path>`.
+/// * `exclude` - Optional, glob patterns of relative paths to skip, e.g. `exclude = ["**/*.log", "tmp"]`.
+///
+#[macro_export]
+macro_rules! assert_dir_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::directory::assert_dir_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+            &[],
+        )
+    }};
+    ($expected:expr, $actual:expr, exclude = [$($exclude:expr),* $(,)?]) => {{
+        $crate::assertions::directory::assert_dir_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+            &[$($exclude),*],
+        )
+    }};
+}
+
+/// Checks that two directory trees hold the same files and directories, like
+/// [`assert_dir_eq`](crate::assert_dir_eq), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected directory, anything that implements `AsRef<Path>`.
+/// * `$actual` - The actual directory, anything that implements `AsRef<Path>`.
+/// * `exclude` - Optional, glob patterns of relative paths to skip, e.g. `exclude = ["**/*.log", "tmp"]`.
+///
+#[macro_export]
+macro_rules! check_dir_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::directory::check_dir_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+            &[],
+        )
+    }};
+    ($expected:expr, $actual:expr, exclude = [$($exclude:expr),* $(,)?]) => {{
+        $crate::assertions::directory::check_dir_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+            &[$($exclude),*],
+        )
+    }};
+}
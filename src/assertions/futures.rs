@@ -0,0 +1,129 @@
+//! Async variants of the assertion macros.
+//!
+//! Each macro awaits the future it is given and then applies the same checks as its synchronous counterpart, so they
+//! can only be used inside an `async` block or function, such as an async test.
+
+/// Awaits a future resolving to a [`Result`], asserts it is an [`Ok`], and returns the value.  See [`is_ok`](crate::is_ok).
+///
+/// # Arguments
+///
+/// * `$future` - A future that is expected to resolve to an [`Ok`].
+///
+#[macro_export]
+macro_rules! async_is_ok {
+    ($future:expr) => {
+        $crate::is_ok!($future.await)
+    };
+}
+
+/// Awaits a future resolving to a [`Result`], asserts it is an [`Err`], and returns the error.  See
+/// [`is_error`](crate::is_error).
+///
+/// # Arguments
+///
+/// * `$future` - A future that is expected to resolve to an [`Err`].
+///
+#[macro_export]
+macro_rules! async_is_error {
+    ($future:expr) => {
+        $crate::is_error!($future.await)
+    };
+}
+
+/// Awaits a future and asserts its [`nape_kernel::error::Error`] matches the expected kind, audience, and message.
+/// See [`kernel_error_eq`](crate::kernel_error_eq).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected error message. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! async_kernel_error_eq {
+    ($future:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::kernel_error_eq!($future.await, $expected_kind, $expected_audience, $expected_message)
+    };
+}
+
+/// Awaits a future and asserts its [`nape_kernel::error::Error`] matches the expected kind and audience, and has a
+/// message.  See [`kernel_error_has_message`](crate::kernel_error_has_message).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+///
+#[macro_export]
+macro_rules! async_kernel_error_has_message {
+    ($future:expr, $expected_kind:expr, $expected_audience:expr) => {
+        $crate::kernel_error_has_message!($future.await, $expected_kind, $expected_audience)
+    };
+}
+
+/// Awaits a future and asserts its [`nape_kernel::error::Error`] has the expected kind and audience, and the message
+/// starts with a specific phrase.  See [`kernel_error_starts_with`](crate::kernel_error_starts_with).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! async_kernel_error_starts_with {
+    ($future:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::kernel_error_starts_with!($future.await, $expected_kind, $expected_audience, $expected_message)
+    };
+}
+
+/// Awaits a future and asserts its [`nape_kernel::error::Error`] has the expected kind and audience, and the message
+/// contains a specific phrase.  See [`kernel_error_contains`](crate::kernel_error_contains).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected message phrase. Should be a [`String`].
+///
+#[macro_export]
+macro_rules! async_kernel_error_contains {
+    ($future:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        $crate::kernel_error_contains!($future.await, $expected_kind, $expected_audience, $expected_message)
+    };
+}
+
+/// Awaits a future and asserts its [`nape_kernel::error::Error`] has the expected kind and audience, and the message
+/// matches a regular expression.  See [`kernel_error_matches`](crate::kernel_error_matches).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_pattern` - The regular expression the message must match. Should be a `&str`.
+///
+#[macro_export]
+macro_rules! async_kernel_error_matches {
+    ($future:expr, $expected_kind:expr, $expected_audience:expr, $expected_pattern:expr) => {
+        $crate::kernel_error_matches!($future.await, $expected_kind, $expected_audience, $expected_pattern)
+    };
+}
+
+/// Awaits a future and starts a fluent [`crate::matchers::KernelErrorAssert`] over its
+/// [`nape_kernel::error::Error`].  See [`assert_kernel_error`](crate::assert_kernel_error).
+///
+/// # Arguments
+///
+/// * `$future` - A future resolving to a `Result` that is expected to be an [`nape_kernel::error::Error`].
+///
+#[macro_export]
+macro_rules! async_assert_kernel_error {
+    ($future:expr) => {
+        $crate::assert_kernel_error!($future.await)
+    };
+}
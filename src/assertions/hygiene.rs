@@ -0,0 +1,136 @@
+use super::report::{self, MismatchReport};
+use regex::Regex;
+
+/// The markers of internal detail that must not appear in the message of an error shown to a user, as pairs of a
+/// description and a regular expression.
+pub const INTERNAL_DETAIL_MARKERS: &[(&str, &str)] = &[
+    ("absolute file path", r#"(?:^|[\s'"`(=:])/(?:[\w.@-]+/)+[\w.@-]+"#),
+    ("absolute file path", r"\b[A-Za-z]:\\(?:[\w .@-]+\\)*[\w.@-]+"),
+    ("panic message", r"panicked at"),
+    ("source location", r"\.rs:\d+"),
+    ("struct debug dump", r"\b[A-Z]\w*\s*\{\s*[a-z_]\w*:\s"),
+];
+
+/// Returns the description of the first marker of internal detail found in the message, checking the
+/// [`INTERNAL_DETAIL_MARKERS`] and then the extra patterns, whose description is the pattern itself.
+///
+/// # Arguments
+///
+/// * `message` - The message of an error shown to a user.
+/// * `extra_patterns` - Further regular expressions marking internal detail, e.g. SQL or hostnames.
+///
+pub fn find_internal_detail(message: &str, extra_patterns: &[&str]) -> Option<String> {
+    let markers = INTERNAL_DETAIL_MARKERS
+        .iter()
+        .copied()
+        .chain(extra_patterns.iter().map(|pattern| (*pattern, *pattern)));
+
+    for (description, pattern) in markers {
+        let regex = Regex::new(pattern)
+            .unwrap_or_else(|err| panic!("The pattern '{}' is not a valid regex:\n\t{}\n", pattern, err));
+        if regex.is_match(message) {
+            return Some(description.to_string());
+        }
+    }
+    None
+}
+
+/// Checks none of the messages contain internal detail.  See
+/// [`check_no_system_details_for_user_audience`](crate::check_no_system_details_for_user_audience).
+///
+/// # Arguments
+///
+/// * `messages` - The messages of the errors for the user audience, with their index in the scanned collection.
+/// * `extra_patterns` - Further regular expressions marking internal detail.
+///
+pub fn check_user_messages(messages: &[(usize, String)], extra_patterns: &[&str]) -> Result<(), MismatchReport> {
+    let offending: Vec<String> = messages
+        .iter()
+        .filter_map(|(index, message)| {
+            find_internal_detail(message, extra_patterns)
+                .map(|marker| format!("\t[{}] {}: {:?}", index, marker, message))
+        })
+        .collect();
+    if offending.is_empty() {
+        return Ok(());
+    }
+    Err(MismatchReport::new(
+        "message",
+        format!(
+            "No User audience Error was expected to expose internal details, although {} did:",
+            offending.len()
+        ),
+    )
+    .hint(offending.join("\n")))
+}
+
+/// Asserts none of the messages contain internal detail.  See
+/// [`assert_no_system_details_for_user_audience`](crate::assert_no_system_details_for_user_audience).
+pub fn assert_user_messages(messages: &[(usize, String)], extra_patterns: &[&str]) {
+    report::enforce(check_user_messages(messages, extra_patterns));
+}
+
+/// Asserts that no [`nape_kernel::error::Error`] for the `User` audience in a collection exposes internal details
+/// in its message: absolute file paths, panic messages, source locations, or struct debug dumps such as
+/// `Config { path: ... }`.  On failure it lists every offending error with its index and the marker found.
+///
+/// The audience is compared by its [`Debug`](std::fmt::Debug) rendering, so any error type with an `audience` field
+/// whose user variant is named `User` can be scanned.
+///
+/// # Arguments
+///
+/// * `$errors` - Anything that can be iterated into [`nape_kernel::error::Error`]s or references to them.
+/// * `[$pattern, ...]` - Optional. Further regular expressions marking internal detail, e.g. `r"SELECT .* FROM"`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_no_system_details_for_user_audience;
+/// # #[derive(Debug)] enum Audience { User }
+/// # struct Error { audience: Audience, message: String }
+/// # let errors: Vec<Error> = Vec::new();
+///
+/// assert_no_system_details_for_user_audience!(&errors);
+/// assert_no_system_details_for_user_audience!(&errors, [r"SELECT .* FROM", r"\.internal\b"]);
+/// ```
+#[macro_export]
+macro_rules! assert_no_system_details_for_user_audience {
+    ($errors:expr $(, [$($pattern:expr),* $(,)?])?) => {
+        $crate::assertions::hygiene::assert_user_messages(
+            &$crate::__user_audience_messages!($errors),
+            &[$($($pattern),*)?],
+        )
+    };
+}
+
+/// Checks that no [`nape_kernel::error::Error`] for the `User` audience in a collection exposes internal details,
+/// like [`assert_no_system_details_for_user_audience`](crate::assert_no_system_details_for_user_audience), returning
+/// a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$errors` - Anything that can be iterated into [`nape_kernel::error::Error`]s or references to them.
+/// * `[$pattern, ...]` - Optional. Further regular expressions marking internal detail.
+///
+#[macro_export]
+macro_rules! check_no_system_details_for_user_audience {
+    ($errors:expr $(, [$($pattern:expr),* $(,)?])?) => {
+        $crate::assertions::hygiene::check_user_messages(
+            &$crate::__user_audience_messages!($errors),
+            &[$($($pattern),*)?],
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __user_audience_messages {
+    ($errors:expr) => {
+        $errors
+            .into_iter()
+            .enumerate()
+            .filter(|(_, e)| format!("{:?}", e.audience) == "User")
+            .map(|(index, e)| (index, e.message.to_string()))
+            .collect::<Vec<(usize, String)>>()
+    };
+}
@@ -0,0 +1,370 @@
+use super::report::{self, MismatchReport};
+use serde_json::Value;
+
+/// A JSON document given to the JSON assertions, either as text or as an already parsed [`Value`].
+pub trait JsonInput {
+    /// Returns the document as a [`Value`], or a report when it is not valid JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - What the document is, used in the report, e.g. `expected`.
+    ///
+    fn try_to_json_value(&self, description: &str) -> Result<Value, MismatchReport>;
+
+    /// Returns the document as a [`Value`], panicking with a clear message when it is not valid JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - What the document is, used in the failure message, e.g. `expected`.
+    ///
+    fn to_json_value(&self, description: &str) -> Value {
+        self.try_to_json_value(description)
+            .unwrap_or_else(|report| report::fail(report))
+    }
+}
+
+impl JsonInput for str {
+    fn try_to_json_value(&self, description: &str) -> Result<Value, MismatchReport> {
+        serde_json::from_str(self).map_err(|err| {
+            MismatchReport::new(description, format!("The {} document is not valid JSON.", description))
+                .hint(format!("\t{}", err))
+        })
+    }
+}
+
+impl JsonInput for String {
+    fn try_to_json_value(&self, description: &str) -> Result<Value, MismatchReport> {
+        self.as_str().try_to_json_value(description)
+    }
+}
+
+impl JsonInput for Value {
+    fn try_to_json_value(&self, _description: &str) -> Result<Value, MismatchReport> {
+        Ok(self.clone())
+    }
+}
+
+impl<T: JsonInput + ?Sized> JsonInput for &T {
+    fn try_to_json_value(&self, description: &str) -> Result<Value, MismatchReport> {
+        (**self).try_to_json_value(description)
+    }
+}
+
+/// Where two JSON documents first diverge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDifference {
+    /// The JSON pointer to the divergent node, e.g. `/metadata/owner`.
+    pub pointer: String,
+    /// The expected node, or `<missing>`.
+    pub expected: String,
+    /// The actual node, or `<missing>`.
+    pub actual: String,
+}
+
+/// How two JSON documents are compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// The documents must be equal.
+    Strict,
+    /// Every node of the expected document must be in the actual document, which may have extra object members
+    /// and extra trailing array elements.
+    Includes,
+}
+
+/// Returns where the two JSON values first diverge, or [`None`] when they match.
+///
+/// # Arguments
+///
+/// * `expected` - The expected value.
+/// * `actual` - The actual value.
+/// * `mode` - Whether the actual value must equal, or only include, the expected value.
+/// * `ignore` - Paths to skip, such as `$.metadata.timestamp` or `$.evidence[*].id`, where `*` matches any member
+///   or index.
+///
+pub fn first_difference(expected: &Value, actual: &Value, mode: Mode, ignore: &[&str]) -> Option<JsonDifference> {
+    differences(expected, actual, mode, ignore).into_iter().next()
+}
+
+/// Returns every node where the two JSON values diverge, in document order, or an empty list when they match.  A
+/// node that differs is reported once, without the nodes below it.
+///
+/// # Arguments
+///
+/// * `expected` - The expected value.
+/// * `actual` - The actual value.
+/// * `mode` - Whether the actual value must equal, or only include, the expected value.
+/// * `ignore` - Paths to skip, such as `$.metadata.timestamp` or `$.evidence[*].id`, where `*` matches any member
+///   or index.
+///
+pub fn differences(expected: &Value, actual: &Value, mode: Mode, ignore: &[&str]) -> Vec<JsonDifference> {
+    let ignore: Vec<Vec<String>> = ignore.iter().map(|path| path_segments(path)).collect();
+    differences_below(&[], expected, actual, mode, &ignore)
+}
+
+/// Returns the differences like [`differences`], for values found at the given segments of a larger document, with
+/// the ignored paths already split into segments and relative to that document.
+pub(crate) fn differences_below(
+    segments: &[String],
+    expected: &Value,
+    actual: &Value,
+    mode: Mode,
+    ignore: &[Vec<String>],
+) -> Vec<JsonDifference> {
+    let mut found = Vec::new();
+    differences_at(&mut segments.to_vec(), expected, actual, mode, ignore, &mut found);
+    found
+}
+
+fn differences_at(
+    segments: &mut Vec<String>,
+    expected: &Value,
+    actual: &Value,
+    mode: Mode,
+    ignore: &[Vec<String>],
+    found: &mut Vec<JsonDifference>,
+) {
+    if is_ignored(segments, ignore) {
+        return;
+    }
+
+    match (expected, actual) {
+        (Value::Object(expected_map), Value::Object(actual_map)) => {
+            for (key, expected_value) in expected_map {
+                segments.push(key.clone());
+                match actual_map.get(key) {
+                    Some(actual_value) => differences_at(segments, expected_value, actual_value, mode, ignore, found),
+                    None if is_ignored(segments, ignore) => {}
+                    None => found.push(difference(segments, Some(expected_value), None)),
+                }
+                segments.pop();
+            }
+            if mode == Mode::Strict {
+                for (key, actual_value) in actual_map {
+                    if !expected_map.contains_key(key) {
+                        segments.push(key.clone());
+                        if !is_ignored(segments, ignore) {
+                            found.push(difference(segments, None, Some(actual_value)));
+                        }
+                        segments.pop();
+                    }
+                }
+            }
+        }
+        (Value::Array(expected_items), Value::Array(actual_items)) => {
+            let length = match mode {
+                Mode::Strict => expected_items.len().max(actual_items.len()),
+                Mode::Includes => expected_items.len(),
+            };
+            for index in 0..length {
+                segments.push(index.to_string());
+                match (expected_items.get(index), actual_items.get(index)) {
+                    (Some(expected_item), Some(actual_item)) => {
+                        differences_at(segments, expected_item, actual_item, mode, ignore, found)
+                    }
+                    _ if is_ignored(segments, ignore) => {}
+                    (expected_item, actual_item) => found.push(difference(segments, expected_item, actual_item)),
+                }
+                segments.pop();
+            }
+        }
+        _ if expected == actual => {}
+        _ => found.push(difference(segments, Some(expected), Some(actual))),
+    }
+}
+
+pub(crate) fn difference(segments: &[String], expected: Option<&Value>, actual: Option<&Value>) -> JsonDifference {
+    JsonDifference {
+        pointer: pointer(segments),
+        expected: expected.map(Value::to_string).unwrap_or_else(|| "<missing>".to_string()),
+        actual: actual.map(Value::to_string).unwrap_or_else(|| "<missing>".to_string()),
+    }
+}
+
+/// Renders the segments as a JSON pointer, escaping `~` and `/` in member names.
+fn pointer(segments: &[String]) -> String {
+    if segments.is_empty() {
+        return "/".to_string();
+    }
+    segments
+        .iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+pub(crate) fn is_ignored(segments: &[String], ignore: &[Vec<String>]) -> bool {
+    ignore.iter().any(|pattern| {
+        pattern.len() == segments.len()
+            && pattern
+                .iter()
+                .zip(segments)
+                .all(|(pattern_segment, segment)| pattern_segment == "*" || pattern_segment == segment)
+    })
+}
+
+/// Splits a path such as `$.evidence[0].id` into its member names and indexes.
+pub(crate) fn path_segments(path: &str) -> Vec<String> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let mut rest = part;
+        if let Some(bracket) = rest.find('[') {
+            if bracket > 0 {
+                segments.push(rest[..bracket].to_string());
+            }
+            rest = &rest[bracket..];
+            while let Some(end) = rest.find(']') {
+                segments.push(rest[1..end].trim_matches(|c| c == '\'' || c == '"').to_string());
+                rest = &rest[end + 1..];
+            }
+        } else {
+            segments.push(rest.to_string());
+        }
+    }
+
+    segments
+}
+
+/// Checks the JSON documents are equal, or that the actual document includes the expected one, skipping the
+/// ignored paths.  See [`check_json_eq`](crate::check_json_eq) and
+/// [`check_json_includes`](crate::check_json_includes).
+pub fn check_json(
+    expected: &dyn JsonInput,
+    actual: &dyn JsonInput,
+    mode: Mode,
+    ignore: &[&str],
+) -> Result<(), MismatchReport> {
+    let expected_value = expected.try_to_json_value("expected")?;
+    let actual_value = actual.try_to_json_value("actual")?;
+
+    match first_difference(&expected_value, &actual_value, mode, ignore) {
+        Some(difference) => {
+            let description = match mode {
+                Mode::Strict => "The JSON documents differ",
+                Mode::Includes => "The actual JSON document does not include the expected document",
+            };
+            Err(
+                MismatchReport::new(&difference.pointer, format!("{} at '{}'.", description, difference.pointer))
+                    .expected(difference.expected)
+                    .actual(difference.actual),
+            )
+        }
+        None => Ok(()),
+    }
+}
+
+/// Asserts the JSON documents match in the given mode, see [`assert_json_eq`](crate::assert_json_eq) and
+/// [`assert_json_includes`](crate::assert_json_includes).
+pub fn assert_json(expected: &dyn JsonInput, actual: &dyn JsonInput, mode: Mode, ignore: &[&str]) {
+    report::enforce(check_json(expected, actual, mode, ignore));
+}
+
+/// Asserts that two JSON documents are structurally equal, ignoring member order and formatting.  On failure it
+/// reports the JSON pointer where the documents first diverge.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `$actual` - The actual JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `ignore` - Optional, paths to skip such as `["$.metadata.timestamp", "$.evidence[*].id"]`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_json_eq;
+///
+/// assert_json_eq!(
+///     r#"{"subject": "s1", "metadata": {"timestamp": "2024-01-01"}}"#,
+///     r#"{"metadata": {"timestamp": "2025-06-30"}, "subject": "s1"}"#,
+///     ignore = ["$.metadata.timestamp"]
+/// );
+/// ```
+///
+#[macro_export]
+macro_rules! assert_json_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::json::assert_json(&$expected, &$actual, $crate::assertions::json::Mode::Strict, &[])
+    }};
+    ($expected:expr, $actual:expr, ignore = [$($ignore:expr),* $(,)?]) => {{
+        $crate::assertions::json::assert_json(
+            &$expected,
+            &$actual,
+            $crate::assertions::json::Mode::Strict,
+            &[$($ignore),*],
+        )
+    }};
+}
+
+/// Asserts that a JSON document includes every node of an expected document.  The actual document may have extra
+/// object members and extra trailing array elements.  On failure it reports the JSON pointer of the first node
+/// that is missing or different.
+///
+/// # Arguments
+///
+/// * `$actual` - The actual JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `$expected` - The JSON that must be included, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `ignore` - Optional, paths to skip such as `["$.metadata.timestamp", "$.evidence[*].id"]`.
+///
+#[macro_export]
+macro_rules! assert_json_includes {
+    ($actual:expr, $expected:expr) => {{
+        $crate::assertions::json::assert_json(&$expected, &$actual, $crate::assertions::json::Mode::Includes, &[])
+    }};
+    ($actual:expr, $expected:expr, ignore = [$($ignore:expr),* $(,)?]) => {{
+        $crate::assertions::json::assert_json(
+            &$expected,
+            &$actual,
+            $crate::assertions::json::Mode::Includes,
+            &[$($ignore),*],
+        )
+    }};
+}
+
+/// Checks that two JSON documents are structurally equal, like [`assert_json_eq`](crate::assert_json_eq), returning
+/// a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `$actual` - The actual JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `ignore` - Optional, paths to skip such as `["$.metadata.timestamp", "$.evidence[*].id"]`.
+///
+#[macro_export]
+macro_rules! check_json_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::json::check_json(&$expected, &$actual, $crate::assertions::json::Mode::Strict, &[])
+    }};
+    ($expected:expr, $actual:expr, ignore = [$($ignore:expr),* $(,)?]) => {{
+        $crate::assertions::json::check_json(
+            &$expected,
+            &$actual,
+            $crate::assertions::json::Mode::Strict,
+            &[$($ignore),*],
+        )
+    }};
+}
+
+/// Checks that a JSON document includes every node of an expected document, like
+/// [`assert_json_includes`](crate::assert_json_includes), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - The actual JSON, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `$expected` - The JSON that must be included, as a `&str`, [`String`], or [`serde_json::Value`].
+/// * `ignore` - Optional, paths to skip such as `["$.metadata.timestamp", "$.evidence[*].id"]`.
+///
+#[macro_export]
+macro_rules! check_json_includes {
+    ($actual:expr, $expected:expr) => {{
+        $crate::assertions::json::check_json(&$expected, &$actual, $crate::assertions::json::Mode::Includes, &[])
+    }};
+    ($actual:expr, $expected:expr, ignore = [$($ignore:expr),* $(,)?]) => {{
+        $crate::assertions::json::check_json(
+            &$expected,
+            &$actual,
+            $crate::assertions::json::Mode::Includes,
+            &[$($ignore),*],
+        )
+    }};
+}
@@ -0,0 +1,290 @@
+use super::report::{self, MismatchReport};
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+/// A heading of a Markdown document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    /// The level, from 1 for `#` to 6 for `######`.
+    pub level: u8,
+    /// The text, without inline formatting.
+    pub text: String,
+}
+
+/// A link of a Markdown document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    /// The text, without inline formatting.
+    pub text: String,
+    pub url: String,
+}
+
+/// The structure of a Markdown document that the Markdown assertions check, created with [`outline`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkdownOutline {
+    /// Every heading, in document order.
+    pub headings: Vec<Heading>,
+    /// Every table as its rows, including the header row, where each row is the text of its cells.
+    pub tables: Vec<Vec<Vec<String>>>,
+    /// Every link, in document order.
+    pub links: Vec<Link>,
+}
+
+/// Parses the document as CommonMark with GitHub tables, and returns its headings, tables, and links.  Inline
+/// formatting such as emphasis and code spans is reduced to its text.
+pub fn outline(markdown: &str) -> MarkdownOutline {
+    let mut outline = MarkdownOutline::default();
+    let mut text: Option<String> = None;
+    let mut row: Vec<String> = Vec::new();
+    let mut open_links: Vec<(usize, String)> = Vec::new();
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+        match event {
+            Event::Start(Tag::Heading { .. }) | Event::Start(Tag::TableCell) => text = Some(String::new()),
+            Event::End(TagEnd::Heading(level)) => outline.headings.push(Heading {
+                level: level as u8,
+                text: text.take().unwrap_or_default().trim().to_string(),
+            }),
+            Event::Start(Tag::Table(_)) => outline.tables.push(Vec::new()),
+            Event::End(TagEnd::TableCell) => row.push(text.take().unwrap_or_default().trim().to_string()),
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                if let Some(table) = outline.tables.last_mut() {
+                    table.push(std::mem::take(&mut row));
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                open_links.push((outline.links.len(), String::new()));
+                outline.links.push(Link {
+                    text: String::new(),
+                    url: dest_url.to_string(),
+                });
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((index, link_text)) = open_links.pop() {
+                    outline.links[index].text = link_text.trim().to_string();
+                    push_text(&mut text, &mut open_links, &link_text);
+                }
+            }
+            Event::Text(content) | Event::Code(content) => push_text(&mut text, &mut open_links, &content),
+            Event::SoftBreak | Event::HardBreak => push_text(&mut text, &mut open_links, " "),
+            _ => {}
+        }
+    }
+    outline
+}
+
+/// Appends inline text to the innermost open link, or else to the open heading or cell.
+fn push_text(text: &mut Option<String>, open_links: &mut [(usize, String)], content: &str) {
+    match open_links.last_mut() {
+        Some((_, link_text)) => link_text.push_str(content),
+        None => {
+            if let Some(text) = text.as_mut() {
+                text.push_str(content);
+            }
+        }
+    }
+}
+
+/// Checks the document has a heading with the text, at the level when one is given.  See
+/// [`check_md_has_heading`](crate::check_md_has_heading).
+pub fn check_md_has_heading(markdown: &str, level: Option<u8>, text: &str) -> Result<(), MismatchReport> {
+    let headings = outline(markdown).headings;
+    if headings
+        .iter()
+        .any(|heading| heading.text == text && level.is_none_or(|level| heading.level == level))
+    {
+        return Ok(());
+    }
+    let expected = match level {
+        Some(level) => format!("{} {}", "#".repeat(level as usize), text),
+        None => text.to_string(),
+    };
+    let found: Vec<String> = headings
+        .iter()
+        .map(|heading| format!("\t\t{} {}", "#".repeat(heading.level as usize), heading.text))
+        .collect();
+    Err(MismatchReport::new("heading", "The Markdown document does not have the expected heading.")
+        .expected(expected)
+        .hint(found_list("Headings", &found)))
+}
+
+/// Checks a table of the document has a row with the cells.  See
+/// [`check_md_table_row`](crate::check_md_table_row).
+pub fn check_md_table_row(markdown: &str, cells: &[&str]) -> Result<(), MismatchReport> {
+    let tables = outline(markdown).tables;
+    if tables.iter().flatten().any(|row| row.iter().map(String::as_str).eq(cells.iter().copied())) {
+        return Ok(());
+    }
+    let found: Vec<String> = tables
+        .iter()
+        .enumerate()
+        .flat_map(|(index, table)| {
+            table
+                .iter()
+                .map(move |row| format!("\t\t[table {}] | {} |", index, row.join(" | ")))
+        })
+        .collect();
+    Err(MismatchReport::new("table row", "The Markdown document does not have a table row with the expected cells.")
+        .expected(format!("| {} |", cells.join(" | ")))
+        .hint(found_list("Rows", &found)))
+}
+
+/// Checks the document has a link with the text and URL.  See [`check_md_link`](crate::check_md_link).
+pub fn check_md_link(markdown: &str, text: &str, url: &str) -> Result<(), MismatchReport> {
+    let links = outline(markdown).links;
+    if links.iter().any(|link| link.text == text && link.url == url) {
+        return Ok(());
+    }
+    let found: Vec<String> = links
+        .iter()
+        .map(|link| format!("\t\t[{}]({})", link.text, link.url))
+        .collect();
+    Err(MismatchReport::new("link", "The Markdown document does not have the expected link.")
+        .expected(format!("[{}]({})", text, url))
+        .hint(found_list("Links", &found)))
+}
+
+/// Asserts the document has a heading with the text.  See [`assert_md_has_heading`](crate::assert_md_has_heading).
+pub fn assert_md_has_heading(markdown: &str, level: Option<u8>, text: &str) {
+    report::enforce(check_md_has_heading(markdown, level, text));
+}
+
+/// Asserts a table of the document has a row with the cells.  See
+/// [`assert_md_table_row`](crate::assert_md_table_row).
+pub fn assert_md_table_row(markdown: &str, cells: &[&str]) {
+    report::enforce(check_md_table_row(markdown, cells));
+}
+
+/// Asserts the document has a link with the text and URL.  See [`assert_md_link`](crate::assert_md_link).
+pub fn assert_md_link(markdown: &str, text: &str, url: &str) {
+    report::enforce(check_md_link(markdown, text, url));
+}
+
+fn found_list(name: &str, found: &[String]) -> String {
+    if found.is_empty() {
+        return format!("\t{}:\n\t\t<none>", name);
+    }
+    format!("\t{}:\n{}", name, found.join("\n"))
+}
+
+/// Asserts that a Markdown document has a heading with the text, optionally at a level.  The document is parsed,
+/// so the heading may be written in ATX (`## Summary`) or Setext style, and inline formatting is ignored.  On
+/// failure it lists the document's headings.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `$level` - Optional. The heading level, from 1 to 6.
+/// * `$text` - The heading text.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::{assert_md_has_heading, assert_md_link, assert_md_table_row};
+///
+/// let report = "# Assurance Report\n\n| Evidence | Status |\n|---|---|\n| build-log | **passed** |\n\n\
+///               See [the procedure](https://example.com/p1).";
+/// assert_md_has_heading!(report, 1, "Assurance Report");
+/// assert_md_table_row!(report, ["build-log", "passed"]);
+/// assert_md_link!(report, "the procedure", "https://example.com/p1");
+/// ```
+#[macro_export]
+macro_rules! assert_md_has_heading {
+    ($markdown:expr, $text:expr) => {
+        $crate::assertions::markdown::assert_md_has_heading(::std::convert::AsRef::<str>::as_ref(&$markdown), None, $text)
+    };
+    ($markdown:expr, $level:expr, $text:expr) => {
+        $crate::assertions::markdown::assert_md_has_heading(
+            ::std::convert::AsRef::<str>::as_ref(&$markdown),
+            Some($level),
+            $text,
+        )
+    };
+}
+
+/// Asserts that a table in a Markdown document has a row whose cells are exactly the given texts, in order.  The
+/// header row counts as a row, and inline formatting in cells is ignored.  On failure it lists every table row.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `[$cell, ...]` - The text of each cell of the row.
+///
+#[macro_export]
+macro_rules! assert_md_table_row {
+    ($markdown:expr, [$($cell:expr),* $(,)?]) => {
+        $crate::assertions::markdown::assert_md_table_row(::std::convert::AsRef::<str>::as_ref(&$markdown), &[$($cell),*])
+    };
+}
+
+/// Asserts that a Markdown document has a link with the text and URL, whether written inline or as a reference
+/// link.  On failure it lists the document's links.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `$text` - The link text, without inline formatting.
+/// * `$url` - The link destination.
+///
+#[macro_export]
+macro_rules! assert_md_link {
+    ($markdown:expr, $text:expr, $url:expr) => {
+        $crate::assertions::markdown::assert_md_link(::std::convert::AsRef::<str>::as_ref(&$markdown), $text, $url)
+    };
+}
+
+/// Checks that a Markdown document has a heading with the text, optionally at a level, like
+/// [`assert_md_has_heading`](crate::assert_md_has_heading), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `$level` - Optional. The heading level, from 1 to 6.
+/// * `$text` - The heading text.
+///
+#[macro_export]
+macro_rules! check_md_has_heading {
+    ($markdown:expr, $text:expr) => {
+        $crate::assertions::markdown::check_md_has_heading(::std::convert::AsRef::<str>::as_ref(&$markdown), None, $text)
+    };
+    ($markdown:expr, $level:expr, $text:expr) => {
+        $crate::assertions::markdown::check_md_has_heading(
+            ::std::convert::AsRef::<str>::as_ref(&$markdown),
+            Some($level),
+            $text,
+        )
+    };
+}
+
+/// Checks that a table in a Markdown document has a row with the given cells, like
+/// [`assert_md_table_row`](crate::assert_md_table_row), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `[$cell, ...]` - The text of each cell of the row.
+///
+#[macro_export]
+macro_rules! check_md_table_row {
+    ($markdown:expr, [$($cell:expr),* $(,)?]) => {
+        $crate::assertions::markdown::check_md_table_row(::std::convert::AsRef::<str>::as_ref(&$markdown), &[$($cell),*])
+    };
+}
+
+/// Checks that a Markdown document has a link with the text and URL, like
+/// [`assert_md_link`](crate::assert_md_link), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$markdown` - The document, as anything that can be referenced as a `str`.
+/// * `$text` - The link text, without inline formatting.
+/// * `$url` - The link destination.
+///
+#[macro_export]
+macro_rules! check_md_link {
+    ($markdown:expr, $text:expr, $url:expr) => {
+        $crate::assertions::markdown::check_md_link(::std::convert::AsRef::<str>::as_ref(&$markdown), $text, $url)
+    };
+}
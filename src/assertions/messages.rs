@@ -0,0 +1,204 @@
+//! Resolves localized error messages by key, so error assertions name the message a usecase should return instead
+//! of hard-coding its English text.
+//!
+//! A [`MessageCatalog`] holds one locale's messages, loaded from the same YAML or JSON files the CLI is localized
+//! with, where nested keys are joined with dots, e.g. `errors.evidence.missing`.  Messages may have `{name}`
+//! placeholders.  The catalog is installed for the process with [`set_message_catalog`], and
+//! [`kernel_error_msg_key`](crate::kernel_error_msg_key) then asserts an error's message is the catalog's message for
+//! the key.  Placeholders given as arguments are substituted, and the others match any text:
+//!
+//! ```no_run
+//! use attestify_test_framework::assertions::messages::{MessageCatalog, set_message_catalog};
+//! use attestify_test_framework::kernel_error_msg_key;
+//! # #[derive(Debug, PartialEq)] enum Kind { InvalidInput }
+//! # #[derive(Debug, PartialEq)] enum Audience { User }
+//! # struct Error { kind: Kind, audience: Audience, message: String }
+//! # fn download(name: &str) -> Result<(), Error> { unimplemented!() }
+//!
+//! set_message_catalog(MessageCatalog::from_file("fr", "tests/data/locales/fr.yaml"));
+//!
+//! let result = download("build.log");
+//! kernel_error_msg_key!(result, Kind::InvalidInput, Audience::User, "errors.evidence.missing", name = "build.log");
+//! ```
+
+use super::report::MismatchReport;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// The catalog installed for the process by [`set_message_catalog`].
+static CATALOG: Mutex<Option<Arc<MessageCatalog>>> = Mutex::new(None);
+
+/// The localized messages of one locale, by key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageCatalog {
+    locale: String,
+    messages: BTreeMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// Creates an empty catalog for the locale, e.g. `fr` or `en-GB`.
+    pub fn new(locale: &str) -> Self {
+        MessageCatalog {
+            locale: locale.to_string(),
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the message for the key, replacing any message it had.
+    pub fn message(mut self, key: &str, message: &str) -> Self {
+        self.messages.insert(key.to_string(), message.to_string());
+        self
+    }
+
+    /// Creates a catalog from YAML or JSON text, whose nested keys are joined with dots.  The test fails when the
+    /// text is not a mapping of keys to messages.
+    pub fn from_yaml(locale: &str, yaml: &str) -> Self {
+        let document: Value = serde_yaml::from_str(yaml)
+            .unwrap_or_else(|err| panic!("Could not parse the message catalog '{}':\n\t{}\n", locale, err));
+        let mut catalog = MessageCatalog::new(locale);
+        flatten("", &document, &mut catalog.messages)
+            .unwrap_or_else(|key| panic!("The message '{}' in the catalog '{}' is not text.", key, locale));
+        catalog
+    }
+
+    /// Creates a catalog from a YAML or JSON file, like [`MessageCatalog::from_yaml`].
+    pub fn from_file(locale: &str, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let yaml = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Could not read the message catalog '{}':\n\t{}\n", path.display(), err));
+        Self::from_yaml(locale, &yaml)
+    }
+
+    /// Returns the locale of the catalog.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Returns the message for the key, with its placeholders.
+    pub fn resolve(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Returns the message for the key with the arguments substituted for their placeholders.  Placeholders with no
+    /// argument are left as they are.
+    pub fn render(&self, key: &str, args: &[(&str, &dyn Display)]) -> Option<String> {
+        self.resolve(key).map(|message| substitute(message, args))
+    }
+
+    /// Checks the message is the catalog's message for the key, returning a [`MismatchReport`] instead of panicking.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the expected message, e.g. `errors.evidence.missing`.
+    /// * `message` - The actual message.
+    /// * `args` - The values of the message's placeholders, by name.  Placeholders with no value match any text.
+    ///
+    pub fn check_message(&self, key: &str, message: &str, args: &[(&str, &dyn Display)]) -> Result<(), MismatchReport> {
+        let Some(expected) = self.render(key, args) else {
+            let keys: Vec<String> = self.messages.keys().map(|key| format!("\t\t{}", key)).collect();
+            return Err(MismatchReport::new(
+                "message",
+                format!("The message key '{}' is not in the catalog '{}'.", key, self.locale),
+            )
+            .expected(key)
+            .hint(format!("\tKeys:\n{}", if keys.is_empty() { "\t\t<none>".to_string() } else { keys.join("\n") })));
+        };
+        if matches_template(&expected, message) {
+            return Ok(());
+        }
+        Err(
+            MismatchReport::new(
+                "message",
+                format!("The Error Message is not the message '{}' of the catalog '{}'.", key, self.locale),
+            )
+            .expected(format!("{:?}", expected))
+            .actual(format!("{:?}", message))
+            .hint(crate::diff::render(&expected, message)),
+        )
+    }
+}
+
+/// Installs the catalog the message key assertions resolve keys with, for every test in the process.
+pub fn set_message_catalog(catalog: MessageCatalog) {
+    *CATALOG.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(catalog));
+}
+
+/// Returns the installed catalog, if any.
+pub fn message_catalog() -> Option<Arc<MessageCatalog>> {
+    CATALOG.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Checks the message is the installed catalog's message for the key, like [`MessageCatalog::check_message`].  The
+/// test fails when no catalog is installed.
+pub fn check_message(key: &str, message: &str, args: &[(&str, &dyn Display)]) -> Result<(), MismatchReport> {
+    let catalog = message_catalog().unwrap_or_else(|| {
+        panic!("No message catalog is installed to resolve '{}'.  Call set_message_catalog first.", key)
+    });
+    catalog.check_message(key, message, args)
+}
+
+/// Adds every message in the YAML node to the map under its dotted key, returning the key of a node that is not
+/// text.
+fn flatten(prefix: &str, node: &Value, messages: &mut BTreeMap<String, String>) -> Result<(), String> {
+    match node {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let key = match key {
+                    Value::String(key) => key.clone(),
+                    other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+                };
+                let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten(&key, value, messages)?;
+            }
+            Ok(())
+        }
+        Value::String(message) => {
+            messages.insert(prefix.to_string(), message.clone());
+            Ok(())
+        }
+        _ => Err(prefix.to_string()),
+    }
+}
+
+fn substitute(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(message.to_string(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+/// Returns true when the message is the template, with each remaining `{name}` placeholder matching any text.
+fn matches_template(template: &str, message: &str) -> bool {
+    let mut literals = Vec::new();
+    let (mut literal_start, mut search) = (0, 0);
+    while let Some(start) = template[search..].find('{').map(|start| search + start) {
+        let name_length = template[start + 1..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(template.len() - start - 1);
+        if name_length > 0 && template[start + 1 + name_length..].starts_with('}') {
+            literals.push(&template[literal_start..start]);
+            literal_start = start + name_length + 2;
+        }
+        search = start + 1;
+    }
+    literals.push(&template[literal_start..]);
+
+    let (first, last) = (literals[0], literals[literals.len() - 1]);
+    if literals.len() == 1 {
+        return message == first;
+    }
+    if !message.starts_with(first) || message.len() < first.len() + last.len() || !message.ends_with(last) {
+        return false;
+    }
+    let mut remaining = &message[first.len()..message.len() - last.len()];
+    for literal in &literals[1..literals.len() - 1] {
+        match remaining.find(literal) {
+            Some(position) => remaining = &remaining[position + literal.len()..],
+            None => return false,
+        }
+    }
+    true
+}
@@ -0,0 +1,119 @@
+use super::report::{self, MismatchReport};
+
+/// The names of the segments after the `nrn` scheme, in order.
+pub const NRN_SEGMENTS: [&str; 3] = ["resource type", "owner", "name"];
+
+/// Returns why the value is not an NRN, or [`None`] when it is one.
+///
+/// An NRN (NAPE resource name) is `nrn:<resource type>:<owner>:<name>`, e.g. `nrn:procedure:attestify:build`, where
+/// every segment is made of lowercase letters, digits, and hyphens, and starts with a letter or digit.
+///
+/// # Arguments
+///
+/// * `value` - The value to validate.
+/// * `resource_type` - Optional. The resource type the NRN must have, e.g. `procedure`.
+///
+pub fn nrn_problem(value: &str, resource_type: Option<&str>) -> Option<String> {
+    let Some(rest) = value.strip_prefix("nrn:") else {
+        return Some("must start with 'nrn:'".to_string());
+    };
+    let segments: Vec<&str> = rest.split(':').collect();
+    if segments.len() != NRN_SEGMENTS.len() {
+        return Some(format!(
+            "must have 3 segments after 'nrn:', the resource type, owner, and name, found {}",
+            segments.len()
+        ));
+    }
+    for (segment, name) in segments.iter().zip(NRN_SEGMENTS) {
+        if let Some(problem) = segment_problem(segment) {
+            return Some(format!("the {} segment {:?} {}", name, segment, problem));
+        }
+    }
+    match resource_type {
+        Some(expected) if segments[0] != expected => Some(format!(
+            "must have the resource type {:?}, found {:?}",
+            expected, segments[0]
+        )),
+        _ => None,
+    }
+}
+
+fn segment_problem(segment: &str) -> Option<&'static str> {
+    let mut chars = segment.chars();
+    match chars.next() {
+        None => Some("is empty"),
+        Some(first) if !(first.is_ascii_lowercase() || first.is_ascii_digit()) => {
+            Some("must start with a lowercase letter or digit")
+        }
+        _ if !chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') => {
+            Some("must only contain lowercase letters, digits, and hyphens")
+        }
+        _ => None,
+    }
+}
+
+/// Checks the value is an NRN, optionally of the resource type.  See [`check_valid_nrn`](crate::check_valid_nrn).
+pub fn check_valid_nrn(value: &str, resource_type: Option<&str>) -> Result<(), MismatchReport> {
+    match nrn_problem(value, resource_type) {
+        None => Ok(()),
+        Some(problem) => Err(MismatchReport::new("nrn", format!("The value is not a valid NRN; it {}.", problem))
+            .expected(format!("nrn:{}:<owner>:<name>", resource_type.unwrap_or("<resource type>")))
+            .actual(format!("{:?}", value))),
+    }
+}
+
+/// Asserts the value is an NRN, optionally of the resource type.  See [`assert_valid_nrn`](crate::assert_valid_nrn).
+pub fn assert_valid_nrn(value: &str, resource_type: Option<&str>) {
+    report::enforce(check_valid_nrn(value, resource_type));
+}
+
+/// Asserts that a value is a valid NRN (NAPE resource name), `nrn:<resource type>:<owner>:<name>`, where every
+/// segment is made of lowercase letters, digits, and hyphens, and starts with a letter or digit.  On failure it
+/// names the offending segment.
+///
+/// # Arguments
+///
+/// * `$value` - The value, as anything that can be referenced as a `str`.
+/// * `$resource_type` - Optional. The resource type the NRN must have, e.g. `"procedure"`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_valid_nrn;
+///
+/// assert_valid_nrn!("nrn:procedure:attestify:build");
+/// assert_valid_nrn!("nrn:subject:attestify:test-subject", "subject");
+/// ```
+#[macro_export]
+macro_rules! assert_valid_nrn {
+    ($value:expr) => {
+        $crate::assertions::nrn::assert_valid_nrn(::std::convert::AsRef::<str>::as_ref(&$value), None)
+    };
+    ($value:expr, $resource_type:expr) => {
+        $crate::assertions::nrn::assert_valid_nrn(
+            ::std::convert::AsRef::<str>::as_ref(&$value),
+            Some($resource_type),
+        )
+    };
+}
+
+/// Checks that a value is a valid NRN, like [`assert_valid_nrn`](crate::assert_valid_nrn), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$value` - The value, as anything that can be referenced as a `str`.
+/// * `$resource_type` - Optional. The resource type the NRN must have, e.g. `"procedure"`.
+///
+#[macro_export]
+macro_rules! check_valid_nrn {
+    ($value:expr) => {
+        $crate::assertions::nrn::check_valid_nrn(::std::convert::AsRef::<str>::as_ref(&$value), None)
+    };
+    ($value:expr, $resource_type:expr) => {
+        $crate::assertions::nrn::check_valid_nrn(
+            ::std::convert::AsRef::<str>::as_ref(&$value),
+            Some($resource_type),
+        )
+    };
+}
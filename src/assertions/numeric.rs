@@ -0,0 +1,179 @@
+use super::config;
+use super::report::{self, MismatchReport};
+
+/// Checks the value is within `epsilon` of the expected value.  See [`check_approx_eq`](crate::check_approx_eq).
+///
+/// # Arguments
+///
+/// * `expression` - The expression that produced the value, named in the report.
+/// * `actual` - The value.
+/// * `expected` - The expected value.
+/// * `epsilon` - The largest absolute difference allowed.
+///
+pub fn check_approx_eq(expression: &str, actual: f64, expected: f64, epsilon: f64) -> Result<(), MismatchReport> {
+    let delta = (actual - expected).abs();
+    if delta <= epsilon {
+        return Ok(());
+    }
+    Err(
+        MismatchReport::new(expression, summary(actual, expected, "is not within the tolerance of"))
+            .expected(format!("{} {} {}", expected, config::config().plus_minus(), epsilon))
+            .actual(format!("{} (delta {})", actual, delta)),
+    )
+}
+
+/// Asserts the value is within `epsilon` of the expected value.  See [`assert_approx_eq`](crate::assert_approx_eq).
+pub fn assert_approx_eq(expression: &str, actual: f64, expected: f64, epsilon: f64) {
+    report::enforce(check_approx_eq(expression, actual, expected, epsilon));
+}
+
+/// Checks the value is within a percentage of the expected value.  See
+/// [`check_approx_eq_pct`](crate::check_approx_eq_pct).
+///
+/// # Arguments
+///
+/// * `expression` - The expression that produced the value, named in the report.
+/// * `actual` - The value.
+/// * `expected` - The expected value.
+/// * `tolerance_pct` - The largest difference allowed, as a percentage of the expected value, e.g. `1.0` for 1%.
+///
+pub fn check_approx_eq_pct(
+    expression: &str,
+    actual: f64,
+    expected: f64,
+    tolerance_pct: f64,
+) -> Result<(), MismatchReport> {
+    let delta = (actual - expected).abs();
+    if delta <= (expected * tolerance_pct / 100.0).abs() {
+        return Ok(());
+    }
+    let relative = if expected == 0.0 {
+        "of an expected 0".to_string()
+    } else {
+        format!("{}%", delta / expected.abs() * 100.0)
+    };
+    Err(
+        MismatchReport::new(expression, summary(actual, expected, "is not within the percentage of"))
+            .expected(format!("{} {} {}%", expected, config::config().plus_minus(), tolerance_pct))
+            .actual(format!("{} (delta {}, {})", actual, delta, relative)),
+    )
+}
+
+/// Asserts the value is within a percentage of the expected value.  See
+/// [`assert_approx_eq_pct`](crate::assert_approx_eq_pct).
+pub fn assert_approx_eq_pct(expression: &str, actual: f64, expected: f64, tolerance_pct: f64) {
+    report::enforce(check_approx_eq_pct(expression, actual, expected, tolerance_pct));
+}
+
+fn summary(actual: f64, expected: f64, mismatch: &str) -> String {
+    if actual.is_nan() || expected.is_nan() {
+        "The value is not a number, which is never approximately equal.".to_string()
+    } else {
+        format!("The value {} the expected value.", mismatch)
+    }
+}
+
+/// Asserts that a floating point value, such as a score or a coverage percentage in assurance metrics, is within an
+/// absolute tolerance of the expected value.  On failure it reports the tolerance and the delta, e.g.
+/// `expected 0.75 ± 0.001, got 0.7525 (delta 0.0025)`.  `NaN` is never approximately equal.
+///
+/// # Arguments
+///
+/// * `$actual` - The value, of any numeric type, compared as an [`f64`].
+/// * `$expected` - The expected value, of any numeric type.
+/// * `$epsilon` - The largest absolute difference allowed.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_approx_eq;
+///
+/// let score = 3.0 / 4.0;
+/// assert_approx_eq!(score, 0.75, 1e-9);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($actual:expr, $expected:expr, $epsilon:expr $(,)?) => {
+        $crate::assertions::numeric::assert_approx_eq(
+            stringify!($actual),
+            ($actual) as f64,
+            ($expected) as f64,
+            ($epsilon) as f64,
+        )
+    };
+}
+
+/// Asserts that a floating point value is within a percentage of the expected value, for values whose tolerance
+/// scales with their size.  On failure it reports the tolerance and the delta, absolute and as a percentage.
+///
+/// # Arguments
+///
+/// * `$actual` - The value, of any numeric type, compared as an [`f64`].
+/// * `$expected` - The expected value, of any numeric type.
+/// * `$tolerance_pct` - The largest difference allowed, as a percentage of the expected value, e.g. `1.0` for 1%.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_approx_eq_pct;
+///
+/// let coverage = 82.4;
+/// assert_approx_eq_pct!(coverage, 82.0, 1.0);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_approx_eq_pct {
+    ($actual:expr, $expected:expr, $tolerance_pct:expr $(,)?) => {
+        $crate::assertions::numeric::assert_approx_eq_pct(
+            stringify!($actual),
+            ($actual) as f64,
+            ($expected) as f64,
+            ($tolerance_pct) as f64,
+        )
+    };
+}
+
+/// Checks that a floating point value is within an absolute tolerance of the expected value, like
+/// [`assert_approx_eq`](crate::assert_approx_eq), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - The value, of any numeric type, compared as an [`f64`].
+/// * `$expected` - The expected value, of any numeric type.
+/// * `$epsilon` - The largest absolute difference allowed.
+///
+#[macro_export]
+macro_rules! check_approx_eq {
+    ($actual:expr, $expected:expr, $epsilon:expr $(,)?) => {
+        $crate::assertions::numeric::check_approx_eq(
+            stringify!($actual),
+            ($actual) as f64,
+            ($expected) as f64,
+            ($epsilon) as f64,
+        )
+    };
+}
+
+/// Checks that a floating point value is within a percentage of the expected value, like
+/// [`assert_approx_eq_pct`](crate::assert_approx_eq_pct), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - The value, of any numeric type, compared as an [`f64`].
+/// * `$expected` - The expected value, of any numeric type.
+/// * `$tolerance_pct` - The largest difference allowed, as a percentage of the expected value.
+///
+#[macro_export]
+macro_rules! check_approx_eq_pct {
+    ($actual:expr, $expected:expr, $tolerance_pct:expr $(,)?) => {
+        $crate::assertions::numeric::check_approx_eq_pct(
+            stringify!($actual),
+            ($actual) as f64,
+            ($expected) as f64,
+            ($tolerance_pct) as f64,
+        )
+    };
+}
@@ -0,0 +1,136 @@
+use super::report::{self, MismatchReport};
+use std::path::Path;
+
+/// Returns the components of the path, splitting on both `/` and `\` so the result is the same on every platform.
+/// `.` components and repeated separators are ignored, and a leading separator is kept as a `/` component so
+/// absolute and relative paths differ.
+pub fn path_components(path: &Path) -> Vec<String> {
+    let text = path.to_string_lossy();
+    let mut components = Vec::new();
+    if text.starts_with(['/', '\\']) {
+        components.push("/".to_string());
+    }
+    components.extend(
+        text.split(['/', '\\'])
+            .filter(|component| !component.is_empty() && *component != ".")
+            .map(str::to_string),
+    );
+    components
+}
+
+/// Checks the paths have the same components, regardless of separator.  See
+/// [`check_path_eq`](crate::check_path_eq).
+pub fn check_path_eq(expected: &Path, actual: &Path) -> Result<(), MismatchReport> {
+    if path_components(expected) == path_components(actual) {
+        return Ok(());
+    }
+    Err(MismatchReport::new("path", "The paths do not match.")
+        .expected(expected.display().to_string())
+        .actual(actual.display().to_string()))
+}
+
+/// Checks the path ends with the components of the suffix, regardless of separator.  See
+/// [`check_path_ends_with`](crate::check_path_ends_with).
+pub fn check_path_ends_with(path: &Path, suffix: &Path) -> Result<(), MismatchReport> {
+    if path_components(path).ends_with(&path_components(suffix)) {
+        return Ok(());
+    }
+    Err(MismatchReport::new("path", "The path does not end with the expected components.")
+        .expected(format!("a path ending with {}", suffix.display()))
+        .actual(path.display().to_string()))
+}
+
+/// Asserts the paths have the same components, regardless of separator.  See
+/// [`assert_path_eq`](crate::assert_path_eq).
+pub fn assert_path_eq(expected: &Path, actual: &Path) {
+    report::enforce(check_path_eq(expected, actual));
+}
+
+/// Asserts the path ends with the components of the suffix, regardless of separator.  See
+/// [`assert_path_ends_with`](crate::assert_path_ends_with).
+pub fn assert_path_ends_with(path: &Path, suffix: &Path) {
+    report::enforce(check_path_ends_with(path, suffix));
+}
+
+/// Asserts that two paths have the same components, whether they are separated by `/` or `\`, so an expected path
+/// written with `/` matches a path built on Windows.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$actual` - The actual path, as anything that can be referenced as a [`Path`](std::path::Path).
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_path_eq;
+/// use std::path::PathBuf;
+///
+/// let written = PathBuf::from("evidence").join("build-log.json");
+/// assert_path_eq!("evidence/build-log.json", written);
+/// ```
+#[macro_export]
+macro_rules! assert_path_eq {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::path::assert_path_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+        )
+    };
+}
+
+/// Asserts that a path ends with the components of a suffix, whether they are separated by `/` or `\`.  Only whole
+/// components match, so `reports/report.yaml` does not end with `port.yaml`.
+///
+/// # Arguments
+///
+/// * `$path` - The path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$suffix` - The expected trailing components, as anything that can be referenced as a [`Path`](std::path::Path).
+///
+#[macro_export]
+macro_rules! assert_path_ends_with {
+    ($path:expr, $suffix:expr) => {
+        $crate::assertions::path::assert_path_ends_with(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$suffix),
+        )
+    };
+}
+
+/// Checks that two paths have the same components regardless of separator, like
+/// [`assert_path_eq`](crate::assert_path_eq), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$actual` - The actual path, as anything that can be referenced as a [`Path`](std::path::Path).
+///
+#[macro_export]
+macro_rules! check_path_eq {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::path::check_path_eq(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$expected),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$actual),
+        )
+    };
+}
+
+/// Checks that a path ends with the components of a suffix regardless of separator, like
+/// [`assert_path_ends_with`](crate::assert_path_ends_with), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$suffix` - The expected trailing components, as anything that can be referenced as a [`Path`](std::path::Path).
+///
+#[macro_export]
+macro_rules! check_path_ends_with {
+    ($path:expr, $suffix:expr) => {
+        $crate::assertions::path::check_path_ends_with(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$suffix),
+        )
+    };
+}
@@ -0,0 +1,183 @@
+use super::report::{self, MismatchReport};
+use crate::workspace::unique_temp_dir;
+use std::fs::{self, OpenOptions, Permissions};
+use std::io;
+use std::path::Path;
+
+/// The permissions [`assert_file_mode`](crate::assert_file_mode) expects of a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMode {
+    /// The read-only attribute is set, or on Unix, no write bit is set.
+    ReadOnly,
+    /// The read-only attribute is not set, or on Unix, some write bit is set.
+    Writable,
+    /// The Unix permission bits, e.g. `0o644`, compared with the lowest 12 bits of the mode.
+    #[cfg(unix)]
+    Unix(u32),
+}
+
+/// Returns the permissions of the path, following symbolic links, failing the test when it does not exist.
+fn permissions(path: &Path) -> Result<Permissions, MismatchReport> {
+    fs::metadata(path).map(|metadata| metadata.permissions()).map_err(|err| {
+        MismatchReport::new(path.display().to_string(), "The path does not exist.").hint(format!("\t{}", err))
+    })
+}
+
+#[cfg(unix)]
+fn describe(permissions: &Permissions) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let access = if permissions.readonly() { "read-only" } else { "writable" };
+    format!("{} (mode {:#o})", access, permissions.mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn describe(permissions: &Permissions) -> String {
+    if permissions.readonly() { "read-only" } else { "writable" }.to_string()
+}
+
+/// Checks the file or directory has the mode.  See [`check_file_mode`](crate::check_file_mode).
+pub fn check_file_mode(path: &Path, mode: FileMode) -> Result<(), MismatchReport> {
+    let permissions = permissions(path)?;
+    let (matched, expected) = match mode {
+        FileMode::ReadOnly => (permissions.readonly(), "read-only".to_string()),
+        FileMode::Writable => (!permissions.readonly(), "writable".to_string()),
+        #[cfg(unix)]
+        FileMode::Unix(bits) => {
+            use std::os::unix::fs::PermissionsExt;
+            (permissions.mode() & 0o7777 == bits, format!("mode {:#o}", bits))
+        }
+    };
+    if matched {
+        return Ok(());
+    }
+    Err(
+        MismatchReport::new(path.display().to_string(), "The permissions of the path do not match.")
+            .expected(expected)
+            .actual(describe(&permissions)),
+    )
+}
+
+/// Asserts the file or directory has the mode.  See [`assert_file_mode`](crate::assert_file_mode).
+pub fn assert_file_mode(path: &Path, mode: FileMode) {
+    report::enforce(check_file_mode(path, mode));
+}
+
+/// Makes the file or directory read-only: on Unix by clearing its write bits, on Windows by setting its read-only
+/// attribute.
+///
+/// On Windows the attribute does not stop files being created in a directory, so make the files themselves
+/// read-only there.
+pub fn make_readonly(path: &Path) {
+    let mut permissions = fs::metadata(path)
+        .unwrap_or_else(|err| panic!("Could not read the permissions of '{}':\n\t{}\n", path.display(), err))
+        .permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(path, permissions)
+        .unwrap_or_else(|err| panic!("Could not make '{}' read-only:\n\t{}\n", path.display(), err));
+}
+
+/// Makes the file or directory writable again: on Unix by setting its owner write bit, on Windows by clearing its
+/// read-only attribute.
+pub fn make_writable(path: &Path) {
+    set_writable(path).unwrap_or_else(|err| panic!("Could not make '{}' writable:\n\t{}\n", path.display(), err));
+}
+
+/// Makes the file or directory writable, returning the error instead of panicking, e.g. while cleaning up in `drop`.
+pub(crate) fn set_writable(path: &Path) -> io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+/// Returns true when writing to a read-only file fails for the current user.  It does not when tests run as root
+/// on Unix, e.g. in many containers, so a test that expects a write to fail can skip itself instead of failing.
+pub fn readonly_enforced() -> bool {
+    let dir = unique_temp_dir("nape_permissions");
+    let file = dir.join("probe");
+    fs::write(&file, "").unwrap_or_else(|_| panic!("Could not write to the file '{}'.", file.display()));
+    make_readonly(&file);
+    let enforced = OpenOptions::new().write(true).open(&file).is_err();
+    make_writable(&file);
+    let _ = fs::remove_dir_all(&dir);
+    enforced
+}
+
+/// Asserts that a file or directory is read-only, writable, or on Unix has exact permission bits.  On failure it
+/// reports the actual permissions.
+///
+/// # Arguments
+///
+/// * `$path` - The path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$mode` - `readonly`, `writable`, or on Unix the permission bits, e.g. `0o644`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_file_mode;
+/// use attestify_test_framework::workspace::TestWorkspace;
+///
+/// let workspace = TestWorkspace::new();
+/// let report = workspace.create_file("reports/report.yaml", "");
+/// workspace.make_readonly("reports/report.yaml");
+/// assert_file_mode!(report, readonly);
+/// // the gateway's write to the report now fails with a GatewayError
+/// ```
+#[macro_export]
+macro_rules! assert_file_mode {
+    ($path:expr, readonly) => {
+        $crate::assertions::permissions::assert_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::ReadOnly,
+        )
+    };
+    ($path:expr, writable) => {
+        $crate::assertions::permissions::assert_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::Writable,
+        )
+    };
+    ($path:expr, $bits:expr) => {
+        $crate::assertions::permissions::assert_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::Unix($bits),
+        )
+    };
+}
+
+/// Checks that a file or directory is read-only, writable, or on Unix has exact permission bits, like
+/// [`assert_file_mode`](crate::assert_file_mode), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$path` - The path, as anything that can be referenced as a [`Path`](std::path::Path).
+/// * `$mode` - `readonly`, `writable`, or on Unix the permission bits, e.g. `0o644`.
+///
+#[macro_export]
+macro_rules! check_file_mode {
+    ($path:expr, readonly) => {
+        $crate::assertions::permissions::check_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::ReadOnly,
+        )
+    };
+    ($path:expr, writable) => {
+        $crate::assertions::permissions::check_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::Writable,
+        )
+    };
+    ($path:expr, $bits:expr) => {
+        $crate::assertions::permissions::check_file_mode(
+            ::std::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+            $crate::assertions::permissions::FileMode::Unix($bits),
+        )
+    };
+}
@@ -0,0 +1,163 @@
+use super::config;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+
+/// The assertions evaluated since [`take_evaluated_assertions`] was last called, counted only while the assertions
+/// are disabled.  It is shared by every thread, as a test may assert on threads it spawns.
+static EVALUATED_ASSERTIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static LAST_FAILURE: RefCell<Option<MismatchReport>> = const { RefCell::new(None) };
+}
+
+/// Why an assertion failed, built by every assertion so failures can be consumed programmatically instead of by
+/// parsing panic messages.
+///
+/// Every `check_*` macro and function returns one in an [`Err`], and the matching assertion panics with its
+/// rendering.  Reports are rendered by one formatter, its [`Display`](fmt::Display) implementation, which colors the
+/// labels and truncates the values as the [assertions config](super::config()) says:
+///
+/// ```text
+/// Kind does not match.
+///     Expected:   GatewayError
+///     Actual:     InvalidInput
+/// <hint>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MismatchReport {
+    /// What was compared, e.g. `kind`, `message`, or the path of a document node such as `$.metadata.owner`.
+    pub field: String,
+    /// One sentence describing the failure, e.g. `Kind does not match.`
+    pub summary: String,
+    /// The expected value, rendered for display.
+    pub expected: Option<String>,
+    /// The actual value, rendered for display.
+    pub actual: Option<String>,
+    /// Further detail rendered below the values, such as a diff or a list of the offending items.
+    pub hint: Option<String>,
+}
+
+impl MismatchReport {
+    /// Creates a report with no expected or actual value and no hint.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - What was compared.
+    /// * `summary` - One sentence describing the failure.
+    ///
+    pub fn new(field: impl Into<String>, summary: impl Into<String>) -> Self {
+        MismatchReport {
+            field: field.into(),
+            summary: summary.into(),
+            expected: None,
+            actual: None,
+            hint: None,
+        }
+    }
+
+    /// Sets the expected value.
+    pub fn expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Sets the actual value.
+    pub fn actual(mut self, actual: impl Into<String>) -> Self {
+        self.actual = Some(actual.into());
+        self
+    }
+
+    /// Sets the detail rendered below the values.
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+impl fmt::Display for MismatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let config = config::config();
+        f.write_str(&self.summary)?;
+        if let Some(expected) = &self.expected {
+            write!(f, "\n\t{}\t{}", config.paint(GREEN, "Expected:"), config.truncate(expected))?;
+        }
+        if let Some(actual) = &self.actual {
+            write!(f, "\n\t{}\t{}", config.paint(RED, "Actual:"), config.truncate(actual))?;
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, "\n{}", hint.trim_end())?;
+        }
+        writeln!(f)
+    }
+}
+
+impl Error for MismatchReport {}
+
+/// The panic payload of an assertion that failed while the assertions are disabled, see [`assertions_disabled`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisabledAssertion;
+
+/// Returns true when the crate was built with `--cfg attestify_assertion_audit`, which disables every assertion for
+/// the assertion audit of a [`TestHarness`](crate::harness::TestHarness).
+pub const fn assertions_disabled() -> bool {
+    cfg!(attestify_assertion_audit)
+}
+
+/// Fails the test with the report when the check failed, and returns the checked value otherwise.  This is how
+/// every assertion ends, so the assertion audit can tell whether a test evaluated any assertion.
+///
+/// # Arguments
+///
+/// * `result` - The result of the assertion's check, e.g. of [`check_kernel_error_eq`](crate::check_kernel_error_eq).
+///
+pub fn enforce<T>(result: Result<T, MismatchReport>) -> T {
+    match result {
+        Ok(value) => {
+            record_evaluation();
+            value
+        }
+        Err(report) => fail(report),
+    }
+}
+
+/// Counts an assertion as evaluated, for an assertion that does not end with [`enforce`]; it is called before the
+/// assertion checks anything.  It does nothing unless the [assertions are disabled](assertions_disabled).
+pub fn record_evaluation() {
+    if assertions_disabled() {
+        EVALUATED_ASSERTIONS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns and resets the number of assertions evaluated while the assertions are disabled, see [`enforce`].
+pub fn take_evaluated_assertions() -> usize {
+    EVALUATED_ASSERTIONS.swap(0, Ordering::SeqCst)
+}
+
+/// Fails the test with the rendered report.  This is how every assertion panics.
+///
+/// The report is also kept for the current thread, so a harness that catches the panic can recover the structured
+/// report with [`take_last_failure`] instead of parsing the panic message.
+///
+/// When the [assertions are disabled](assertions_disabled) the assertion does not fail the test: it ends the test
+/// silently, with a [`DisabledAssertion`] payload that [`run_test`](crate::reporting::run_test) counts as a pass.
+/// The test cannot continue, as the assertion may not be able to return a value for it to continue with.
+pub fn fail(report: MismatchReport) -> ! {
+    record_evaluation();
+    let message = report.to_string();
+    LAST_FAILURE.with(|last| *last.borrow_mut() = Some(report));
+    if assertions_disabled() {
+        panic::resume_unwind(Box::new(DisabledAssertion));
+    }
+    panic!("{}", message)
+}
+
+/// Returns and forgets the report of the last assertion that failed on the current thread, see [`fail`].
+pub fn take_last_failure() -> Option<MismatchReport> {
+    LAST_FAILURE.with(|last| last.borrow_mut().take())
+}
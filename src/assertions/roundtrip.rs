@@ -0,0 +1,120 @@
+use super::report::{self, MismatchReport};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::{self, Debug};
+
+/// A serialization format supported by the roundtrip assertions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Json,
+}
+
+impl Format {
+    /// Serializes the value into a document of this format.
+    pub fn serialize<T: Serialize + ?Sized>(&self, value: &T) -> Result<String, String> {
+        match self {
+            Format::Yaml => serde_yaml::to_string(value).map_err(|err| err.to_string()),
+            Format::Json => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Deserializes a document of this format into a value.
+    pub fn deserialize<T: DeserializeOwned>(&self, document: &str) -> Result<T, String> {
+        match self {
+            Format::Yaml => serde_yaml::from_str(document).map_err(|err| err.to_string()),
+            Format::Json => serde_json::from_str(document).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Yaml => f.write_str("YAML"),
+            Format::Json => f.write_str("JSON"),
+        }
+    }
+}
+
+/// Checks the value survives serializing into the format and deserializing back unchanged.  See
+/// [`check_serializes_roundtrip`](crate::check_serializes_roundtrip).
+pub fn check_serializes_roundtrip<T>(value: &T, format: Format) -> Result<(), MismatchReport>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let document = format.serialize(value).map_err(|err| {
+        MismatchReport::new("serialize", format!("The value could not be serialized to {}.", format))
+            .hint(format!("\t{}", err))
+    })?;
+    let roundtripped: T = format.deserialize(&document).map_err(|err| {
+        MismatchReport::new("deserialize", format!("The {} document could not be deserialized.", format))
+            .hint(format!("\t{}\n\tDocument:\n{}", err, document))
+    })?;
+    if roundtripped == *value {
+        return Ok(());
+    }
+    Err(
+        MismatchReport::new("value", format!("The value does not survive a {} roundtrip.", format)).hint(format!(
+            "{}\n\tDocument:\n{}",
+            crate::diff::render(&format!("{:#?}", value), &format!("{:#?}", roundtripped)),
+            document
+        )),
+    )
+}
+
+/// Asserts the value survives serializing into the format and deserializing back unchanged.  See
+/// [`assert_serializes_roundtrip`](crate::assert_serializes_roundtrip).
+pub fn assert_serializes_roundtrip<T>(value: &T, format: Format)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    report::enforce(check_serializes_roundtrip(value, format));
+}
+
+/// Asserts that a value serializes to YAML or JSON and deserializes back to an equal value, showing a diff of the
+/// value and its roundtripped copy on failure.
+///
+/// # Arguments
+///
+/// * `$value` - The value, which must implement `Serialize`, `DeserializeOwned`, `PartialEq`, and `Debug`.
+/// * `yaml` or `json` - The format to roundtrip through.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_serializes_roundtrip;
+/// # fn build_procedure() -> serde_json::Value { serde_json::json!({ "name": "build" }) }
+///
+/// let procedure = build_procedure();
+/// assert_serializes_roundtrip!(procedure, yaml);
+/// assert_serializes_roundtrip!(procedure, json);
+/// ```
+#[macro_export]
+macro_rules! assert_serializes_roundtrip {
+    ($value:expr, yaml) => {
+        $crate::assertions::roundtrip::assert_serializes_roundtrip(&$value, $crate::assertions::roundtrip::Format::Yaml)
+    };
+    ($value:expr, json) => {
+        $crate::assertions::roundtrip::assert_serializes_roundtrip(&$value, $crate::assertions::roundtrip::Format::Json)
+    };
+}
+
+/// Checks that a value serializes to YAML or JSON and deserializes back to an equal value, like
+/// [`assert_serializes_roundtrip`](crate::assert_serializes_roundtrip), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$value` - The value, which must implement `Serialize`, `DeserializeOwned`, `PartialEq`, and `Debug`.
+/// * `yaml` or `json` - The format to roundtrip through.
+///
+#[macro_export]
+macro_rules! check_serializes_roundtrip {
+    ($value:expr, yaml) => {
+        $crate::assertions::roundtrip::check_serializes_roundtrip(&$value, $crate::assertions::roundtrip::Format::Yaml)
+    };
+    ($value:expr, json) => {
+        $crate::assertions::roundtrip::check_serializes_roundtrip(&$value, $crate::assertions::roundtrip::Format::Json)
+    };
+}
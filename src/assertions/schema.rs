@@ -0,0 +1,325 @@
+use super::report::{self, MismatchReport};
+use regex::Regex;
+use serde_yaml::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A NAPE document type with a bundled schema definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// A procedure definition, with `kind`, `metadata`, and `activities`.
+    Procedure,
+    /// The metadata document written beside an evidence payload.
+    EvidenceMetadata,
+}
+
+impl fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaKind::Procedure => f.write_str("Procedure"),
+            SchemaKind::EvidenceMetadata => f.write_str("EvidenceMetadata"),
+        }
+    }
+}
+
+/// One way a document does not conform to its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// The path to the offending node, e.g. `$.metadata.version` or `$.activities[1].name`.
+    pub path: String,
+    /// What is wrong with the node.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The constraint on a scalar value.
+#[derive(Clone, Copy)]
+enum Scalar {
+    /// Any string, including an empty one.
+    Text,
+    /// A string that is not empty.
+    Name,
+    /// One of the strings.
+    OneOf(&'static [&'static str]),
+    /// An NRN of the resource type, e.g. `nrn:procedure:attestify:build`.
+    Nrn(&'static str),
+    /// A semantic version, e.g. `1.0.0` or `2.1.0-rc.1`.
+    SemVer,
+    /// An RFC 3339 timestamp, e.g. `2024-01-01T00:00:00Z`.
+    Timestamp,
+    /// A non-negative integer, written as a number or a string of digits.
+    Count,
+    /// A lowercase hex SHA-256 digest.
+    Sha256,
+}
+
+/// A node in a schema definition.  Fields not listed in a [`Node::Mapping`] are allowed.
+#[derive(Clone, Copy)]
+enum Node {
+    Scalar(Scalar),
+    Mapping(&'static [Field]),
+    /// A mapping with any keys, whose values all match the node.
+    MappingOf(&'static Node),
+    /// A list whose items all match the node.  When `unique_by` names a field, no two items may share its value.
+    List {
+        item: &'static Node,
+        unique_by: Option<&'static str>,
+    },
+}
+
+struct Field {
+    name: &'static str,
+    required: bool,
+    node: Node,
+}
+
+const fn required(name: &'static str, node: Node) -> Field {
+    Field { name, required: true, node }
+}
+
+const fn optional(name: &'static str, node: Node) -> Field {
+    Field { name, required: false, node }
+}
+
+const ACTION: Node = Node::Mapping(&[
+    required("name", Node::Scalar(Scalar::Name)),
+    required("evidence", Node::Scalar(Scalar::Name)),
+]);
+
+const ACTIVITY: Node = Node::Mapping(&[
+    required("nrn", Node::Scalar(Scalar::Nrn("activity"))),
+    required("name", Node::Scalar(Scalar::Name)),
+    optional("description", Node::Scalar(Scalar::Text)),
+    optional("actions", Node::List { item: &ACTION, unique_by: Some("name") }),
+]);
+
+const PROCEDURE: Node = Node::Mapping(&[
+    required("kind", Node::Scalar(Scalar::OneOf(&["Procedure"]))),
+    required(
+        "metadata",
+        Node::Mapping(&[
+            required("nrn", Node::Scalar(Scalar::Nrn("procedure"))),
+            required("name", Node::Scalar(Scalar::Name)),
+            required("version", Node::Scalar(Scalar::SemVer)),
+            optional("description", Node::Scalar(Scalar::Text)),
+            optional("labels", Node::MappingOf(&Node::Scalar(Scalar::Text))),
+        ]),
+    ),
+    required("activities", Node::List { item: &ACTIVITY, unique_by: Some("name") }),
+]);
+
+const EVIDENCE_METADATA: Node = Node::Mapping(&[
+    required("name", Node::Scalar(Scalar::Name)),
+    required("media_type", Node::Scalar(Scalar::Name)),
+    required("size", Node::Scalar(Scalar::Count)),
+    required("sha256", Node::Scalar(Scalar::Sha256)),
+    required("collected_at", Node::Scalar(Scalar::Timestamp)),
+    required("collector", Node::Scalar(Scalar::Name)),
+]);
+
+impl SchemaKind {
+    fn root(&self) -> &'static Node {
+        match self {
+            SchemaKind::Procedure => &PROCEDURE,
+            SchemaKind::EvidenceMetadata => &EVIDENCE_METADATA,
+        }
+    }
+}
+
+/// Validates a YAML document against the bundled schema of the document type, returning every violation in document
+/// order.  A document that is not valid YAML has a single violation at `$`.
+pub fn validate(document: &str, kind: SchemaKind) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    match serde_yaml::from_str::<Value>(document) {
+        Ok(value) => validate_node("$", &value, kind.root(), &mut violations),
+        Err(err) => violations.push(violation("$", format!("is not valid YAML: {}", err))),
+    }
+    violations
+}
+
+fn validate_node(path: &str, value: &Value, node: &Node, violations: &mut Vec<SchemaViolation>) {
+    match node {
+        Node::Scalar(scalar) => {
+            if let Some(problem) = scalar_problem(value, *scalar) {
+                violations.push(violation(path, problem));
+            }
+        }
+        Node::Mapping(fields) => {
+            let Some(mapping) = value.as_mapping() else {
+                violations.push(violation(path, format!("must be a mapping, found {}", describe(value))));
+                return;
+            };
+            for field in fields.iter() {
+                let field_path = format!("{}.{}", path, field.name);
+                match mapping.get(field.name) {
+                    Some(field_value) => validate_node(&field_path, field_value, &field.node, violations),
+                    None if field.required => violations.push(violation(&field_path, "is required")),
+                    None => {}
+                }
+            }
+        }
+        Node::MappingOf(item) => {
+            let Some(mapping) = value.as_mapping() else {
+                violations.push(violation(path, format!("must be a mapping, found {}", describe(value))));
+                return;
+            };
+            for (key, item_value) in mapping {
+                let key = key.as_str().map(str::to_string).unwrap_or_else(|| describe(key));
+                validate_node(&format!("{}.{}", path, key), item_value, item, violations);
+            }
+        }
+        Node::List { item, unique_by } => {
+            let Some(items) = value.as_sequence() else {
+                violations.push(violation(path, format!("must be a list, found {}", describe(value))));
+                return;
+            };
+            let mut seen = HashSet::new();
+            for (index, item_value) in items.iter().enumerate() {
+                let item_path = format!("{}[{}]", path, index);
+                validate_node(&item_path, item_value, item, violations);
+                let key = unique_by.and_then(|field| item_value.get(field).map(|key| (field, key)));
+                if let Some((field, key)) = key
+                    && !seen.insert(serde_yaml::to_string(key).unwrap_or_default())
+                {
+                    violations.push(violation(
+                        &format!("{}.{}", item_path, field),
+                        format!("must be unique, {} is repeated", describe(key)),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn scalar_problem(value: &Value, scalar: Scalar) -> Option<String> {
+    if let Scalar::Count = scalar {
+        let valid = match value {
+            Value::Number(number) => number.is_u64(),
+            Value::String(text) => !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()),
+            _ => false,
+        };
+        return (!valid).then(|| format!("must be a non-negative integer, found {}", describe(value)));
+    }
+
+    let Some(text) = value.as_str() else {
+        return Some(format!("must be a string, found {}", describe(value)));
+    };
+    let (valid, expected) = match scalar {
+        Scalar::Text => (true, String::new()),
+        Scalar::Name => (!text.trim().is_empty(), "must not be empty".to_string()),
+        Scalar::OneOf(allowed) => (allowed.contains(&text), format!("must be one of {:?}", allowed)),
+        Scalar::Nrn(resource_type) => (
+            super::nrn::nrn_problem(text, Some(resource_type)).is_none(),
+            format!("must be an NRN like 'nrn:{}:<owner>:<name>'", resource_type),
+        ),
+        Scalar::SemVer => (
+            matches(r"^\d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$", text),
+            "must be a semantic version like '1.0.0'".to_string(),
+        ),
+        Scalar::Timestamp => (
+            chrono::DateTime::parse_from_rfc3339(text).is_ok(),
+            "must be an RFC 3339 timestamp like '2024-01-01T00:00:00Z'".to_string(),
+        ),
+        Scalar::Sha256 => (matches("^[0-9a-f]{64}$", text), "must be a lowercase hex SHA-256 digest".to_string()),
+        Scalar::Count => unreachable!(),
+    };
+    (!valid).then(|| format!("{}, found {:?}", expected, text))
+}
+
+fn matches(pattern: &str, text: &str) -> bool {
+    Regex::new(pattern).expect("The schema pattern is not valid.").is_match(text)
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(flag) => format!("the boolean {}", flag),
+        Value::Number(number) => format!("the number {}", number),
+        Value::String(text) => format!("the string {:?}", text),
+        Value::Sequence(_) => "a list".to_string(),
+        Value::Mapping(_) => "a mapping".to_string(),
+        Value::Tagged(tagged) => format!("a value tagged {}", tagged.tag),
+    }
+}
+
+fn violation(path: &str, message: impl Into<String>) -> SchemaViolation {
+    SchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Checks the YAML document conforms to the bundled schema of the document type.  See
+/// [`check_valid_nape_schema`](crate::check_valid_nape_schema).
+pub fn check_valid_nape_schema(document: &str, kind: SchemaKind) -> Result<(), MismatchReport> {
+    let violations = validate(document, kind);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let field = match violations.as_slice() {
+        [only] => only.path.clone(),
+        _ => "$".to_string(),
+    };
+    let listed: Vec<String> = violations.iter().map(|violation| format!("\t{}", violation)).collect();
+    Err(MismatchReport::new(
+        field,
+        format!(
+            "The document does not conform to the {} schema ({} violation(s)):",
+            kind,
+            violations.len()
+        ),
+    )
+    .hint(listed.join("\n")))
+}
+
+/// Asserts the YAML document conforms to the bundled schema of the document type.  See
+/// [`assert_valid_nape_schema`](crate::assert_valid_nape_schema).
+pub fn assert_valid_nape_schema(document: &str, kind: SchemaKind) {
+    report::enforce(check_valid_nape_schema(document, kind));
+}
+
+/// Asserts that a YAML document conforms to the bundled schema of a NAPE document type, listing every violation
+/// with its path on failure.
+///
+/// # Arguments
+///
+/// * `$document` - The YAML document, as anything that can be referenced as a `str`.
+/// * `$kind` - The document type. Should be of type [`SchemaKind`](crate::assertions::schema::SchemaKind).
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_valid_nape_schema;
+/// use attestify_test_framework::assertions::schema::SchemaKind;
+/// use attestify_test_framework::fixtures::procedure_fixture;
+///
+/// let document = procedure_fixture::full();
+/// assert_valid_nape_schema!(document.yaml, SchemaKind::Procedure);
+/// ```
+#[macro_export]
+macro_rules! assert_valid_nape_schema {
+    ($document:expr, $kind:expr) => {
+        $crate::assertions::schema::assert_valid_nape_schema(::std::convert::AsRef::<str>::as_ref(&$document), $kind)
+    };
+}
+
+/// Checks that a YAML document conforms to the bundled schema of a NAPE document type, like
+/// [`assert_valid_nape_schema`](crate::assert_valid_nape_schema), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$document` - The YAML document, as anything that can be referenced as a `str`.
+/// * `$kind` - The document type. Should be of type [`SchemaKind`](crate::assertions::schema::SchemaKind).
+///
+#[macro_export]
+macro_rules! check_valid_nape_schema {
+    ($document:expr, $kind:expr) => {
+        $crate::assertions::schema::check_valid_nape_schema(::std::convert::AsRef::<str>::as_ref(&$document), $kind)
+    };
+}
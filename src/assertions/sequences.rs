@@ -0,0 +1,196 @@
+use super::report::{self, MismatchReport};
+use futures_core::Stream;
+use std::fmt::Debug;
+use std::future::poll_fn;
+use std::pin::pin;
+
+/// Compares a sequence one item at a time with the expected items, so it is consumed no further than the first
+/// divergence.
+struct Comparison<'a, E> {
+    expected: &'a [E],
+    yielded: Vec<String>,
+}
+
+impl<'a, E: Debug> Comparison<'a, E> {
+    fn new(expected: &'a [E]) -> Self {
+        Comparison {
+            expected,
+            yielded: Vec::new(),
+        }
+    }
+
+    /// Compares the next item of the sequence, or [`None`] when it ended, returning the outcome once it is known.
+    fn next<T: PartialEq<E> + Debug>(&mut self, item: Option<T>) -> Option<Result<(), MismatchReport>> {
+        let position = self.yielded.len();
+        match (item, self.expected.get(position)) {
+            (Some(item), Some(expected)) if item == *expected => {
+                self.yielded.push(format!("{:?}", item));
+                None
+            }
+            (Some(item), Some(expected)) => Some(Err(MismatchReport::new(
+                format!("[{}]", position),
+                format!("The sequence diverged from the expected items at position {}.", position),
+            )
+            .expected(format!("{:?}", expected))
+            .actual(format!("{:?}", item))
+            .hint(self.render_yielded()))),
+            (Some(item), None) => Some(Err(MismatchReport::new(
+                format!("[{}]", position),
+                format!("The sequence yielded more than the {} expected items.", self.expected.len()),
+            )
+            .expected("the end of the sequence")
+            .actual(format!("{:?}", item))
+            .hint(self.render_yielded()))),
+            (None, Some(expected)) => Some(Err(MismatchReport::new(
+                format!("[{}]", position),
+                format!(
+                    "The sequence ended after {} items, although {} were expected.",
+                    position,
+                    self.expected.len()
+                ),
+            )
+            .expected(format!("{:?}", expected))
+            .actual("the end of the sequence")
+            .hint(self.render_yielded()))),
+            (None, None) => Some(Ok(())),
+        }
+    }
+
+    fn render_yielded(&self) -> String {
+        if self.yielded.is_empty() {
+            return "\tMatched:\n\t\t<none>".to_string();
+        }
+        let items: Vec<String> = self
+            .yielded
+            .iter()
+            .enumerate()
+            .map(|(position, item)| format!("\t\t[{}] {}", position, item))
+            .collect();
+        format!("\tMatched:\n{}", items.join("\n"))
+    }
+}
+
+/// Checks the iterator yields exactly the expected items, in order.  See
+/// [`check_iter_yields`](crate::check_iter_yields).
+pub fn check_iter_yields<T, E>(actual: impl IntoIterator<Item = T>, expected: &[E]) -> Result<(), MismatchReport>
+where
+    T: PartialEq<E> + Debug,
+    E: Debug,
+{
+    let mut comparison = Comparison::new(expected);
+    let mut actual = actual.into_iter();
+    loop {
+        if let Some(outcome) = comparison.next(actual.next()) {
+            return outcome;
+        }
+    }
+}
+
+/// Asserts the iterator yields exactly the expected items, in order.  See
+/// [`assert_iter_yields`](crate::assert_iter_yields).
+pub fn assert_iter_yields<T, E>(actual: impl IntoIterator<Item = T>, expected: &[E])
+where
+    T: PartialEq<E> + Debug,
+    E: Debug,
+{
+    report::enforce(check_iter_yields(actual, expected));
+}
+
+/// Checks the stream yields exactly the expected items, in order.  See
+/// [`check_stream_yields`](crate::check_stream_yields).
+pub async fn check_stream_yields<T, E>(actual: impl Stream<Item = T>, expected: &[E]) -> Result<(), MismatchReport>
+where
+    T: PartialEq<E> + Debug,
+    E: Debug,
+{
+    let mut comparison = Comparison::new(expected);
+    let mut actual = pin!(actual);
+    loop {
+        let item = poll_fn(|context| actual.as_mut().poll_next(context)).await;
+        if let Some(outcome) = comparison.next(item) {
+            return outcome;
+        }
+    }
+}
+
+/// Asserts the stream yields exactly the expected items, in order.  See
+/// [`assert_stream_yields`](crate::assert_stream_yields).
+pub async fn assert_stream_yields<T, E>(actual: impl Stream<Item = T>, expected: &[E])
+where
+    T: PartialEq<E> + Debug,
+    E: Debug,
+{
+    report::enforce(check_stream_yields(actual, expected).await);
+}
+
+/// Asserts that an iterator yields exactly the expected items, in order.  The iterator is consumed only up to the
+/// first divergence, so an infinite iterator fails on its first extra item.  On failure it reports the position
+/// that diverged, or whether the sequence ended early or late, and lists the items that matched.
+///
+/// # Arguments
+///
+/// * `$actual` - Anything that can be iterated, such as an iterator or a `Vec`.
+/// * `[$item, ...]` - The expected items, each comparable to the actual items with `==`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_iter_yields;
+///
+/// let activities = vec!["collect", "sign", "upload"];
+/// assert_iter_yields!(activities.iter().map(|activity| activity.len()), [7, 4, 6]);
+/// ```
+#[macro_export]
+macro_rules! assert_iter_yields {
+    ($actual:expr, [$($item:expr),* $(,)?]) => {
+        $crate::assertions::sequences::assert_iter_yields($actual, &[$($item),*])
+    };
+}
+
+/// Awaits the items of an async [`Stream`](futures_core::Stream) and asserts it yields exactly the expected items,
+/// in order, like [`assert_iter_yields`](crate::assert_iter_yields).  It can only be used inside an `async` block or
+/// function, such as an async test.
+///
+/// # Arguments
+///
+/// * `$actual` - A stream, which does not need to be pinned.
+/// * `[$item, ...]` - The expected items, each comparable to the actual items with `==`.
+///
+#[macro_export]
+macro_rules! assert_stream_yields {
+    ($actual:expr, [$($item:expr),* $(,)?]) => {
+        $crate::assertions::sequences::assert_stream_yields($actual, &[$($item),*]).await
+    };
+}
+
+/// Checks that an iterator yields exactly the expected items, in order, like
+/// [`assert_iter_yields`](crate::assert_iter_yields), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - Anything that can be iterated, such as an iterator or a `Vec`.
+/// * `[$item, ...]` - The expected items, each comparable to the actual items with `==`.
+///
+#[macro_export]
+macro_rules! check_iter_yields {
+    ($actual:expr, [$($item:expr),* $(,)?]) => {
+        $crate::assertions::sequences::check_iter_yields($actual, &[$($item),*])
+    };
+}
+
+/// Awaits the items of an async [`Stream`](futures_core::Stream) and checks it yields exactly the expected items,
+/// like [`assert_stream_yields`](crate::assert_stream_yields), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - A stream, which does not need to be pinned.
+/// * `[$item, ...]` - The expected items, each comparable to the actual items with `==`.
+///
+#[macro_export]
+macro_rules! check_stream_yields {
+    ($actual:expr, [$($item:expr),* $(,)?]) => {
+        $crate::assertions::sequences::check_stream_yields($actual, &[$($item),*]).await
+    };
+}
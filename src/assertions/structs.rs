@@ -0,0 +1,312 @@
+use super::json::{self, JsonDifference, Mode};
+use super::report::{self, MismatchReport};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Returns the value serialized as a [`Value`], panicking with a clear message when it cannot be, such as a map
+/// with keys that are not strings.
+///
+/// # Arguments
+///
+/// * `value` - The value to serialize.
+/// * `description` - What the value is, used in the failure message, e.g. `expected`.
+///
+pub fn to_value<T: Serialize + ?Sized>(value: &T, description: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|err| {
+        panic!(
+            "The {} {} could not be serialized for comparison:\n\t{}\n",
+            description,
+            std::any::type_name::<T>(),
+            err
+        )
+    })
+}
+
+/// Renders a JSON pointer such as `/evidence/0/name` as a field path such as `$.evidence[0].name`.
+pub fn field_path(pointer: &str) -> String {
+    let mut path = "$".to_string();
+    for segment in pointer.split('/').skip(1).filter(|segment| !segment.is_empty()) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if segment.parse::<usize>().is_ok() {
+            path.push_str(&format!("[{}]", segment));
+        } else {
+            path.push('.');
+            path.push_str(&segment);
+        }
+    }
+    path
+}
+
+/// Returns the name of the type without its module path, e.g. `AssuranceReport` or `Vec<my_crate::Evidence>`.
+pub(crate) fn type_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let end = name.find('<').unwrap_or(name.len());
+    let start = name[..end].rfind("::").map_or(0, |index| index + 2);
+    &name[start..]
+}
+
+/// Renders the differences as a list of field paths with their expected and actual values.
+pub(crate) fn render_differences(differences: &[JsonDifference]) -> String {
+    differences
+        .iter()
+        .map(|difference| {
+            format!(
+                "\t{}\n\t\tExpected:\t{}\n\t\tActual:\t{}",
+                field_path(&difference.pointer),
+                difference.expected,
+                difference.actual
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Checks the values serialize to the same document.  See [`check_struct_eq`](crate::check_struct_eq).
+pub fn check_struct_eq<T: Serialize + ?Sized>(expected: &T, actual: &T) -> Result<(), MismatchReport> {
+    let differences = json::differences(&to_value(expected, "expected"), &to_value(actual, "actual"), Mode::Strict, &[]);
+    match differences.first() {
+        None => Ok(()),
+        Some(first) => Err(MismatchReport::new(
+            field_path(&first.pointer),
+            format!(
+                "The {} values differ in {} field(s):",
+                type_name::<T>(),
+                differences.len()
+            ),
+        )
+        .hint(render_differences(&differences))),
+    }
+}
+
+/// Asserts the values serialize to the same document.  See [`assert_struct_eq`](crate::assert_struct_eq).
+pub fn assert_struct_eq<T: Serialize + ?Sized>(expected: &T, actual: &T) {
+    report::enforce(check_struct_eq(expected, actual));
+}
+
+/// What a field given to [`assert_struct_matches`](crate::assert_struct_matches) must be.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldMatcher {
+    /// The field must serialize to the value.
+    Equals(Value),
+    /// The field, and everything below it, is not compared.
+    Ignore,
+}
+
+/// Returns a matcher for a field that is not compared, such as a timestamp or a generated id.
+pub fn ignore() -> FieldMatcher {
+    FieldMatcher::Ignore
+}
+
+/// Converts a value into a [`FieldMatcher`].  Implemented for [`FieldMatcher`], and for any `Serialize` value,
+/// which the field must equal.
+pub trait IntoFieldMatcher {
+    fn into_field_matcher(self) -> FieldMatcher;
+}
+
+impl IntoFieldMatcher for FieldMatcher {
+    fn into_field_matcher(self) -> FieldMatcher {
+        self
+    }
+}
+
+impl<T: Serialize> IntoFieldMatcher for T {
+    fn into_field_matcher(self) -> FieldMatcher {
+        FieldMatcher::Equals(to_value(&self, "expected field"))
+    }
+}
+
+/// Checks the fields of the value match.  See [`check_struct_matches`](crate::check_struct_matches).
+///
+/// # Arguments
+///
+/// * `actual` - The value to check.
+/// * `fields` - The paths of the fields, such as `subject.nrn` or `evidence[*].id`, with their matchers.
+///
+pub fn check_struct_matches<T: Serialize + ?Sized>(
+    actual: &T,
+    fields: &[(&str, FieldMatcher)],
+) -> Result<(), MismatchReport> {
+    let actual_value = to_value(actual, "actual");
+    let ignore: Vec<Vec<String>> = fields
+        .iter()
+        .filter(|(_, matcher)| *matcher == FieldMatcher::Ignore)
+        .map(|(path, _)| json::path_segments(path))
+        .collect();
+
+    let mut differences = Vec::new();
+    for (path, matcher) in fields {
+        let FieldMatcher::Equals(expected) = matcher else {
+            continue;
+        };
+        let pattern = json::path_segments(path);
+        let mut nodes = Vec::new();
+        matching_nodes(&actual_value, &pattern, &mut Vec::new(), &mut nodes);
+        if nodes.is_empty() {
+            differences.push(json::difference(&pattern, Some(expected), None));
+        }
+        for (segments, node) in nodes {
+            differences.extend(json::differences_below(&segments, expected, node, Mode::Strict, &ignore));
+        }
+    }
+
+    match differences.first() {
+        None => Ok(()),
+        Some(first) => Err(MismatchReport::new(
+            field_path(&first.pointer),
+            format!(
+                "The {} value does not match in {} field(s):",
+                type_name::<T>(),
+                differences.len()
+            ),
+        )
+        .hint(render_differences(&differences))),
+    }
+}
+
+/// Asserts the fields of the value match.  See [`assert_struct_matches`](crate::assert_struct_matches).
+pub fn assert_struct_matches<T: Serialize + ?Sized>(actual: &T, fields: &[(&str, FieldMatcher)]) {
+    report::enforce(check_struct_matches(actual, fields));
+}
+
+/// Collects the nodes at the path, where a `*` segment matches every member or element.
+fn matching_nodes<'a>(
+    value: &'a Value,
+    pattern: &[String],
+    segments: &mut Vec<String>,
+    nodes: &mut Vec<(Vec<String>, &'a Value)>,
+) {
+    let Some((segment, rest)) = pattern.split_first() else {
+        nodes.push((segments.clone(), value));
+        return;
+    };
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| segment == "*" || *key == segment)
+            .map(|(key, child)| (key.clone(), child))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| segment == "*" || index.to_string() == *segment)
+            .map(|(index, child)| (index.to_string(), child))
+            .collect(),
+        _ => Vec::new(),
+    };
+    for (key, child) in children {
+        segments.push(key);
+        matching_nodes(child, rest, segments, nodes);
+        segments.pop();
+    }
+}
+
+/// Asserts that two values of a `Serialize` type are equal, comparing their serialized forms.  On failure it lists
+/// every differing field with its path, expected value, and actual value, instead of two complete `Debug`
+/// renderings.  Struct fields are listed in alphabetical order, as they are serialized by `serde_json`.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected value, of a type implementing `serde::Serialize`.
+/// * `$actual` - The actual value, of the same type.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_struct_eq;
+/// # let (expected_report, report) = (serde_json::Value::Null, serde_json::Value::Null);
+///
+/// assert_struct_eq!(expected_report, report);
+/// ```
+///
+/// A failure reads:
+///
+/// ```text
+/// The AssuranceReport values differ in 2 field(s):
+///     $.evidence[1].status
+///         Expected:   "passed"
+///         Actual:     "failed"
+///     $.subject.nrn
+///         Expected:   "nrn:procedure:attestify/p1"
+///         Actual:     "nrn:procedure:attestify/p2"
+/// ```
+#[macro_export]
+macro_rules! assert_struct_eq {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::structs::assert_struct_eq(&$expected, &$actual)
+    };
+}
+
+/// Checks that two values of a `Serialize` type are equal, like [`assert_struct_eq`](crate::assert_struct_eq),
+/// returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected value, of a type implementing `serde::Serialize`.
+/// * `$actual` - The actual value, of the same type.
+///
+#[macro_export]
+macro_rules! check_struct_eq {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::structs::check_struct_eq(&$expected, &$actual)
+    };
+}
+
+/// Asserts that the given fields of a `Serialize` value match, leaving every other field unchecked.  Each field is
+/// either compared with a value, which must equal the serialized field, or marked with
+/// [`ignore()`](crate::assertions::structs::ignore) so it is skipped wherever it appears, including inside a
+/// compared value.  On failure it lists every differing field like [`assert_struct_eq`](crate::assert_struct_eq).
+///
+/// # Arguments
+///
+/// * `$actual` - The value to check, of a type implementing `serde::Serialize`.
+/// * `{ $path: $matcher, ... }` - The field paths, such as `"subject.nrn"`, `"evidence[0].name"`, or
+///   `"metadata.*"`, where `*` matches any member or index, each with a `Serialize` value or `ignore()`.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_struct_matches;
+/// use attestify_test_framework::assertions::structs::ignore;
+/// # let report = serde_json::Value::Null;
+///
+/// assert_struct_matches!(report, {
+///     "subject.nrn": "nrn:procedure:attestify/p1",
+///     "evidence[*].status": "passed",
+///     "evidence[*].id": ignore(),
+///     "metadata.*": ignore(),
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_struct_matches {
+    ($actual:expr, { $($path:literal : $matcher:expr),* $(,)? }) => {
+        $crate::assertions::structs::assert_struct_matches(
+            &$actual,
+            &[$((
+                $path,
+                $crate::assertions::structs::IntoFieldMatcher::into_field_matcher($matcher),
+            )),*],
+        )
+    };
+}
+
+/// Checks that the given fields of a `Serialize` value match, like
+/// [`assert_struct_matches`](crate::assert_struct_matches), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - The value to check, of a type implementing `serde::Serialize`.
+/// * `{ $path: $matcher, ... }` - The field paths, each with a `Serialize` value or `ignore()`.
+///
+#[macro_export]
+macro_rules! check_struct_matches {
+    ($actual:expr, { $($path:literal : $matcher:expr),* $(,)? }) => {
+        $crate::assertions::structs::check_struct_matches(
+            &$actual,
+            &[$((
+                $path,
+                $crate::assertions::structs::IntoFieldMatcher::into_field_matcher($matcher),
+            )),*],
+        )
+    };
+}
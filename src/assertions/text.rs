@@ -0,0 +1,103 @@
+use super::report::{self, MismatchReport};
+
+/// Returns the text with `\r\n` and `\r` line endings replaced by `\n`, the trailing whitespace of every line removed,
+/// and trailing blank lines removed.  When `ignore_indentation` is true the leading whitespace of every line is
+/// removed as well.
+///
+/// # Arguments
+///
+/// * `text` - The text to normalize.
+/// * `ignore_indentation` - Whether to remove the leading whitespace of every line.
+///
+pub fn normalize_text(text: &str, ignore_indentation: bool) -> String {
+    let unified = text.replace("\r\n", "\n").replace('\r', "\n");
+    let lines: Vec<&str> = unified
+        .split('\n')
+        .map(|line| if ignore_indentation { line.trim() } else { line.trim_end() })
+        .collect();
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+/// Checks the texts are equal once normalized with [`normalize_text`].  See
+/// [`check_text_eq_normalized`](crate::check_text_eq_normalized).
+pub fn check_text_eq_normalized(expected: &str, actual: &str, ignore_indentation: bool) -> Result<(), MismatchReport> {
+    let expected = normalize_text(expected, ignore_indentation);
+    let actual = normalize_text(actual, ignore_indentation);
+    if expected == actual {
+        return Ok(());
+    }
+    Err(
+        MismatchReport::new("text", "The texts do not match after normalizing whitespace and line endings.")
+            .hint(crate::diff::render(&expected, &actual)),
+    )
+}
+
+/// Asserts the texts are equal once normalized with [`normalize_text`].  See
+/// [`assert_text_eq_normalized`](crate::assert_text_eq_normalized).
+pub fn assert_text_eq_normalized(expected: &str, actual: &str, ignore_indentation: bool) {
+    report::enforce(check_text_eq_normalized(expected, actual, ignore_indentation));
+}
+
+/// Asserts that two texts are equal after normalizing CRLF and CR line endings to LF and removing trailing
+/// whitespace and trailing blank lines, showing a diff of the normalized texts on failure.  This keeps golden
+/// comparisons of rendered reports passing on Windows agents.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected text, as anything that can be referenced as a `str`.
+/// * `$actual` - The actual text, as anything that can be referenced as a `str`.
+/// * `ignore_indentation` - Optional. When given, the leading whitespace of every line is also ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_text_eq_normalized;
+///
+/// assert_text_eq_normalized!("status: passed\n", "status: passed  \r\n\r\n");
+/// assert_text_eq_normalized!("a:\n  b: 1", "a:\n    b: 1", ignore_indentation);
+/// ```
+#[macro_export]
+macro_rules! assert_text_eq_normalized {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::text::assert_text_eq_normalized(
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            false,
+        )
+    };
+    ($expected:expr, $actual:expr, ignore_indentation) => {
+        $crate::assertions::text::assert_text_eq_normalized(
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            true,
+        )
+    };
+}
+
+/// Checks that two texts are equal after normalizing line endings and trailing whitespace, like
+/// [`assert_text_eq_normalized`](crate::assert_text_eq_normalized), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected text, as anything that can be referenced as a `str`.
+/// * `$actual` - The actual text, as anything that can be referenced as a `str`.
+/// * `ignore_indentation` - Optional. When given, the leading whitespace of every line is also ignored.
+///
+#[macro_export]
+macro_rules! check_text_eq_normalized {
+    ($expected:expr, $actual:expr) => {
+        $crate::assertions::text::check_text_eq_normalized(
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            false,
+        )
+    };
+    ($expected:expr, $actual:expr, ignore_indentation) => {
+        $crate::assertions::text::check_text_eq_normalized(
+            ::std::convert::AsRef::<str>::as_ref(&$expected),
+            ::std::convert::AsRef::<str>::as_ref(&$actual),
+            true,
+        )
+    };
+}
@@ -0,0 +1,292 @@
+use super::report::{self, MismatchReport};
+use serde_yaml::Value;
+
+/// Where two YAML documents first diverge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlDifference {
+    /// The path to the divergent node, e.g. `$.metadata.owner` or `$.activities[2]`.
+    pub path: String,
+    /// The expected node, rendered as YAML, or `<missing>`.
+    pub expected: String,
+    /// The actual node, rendered as YAML, or `<missing>`.
+    pub actual: String,
+}
+
+/// Parses a YAML document, panicking with a clear message when it is not valid YAML.
+///
+/// # Arguments
+///
+/// * `document` - The YAML document.
+/// * `description` - What the document is, used in the failure message, e.g. `expected`.
+///
+pub fn parse(document: &str, description: &str) -> Value {
+    try_parse(document, description).unwrap_or_else(|report| report::fail(report))
+}
+
+/// Parses a YAML document, returning a report when it is not valid YAML.
+///
+/// # Arguments
+///
+/// * `document` - The YAML document.
+/// * `description` - What the document is, used in the report, e.g. `expected`.
+///
+pub fn try_parse(document: &str, description: &str) -> Result<Value, MismatchReport> {
+    serde_yaml::from_str(document).map_err(|err| {
+        MismatchReport::new(description, format!("The {} document is not valid YAML.", description))
+            .hint(format!("\t{}", err))
+    })
+}
+
+/// Returns where the two YAML values first diverge, or [`None`] when they are structurally equal.  Mapping key
+/// order is ignored.
+pub fn first_difference(expected: &Value, actual: &Value) -> Option<YamlDifference> {
+    difference_at("$", expected, actual)
+}
+
+fn difference_at(path: &str, expected: &Value, actual: &Value) -> Option<YamlDifference> {
+    match (expected, actual) {
+        (Value::Mapping(expected_map), Value::Mapping(actual_map)) => {
+            for (key, expected_value) in expected_map {
+                let key_path = format!("{}.{}", path, render_key(key));
+                match actual_map.get(key) {
+                    Some(actual_value) => {
+                        if let Some(difference) = difference_at(&key_path, expected_value, actual_value) {
+                            return Some(difference);
+                        }
+                    }
+                    None => return Some(difference(key_path, Some(expected_value), None)),
+                }
+            }
+            actual_map
+                .iter()
+                .find(|(key, _)| !expected_map.contains_key(*key))
+                .map(|(key, actual_value)| {
+                    difference(format!("{}.{}", path, render_key(key)), None, Some(actual_value))
+                })
+        }
+        (Value::Sequence(expected_items), Value::Sequence(actual_items)) => {
+            for index in 0..expected_items.len().max(actual_items.len()) {
+                let index_path = format!("{}[{}]", path, index);
+                match (expected_items.get(index), actual_items.get(index)) {
+                    (Some(expected_item), Some(actual_item)) => {
+                        if let Some(difference) = difference_at(&index_path, expected_item, actual_item) {
+                            return Some(difference);
+                        }
+                    }
+                    (expected_item, actual_item) => return Some(difference(index_path, expected_item, actual_item)),
+                }
+            }
+            None
+        }
+        (Value::Tagged(expected_tagged), Value::Tagged(actual_tagged)) if expected_tagged.tag == actual_tagged.tag => {
+            difference_at(path, &expected_tagged.value, &actual_tagged.value)
+        }
+        _ if expected == actual => None,
+        _ => Some(difference(path.to_string(), Some(expected), Some(actual))),
+    }
+}
+
+fn difference(path: String, expected: Option<&Value>, actual: Option<&Value>) -> YamlDifference {
+    YamlDifference {
+        path,
+        expected: expected.map(render).unwrap_or_else(|| "<missing>".to_string()),
+        actual: actual.map(render).unwrap_or_else(|| "<missing>".to_string()),
+    }
+}
+
+fn render(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{:?}", value))
+}
+
+fn render_key(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        other => render(other),
+    }
+}
+
+/// Returns the node at the path in the YAML value.  The path is made of `.`-separated mapping keys and `[index]`
+/// sequence indexes, optionally starting with `$`, e.g. `$.activities[0].name`.
+///
+/// When the path does not exist, the error describes the first segment that could not be found.
+pub fn lookup<'a>(value: &'a Value, path: &str) -> Result<&'a Value, String> {
+    let mut current = value;
+    let mut walked = String::from("$");
+
+    for segment in path_segments(path) {
+        let next = match &segment {
+            Segment::Key(key) => current.as_mapping().and_then(|map| map.get(key.as_str())),
+            Segment::Index(index) => current.as_sequence().and_then(|items| items.get(*index)),
+        };
+        match &segment {
+            Segment::Key(key) => walked.push_str(&format!(".{}", key)),
+            Segment::Index(index) => walked.push_str(&format!("[{}]", index)),
+        }
+        current = next.ok_or_else(|| format!("The path '{}' does not exist.", walked))?;
+    }
+
+    Ok(current)
+}
+
+/// Returns the path starting with `$`, e.g. `a.b` becomes `$.a.b`.
+fn normalize_path(path: &str) -> String {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    if path.is_empty() || path.starts_with('.') || path.starts_with('[') {
+        format!("${}", path)
+    } else {
+        format!("$.{}", path)
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn path_segments(path: &str) -> Vec<Segment> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let mut rest = part;
+        if let Some(bracket) = rest.find('[') {
+            if bracket > 0 {
+                segments.push(Segment::Key(rest[..bracket].to_string()));
+            }
+            rest = &rest[bracket..];
+            while let Some(end) = rest.find(']') {
+                match rest[1..end].parse::<usize>() {
+                    Ok(index) => segments.push(Segment::Index(index)),
+                    Err(_) => segments.push(Segment::Key(rest[1..end].to_string())),
+                }
+                rest = &rest[end + 1..];
+            }
+        } else {
+            segments.push(Segment::Key(rest.to_string()));
+        }
+    }
+
+    segments
+}
+
+/// Checks the YAML documents are structurally equal, ignoring mapping key order and formatting.  See
+/// [`check_yaml_eq`](crate::check_yaml_eq).
+pub fn check_yaml_eq(expected: &str, actual: &str) -> Result<(), MismatchReport> {
+    let expected_value = try_parse(expected, "expected")?;
+    let actual_value = try_parse(actual, "actual")?;
+
+    match first_difference(&expected_value, &actual_value) {
+        Some(difference) => Err(MismatchReport::new(
+            &difference.path,
+            format!("The YAML documents differ at '{}'.", difference.path),
+        )
+        .expected(difference.expected)
+        .actual(difference.actual)),
+        None => Ok(()),
+    }
+}
+
+/// Asserts the YAML documents are structurally equal, ignoring mapping key order and formatting.  See
+/// [`assert_yaml_eq`](crate::assert_yaml_eq).
+pub fn assert_yaml_eq(expected: &str, actual: &str) {
+    report::enforce(check_yaml_eq(expected, actual));
+}
+
+/// Checks the YAML document contains the path, and when given, that the node at the path equals the expected
+/// YAML.  See [`check_yaml_contains_path`](crate::check_yaml_contains_path).
+pub fn check_yaml_contains_path(document: &str, path: &str, expected: Option<&str>) -> Result<(), MismatchReport> {
+    let value = try_parse(document, "actual")?;
+    let node = lookup(&value, path).map_err(|message| MismatchReport::new(normalize_path(path), message))?;
+
+    if let Some(expected) = expected {
+        let expected_value = try_parse(expected, "expected")?;
+        if let Some(difference) = first_difference(&expected_value, node) {
+            let path = format!("{}{}", normalize_path(path), &difference.path[1..]);
+            return Err(
+                MismatchReport::new(&path, format!("The YAML document differs at '{}'.", path))
+                    .expected(difference.expected)
+                    .actual(difference.actual),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Asserts the YAML document contains the path, and when given, that the node at the path equals the expected
+/// YAML.  See [`assert_yaml_contains_path`](crate::assert_yaml_contains_path).
+pub fn assert_yaml_contains_path(document: &str, path: &str, expected: Option<&str>) {
+    report::enforce(check_yaml_contains_path(document, path, expected));
+}
+
+/// Asserts that two YAML documents are structurally equal, ignoring mapping key order and formatting.  On failure
+/// it reports the exact path where the documents diverge.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected YAML document as a `&str`.
+/// * `$actual` - The actual YAML document as a `&str`.
+///
+#[macro_export]
+macro_rules! assert_yaml_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::yaml::assert_yaml_eq(&$expected, &$actual)
+    }};
+}
+
+/// Asserts that a YAML document contains a path, and optionally that the node at the path equals some expected
+/// YAML.
+///
+/// The path is made of `.`-separated mapping keys and `[index]` sequence indexes, e.g. `$.activities[0].name`.
+///
+/// # Arguments
+///
+/// * `$document` - The YAML document as a `&str`.
+/// * `$path` - The path that must exist.
+/// * `$expected` - Optional, the expected YAML at the path as a `&str`.
+///
+#[macro_export]
+macro_rules! assert_yaml_contains_path {
+    ($document:expr, $path:expr) => {{
+        $crate::assertions::yaml::assert_yaml_contains_path(&$document, $path, None)
+    }};
+    ($document:expr, $path:expr, $expected:expr) => {{
+        $crate::assertions::yaml::assert_yaml_contains_path(&$document, $path, Some(&$expected))
+    }};
+}
+
+/// Checks that two YAML documents are structurally equal, like [`assert_yaml_eq`](crate::assert_yaml_eq), returning
+/// a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$expected` - The expected YAML document as a `&str`.
+/// * `$actual` - The actual YAML document as a `&str`.
+///
+#[macro_export]
+macro_rules! check_yaml_eq {
+    ($expected:expr, $actual:expr) => {{
+        $crate::assertions::yaml::check_yaml_eq(&$expected, &$actual)
+    }};
+}
+
+/// Checks that a YAML document contains a path, and optionally that the node at the path equals some expected
+/// YAML, like [`assert_yaml_contains_path`](crate::assert_yaml_contains_path), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$document` - The YAML document as a `&str`.
+/// * `$path` - The path that must exist.
+/// * `$expected` - Optional, the expected YAML at the path as a `&str`.
+///
+#[macro_export]
+macro_rules! check_yaml_contains_path {
+    ($document:expr, $path:expr) => {{
+        $crate::assertions::yaml::check_yaml_contains_path(&$document, $path, None)
+    }};
+    ($document:expr, $path:expr, $expected:expr) => {{
+        $crate::assertions::yaml::check_yaml_contains_path(&$document, $path, Some(&$expected))
+    }};
+}
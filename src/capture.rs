@@ -0,0 +1,110 @@
+//! Captures what a closure writes to stdout and stderr, so tests can assert on the console messages a usecase
+//! shows its users without spawning a process.
+//!
+//! ```no_run
+//! use attestify_test_framework::capture::capture_output;
+//!
+//! let captured = capture_output(|| {
+//!     println!("Collected 3 evidence files");
+//!     eprintln!("warning: the procedure has no owner");
+//!     3
+//! });
+//! assert_eq!(captured.result, 3);
+//! assert_eq!(captured.stdout, "Collected 3 evidence files\n");
+//! assert!(captured.stderr.contains("no owner"));
+//! ```
+//!
+//! The file descriptors of stdout and stderr are redirected to temporary files while the closure runs, so output
+//! written by child processes and foreign code is captured too.  The redirection is process-wide: captures are
+//! serialized by a lock, and output written by other threads during a capture is captured as well.
+//!
+//! libtest captures `print!` output of tests before it reaches the file descriptors, so tests using
+//! `capture_output` must run with `--nocapture` or in a `harness = false` binary, e.g. one run by a
+//! [`TestHarness`](crate::harness::TestHarness).
+//!
+//! Only available on Unix.
+
+use crate::workspace::unique_temp_dir;
+use std::fs;
+use std::io::{self, Write};
+use std::os::fd::{AsRawFd, RawFd};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Serializes captures, since they redirect process-wide file descriptors.
+static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+/// The result of the closure given to [`capture_output`] and what it wrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedOutput<R> {
+    pub result: R,
+    /// Everything written to stdout, lossily decoded as UTF-8.
+    pub stdout: String,
+    /// Everything written to stderr, lossily decoded as UTF-8.
+    pub stderr: String,
+}
+
+/// Runs the closure with stdout and stderr redirected, and returns its result with the captured text.  When the
+/// closure panics, the streams are restored and the panic continues.
+pub fn capture_output<R>(work: impl FnOnce() -> R) -> CapturedOutput<R> {
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let dir = unique_temp_dir("nape_capture");
+    let stdout_file = dir.join("stdout");
+    let stderr_file = dir.join("stderr");
+
+    let outcome = {
+        let _stdout = Redirect::new(libc::STDOUT_FILENO, &stdout_file);
+        let _stderr = Redirect::new(libc::STDERR_FILENO, &stderr_file);
+        panic::catch_unwind(AssertUnwindSafe(work))
+    };
+
+    let stdout = read_lossy(&stdout_file);
+    let stderr = read_lossy(&stderr_file);
+    let _ = fs::remove_dir_all(&dir);
+
+    match outcome {
+        Ok(result) => CapturedOutput { result, stdout, stderr },
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Points a file descriptor at a file until dropped, then restores it.
+struct Redirect {
+    fd: RawFd,
+    saved: RawFd,
+}
+
+impl Redirect {
+    fn new(fd: RawFd, path: &Path) -> Self {
+        flush();
+        let file = fs::File::create(path)
+            .unwrap_or_else(|err| panic!("Could not create the file '{}':\n\t{}\n", path.display(), err));
+        // SAFETY: `fd` is a standard stream, and the duplicate is owned by the redirect until it is restored.
+        let saved = unsafe { libc::dup(fd) };
+        if saved < 0 || unsafe { libc::dup2(file.as_raw_fd(), fd) } < 0 {
+            panic!("Could not redirect the file descriptor {}:\n\t{}\n", fd, io::Error::last_os_error());
+        }
+        Redirect { fd, saved }
+    }
+}
+
+impl Drop for Redirect {
+    fn drop(&mut self) {
+        flush();
+        // SAFETY: `saved` is the duplicate made in `new`, which is closed only here.
+        unsafe {
+            libc::dup2(self.saved, self.fd);
+            libc::close(self.saved);
+        }
+    }
+}
+
+fn flush() {
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+}
+
+fn read_lossy(path: &Path) -> String {
+    String::from_utf8_lossy(&fs::read(path).unwrap_or_default()).into_owned()
+}
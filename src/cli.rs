@@ -0,0 +1,356 @@
+//! End-to-end tests of compiled attestify binaries.
+//!
+//! A [`CliRunner`] spawns the binary with arguments, environment variables, stdin, and a working directory, and
+//! returns a [`CliOutput`] whose assertions show the full stdout and stderr on failure:
+//!
+//! ```no_run
+//! use attestify_test_framework::cli::CliRunner;
+//! use attestify_test_framework::workspace::TestWorkspace;
+//!
+//! let workspace = TestWorkspace::new();
+//! CliRunner::cargo_bin("nape")
+//!     .args(["evidence", "collect", "--procedure", "p1"])
+//!     .env("NAPE_LOG", "debug")
+//!     .current_dir(workspace.root())
+//!     .run()
+//!     .assert_success()
+//!     .assert_stdout_contains("Collected 3 evidence files")
+//!     .assert_stderr_matches(r"took \d+ms")
+//!     .assert_file_exists("report.yaml");
+//! ```
+
+use crate::assertions::report::{self, MismatchReport};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Spawns a binary for an end-to-end test, created with [`CliRunner::new`] or [`CliRunner::cargo_bin`].
+#[derive(Debug, Clone)]
+pub struct CliRunner {
+    program: PathBuf,
+    args: Vec<OsString>,
+    envs: Vec<(OsString, Option<OsString>)>,
+    stdin: Option<Vec<u8>>,
+    current_dir: Option<PathBuf>,
+}
+
+impl CliRunner {
+    /// Creates a runner for the program, either a path or a name looked up on the `PATH`.
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        CliRunner {
+            program: program.into(),
+            args: Vec::new(),
+            envs: Vec::new(),
+            stdin: None,
+            current_dir: None,
+        }
+    }
+
+    /// Creates a runner for a binary of the package under test.
+    ///
+    /// Cargo passes the path of each binary to integration tests in a `CARGO_BIN_EXE_<name>` environment variable
+    /// at compile time, so `CliRunner::new(env!("CARGO_BIN_EXE_nape"))` is the most reliable choice.  This looks the
+    /// variable up at runtime instead, and falls back to the binary next to the test executable in the target
+    /// directory.
+    pub fn cargo_bin(name: &str) -> Self {
+        if let Some(path) = std::env::var_os(format!("CARGO_BIN_EXE_{}", name)) {
+            return CliRunner::new(path);
+        }
+        let test_executable = std::env::current_exe().expect("Could not locate the test executable.");
+        let target_dir = test_executable
+            .parent()
+            .map(|dir| if dir.ends_with("deps") { dir.parent().unwrap_or(dir) } else { dir })
+            .expect("The test executable is not in a directory.");
+        CliRunner::new(target_dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX)))
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, args: I) -> Self {
+        self.args.extend(args.into_iter().map(|arg| arg.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets an environment variable of the process.  The rest of the environment is inherited from the test.
+    pub fn env(mut self, name: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.envs
+            .push((name.as_ref().to_os_string(), Some(value.as_ref().to_os_string())));
+        self
+    }
+
+    /// Removes an environment variable the process would inherit from the test.
+    pub fn env_remove(mut self, name: impl AsRef<OsStr>) -> Self {
+        self.envs.push((name.as_ref().to_os_string(), None));
+        self
+    }
+
+    /// Writes the bytes to the process's stdin, then closes it.  Without it, stdin is empty.
+    pub fn stdin(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
+
+    /// Runs the process in the directory, which is also where [`CliOutput::assert_file_exists`] resolves relative
+    /// paths.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Spawns the process and waits for it to exit.  Panics when it cannot be spawned.
+    pub fn run(&self) -> CliOutput {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for (name, value) in &self.envs {
+            match value {
+                Some(value) => command.env(name, value),
+                None => command.env_remove(name),
+            };
+        }
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+
+        let mut child = command
+            .spawn()
+            .unwrap_or_else(|err| panic!("Could not run '{}':\n\t{}\n", self.program.display(), err));
+        let input = self.stdin.clone().unwrap_or_default();
+        let mut stdin = child.stdin.take().expect("The stdin of the process was not piped.");
+        let writer = std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+        let output = child
+            .wait_with_output()
+            .unwrap_or_else(|err| panic!("Could not wait for '{}':\n\t{}\n", self.program.display(), err));
+        let _ = writer.join();
+
+        CliOutput {
+            command: self.render(),
+            status: output.status,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            dir: self.current_dir.clone(),
+        }
+    }
+
+    fn render(&self) -> String {
+        std::iter::once(self.program.as_os_str())
+            .chain(self.args.iter().map(OsString::as_os_str))
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The result of a [`CliRunner::run`].  Every assertion returns the output, so assertions can be chained.
+#[derive(Debug, Clone)]
+pub struct CliOutput {
+    command: String,
+    status: ExitStatus,
+    stdout: String,
+    stderr: String,
+    dir: Option<PathBuf>,
+}
+
+impl CliOutput {
+    pub fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    /// Returns the exit code, or [`None`] when the process was terminated by a signal.
+    pub fn code(&self) -> Option<i32> {
+        self.status.code()
+    }
+
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    pub fn stderr(&self) -> &str {
+        &self.stderr
+    }
+
+    /// Asserts the process exited with code 0.
+    pub fn assert_success(&self) -> &Self {
+        self.assert_exit_code(0)
+    }
+
+    /// Asserts the process exited with a code other than 0, or was terminated by a signal.
+    pub fn assert_failure(&self) -> &Self {
+        report::record_evaluation();
+        if self.status.success() {
+            self.fail(
+                MismatchReport::new("exit code", "The command succeeded, although it was expected to fail.")
+                    .expected("a non-zero exit code")
+                    .actual(self.render_code()),
+            );
+        }
+        self
+    }
+
+    /// Asserts the process exited with the code.
+    pub fn assert_exit_code(&self, code: i32) -> &Self {
+        report::record_evaluation();
+        if self.status.code() != Some(code) {
+            self.fail(
+                MismatchReport::new("exit code", "The command did not exit with the expected code.")
+                    .expected(code.to_string())
+                    .actual(self.render_code()),
+            );
+        }
+        self
+    }
+
+    /// Asserts stdout equals the text exactly.
+    pub fn assert_stdout_eq(&self, expected: &str) -> &Self {
+        self.assert_stream_eq("stdout", &self.stdout, expected)
+    }
+
+    /// Asserts stdout contains the text.
+    pub fn assert_stdout_contains(&self, expected: &str) -> &Self {
+        self.assert_stream_contains("stdout", &self.stdout, expected)
+    }
+
+    /// Asserts stdout matches the regular expression somewhere.
+    pub fn assert_stdout_matches(&self, pattern: &str) -> &Self {
+        self.assert_stream_matches("stdout", &self.stdout, pattern)
+    }
+
+    /// Asserts stderr equals the text exactly.
+    pub fn assert_stderr_eq(&self, expected: &str) -> &Self {
+        self.assert_stream_eq("stderr", &self.stderr, expected)
+    }
+
+    /// Asserts stderr contains the text.
+    pub fn assert_stderr_contains(&self, expected: &str) -> &Self {
+        self.assert_stream_contains("stderr", &self.stderr, expected)
+    }
+
+    /// Asserts stderr matches the regular expression somewhere.
+    pub fn assert_stderr_matches(&self, pattern: &str) -> &Self {
+        self.assert_stream_matches("stderr", &self.stderr, pattern)
+    }
+
+    /// Asserts the file exists.  A relative path is resolved against the runner's working directory.
+    pub fn assert_file_exists(&self, path: impl AsRef<Path>) -> &Self {
+        report::record_evaluation();
+        let path = self.resolve(path.as_ref());
+        if !path.is_file() {
+            self.fail(MismatchReport::new(
+                path.display().to_string(),
+                format!("The command did not generate the file '{}'.", path.display()),
+            ));
+        }
+        self
+    }
+
+    /// Asserts the file exists and contains the text.  A relative path is resolved against the runner's working
+    /// directory.
+    pub fn assert_file_contains(&self, path: impl AsRef<Path>, expected: &str) -> &Self {
+        self.assert_file_exists(path.as_ref());
+        let path = self.resolve(path.as_ref());
+        let contents = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Could not read the file '{}':\n\t{}\n", path.display(), err));
+        if !contents.contains(expected) {
+            self.fail(
+                MismatchReport::new(
+                    path.display().to_string(),
+                    format!("The file '{}' does not contain the expected text.", path.display()),
+                )
+                .expected(format!("{:?}", expected))
+                .actual(format!("{:?}", contents)),
+            );
+        }
+        self
+    }
+
+    fn assert_stream_eq(&self, stream: &str, actual: &str, expected: &str) -> &Self {
+        report::record_evaluation();
+        if actual != expected {
+            self.fail(
+                MismatchReport::new(stream, format!("The {} of the command does not match.", stream))
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", actual)),
+            );
+        }
+        self
+    }
+
+    fn assert_stream_contains(&self, stream: &str, actual: &str, expected: &str) -> &Self {
+        report::record_evaluation();
+        if !actual.contains(expected) {
+            self.fail(
+                MismatchReport::new(
+                    stream,
+                    format!("The {} of the command does not contain the expected text.", stream),
+                )
+                .expected(format!("{:?}", expected)),
+            );
+        }
+        self
+    }
+
+    fn assert_stream_matches(&self, stream: &str, actual: &str, pattern: &str) -> &Self {
+        report::record_evaluation();
+        let regex = regex::Regex::new(pattern)
+            .unwrap_or_else(|err| panic!("The pattern '{}' is not a valid regex:\n\t{}\n", pattern, err));
+        if !regex.is_match(actual) {
+            self.fail(
+                MismatchReport::new(
+                    stream,
+                    format!("The {} of the command does not match the expected pattern.", stream),
+                )
+                .expected(pattern),
+            );
+        }
+        self
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match &self.dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    fn render_code(&self) -> String {
+        match self.status.code() {
+            Some(code) => code.to_string(),
+            None => "terminated by a signal".to_string(),
+        }
+    }
+
+    fn fail(&self, report: MismatchReport) -> ! {
+        let output = format!(
+            "\tCommand:\t{}\n\tExit code:\t{}\n\tStdout:\n{}\n\tStderr:\n{}",
+            self.command,
+            self.render_code(),
+            indent(&self.stdout),
+            indent(&self.stderr)
+        );
+        let hint = match &report.hint {
+            Some(hint) => format!("{}\n{}", hint.trim_end(), output),
+            None => output,
+        };
+        report::fail(report.hint(hint))
+    }
+}
+
+fn indent(text: &str) -> String {
+    if text.is_empty() {
+        return "\t\t<empty>".to_string();
+    }
+    text.lines()
+        .map(|line| format!("\t\t{}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
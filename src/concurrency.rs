@@ -0,0 +1,137 @@
+//! Stress tests for the thread-safety of gateway implementations and doubles.
+//!
+//! [`run_concurrently`] calls a closure from many threads at once, released together by a barrier so the calls
+//! overlap, and fails the test with every panic and error together:
+//!
+//! ```no_run
+//! use attestify_test_framework::concurrency::run_concurrently;
+//! use attestify_test_framework::doubles::InMemoryEventBus;
+//! # #[derive(Debug, Clone)] struct Error;
+//! # #[derive(Debug)] struct EvidenceCollected;
+//!
+//! let bus = InMemoryEventBus::<Error>::new();
+//! run_concurrently(8, 100, || bus.publish(EvidenceCollected));
+//! bus.assert_published_times::<EvidenceCollected>(800);
+//! ```
+
+use crate::assertions::report::{self, MismatchReport};
+use crate::panics::{panic_message, silence_panics};
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Barrier, Mutex};
+use std::thread;
+
+/// How many failures a report lists; the rest are only counted.
+const LISTED_FAILURES: usize = 20;
+
+/// What a closure run by [`run_concurrently`] returns: nothing, or a [`Result`] whose error is a failure, such as a
+/// kernel `Error`.
+pub trait ConcurrentOutcome {
+    /// Returns the rendering of the failure, or [`None`] when the call succeeded.
+    fn failure(self) -> Option<String>;
+}
+
+impl ConcurrentOutcome for () {
+    fn failure(self) -> Option<String> {
+        None
+    }
+}
+
+impl<T, E: Debug> ConcurrentOutcome for Result<T, E> {
+    fn failure(self) -> Option<String> {
+        self.err().map(|error| format!("returned {:?}", error))
+    }
+}
+
+/// A call that panicked or returned an error.
+struct Failure {
+    thread: usize,
+    iteration: usize,
+    description: String,
+}
+
+/// Checks the closure succeeds on every call from every thread, like [`run_concurrently`], returning a
+/// [`MismatchReport`] instead of panicking.
+pub fn check_concurrently<R, F>(threads: usize, iterations: usize, work: F) -> Result<(), MismatchReport>
+where
+    R: ConcurrentOutcome,
+    F: Fn() -> R + Sync,
+{
+    let barrier = Barrier::new(threads);
+    let failures = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for thread in 0..threads {
+            let (barrier, failures, work) = (&barrier, &failures, &work);
+            scope.spawn(move || {
+                barrier.wait();
+                silence_panics(|| {
+                    for iteration in 0..iterations {
+                        let description = match panic::catch_unwind(AssertUnwindSafe(work)) {
+                            Ok(outcome) => outcome.failure(),
+                            Err(payload) => Some(format!("panicked: {}", panic_message(payload.as_ref()))),
+                        };
+                        if let Some(description) = description {
+                            failures.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(Failure {
+                                thread,
+                                iteration,
+                                description,
+                            });
+                        }
+                    }
+                })
+            });
+        }
+    });
+
+    let mut failures = failures.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if failures.is_empty() {
+        return Ok(());
+    }
+    failures.sort_by_key(|failure| (failure.iteration, failure.thread));
+    let mut listed: Vec<String> = failures
+        .iter()
+        .take(LISTED_FAILURES)
+        .map(|failure| {
+            format!(
+                "\t\t[thread {}, iteration {}] {}",
+                failure.thread, failure.iteration, failure.description
+            )
+        })
+        .collect();
+    if failures.len() > LISTED_FAILURES {
+        listed.push(format!("\t\t... and {} more", failures.len() - LISTED_FAILURES));
+    }
+    Err(MismatchReport::new(
+        "concurrency",
+        format!(
+            "{} of {} calls from {} threads failed.",
+            failures.len(),
+            threads * iterations,
+            threads
+        ),
+    )
+    .expected("every call to succeed")
+    .actual(format!("{} failures", failures.len()))
+    .hint(format!("\tFailures:\n{}", listed.join("\n"))))
+}
+
+/// Calls the closure `iterations` times from each of `threads` threads, and fails the test with every panic and
+/// error together.
+///
+/// The threads wait on a barrier until all of them have started, so their calls overlap as much as possible.  Every
+/// call is made even after one fails, and the panics are not printed as they happen.  The threads are scoped, so the
+/// closure may borrow the gateway under test.
+///
+/// # Arguments
+///
+/// * `threads` - How many threads call the closure at once.
+/// * `iterations` - How many times each thread calls the closure.
+/// * `work` - The call under test, returning nothing or a [`Result`] whose error fails the test.
+///
+pub fn run_concurrently<R, F>(threads: usize, iterations: usize, work: F)
+where
+    R: ConcurrentOutcome,
+    F: Fn() -> R + Sync,
+{
+    report::enforce(check_concurrently(threads, iterations, work));
+}
@@ -0,0 +1,329 @@
+//! A standard battery of contract tests every real and fake gateway implementation runs, so the doubles used by
+//! usecase tests are proven to behave like the production gateways they stand in for.
+//!
+//! The kernel's gateway traits are not visible to this crate, so a test adapts each implementation to
+//! [`ResourceGateway`], the operations the battery needs, on a small wrapper like the [doubles](crate::doubles).
+//! [`contract_tests!`](crate::contract_tests) then expands to one `#[test]` per clause of the contract:
+//!
+//! * `happy_path` - a written resource is read back unchanged.
+//! * `missing_resource` - reading a resource that was never written fails with the not-found kind and audience.
+//! * `permission_denied` - writing through a gateway denied access fails with the permission-denied kind and
+//!   audience.
+//! * `large_payload` - a payload of [`DEFAULT_LARGE_PAYLOAD`] bytes, or the given size, is read back unchanged.
+//!
+//! ```no_run
+//! use attestify_test_framework::contract_tests;
+//! use attestify_test_framework::contracts;
+//! use attestify_test_framework::doubles::InMemoryFileSystem;
+//! # #[derive(Debug, Clone, PartialEq)] enum Kind { NotFound, PermissionDenied }
+//! # #[derive(Debug, Clone, PartialEq)] enum Audience { User }
+//! # #[derive(Debug, Clone)] struct Error { kind: Kind, audience: Audience, message: String }
+//! # fn error(kind: Kind) -> Error { Error { kind, audience: Audience::User, message: "failed".to_string() } }
+//!
+//! fn fake() -> InMemoryFileSystem<Error> {
+//!     InMemoryFileSystem::new(|_| error(Kind::NotFound))
+//! }
+//!
+//! fn denied() -> InMemoryFileSystem<Error> {
+//!     fake().fail_on_write(contracts::RESOURCE_KEY, error(Kind::PermissionDenied))
+//! }
+//!
+//! contract_tests! {
+//!     name: in_memory_filesystem_contract,
+//!     gateway: fake,
+//!     denied_gateway: denied,
+//!     not_found: (Kind::NotFound, Audience::User),
+//!     permission_denied: (Kind::PermissionDenied, Audience::User),
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Beyond the battery, a [`ParityHarness`] runs the same scenarios against a double and the real implementation and
+//! diffs what each observed, catching drift between the fakes and the production gateways.  The real
+//! implementation, which may need a network or credentials, is only used when [`PARITY_TESTS_ENV_VAR`] is set to
+//! `1`.
+
+use crate::assertions::digest::sha256_hex;
+use crate::assertions::report::{self, MismatchReport};
+use crate::doubles::InMemoryFileSystem;
+use crate::fixtures::large_file::FilePattern;
+use std::env;
+use std::fmt::Debug;
+use std::path::Path;
+
+/// The environment variable that runs [`ParityHarness`] scenarios against the real implementation too, when it is
+/// set to `1`.
+pub const PARITY_TESTS_ENV_VAR: &str = "PARITY_TESTS";
+
+/// The key of the resource the battery writes and reads.
+pub const RESOURCE_KEY: &str = "contract/resource.json";
+
+/// The key of a resource the battery never writes.
+pub const MISSING_KEY: &str = "contract/missing.json";
+
+/// The contents of the resource written by the `happy_path` test.
+pub const RESOURCE_CONTENTS: &[u8] = br#"{"evidence": "contract", "status": "passed"}"#;
+
+/// The size in bytes of the payload written by the `large_payload` test, unless given to
+/// [`contract_tests!`](crate::contract_tests).
+pub const DEFAULT_LARGE_PAYLOAD: u64 = 8 * 1024 * 1024;
+
+/// The seed of the pseudo-random payload written by the `large_payload` test.
+const LARGE_PAYLOAD_SEED: u64 = 0x5EED;
+
+/// The operations of a gateway the contract battery needs, implemented by a test on a wrapper that forwards to the
+/// kernel gateway trait, typically its `write` and `read` methods.
+pub trait ResourceGateway {
+    /// The error of the gateway, typically an [`nape_kernel::error::Error`].
+    type Error: Debug;
+
+    /// Stores the resource under the key, replacing any resource it had.
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), Self::Error>;
+
+    /// Returns the resource stored under the key.
+    fn read(&self, key: &str) -> Result<Vec<u8>, Self::Error>;
+}
+
+impl<E: Clone + Debug> ResourceGateway for InMemoryFileSystem<E> {
+    type Error = E;
+
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), E> {
+        InMemoryFileSystem::write(self, Path::new(key), contents)
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>, E> {
+        InMemoryFileSystem::read(self, Path::new(key))
+    }
+}
+
+/// Returns the pseudo-random payload of the size written by the `large_payload` test.
+pub fn large_payload(size: u64) -> Vec<u8> {
+    let mut payload = vec![0; usize::try_from(size).expect("The payload is too large for this platform.")];
+    FilePattern::Random { seed: LARGE_PAYLOAD_SEED }.fill(0, &mut payload);
+    payload
+}
+
+/// Checks the gateway reads back the contents it wrote under the key, unchanged.  Contents are compared by length
+/// and digest, so a large payload does not flood the report.
+pub fn check_round_trip<G: ResourceGateway + ?Sized>(
+    gateway: &G,
+    key: &str,
+    contents: &[u8],
+) -> Result<(), MismatchReport> {
+    if let Err(err) = gateway.write(key, contents) {
+        return Err(MismatchReport::new(key, "The gateway failed to write the resource.").actual(format!("{:?}", err)));
+    }
+    let read = gateway.read(key).map_err(|err| {
+        MismatchReport::new(key, "The gateway failed to read the resource it wrote.").actual(format!("{:?}", err))
+    })?;
+    if read == contents {
+        return Ok(());
+    }
+    let describe = |bytes: &[u8]| format!("{} bytes, sha256 {}", bytes.len(), sha256_hex(bytes));
+    Err(
+        MismatchReport::new(key, "The gateway did not read back the resource it wrote.")
+            .expected(describe(contents))
+            .actual(describe(&read)),
+    )
+}
+
+/// Returns true when [`ParityHarness`] scenarios should run against the real implementation, see
+/// [`PARITY_TESTS_ENV_VAR`].
+pub fn parity_requested() -> bool {
+    env::var(PARITY_TESTS_ENV_VAR).as_deref() == Ok("1")
+}
+
+/// Creates a fresh implementation of the gateway for a scenario.
+type Factory<G> = Box<dyn Fn() -> Box<G>>;
+
+/// Runs a scenario against an implementation and renders what it observed.
+type Scenario<G> = Box<dyn Fn(&G) -> String>;
+
+/// Runs the same scenarios against an in-memory double and the real implementation of a gateway, and diffs their
+/// observable results, so drift between a fake and the production gateway fails a test.
+///
+/// The gateway is any trait object, such as `dyn ResourceGateway<Error = Error>` or the test's wrapper of a kernel
+/// gateway trait.  Each scenario gets fresh implementations from the factories and returns what it observed, which
+/// is compared by its [`Debug`] rendering; map errors to what both implementations must agree on, such as their
+/// kind and audience, rather than their messages.  The real implementation is only created when
+/// [`parity_requested`] is true, otherwise only the double runs, so the scenarios still cannot panic unnoticed:
+///
+/// ```no_run
+/// use attestify_test_framework::contracts::{ParityHarness, ResourceGateway};
+/// use attestify_test_framework::doubles::InMemoryFileSystem;
+/// # #[derive(Debug, Clone)] struct Error { kind: &'static str }
+/// # fn local_filesystem() -> InMemoryFileSystem<Error> { unimplemented!() }
+///
+/// ParityHarness::<dyn ResourceGateway<Error = Error>>::new(
+///     || Box::new(InMemoryFileSystem::new(|_| Error { kind: "NotFound" })),
+///     || Box::new(local_filesystem()),
+/// )
+/// .scenario("read_missing", |gateway| gateway.read("evidence/missing.json").map_err(|err| err.kind))
+/// .scenario("write_then_read", |gateway| {
+///     gateway.write("evidence/build.log", b"passed").map_err(|err| err.kind)?;
+///     gateway.read("evidence/build.log").map_err(|err| err.kind)
+/// })
+/// .assert_parity();
+/// ```
+pub struct ParityHarness<G: ?Sized> {
+    double: Factory<G>,
+    real: Factory<G>,
+    compare: bool,
+    scenarios: Vec<(String, Scenario<G>)>,
+}
+
+impl<G: ?Sized + 'static> ParityHarness<G> {
+    /// Creates a harness with no scenarios, comparing with the real implementation when [`parity_requested`].
+    ///
+    /// # Arguments
+    ///
+    /// * `double` - Creates the double, once per scenario.
+    /// * `real` - Creates the real implementation, once per scenario, and only when comparing.
+    ///
+    pub fn new(double: impl Fn() -> Box<G> + 'static, real: impl Fn() -> Box<G> + 'static) -> Self {
+        ParityHarness {
+            double: Box::new(double),
+            real: Box::new(real),
+            compare: parity_requested(),
+            scenarios: Vec::new(),
+        }
+    }
+
+    /// Sets whether to compare with the real implementation, instead of [`parity_requested`].
+    pub fn compare_with_real(mut self, compare: bool) -> Self {
+        self.compare = compare;
+        self
+    }
+
+    /// Returns true when the scenarios are compared with the real implementation.
+    pub fn compares_with_real(&self) -> bool {
+        self.compare
+    }
+
+    /// Adds a scenario, which runs against an implementation and returns what it observed.
+    pub fn scenario<O: Debug>(mut self, name: &str, scenario: impl Fn(&G) -> O + 'static) -> Self {
+        self.scenarios
+            .push((name.to_string(), Box::new(move |gateway| format!("{:#?}", scenario(gateway)))));
+        self
+    }
+
+    /// Runs every scenario, returning a [`MismatchReport`] with a diff of each scenario whose results differ
+    /// instead of panicking.
+    pub fn check_parity(&self) -> Result<(), MismatchReport> {
+        let mut diverged = Vec::new();
+        for (name, scenario) in &self.scenarios {
+            let double = scenario(&*(self.double)());
+            if !self.compare {
+                continue;
+            }
+            let real = scenario(&*(self.real)());
+            if real != double {
+                diverged.push(format!(
+                    "\tScenario '{}', expected from the real implementation, actual from the double:\n{}",
+                    name,
+                    crate::diff::render(&real, &double)
+                ));
+            }
+        }
+        if diverged.is_empty() {
+            return Ok(());
+        }
+        Err(MismatchReport::new(
+            "parity",
+            format!(
+                "The double and the real implementation diverged in {} of {} scenarios.",
+                diverged.len(),
+                self.scenarios.len()
+            ),
+        )
+        .hint(diverged.join("\n")))
+    }
+
+    /// Runs every scenario, failing the test with a diff of each scenario whose results differ.
+    pub fn assert_parity(&self) {
+        report::enforce(self.check_parity());
+    }
+}
+
+/// Expands to the contract battery for a gateway implementation: one `#[test]` for each of `happy_path`,
+/// `missing_resource`, `permission_denied`, and `large_payload`, in a module with the given name.  See
+/// [`contracts`](crate::contracts).
+///
+/// The tests are generated in a module with the battery's name, which imports everything from the enclosing module.
+///
+/// # Arguments
+///
+/// * `name` - The name of the generated module, e.g. `local_filesystem_contract`.
+/// * `gateway` - A function or closure creating the implementation under test, which implements
+///   [`ResourceGateway`](crate::contracts::ResourceGateway).  It is called once per test.
+/// * `denied_gateway` - A function or closure creating the implementation with writes to
+///   [`RESOURCE_KEY`](crate::contracts::RESOURCE_KEY) denied, e.g. over a read-only directory.
+/// * `not_found` - The kind and audience of the error reading a missing resource.
+/// * `permission_denied` - The kind and audience of the error writing a denied resource.
+/// * `large_payload` - Optionally, the size in bytes of the large payload, by default
+///   [`DEFAULT_LARGE_PAYLOAD`](crate::contracts::DEFAULT_LARGE_PAYLOAD).
+///
+#[macro_export]
+macro_rules! contract_tests {
+    (
+        name: $name:ident,
+        gateway: $gateway:expr,
+        denied_gateway: $denied_gateway:expr,
+        not_found: ($not_found_kind:expr, $not_found_audience:expr),
+        permission_denied: ($denied_kind:expr, $denied_audience:expr)
+        $(, large_payload: $large_payload:expr)? $(,)?
+    ) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn happy_path() {
+                let gateway = ($gateway)();
+                $crate::assertions::report::enforce($crate::contracts::check_round_trip(
+                    &gateway,
+                    $crate::contracts::RESOURCE_KEY,
+                    $crate::contracts::RESOURCE_CONTENTS,
+                ))
+            }
+
+            #[test]
+            fn missing_resource() {
+                let gateway = ($gateway)();
+                $crate::kernel_error_has_message!(
+                    $crate::contracts::ResourceGateway::read(&gateway, $crate::contracts::MISSING_KEY),
+                    $not_found_kind,
+                    $not_found_audience
+                );
+            }
+
+            #[test]
+            fn permission_denied() {
+                let gateway = ($denied_gateway)();
+                $crate::kernel_error_has_message!(
+                    $crate::contracts::ResourceGateway::write(
+                        &gateway,
+                        $crate::contracts::RESOURCE_KEY,
+                        $crate::contracts::RESOURCE_CONTENTS,
+                    ),
+                    $denied_kind,
+                    $denied_audience
+                );
+            }
+
+            #[test]
+            fn large_payload() {
+                #[allow(unused_variables)]
+                let size = $crate::contracts::DEFAULT_LARGE_PAYLOAD;
+                $(let size: u64 = $large_payload;)?
+                let gateway = ($gateway)();
+                $crate::assertions::report::enforce($crate::contracts::check_round_trip(
+                    &gateway,
+                    $crate::contracts::RESOURCE_KEY,
+                    &$crate::contracts::large_payload(size),
+                ))
+            }
+        }
+    };
+}
@@ -0,0 +1,270 @@
+use crate::assertions::config::{self, OutputConfig};
+use std::ops::Range;
+
+/// The environment variable that turns on ANSI coloring of rendered diffs, see [`config::COLOR_ENV_VAR`].
+pub const COLOR_ENV_VAR: &str = config::COLOR_ENV_VAR;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RED_HIGHLIGHT: &str = "\x1b[1;41m";
+const GREEN_HIGHLIGHT: &str = "\x1b[1;42m";
+const RESET: &str = "\x1b[0m";
+
+/// The largest number of cells of the table the longest common subsequence is computed with, about 32 MiB.  Longer
+/// differing inputs are diffed as one changed block.
+const MAX_LCS_CELLS: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Returns true when ANSI coloring is enabled by the [assertions config](crate::assertions::config()).
+pub fn color_enabled() -> bool {
+    config::config().color
+}
+
+/// Renders a unified diff of the expected and actual strings, with the changed characters of each changed line
+/// highlighted.  Coloring and the length of the diff follow the [assertions config](crate::assertions::config()).
+///
+/// # Arguments
+///
+/// * `expected` - The expected string.
+/// * `actual` - The actual string.
+///
+pub fn render(expected: &str, actual: &str) -> String {
+    render_with_config(expected, actual, &config::config())
+}
+
+/// Renders a unified diff of the expected and actual strings, like [`render`], with coloring set explicitly.
+///
+/// Without color, the changed characters of a line are marked with `^` on the line below it.
+///
+/// # Arguments
+///
+/// * `expected` - The expected string.
+/// * `actual` - The actual string.
+/// * `color` - Whether to color the output with ANSI escape codes.
+///
+pub fn render_with(expected: &str, actual: &str, color: bool) -> String {
+    render_with_config(expected, actual, &config::config().with_color(color))
+}
+
+/// Renders a unified diff of the expected and actual strings, like [`render`], with the configuration set explicitly.
+///
+/// A diff longer than [`OutputConfig::max_diff_lines`] shows only its first difference, with
+/// [`OutputConfig::diff_context`] unchanged lines around it, up to the maximum, and a summary of the differences
+/// left out, so a failure comparing multi-megabyte documents stays readable.
+///
+/// # Arguments
+///
+/// * `expected` - The expected string.
+/// * `actual` - The actual string.
+/// * `config` - How to render the diff.
+///
+pub fn render_with_config(expected: &str, actual: &str, config: &OutputConfig) -> String {
+    let expected_lines: Vec<&str> = expected.split('\n').collect();
+    let actual_lines: Vec<&str> = actual.split('\n').collect();
+    let ops = diff(&expected_lines, &actual_lines);
+    let shown = match config.max_diff_lines {
+        Some(max_diff_lines) if ops.len() > max_diff_lines => {
+            let shown = first_difference(&ops, config.diff_context);
+            shown.start..shown.end.min(shown.start + max_diff_lines)
+        }
+        _ => 0..ops.len(),
+    };
+
+    let mut output = String::from("\t--- expected\n\t+++ actual\n");
+    if shown.start > 0 {
+        output.push_str(&format!("\t{} {} unchanged lines\n", config.ellipsis(), shown.start));
+    }
+
+    let mut deleted: Vec<&str> = Vec::new();
+    let mut inserted: Vec<&str> = Vec::new();
+    for op in &ops[shown.clone()] {
+        match *op {
+            Op::Delete(i) => deleted.push(expected_lines[i]),
+            Op::Insert(j) => inserted.push(actual_lines[j]),
+            Op::Equal(i, _) => {
+                flush(&mut output, &mut deleted, &mut inserted, config.color);
+                output.push_str(&format!("\t  {}\n", expected_lines[i]));
+            }
+        }
+    }
+    flush(&mut output, &mut deleted, &mut inserted, config.color);
+
+    let hidden = &ops[shown.end..];
+    let differences = blocks(hidden).len();
+    if differences > 0 {
+        let changed = hidden.iter().filter(|op| !matches!(op, Op::Equal(..))).count();
+        output.push_str(&format!(
+            "\t{} {} more {} ({} changed lines) in the remaining {} lines\n",
+            config.ellipsis(),
+            differences,
+            if differences == 1 { "difference" } else { "differences" },
+            changed,
+            hidden.len()
+        ));
+    } else if !hidden.is_empty() {
+        output.push_str(&format!("\t{} {} unchanged lines\n", config.ellipsis(), hidden.len()));
+    }
+
+    output
+}
+
+/// Returns the ranges of the edits that are consecutive changes.
+fn blocks(ops: &[Op]) -> Vec<Range<usize>> {
+    let mut blocks: Vec<Range<usize>> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Equal(..)) {
+            continue;
+        }
+        match blocks.last_mut() {
+            Some(block) if block.end == index => block.end += 1,
+            _ => blocks.push(index..index + 1),
+        }
+    }
+    blocks
+}
+
+/// Returns the range of the edits of the first difference with `context` unchanged edits around it.  Later
+/// differences whose context would touch are shown with it.
+fn first_difference(ops: &[Op], context: usize) -> Range<usize> {
+    let blocks = blocks(ops);
+    let Some(first) = blocks.first() else {
+        return 0..ops.len();
+    };
+    let mut end = first.end;
+    for block in &blocks[1..] {
+        if block.start - end > 2 * context {
+            break;
+        }
+        end = block.end;
+    }
+    first.start.saturating_sub(context)..(end + context).min(ops.len())
+}
+
+/// Writes a block of changed lines, pairing each deleted line with the inserted line at the same position so the
+/// changed characters can be highlighted.
+fn flush(output: &mut String, deleted: &mut Vec<&str>, inserted: &mut Vec<&str>, color: bool) {
+    for (index, line) in deleted.iter().enumerate() {
+        let changed = inserted
+            .get(index)
+            .map(|other| changed_chars(line, other).0)
+            .unwrap_or_else(|| vec![true; line.chars().count()]);
+        write_line(output, '-', line, &changed, color);
+    }
+    for (index, line) in inserted.iter().enumerate() {
+        let changed = deleted
+            .get(index)
+            .map(|other| changed_chars(other, line).1)
+            .unwrap_or_else(|| vec![true; line.chars().count()]);
+        write_line(output, '+', line, &changed, color);
+    }
+    deleted.clear();
+    inserted.clear();
+}
+
+fn write_line(output: &mut String, sign: char, line: &str, changed: &[bool], color: bool) {
+    if color {
+        let (line_color, highlight) = if sign == '-' {
+            (RED, RED_HIGHLIGHT)
+        } else {
+            (GREEN, GREEN_HIGHLIGHT)
+        };
+        output.push_str(&format!("\t{}{} ", line_color, sign));
+        for (c, is_changed) in line.chars().zip(changed) {
+            if *is_changed {
+                output.push_str(&format!("{}{}{}{}", highlight, c, RESET, line_color));
+            } else {
+                output.push(c);
+            }
+        }
+        output.push_str(RESET);
+        output.push('\n');
+    } else {
+        output.push_str(&format!("\t{} {}\n", sign, line));
+        if changed.iter().any(|is_changed| *is_changed) && changed.iter().any(|is_changed| !*is_changed) {
+            let markers: String = changed
+                .iter()
+                .map(|is_changed| if *is_changed { '^' } else { ' ' })
+                .collect();
+            output.push_str(&format!("\t  {}\n", markers.trim_end()));
+        }
+    }
+}
+
+/// Returns, for each character of the two lines, whether it differs from the other line.
+fn changed_chars(expected: &str, actual: &str) -> (Vec<bool>, Vec<bool>) {
+    let expected_chars: Vec<char> = expected.chars().collect();
+    let actual_chars: Vec<char> = actual.chars().collect();
+    let mut expected_changed = vec![true; expected_chars.len()];
+    let mut actual_changed = vec![true; actual_chars.len()];
+
+    for op in diff(&expected_chars, &actual_chars) {
+        if let Op::Equal(i, j) = op {
+            expected_changed[i] = false;
+            actual_changed[j] = false;
+        }
+    }
+
+    (expected_changed, actual_changed)
+}
+
+/// Computes the edits that turn `a` into `b` from their longest common subsequence.  The common prefix and
+/// suffix are trimmed first, so only the differing middle is compared, and a middle too long for
+/// [`MAX_LCS_CELLS`] is one changed block.
+fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_middle = &a[prefix..a.len() - suffix];
+    let b_middle = &b[prefix..b.len() - suffix];
+    let (n, m) = (a_middle.len(), b_middle.len());
+
+    if (n + 1).saturating_mul(m + 1) > MAX_LCS_CELLS {
+        let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+        ops.extend((0..n).map(|i| Op::Delete(prefix + i)));
+        ops.extend((0..m).map(|j| Op::Insert(prefix + j)));
+        ops.extend((0..suffix).map(|k| Op::Equal(prefix + n + k, prefix + m + k)));
+        return ops;
+    }
+
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if a_middle[i] == b_middle[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a_middle[i] == b_middle[j] {
+            ops.push(Op::Equal(prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push(Op::Delete(prefix + i));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(prefix + j));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(|i| Op::Delete(prefix + i)));
+    ops.extend((j..m).map(|j| Op::Insert(prefix + j)));
+    ops.extend((0..suffix).map(|k| Op::Equal(prefix + n + k, prefix + m + k)));
+
+    ops
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use crate::sync::Mutex;
+
+/// A clock for the kernel's time-provider trait that only moves when the test moves it.
+///
+/// Share it by reference or in an `Arc` so the usecase and the test see the same time, and forward the trait's
+/// `now` to [`FrozenClock::now`]:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::FrozenClock;
+/// use std::time::Duration;
+///
+/// let clock = FrozenClock::at_rfc3339("2024-06-30T12:00:00Z");
+/// // run the usecase
+/// clock.advance(Duration::from_secs(60));
+/// ```
+pub struct FrozenClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl Default for FrozenClock {
+    /// Freezes the clock at `2024-01-01T00:00:00Z`.
+    fn default() -> Self {
+        FrozenClock::at(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+    }
+}
+
+impl FrozenClock {
+    /// Freezes the clock at `2024-01-01T00:00:00Z`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Freezes the clock at the time.
+    pub fn at(now: DateTime<Utc>) -> Self {
+        FrozenClock { now: Mutex::new(now) }
+    }
+
+    /// Freezes the clock at an RFC 3339 timestamp, e.g. `2024-06-30T12:00:00Z`.
+    pub fn at_rfc3339(timestamp: &str) -> Self {
+        let now = DateTime::parse_from_rfc3339(timestamp)
+            .unwrap_or_else(|err| panic!("The timestamp '{}' is not RFC 3339: {}", timestamp, err));
+        FrozenClock::at(now.with_timezone(&Utc))
+    }
+
+    /// Returns the current frozen time.
+    pub fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    /// Moves the clock forward by the duration.
+    pub fn advance(&self, duration: std::time::Duration) {
+        let duration = Duration::from_std(duration).expect("The duration is too large to advance the clock by.");
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Sets the clock to the time, which may be earlier than the current time.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+}
@@ -0,0 +1,277 @@
+use super::failure::InjectedFailure;
+use super::{Call, Double, FailWhen, FrozenClock};
+use crate::sync::Mutex;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a token issued by a [`FakeCredentialProvider`] is valid for, unless set with
+/// [`FakeCredentialProvider::with_token_lifetime`].
+pub const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// Why a [`FakeCredentialProvider`] refused a subscription or token, given to its `error` function to build the
+/// error the real provider returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CredentialFailure {
+    /// The subscription or token does not exist.
+    Unknown,
+    /// The token expired, by the provider's clock.
+    Expired,
+    /// The subscription was revoked, so none of its tokens are valid and no new token is issued.
+    Revoked,
+}
+
+/// An access token issued by a [`FakeCredentialProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The token, `token-<subscription>-<n>` for the nth token of the subscription.
+    pub value: String,
+    /// The subscription the token was issued for.
+    pub subscription: String,
+    /// When the token expires.
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subscription {
+    revoked: bool,
+    tokens: Vec<Token>,
+}
+
+/// Builds the error for a refused subscription or token from the reason and the subscription or token.
+type CredentialError<E> = Box<dyn Fn(CredentialFailure, &str) -> E + Send + Sync>;
+
+/// A double for the kernel's credentials and subscription provider that simulates valid tokens, expired tokens,
+/// and revoked subscriptions, so every authorization branch of a usecase, and its `Audience::User` error message,
+/// is tested.
+///
+/// Each subscription has a current token.  `credentials` returns it, or the error for why it is not valid; `refresh`
+/// issues a new one unless the subscription is revoked; and `validate` checks a token presented to the usecase.
+/// Expiry is decided by a [`FrozenClock`], which the test can share with the usecase and advance past a token's
+/// expiry:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{CredentialFailure, FakeCredentialProvider};
+/// use std::time::Duration;
+/// # #[derive(Debug, Clone)] struct Error(String);
+///
+/// let provider = FakeCredentialProvider::new(|failure, subject| match failure {
+///     CredentialFailure::Unknown => Error(format!("The subscription '{}' does not exist.", subject)),
+///     CredentialFailure::Expired => Error("Your session expired. Sign in again.".to_string()),
+///     CredentialFailure::Revoked => Error("Your subscription was revoked.".to_string()),
+/// })
+/// .with_subscription("team-a")
+/// .with_expired_subscription("team-b")
+/// .with_revoked_subscription("team-c");
+///
+/// provider.clock().advance(Duration::from_secs(7200));
+/// ```
+///
+/// Every operation is recorded under its own name: `credentials`, `refresh`, and `validate`.
+pub struct FakeCredentialProvider<E> {
+    subscriptions: Mutex<BTreeMap<String, Subscription>>,
+    failures: Mutex<HashMap<String, InjectedFailure<E>>>,
+    clock: Arc<FrozenClock>,
+    lifetime: Duration,
+    error: CredentialError<E>,
+    double: Double<E>,
+}
+
+impl<E: Clone> FakeCredentialProvider<E> {
+    /// Creates a provider with no subscriptions and its own [`FrozenClock`].
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Builds the error returned for a refused subscription or token from the reason and the
+    ///   subscription, or the token for `validate`, typically an [`nape_kernel::error::Error`] with
+    ///   `Audience::User`.
+    ///
+    pub fn new(error: impl Fn(CredentialFailure, &str) -> E + Send + Sync + 'static) -> Self {
+        FakeCredentialProvider {
+            subscriptions: Mutex::new(BTreeMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            clock: Arc::new(FrozenClock::new()),
+            lifetime: DEFAULT_TOKEN_LIFETIME,
+            error: Box::new(error),
+            double: Double::new(),
+        }
+    }
+
+    /// Decides expiry by the clock, e.g. one shared with the usecase's time provider.  Set it before adding
+    /// subscriptions, as their tokens expire relative to it.
+    pub fn with_clock(mut self, clock: Arc<FrozenClock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets how long issued tokens are valid for, [`DEFAULT_TOKEN_LIFETIME`] by default.
+    pub fn with_token_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Adds a subscription with a valid token.
+    pub fn with_subscription(self, subscription: &str) -> Self {
+        self.add(subscription, false);
+        self
+    }
+
+    /// Adds a subscription whose token expired a second ago.
+    pub fn with_expired_subscription(self, subscription: &str) -> Self {
+        self.add(subscription, false);
+        self.expire(subscription);
+        self
+    }
+
+    /// Adds a revoked subscription, with a token that is otherwise valid.
+    pub fn with_revoked_subscription(self, subscription: &str) -> Self {
+        self.add(subscription, true);
+        self
+    }
+
+    /// Makes the calls to the method selected by `when` return the error, e.g. to simulate the provider being
+    /// unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `credentials`, `refresh`, or `validate`.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::FirstCalls`] to fail the first attempts of a retry.
+    ///
+    pub fn fail_when(self, method: &str, error: E, when: FailWhen) -> Self {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), InjectedFailure::new(error, when));
+        self
+    }
+
+    fn add(&self, subscription: &str, revoked: bool) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let entry = subscriptions.entry(subscription.to_string()).or_insert(Subscription {
+            revoked,
+            tokens: Vec::new(),
+        });
+        entry.revoked = revoked;
+        let token = self.issue(subscription, entry.tokens.len() + 1);
+        entry.tokens.push(token);
+    }
+
+    fn issue(&self, subscription: &str, number: usize) -> Token {
+        let lifetime = chrono::Duration::from_std(self.lifetime).expect("The token lifetime is too large.");
+        Token {
+            value: format!("token-{}-{}", subscription, number),
+            subscription: subscription.to_string(),
+            expires_at: self.clock.now() + lifetime,
+        }
+    }
+
+    /// Returns the clock expiry is decided by.
+    pub fn clock(&self) -> &FrozenClock {
+        &self.clock
+    }
+
+    /// Makes every token of the subscription expire a second ago, while the test runs.
+    pub fn expire(&self, subscription: &str) {
+        let expired = self.clock.now() - chrono::Duration::seconds(1);
+        if let Some(entry) = self.subscriptions.lock().unwrap().get_mut(subscription) {
+            entry.tokens.iter_mut().for_each(|token| token.expires_at = expired);
+        }
+    }
+
+    /// Revokes the subscription while the test runs, so its tokens are refused.
+    pub fn revoke(&self, subscription: &str) {
+        if let Some(entry) = self.subscriptions.lock().unwrap().get_mut(subscription) {
+            entry.revoked = true;
+        }
+    }
+
+    /// Returns every token issued for the subscription, oldest first.
+    pub fn tokens(&self, subscription: &str) -> Vec<Token> {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .get(subscription)
+            .map(|entry| entry.tokens.clone())
+            .unwrap_or_default()
+    }
+
+    fn before_call(&self, method: &str) -> Result<(), E> {
+        match self.failures.lock().unwrap().get_mut(method).and_then(InjectedFailure::next) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the reason the token cannot be used, if any.
+    fn refusal(&self, revoked: bool, token: &Token) -> Option<CredentialFailure> {
+        if revoked {
+            Some(CredentialFailure::Revoked)
+        } else if token.expires_at <= self.clock.now() {
+            Some(CredentialFailure::Expired)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current token of the subscription, failing when it is unknown, revoked, or its token expired.
+    pub fn credentials(&self, subscription: &str) -> Result<Token, E> {
+        self.double.record("credentials", &[&subscription]);
+        self.before_call("credentials")?;
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let Some(entry) = subscriptions.get(subscription) else {
+            return Err((self.error)(CredentialFailure::Unknown, subscription));
+        };
+        let token = entry.tokens.last().expect("A subscription always has a token.");
+        match self.refusal(entry.revoked, token) {
+            Some(failure) => Err((self.error)(failure, subscription)),
+            None => Ok(token.clone()),
+        }
+    }
+
+    /// Issues a new token for the subscription, failing when it is unknown or revoked.
+    pub fn refresh(&self, subscription: &str) -> Result<Token, E> {
+        self.double.record("refresh", &[&subscription]);
+        self.before_call("refresh")?;
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let Some(entry) = subscriptions.get_mut(subscription) else {
+            return Err((self.error)(CredentialFailure::Unknown, subscription));
+        };
+        if entry.revoked {
+            return Err((self.error)(CredentialFailure::Revoked, subscription));
+        }
+        let token = self.issue(subscription, entry.tokens.len() + 1);
+        entry.tokens.push(token.clone());
+        Ok(token)
+    }
+
+    /// Returns the token presented to the usecase, failing when it was never issued, its subscription is revoked,
+    /// or it expired.  A token is valid until it expires, even after a newer one is issued.
+    pub fn validate(&self, token: &str) -> Result<Token, E> {
+        self.double.record("validate", &[&token]);
+        self.before_call("validate")?;
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let Some((revoked, issued)) = subscriptions
+            .values()
+            .flat_map(|entry| entry.tokens.iter().map(move |issued| (entry.revoked, issued)))
+            .find(|(_, issued)| issued.value == token)
+        else {
+            return Err((self.error)(CredentialFailure::Unknown, token));
+        };
+        match self.refusal(revoked, issued) {
+            Some(failure) => Err((self.error)(failure, token)),
+            None => Ok(issued.clone()),
+        }
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
@@ -0,0 +1,221 @@
+use super::{Call, Double, FailWhen};
+use crate::assertions::report::{self, MismatchReport};
+use crate::assertions::structs::type_name;
+use crate::sync::Mutex;
+use std::any::Any;
+use std::fmt::Debug;
+
+/// An event published to an [`InMemoryEventBus`].
+struct PublishedEvent {
+    /// The type name of the event, without its module path.
+    name: &'static str,
+    rendered: String,
+    event: Box<dyn Any + Send>,
+}
+
+/// A double for the kernel's event publishing trait that keeps every published domain event, so tests assert on
+/// what a usecase published instead of subscribing to a real bus.
+///
+/// Events of any type are kept in publishing order, and assertions select them by type:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::InMemoryEventBus;
+/// # #[derive(Debug, Clone)] struct Error;
+/// # #[derive(Debug, Clone)] struct EvidenceCollected { procedure: String }
+/// # #[derive(Debug, Clone)] struct ReportGenerated;
+///
+/// let bus = InMemoryEventBus::<Error>::new();
+/// // run the usecase with a wrapper that implements the publishing trait by forwarding to bus.publish(event)
+///
+/// bus.assert_published::<EvidenceCollected>(|e| e.procedure == "p1");
+/// bus.assert_not_published::<ReportGenerated>(|_| true);
+/// bus.assert_published_in_order(&["EvidenceCollected", "ReportGenerated"]);
+/// ```
+///
+/// Publishing is recorded as a call to `publish`, and can be made to fail with [`InMemoryEventBus::fail`]; an event
+/// whose publishing failed is not kept.
+pub struct InMemoryEventBus<E> {
+    events: Mutex<Vec<PublishedEvent>>,
+    double: Double<E>,
+}
+
+impl<E: Clone> Default for InMemoryEventBus<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone> InMemoryEventBus<E> {
+    /// Creates a bus with no published events or failures.
+    pub fn new() -> Self {
+        InMemoryEventBus {
+            events: Mutex::new(Vec::new()),
+            double: Double::new(),
+        }
+    }
+
+    /// Programs the error returned by every `publish`.
+    pub fn fail(self, error: E) -> Self {
+        self.double.fail("publish", error);
+        self
+    }
+
+    /// Programs the error returned by the calls to `publish` selected by `when`.
+    pub fn fail_when(self, error: E, when: FailWhen) -> Self {
+        self.double.fail_when("publish", error, when);
+        self
+    }
+
+    /// Keeps the event, unless a failure is programmed for this call.
+    pub fn publish<T: Any + Debug + Send>(&self, event: T) -> Result<(), E> {
+        self.double.invoke_or("publish", &[&event], ())?;
+        self.events.lock().unwrap().push(PublishedEvent {
+            name: type_name::<T>(),
+            rendered: format!("{:?}", event),
+            event: Box::new(event),
+        });
+        Ok(())
+    }
+
+    /// Returns a copy of every published event of the type, in publishing order.
+    pub fn published<T: Any + Clone>(&self) -> Vec<T> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|published| published.event.downcast_ref::<T>().cloned())
+            .collect()
+    }
+
+    /// Returns the type name of every published event, in publishing order, e.g. `["EvidenceCollected"]`.
+    pub fn published_names(&self) -> Vec<&'static str> {
+        self.events.lock().unwrap().iter().map(|published| published.name).collect()
+    }
+
+    /// Returns the number of published events of the type that satisfy the predicate.
+    pub fn count_published<T: Any>(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|published| published.event.downcast_ref::<T>())
+            .filter(|event| predicate(event))
+            .count()
+    }
+
+    /// Checks an event of the type that satisfies the predicate was published, like
+    /// [`InMemoryEventBus::assert_published`], returning a [`MismatchReport`] instead of panicking.
+    pub fn check_published<T: Any>(&self, predicate: impl Fn(&T) -> bool) -> Result<(), MismatchReport> {
+        if self.count_published(predicate) > 0 {
+            return Ok(());
+        }
+        Err(MismatchReport::new(
+            type_name::<T>(),
+            format!("No published {} event satisfies the predicate.", type_name::<T>()),
+        )
+        .hint(self.render_events()))
+    }
+
+    /// Asserts an event of the type that satisfies the predicate was published.  On failure it lists every
+    /// published event.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The check of the event, e.g. `|e| e.procedure == "p1"`; use `|_| true` for any event of the
+    ///   type.
+    ///
+    pub fn assert_published<T: Any>(&self, predicate: impl Fn(&T) -> bool) {
+        report::enforce(self.check_published(predicate));
+    }
+
+    /// Asserts no event of the type that satisfies the predicate was published.
+    pub fn assert_not_published<T: Any>(&self, predicate: impl Fn(&T) -> bool) {
+        report::record_evaluation();
+        let count = self.count_published(predicate);
+        if count > 0 {
+            report::fail(
+                MismatchReport::new(
+                    type_name::<T>(),
+                    format!("A {} event that satisfies the predicate was published.", type_name::<T>()),
+                )
+                .expected("0 events")
+                .actual(format!("{} events", count))
+                .hint(self.render_events()),
+            );
+        }
+    }
+
+    /// Asserts exactly `times` events of the type were published.
+    pub fn assert_published_times<T: Any>(&self, times: usize) {
+        report::record_evaluation();
+        let count = self.count_published::<T>(|_| true);
+        if count != times {
+            report::fail(
+                MismatchReport::new(
+                    type_name::<T>(),
+                    format!("The number of published {} events does not match.", type_name::<T>()),
+                )
+                .expected(format!("{} events", times))
+                .actual(format!("{} events", count))
+                .hint(self.render_events()),
+            );
+        }
+    }
+
+    /// Checks events of the named types were published in the order given, like
+    /// [`InMemoryEventBus::assert_published_in_order`], returning a [`MismatchReport`] instead of panicking.
+    pub fn check_published_in_order(&self, names: &[&str]) -> Result<(), MismatchReport> {
+        let published = self.published_names();
+        let mut remaining = published.iter();
+        for (position, name) in names.iter().enumerate() {
+            if !remaining.any(|published| published == name) {
+                return Err(MismatchReport::new(
+                    *name,
+                    format!(
+                        "No {} event was published after the events before it in the expected order.",
+                        name
+                    ),
+                )
+                .expected(names[..=position].join(" -> "))
+                .actual(published.join(" -> "))
+                .hint(self.render_events()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Asserts events of the named types were published in the order given.  Other events may be published before,
+    /// between, and after them.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The type names of the events, without their module paths, e.g. `["EvidenceCollected",
+    ///   "ReportGenerated"]`.
+    ///
+    pub fn assert_published_in_order(&self, names: &[&str]) {
+        report::enforce(self.check_published_in_order(names));
+    }
+
+    fn render_events(&self) -> String {
+        let events = self.events.lock().unwrap();
+        if events.is_empty() {
+            return "\tPublished:\n\t\t<none>".to_string();
+        }
+        let rendered: Vec<String> = events
+            .iter()
+            .enumerate()
+            .map(|(index, published)| format!("\t\t[{}] {}", index, published.rendered))
+            .collect();
+        format!("\tPublished:\n{}", rendered.join("\n"))
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to `publish`, including failed ones.
+    pub fn call_count(&self) -> usize {
+        self.double.call_count("publish")
+    }
+}
@@ -0,0 +1,55 @@
+use super::{Call, Double, FailWhen};
+
+/// A double for the kernel's evidence retrieval gateway.
+///
+/// Calls to `retrieve` are recorded and need a programmed response.
+pub struct EvidenceRetrievalGatewayDouble<E> {
+    double: Double<E>,
+}
+
+impl<E: Clone> Default for EvidenceRetrievalGatewayDouble<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone> EvidenceRetrievalGatewayDouble<E> {
+    /// Creates an evidence retrieval gateway double with no responses or failures.
+    pub fn new() -> Self {
+        EvidenceRetrievalGatewayDouble {
+            double: Double::new(),
+        }
+    }
+
+    /// Programs the evidence returned by `retrieve`.
+    pub fn respond_to_retrieve(self, evidence: &[u8]) -> Self {
+        self.double.respond("retrieve", evidence.to_vec());
+        self
+    }
+
+    /// Programs the error returned by `retrieve`.
+    pub fn fail(self, error: E) -> Self {
+        self.double.fail("retrieve", error);
+        self
+    }
+
+    /// Programs the error returned by the calls to `retrieve` selected by `when`.
+    pub fn fail_when(self, error: E, when: FailWhen) -> Self {
+        self.double.fail_when("retrieve", error, when);
+        self
+    }
+
+    pub fn retrieve(&self, location: &str) -> Result<Vec<u8>, E> {
+        self.double.invoke("retrieve", &[&location])
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to `retrieve`.
+    pub fn call_count(&self) -> usize {
+        self.double.call_count("retrieve")
+    }
+}
@@ -0,0 +1,43 @@
+/// When an injected failure is returned, counted over the calls it applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailWhen {
+    /// Every call fails.
+    Always,
+    /// Only the nth call fails, counting from 1.
+    OnCall(usize),
+    /// The first n calls succeed and every later call fails.
+    AfterCalls(usize),
+    /// The first n calls fail and every later call succeeds, e.g. to test that a usecase retries.
+    FirstCalls(usize),
+}
+
+impl FailWhen {
+    /// Returns true when the call, counting from 1, fails.
+    pub fn fails(&self, call: usize) -> bool {
+        match *self {
+            FailWhen::Always => true,
+            FailWhen::OnCall(n) => call == n,
+            FailWhen::AfterCalls(n) => call > n,
+            FailWhen::FirstCalls(n) => call <= n,
+        }
+    }
+}
+
+/// An error injected into a double, with the calls it applies to counted so far.
+pub(crate) struct InjectedFailure<E> {
+    error: E,
+    when: FailWhen,
+    calls: usize,
+}
+
+impl<E: Clone> InjectedFailure<E> {
+    pub(crate) fn new(error: E, when: FailWhen) -> Self {
+        InjectedFailure { error, when, calls: 0 }
+    }
+
+    /// Counts a call and returns the error when that call fails.
+    pub(crate) fn next(&mut self) -> Option<E> {
+        self.calls += 1;
+        self.when.fails(self.calls).then(|| self.error.clone())
+    }
+}
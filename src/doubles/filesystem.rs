@@ -0,0 +1,92 @@
+use super::{Call, Double, FailWhen};
+use std::path::{Path, PathBuf};
+
+/// A double for the kernel's filesystem gateway.
+///
+/// Every method is recorded under its own name.  `write`, `create_dir`, and `remove` succeed by default; `read`
+/// and `exists` need a programmed response.
+pub struct FileSystemGatewayDouble<E> {
+    double: Double<E>,
+}
+
+impl<E: Clone> Default for FileSystemGatewayDouble<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone> FileSystemGatewayDouble<E> {
+    /// Creates a filesystem gateway double with no responses or failures.
+    pub fn new() -> Self {
+        FileSystemGatewayDouble {
+            double: Double::new(),
+        }
+    }
+
+    /// Programs the contents returned by `read`.
+    pub fn respond_to_read(self, contents: &str) -> Self {
+        self.double.respond("read", contents.to_string());
+        self
+    }
+
+    /// Programs the value returned by `exists`.
+    pub fn respond_to_exists(self, exists: bool) -> Self {
+        self.double.respond("exists", exists);
+        self
+    }
+
+    /// Programs the error returned by the method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `read`, `write`, `exists`, `create_dir`, or `remove`.
+    /// * `error` - The error returned in an [`Err`].
+    ///
+    pub fn fail(self, method: &str, error: E) -> Self {
+        self.double.fail(method, error);
+        self
+    }
+
+    /// Programs the error returned by the calls to the method selected by `when`.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `read`, `write`, `exists`, `create_dir`, or `remove`.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::FirstCalls`] to fail the first attempts of a retry.
+    ///
+    pub fn fail_when(self, method: &str, error: E, when: FailWhen) -> Self {
+        self.double.fail_when(method, error, when);
+        self
+    }
+
+    pub fn read(&self, path: &Path) -> Result<String, E> {
+        self.double.invoke("read", &[&path])
+    }
+
+    pub fn write(&self, path: &Path, contents: &str) -> Result<(), E> {
+        self.double.invoke_or("write", &[&path, &contents], ())
+    }
+
+    pub fn exists(&self, path: &Path) -> Result<bool, E> {
+        self.double.invoke("exists", &[&path])
+    }
+
+    pub fn create_dir(&self, path: &Path) -> Result<PathBuf, E> {
+        self.double.invoke_or("create_dir", &[&path], path.to_path_buf())
+    }
+
+    pub fn remove(&self, path: &Path) -> Result<(), E> {
+        self.double.invoke_or("remove", &[&path], ())
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
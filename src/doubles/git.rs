@@ -0,0 +1,85 @@
+use super::{Call, Double, FailWhen};
+use std::path::{Path, PathBuf};
+
+/// A double for the kernel's git gateway.
+///
+/// Every method is recorded under its own name.  `clone_repository` succeeds with the destination and `checkout`
+/// succeeds by default; `head_commit` needs a programmed response.
+pub struct GitGatewayDouble<E> {
+    double: Double<E>,
+}
+
+impl<E: Clone> Default for GitGatewayDouble<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Clone> GitGatewayDouble<E> {
+    /// Creates a git gateway double with no responses or failures.
+    pub fn new() -> Self {
+        GitGatewayDouble {
+            double: Double::new(),
+        }
+    }
+
+    /// Programs the path returned by `clone_repository`.
+    pub fn respond_to_clone(self, path: PathBuf) -> Self {
+        self.double.respond("clone_repository", path);
+        self
+    }
+
+    /// Programs the commit SHA returned by `head_commit`.
+    pub fn respond_to_head_commit(self, sha: &str) -> Self {
+        self.double.respond("head_commit", sha.to_string());
+        self
+    }
+
+    /// Programs the error returned by the method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `clone_repository`, `checkout`, or `head_commit`.
+    /// * `error` - The error returned in an [`Err`].
+    ///
+    pub fn fail(self, method: &str, error: E) -> Self {
+        self.double.fail(method, error);
+        self
+    }
+
+    /// Programs the error returned by the calls to the method selected by `when`.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `clone_repository`, `checkout`, or `head_commit`.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::FirstCalls`] to fail the first attempts of a retry.
+    ///
+    pub fn fail_when(self, method: &str, error: E, when: FailWhen) -> Self {
+        self.double.fail_when(method, error, when);
+        self
+    }
+
+    pub fn clone_repository(&self, url: &str, destination: &Path) -> Result<PathBuf, E> {
+        self.double
+            .invoke_or("clone_repository", &[&url, &destination], destination.to_path_buf())
+    }
+
+    pub fn checkout(&self, repository: &Path, reference: &str) -> Result<(), E> {
+        self.double.invoke_or("checkout", &[&repository, &reference], ())
+    }
+
+    pub fn head_commit(&self, repository: &Path) -> Result<String, E> {
+        self.double.invoke("head_commit", &[&repository])
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::sync::Mutex;
+
+enum Format {
+    Sequential { prefix: String },
+    SeededUuid { seed: u64 },
+}
+
+/// An identifier provider for the kernel's identifier-generation trait that yields predictable identifiers.
+///
+/// By default it yields `test-id-0001`, `test-id-0002`, ...; [`SequentialIdProvider::seeded_uuids`] yields
+/// well-formed version 4 UUIDs that are the same for the same seed on every run.
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::SequentialIdProvider;
+///
+/// let ids = SequentialIdProvider::new();
+/// assert_eq!(ids.next_id(), "test-id-0001");
+/// assert_eq!(ids.next_id(), "test-id-0002");
+/// ```
+pub struct SequentialIdProvider {
+    format: Format,
+    generated: Mutex<Vec<String>>,
+}
+
+impl Default for SequentialIdProvider {
+    fn default() -> Self {
+        Self::with_prefix("test-id")
+    }
+}
+
+impl SequentialIdProvider {
+    /// Creates a provider yielding `test-id-0001`, `test-id-0002`, ...
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a provider yielding `<prefix>-0001`, `<prefix>-0002`, ...
+    pub fn with_prefix(prefix: &str) -> Self {
+        SequentialIdProvider {
+            format: Format::Sequential {
+                prefix: prefix.to_string(),
+            },
+            generated: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a provider yielding version 4 UUIDs derived from the seed.
+    pub fn seeded_uuids(seed: u64) -> Self {
+        SequentialIdProvider {
+            format: Format::SeededUuid { seed },
+            generated: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the next identifier.
+    pub fn next_id(&self) -> String {
+        let mut generated = self.generated.lock().unwrap();
+        let number = generated.len() as u64 + 1;
+        let id = match &self.format {
+            Format::Sequential { prefix } => format!("{}-{:04}", prefix, number),
+            Format::SeededUuid { seed } => uuid(*seed, number),
+        };
+        generated.push(id.clone());
+        id
+    }
+
+    /// Returns every identifier yielded so far, in order.
+    pub fn generated(&self) -> Vec<String> {
+        self.generated.lock().unwrap().clone()
+    }
+
+    /// Starts the sequence over.
+    pub fn reset(&self) {
+        self.generated.lock().unwrap().clear();
+    }
+}
+
+/// Formats 128 pseudo-random bits from the seed and number as a version 4, RFC 4122 variant UUID.
+fn uuid(seed: u64, number: u64) -> String {
+    let high = splitmix64(seed ^ splitmix64(number));
+    let low = splitmix64(high ^ number);
+    let high = (high & 0xffff_ffff_ffff_0fff) | 0x0000_0000_0000_4000;
+    let low = (low & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000;
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
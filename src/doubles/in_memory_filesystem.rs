@@ -0,0 +1,202 @@
+use super::failure::InjectedFailure;
+use super::{Call, Double, FailWhen};
+use crate::sync::Mutex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// The operation a failure is injected into, or [`None`] for every operation, and the path.
+type FailureKey = (Option<String>, PathBuf);
+
+/// A filesystem gateway double backed by a map of paths to bytes, so usecase tests never touch the disk.
+///
+/// Files are created by writing them; directories exist implicitly when a file is stored beneath them.  Reading
+/// or deleting a missing file returns the error built by the `not_found` function given to
+/// [`InMemoryFileSystem::new`].  Failures can be injected per path, for every operation or for one operation,
+/// and for every call or only some calls, see [`FailWhen`]:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{FailWhen, InMemoryFileSystem};
+/// # #[derive(Debug, Clone)] struct Error(&'static str);
+///
+/// let filesystem = InMemoryFileSystem::new(|_| Error("not found"))
+///     .fail_on_write("evidence/report.yaml", Error("disk full"))
+///     .fail_when("read", "evidence/payload.json", Error("timed out"), FailWhen::FirstCalls(2));
+/// ```
+///
+/// Every operation is recorded under its own name: `read`, `write`, `list`, `delete`, and `exists`.
+pub struct InMemoryFileSystem<E> {
+    files: Mutex<BTreeMap<PathBuf, Vec<u8>>>,
+    failures: Mutex<HashMap<FailureKey, InjectedFailure<E>>>,
+    not_found: Box<dyn Fn(&Path) -> E + Send + Sync>,
+    double: Double<E>,
+}
+
+impl<E: Clone> InMemoryFileSystem<E> {
+    /// Creates an empty filesystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `not_found` - Builds the error returned when a file does not exist, typically an
+    ///   [`nape_kernel::error::Error`] with the kind and audience the real gateway uses.
+    ///
+    pub fn new(not_found: impl Fn(&Path) -> E + Send + Sync + 'static) -> Self {
+        InMemoryFileSystem {
+            files: Mutex::new(BTreeMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            not_found: Box::new(not_found),
+            double: Double::new(),
+        }
+    }
+
+    /// Stores a file before the test runs.  This is not recorded as a call.
+    pub fn with_file(self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Self {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), contents.as_ref().to_vec());
+        self
+    }
+
+    /// Makes every operation on the path return the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file or directory.
+    /// * `error` - The error returned in an [`Err`].
+    ///
+    pub fn fail_on(self, path: impl AsRef<Path>, error: E) -> Self {
+        self.inject(None, path.as_ref(), error, FailWhen::Always)
+    }
+
+    /// Makes every `read` of the path return the error.
+    pub fn fail_on_read(self, path: impl AsRef<Path>, error: E) -> Self {
+        self.fail_when("read", path, error, FailWhen::Always)
+    }
+
+    /// Makes every `write` to the path return the error.
+    pub fn fail_on_write(self, path: impl AsRef<Path>, error: E) -> Self {
+        self.fail_when("write", path, error, FailWhen::Always)
+    }
+
+    /// Makes every `delete` of the path return the error.
+    pub fn fail_on_delete(self, path: impl AsRef<Path>, error: E) -> Self {
+        self.fail_when("delete", path, error, FailWhen::Always)
+    }
+
+    /// Makes the calls to one operation on the path selected by `when` return the error.  Calls are counted per
+    /// operation and path.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `read`, `write`, `list`, `delete`, or `exists`.
+    /// * `path` - The path of the file or directory.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::OnCall`] for only the nth call.
+    ///
+    pub fn fail_when(self, method: &str, path: impl AsRef<Path>, error: E, when: FailWhen) -> Self {
+        self.inject(Some(method.to_string()), path.as_ref(), error, when)
+    }
+
+    fn inject(self, method: Option<String>, path: &Path, error: E, when: FailWhen) -> Self {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert((method, path.to_path_buf()), InjectedFailure::new(error, when));
+        self
+    }
+
+    /// Returns the injected failure for the call, checking the operation's own failure before the failure for
+    /// every operation.
+    fn failure_for(&self, method: &str, path: &Path) -> Result<(), E> {
+        let mut failures = self.failures.lock().unwrap();
+        for key in [(Some(method.to_string()), path.to_path_buf()), (None, path.to_path_buf())] {
+            if let Some(error) = failures.get_mut(&key).and_then(InjectedFailure::next) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read(&self, path: &Path) -> Result<Vec<u8>, E> {
+        self.double.record("read", &[&path]);
+        self.failure_for("read", path)?;
+        self.files
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| (self.not_found)(path))
+    }
+
+    /// Reads the file as UTF-8 text.  Invalid UTF-8 is replaced, as this is only used for assertions.
+    pub fn read_to_string(&self, path: &Path) -> Result<String, E> {
+        self.read(path)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    pub fn write(&self, path: &Path, contents: &[u8]) -> Result<(), E> {
+        self.double.record("write", &[&path, &String::from_utf8_lossy(contents)]);
+        self.failure_for("write", path)?;
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), contents.to_vec());
+        Ok(())
+    }
+
+    /// Returns the files and implied directories directly inside the directory, sorted.
+    pub fn list(&self, dir: &Path) -> Result<Vec<PathBuf>, E> {
+        self.double.record("list", &[&dir]);
+        self.failure_for("list", dir)?;
+        let entries: BTreeSet<PathBuf> = self
+            .files
+            .lock()
+            .unwrap()
+            .keys()
+            .filter_map(|path| path.strip_prefix(dir).ok())
+            .filter_map(|relative| relative.components().next())
+            .map(|component| dir.join(component))
+            .collect();
+        Ok(entries.into_iter().collect())
+    }
+
+    /// Deletes the file, or every file beneath the path when it is a directory.
+    pub fn delete(&self, path: &Path) -> Result<(), E> {
+        self.double.record("delete", &[&path]);
+        self.failure_for("delete", path)?;
+        let mut files = self.files.lock().unwrap();
+        let before = files.len();
+        files.retain(|stored, _| !stored.starts_with(path));
+        if files.len() == before {
+            return Err((self.not_found)(path));
+        }
+        Ok(())
+    }
+
+    /// Returns true when the path is a stored file or an implied directory.
+    pub fn exists(&self, path: &Path) -> Result<bool, E> {
+        self.double.record("exists", &[&path]);
+        self.failure_for("exists", path)?;
+        Ok(self
+            .files
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|stored| stored.starts_with(path)))
+    }
+
+    /// Returns a copy of every stored file, for assertions on what a usecase wrote.
+    pub fn files(&self) -> BTreeMap<PathBuf, Vec<u8>> {
+        self.files.lock().unwrap().clone()
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
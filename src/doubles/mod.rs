@@ -0,0 +1,238 @@
+//! Configurable test doubles for the kernel's gateway traits.
+//!
+//! Each double records its calls, returns canned responses per method, and can be programmed to fail a method
+//! with a specific error.  The kernel traits are not visible to this crate, so a test implements the trait on a
+//! small wrapper that forwards each method to the double:
+//!
+//! ```no_run
+//! use attestify_test_framework::doubles::FileSystemGatewayDouble;
+//! use std::path::Path;
+//! # #[derive(Debug, Clone)] struct Error;
+//! # trait FileSystemGateway { fn read(&self, path: &Path) -> Result<String, Error>; }
+//!
+//! struct FakeFileSystem(FileSystemGatewayDouble<Error>);
+//!
+//! impl FileSystemGateway for FakeFileSystem {
+//!     fn read(&self, path: &Path) -> Result<String, Error> {
+//!         self.0.read(path)
+//!     }
+//! }
+//! ```
+//!
+//! The recorded calls of any double, and the requests of an [`HttpStub`](crate::http_stub::HttpStub), are verified
+//! with [`verify`], e.g. `verify(&filesystem).method("write").times(3)`.
+
+mod clock;
+mod credentials;
+mod event_bus;
+mod evidence;
+mod failure;
+mod filesystem;
+mod git;
+mod id;
+mod in_memory_filesystem;
+mod object_store;
+mod procedure_catalog;
+mod random;
+mod retry;
+mod spy;
+mod verify;
+
+pub use clock::FrozenClock;
+pub use credentials::{CredentialFailure, DEFAULT_TOKEN_LIFETIME, FakeCredentialProvider, Token};
+pub use event_bus::InMemoryEventBus;
+pub use evidence::EvidenceRetrievalGatewayDouble;
+pub use failure::FailWhen;
+pub use filesystem::FileSystemGatewayDouble;
+pub use git::GitGatewayDouble;
+pub use id::SequentialIdProvider;
+pub(crate) use id::splitmix64;
+pub use in_memory_filesystem::InMemoryFileSystem;
+pub use object_store::InMemoryObjectStore;
+pub use procedure_catalog::FakeProcedureCatalog;
+pub use random::{SeededRandom, TEST_SEED_ENV_VAR, seed_from_env};
+pub use retry::RetryObserver;
+pub use spy::Spy;
+pub use verify::{MethodVerification, RecordsCalls, Verification, verify};
+
+use failure::InjectedFailure;
+use crate::sync::Mutex;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// A single recorded invocation of a double.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// The name of the method that was called.
+    pub method: String,
+    /// The [`Debug`] rendering of each argument, in order.
+    pub args: Vec<String>,
+}
+
+/// The generic double every gateway double is built on.  It records calls, and answers each method with either a
+/// programmed failure or a canned response.
+///
+/// A failure takes precedence over a response for the same method.
+pub struct Double<E> {
+    calls: Mutex<Vec<Call>>,
+    responses: Mutex<HashMap<String, Box<dyn Any + Send>>>,
+    failures: Mutex<HashMap<String, InjectedFailure<E>>>,
+}
+
+impl<E> Default for Double<E> {
+    fn default() -> Self {
+        Double {
+            calls: Mutex::new(Vec::new()),
+            responses: Mutex::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<E: Clone> Double<E> {
+    /// Creates a double with no calls, responses, or failures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Programs the response returned every time the method is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method.
+    /// * `response` - The value returned in an [`Ok`].
+    ///
+    pub fn respond<T: Clone + Send + 'static>(&self, method: &str, response: T) {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), Box::new(response));
+    }
+
+    /// Programs the error returned every time the method is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method.
+    /// * `error` - The error returned in an [`Err`], typically an [`nape_kernel::error::Error`] with the kind and
+    ///   audience under test.
+    ///
+    pub fn fail(&self, method: &str, error: E) {
+        self.fail_when(method, error, FailWhen::Always);
+    }
+
+    /// Programs the error returned by the calls to the method selected by `when`; the other calls get the
+    /// programmed response.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::OnCall`] for only the nth call.
+    ///
+    pub fn fail_when(&self, method: &str, error: E, when: FailWhen) {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert(method.to_string(), InjectedFailure::new(error, when));
+    }
+
+    /// Removes any programmed response and failure for the method.
+    pub fn reset(&self, method: &str) {
+        self.responses.lock().unwrap().remove(method);
+        self.failures.lock().unwrap().remove(method);
+    }
+
+    /// Records a call to the method and returns its programmed failure or response.  If neither is programmed the
+    /// test panics.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method being called.
+    /// * `args` - The arguments of the call, recorded with their [`Debug`] rendering.
+    ///
+    pub fn invoke<T: Clone + 'static>(&self, method: &str, args: &[&dyn Debug]) -> Result<T, E> {
+        self.invoke_or_else(method, args, || {
+            panic!("No response was programmed for the method '{}'.", method)
+        })
+    }
+
+    /// Records a call to the method and returns its programmed failure or response, or the given default when
+    /// neither is programmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method being called.
+    /// * `args` - The arguments of the call, recorded with their [`Debug`] rendering.
+    /// * `default` - The value returned when no response is programmed.
+    ///
+    pub fn invoke_or<T: Clone + 'static>(&self, method: &str, args: &[&dyn Debug], default: T) -> Result<T, E> {
+        self.invoke_or_else(method, args, || default)
+    }
+
+    fn invoke_or_else<T: Clone + 'static>(
+        &self,
+        method: &str,
+        args: &[&dyn Debug],
+        default: impl FnOnce() -> T,
+    ) -> Result<T, E> {
+        self.record(method, args);
+
+        if let Some(error) = self
+            .failures
+            .lock()
+            .unwrap()
+            .get_mut(method)
+            .and_then(InjectedFailure::next)
+        {
+            return Err(error);
+        }
+
+        match self.responses.lock().unwrap().get(method) {
+            Some(response) => Ok(response.downcast_ref::<T>().cloned().unwrap_or_else(|| {
+                panic!(
+                    "The response programmed for the method '{}' is not a '{}'.",
+                    method,
+                    std::any::type_name::<T>()
+                )
+            })),
+            None => Ok(default()),
+        }
+    }
+
+    /// Records a call to the method without answering it, for doubles that compute their own responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method being called.
+    /// * `args` - The arguments of the call, recorded with their [`Debug`] rendering.
+    ///
+    pub fn record(&self, method: &str, args: &[&dyn Debug]) {
+        self.calls.lock().unwrap().push(Call {
+            method: method.to_string(),
+            args: args.iter().map(|arg| format!("{:?}", arg)).collect(),
+        });
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Returns the recorded calls to the method, in order.
+    pub fn calls_to(&self, method: &str) -> Vec<Call> {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.calls_to(method).len()
+    }
+}
@@ -0,0 +1,222 @@
+use super::failure::InjectedFailure;
+use super::{Call, Double, FailWhen};
+use crate::sync::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// The operation a failure is injected into, or [`None`] for every operation, the bucket, and the key, or [`None`]
+/// for every key in the bucket.
+type FailureKey = (Option<String>, String, Option<String>);
+
+/// Builds the error for a missing bucket or object from the bucket and key.
+type NotFound<E> = Box<dyn Fn(&str, &str) -> E + Send + Sync>;
+
+/// A blob storage gateway double backed by a map of buckets and keys to bytes, so evidence archival usecases are
+/// tested without MinIO or cloud credentials.
+///
+/// Buckets must exist before objects are stored in them, like in S3: create them with
+/// [`InMemoryObjectStore::with_bucket`] or [`InMemoryObjectStore::with_object`].  Getting or deleting a missing
+/// object, or using a missing bucket, returns the error built by the `not_found` function given to
+/// [`InMemoryObjectStore::new`].  Failures can be injected per object or per bucket, and latency can be added to
+/// every call or to one operation:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{FailWhen, InMemoryObjectStore};
+/// use std::time::Duration;
+/// # #[derive(Debug, Clone)] struct Error(&'static str);
+///
+/// let store = InMemoryObjectStore::new(|_, _| Error("no such key"))
+///     .with_bucket("evidence-archive")
+///     .with_latency_for("put", Duration::from_millis(200))
+///     .fail_when("get", "evidence-archive", Some("p1/report.yaml"), Error("throttled"), FailWhen::FirstCalls(2));
+/// ```
+///
+/// Every operation is recorded under its own name: `put`, `get`, `list`, and `delete`.
+pub struct InMemoryObjectStore<E> {
+    buckets: Mutex<BTreeMap<String, BTreeMap<String, Vec<u8>>>>,
+    failures: Mutex<HashMap<FailureKey, InjectedFailure<E>>>,
+    latency: HashMap<Option<String>, Duration>,
+    not_found: NotFound<E>,
+    double: Double<E>,
+}
+
+impl<E: Clone> InMemoryObjectStore<E> {
+    /// Creates a store with no buckets.
+    ///
+    /// # Arguments
+    ///
+    /// * `not_found` - Builds the error returned for a missing bucket or object from the bucket and key, typically
+    ///   an [`nape_kernel::error::Error`] with the kind and audience the real gateway uses.  The key is empty for
+    ///   a missing bucket in `list`.
+    ///
+    pub fn new(not_found: impl Fn(&str, &str) -> E + Send + Sync + 'static) -> Self {
+        InMemoryObjectStore {
+            buckets: Mutex::new(BTreeMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            latency: HashMap::new(),
+            not_found: Box::new(not_found),
+            double: Double::new(),
+        }
+    }
+
+    /// Creates an empty bucket before the test runs.
+    pub fn with_bucket(self, bucket: &str) -> Self {
+        self.buckets.lock().unwrap().entry(bucket.to_string()).or_default();
+        self
+    }
+
+    /// Stores an object before the test runs, creating its bucket.  This is not recorded as a call.
+    pub fn with_object(self, bucket: &str, key: &str, contents: impl AsRef<[u8]>) -> Self {
+        self.buckets
+            .lock()
+            .unwrap()
+            .entry(bucket.to_string())
+            .or_default()
+            .insert(key.to_string(), contents.as_ref().to_vec());
+        self
+    }
+
+    /// Delays every call by the duration, before it is answered.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency.insert(None, latency);
+        self
+    }
+
+    /// Delays every call to one operation by the duration, instead of the latency of every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `put`, `get`, `list`, or `delete`.
+    /// * `latency` - How long each call waits before it is answered.
+    ///
+    pub fn with_latency_for(mut self, method: &str, latency: Duration) -> Self {
+        self.latency.insert(Some(method.to_string()), latency);
+        self
+    }
+
+    /// Makes every operation on the object return the error.
+    pub fn fail_on(self, bucket: &str, key: &str, error: E) -> Self {
+        self.inject(None, bucket, Some(key), error, FailWhen::Always)
+    }
+
+    /// Makes every operation on the bucket, and every object in it, return the error.
+    pub fn fail_on_bucket(self, bucket: &str, error: E) -> Self {
+        self.inject(None, bucket, None, error, FailWhen::Always)
+    }
+
+    /// Makes the calls to one operation selected by `when` return the error.  Calls are counted per operation,
+    /// bucket, and key.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `put`, `get`, `list`, or `delete`.
+    /// * `bucket` - The bucket.
+    /// * `key` - The key of the object, the prefix for `list`, or [`None`] for every call on the bucket.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::OnCall`] for only the nth call.
+    ///
+    pub fn fail_when(self, method: &str, bucket: &str, key: Option<&str>, error: E, when: FailWhen) -> Self {
+        self.inject(Some(method.to_string()), bucket, key, error, when)
+    }
+
+    fn inject(self, method: Option<String>, bucket: &str, key: Option<&str>, error: E, when: FailWhen) -> Self {
+        self.failures.lock().unwrap().insert(
+            (method, bucket.to_string(), key.map(str::to_string)),
+            InjectedFailure::new(error, when),
+        );
+        self
+    }
+
+    /// Waits for the call's latency, then returns its injected failure, checking the most specific failure first.
+    fn before_call(&self, method: &str, bucket: &str, key: &str) -> Result<(), E> {
+        if let Some(latency) = self
+            .latency
+            .get(&Some(method.to_string()))
+            .or_else(|| self.latency.get(&None))
+        {
+            std::thread::sleep(*latency);
+        }
+
+        let mut failures = self.failures.lock().unwrap();
+        let method = Some(method.to_string());
+        let bucket = bucket.to_string();
+        let key = Some(key.to_string());
+        for failure_key in [
+            (method.clone(), bucket.clone(), key.clone()),
+            (None, bucket.clone(), key),
+            (method, bucket.clone(), None),
+            (None, bucket, None),
+        ] {
+            if let Some(error) = failures.get_mut(&failure_key).and_then(InjectedFailure::next) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn put(&self, bucket: &str, key: &str, contents: &[u8]) -> Result<(), E> {
+        self.double
+            .record("put", &[&bucket, &key, &String::from_utf8_lossy(contents)]);
+        self.before_call("put", bucket, key)?;
+        match self.buckets.lock().unwrap().get_mut(bucket) {
+            Some(objects) => {
+                objects.insert(key.to_string(), contents.to_vec());
+                Ok(())
+            }
+            None => Err((self.not_found)(bucket, key)),
+        }
+    }
+
+    pub fn get(&self, bucket: &str, key: &str) -> Result<Vec<u8>, E> {
+        self.double.record("get", &[&bucket, &key]);
+        self.before_call("get", bucket, key)?;
+        self.buckets
+            .lock()
+            .unwrap()
+            .get(bucket)
+            .and_then(|objects| objects.get(key))
+            .cloned()
+            .ok_or_else(|| (self.not_found)(bucket, key))
+    }
+
+    /// Returns the keys in the bucket that start with the prefix, sorted.
+    pub fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<String>, E> {
+        self.double.record("list", &[&bucket, &prefix]);
+        self.before_call("list", bucket, prefix)?;
+        let buckets = self.buckets.lock().unwrap();
+        let objects = buckets.get(bucket).ok_or_else(|| (self.not_found)(bucket, ""))?;
+        Ok(objects.keys().filter(|key| key.starts_with(prefix)).cloned().collect())
+    }
+
+    pub fn delete(&self, bucket: &str, key: &str) -> Result<(), E> {
+        self.double.record("delete", &[&bucket, &key]);
+        self.before_call("delete", bucket, key)?;
+        self.buckets
+            .lock()
+            .unwrap()
+            .get_mut(bucket)
+            .and_then(|objects| objects.remove(key))
+            .map(|_| ())
+            .ok_or_else(|| (self.not_found)(bucket, key))
+    }
+
+    /// Returns a copy of every object in the bucket, for assertions on what a usecase archived.
+    pub fn objects(&self, bucket: &str) -> BTreeMap<String, Vec<u8>> {
+        self.buckets
+            .lock()
+            .unwrap()
+            .get(bucket)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
@@ -0,0 +1,285 @@
+use super::failure::InjectedFailure;
+use super::{Call, Double, FailWhen};
+use crate::fixtures::procedure_fixture::ProcedureDocument;
+use crate::sync::Mutex;
+use serde_yaml::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// The operation a failure is injected into, or [`None`] for every operation, and the NRN.
+type FailureKey = (Option<String>, String);
+
+/// Builds the error for a missing procedure from the NRN and the version, or [`None`] for the latest version.
+type NotFound<E> = Box<dyn Fn(&str, Option<&str>) -> E + Send + Sync>;
+
+/// Builds the error for a version conflict from the NRN and the version being published.
+type Conflict<E> = Box<dyn Fn(&str, &str) -> E + Send + Sync>;
+
+/// A double for the kernel's procedure retrieval gateway, backed by a map of procedure NRNs to versions of their
+/// YAML documents, so "download procedure by NRN" usecases are tested without the hosted catalog.
+///
+/// The catalog is seeded with generated documents, YAML text, or fixture files, whose `metadata.nrn` and
+/// `metadata.version` say where they are stored.  `retrieve` returns the latest version, ordered as semantic
+/// versions.  Publishing a version that exists returns the error built by the `conflict` function given to
+/// [`FakeProcedureCatalog::new`], and a conflict can be injected, as if another client published first:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{FailWhen, FakeProcedureCatalog};
+/// use attestify_test_framework::fixtures::procedure_fixture;
+/// use std::time::Duration;
+/// # #[derive(Debug, Clone)] struct Error(String);
+///
+/// let procedure = procedure_fixture::minimal();
+/// let catalog = FakeProcedureCatalog::new(
+///     |nrn, _| Error(format!("{} not found", nrn)),
+///     |nrn, version| Error(format!("{} {} already exists", nrn, version)),
+/// )
+/// .with_procedure(&procedure)
+/// .with_latency(Duration::from_millis(300))
+/// .with_version_conflict(&procedure.procedure.nrn, FailWhen::FirstCalls(1));
+/// ```
+///
+/// Every operation is recorded under its own name: `retrieve`, `retrieve_version`, `versions`, and `publish`.
+pub struct FakeProcedureCatalog<E> {
+    procedures: Mutex<BTreeMap<String, BTreeMap<String, String>>>,
+    failures: Mutex<HashMap<FailureKey, InjectedFailure<E>>>,
+    conflicts: Mutex<HashMap<String, InjectedFailure<()>>>,
+    latency: HashMap<Option<String>, Duration>,
+    not_found: NotFound<E>,
+    conflict: Conflict<E>,
+    double: Double<E>,
+}
+
+impl<E: Clone> FakeProcedureCatalog<E> {
+    /// Creates an empty catalog.
+    ///
+    /// # Arguments
+    ///
+    /// * `not_found` - Builds the error returned for a missing procedure or version from the NRN and the version,
+    ///   typically an [`nape_kernel::error::Error`] with the kind and audience the real gateway uses.
+    /// * `conflict` - Builds the error returned when a published version already exists from the NRN and the
+    ///   version.
+    ///
+    pub fn new(
+        not_found: impl Fn(&str, Option<&str>) -> E + Send + Sync + 'static,
+        conflict: impl Fn(&str, &str) -> E + Send + Sync + 'static,
+    ) -> Self {
+        FakeProcedureCatalog {
+            procedures: Mutex::new(BTreeMap::new()),
+            failures: Mutex::new(HashMap::new()),
+            conflicts: Mutex::new(HashMap::new()),
+            latency: HashMap::new(),
+            not_found: Box::new(not_found),
+            conflict: Box::new(conflict),
+            double: Double::new(),
+        }
+    }
+
+    /// Stores a generated procedure document under its NRN and version before the test runs.
+    pub fn with_procedure(self, document: &ProcedureDocument) -> Self {
+        self.store(&document.procedure.nrn, &document.procedure.version, &document.yaml);
+        self
+    }
+
+    /// Stores a procedure document under the `metadata.nrn` and `metadata.version` of its YAML before the test runs.
+    /// The test fails when either is missing.
+    pub fn with_yaml(self, yaml: &str) -> Self {
+        let (nrn, version) = identify(yaml).unwrap_or_else(|| {
+            panic!("The procedure has no metadata.nrn or metadata.version to store it under:\n\t{}\n", yaml)
+        });
+        self.store(&nrn, &version, yaml);
+        self
+    }
+
+    /// Stores the procedure document in a fixture file, like [`FakeProcedureCatalog::with_yaml`].
+    pub fn with_fixture_file(self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let yaml = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Could not read the procedure '{}':\n\t{}\n", path.display(), err));
+        self.with_yaml(&yaml)
+    }
+
+    /// Delays every call by the duration, before it is answered.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency.insert(None, latency);
+        self
+    }
+
+    /// Delays every call to one operation by the duration, instead of the latency of every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `retrieve`, `retrieve_version`, `versions`, or `publish`.
+    /// * `latency` - How long each call waits before it is answered.
+    ///
+    pub fn with_latency_for(mut self, method: &str, latency: Duration) -> Self {
+        self.latency.insert(Some(method.to_string()), latency);
+        self
+    }
+
+    /// Makes every operation on the procedure return the error.
+    pub fn fail_on(self, nrn: &str, error: E) -> Self {
+        self.inject(None, nrn, error, FailWhen::Always)
+    }
+
+    /// Makes the calls to one operation on the procedure selected by `when` return the error.  Calls are counted
+    /// per operation and NRN.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - One of `retrieve`, `retrieve_version`, `versions`, or `publish`.
+    /// * `nrn` - The NRN of the procedure.
+    /// * `error` - The error returned in an [`Err`].
+    /// * `when` - Which calls fail, e.g. [`FailWhen::FirstCalls`] to fail the first attempts of a retry.
+    ///
+    pub fn fail_when(self, method: &str, nrn: &str, error: E, when: FailWhen) -> Self {
+        self.inject(Some(method.to_string()), nrn, error, when)
+    }
+
+    /// Makes the calls to `publish` the procedure selected by `when` return the conflict error, as if another
+    /// client published the same version first, e.g. to test that a usecase re-reads and retries.
+    pub fn with_version_conflict(self, nrn: &str, when: FailWhen) -> Self {
+        self.conflicts
+            .lock()
+            .unwrap()
+            .insert(nrn.to_string(), InjectedFailure::new((), when));
+        self
+    }
+
+    fn inject(self, method: Option<String>, nrn: &str, error: E, when: FailWhen) -> Self {
+        self.failures
+            .lock()
+            .unwrap()
+            .insert((method, nrn.to_string()), InjectedFailure::new(error, when));
+        self
+    }
+
+    fn store(&self, nrn: &str, version: &str, yaml: &str) {
+        self.procedures
+            .lock()
+            .unwrap()
+            .entry(nrn.to_string())
+            .or_default()
+            .insert(version.to_string(), yaml.to_string());
+    }
+
+    /// Waits for the call's latency, then returns its injected failure, checking the operation's failure first.
+    fn before_call(&self, method: &str, nrn: &str) -> Result<(), E> {
+        if let Some(latency) = self
+            .latency
+            .get(&Some(method.to_string()))
+            .or_else(|| self.latency.get(&None))
+        {
+            std::thread::sleep(*latency);
+        }
+
+        let mut failures = self.failures.lock().unwrap();
+        for failure_key in [(Some(method.to_string()), nrn.to_string()), (None, nrn.to_string())] {
+            if let Some(error) = failures.get_mut(&failure_key).and_then(InjectedFailure::next) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the latest version of the procedure.
+    pub fn retrieve(&self, nrn: &str) -> Result<String, E> {
+        self.double.record("retrieve", &[&nrn]);
+        self.before_call("retrieve", nrn)?;
+        self.procedures
+            .lock()
+            .unwrap()
+            .get(nrn)
+            .and_then(|versions| versions.iter().max_by(|a, b| compare_versions(a.0, b.0)))
+            .map(|(_, yaml)| yaml.clone())
+            .ok_or_else(|| (self.not_found)(nrn, None))
+    }
+
+    pub fn retrieve_version(&self, nrn: &str, version: &str) -> Result<String, E> {
+        self.double.record("retrieve_version", &[&nrn, &version]);
+        self.before_call("retrieve_version", nrn)?;
+        self.procedures
+            .lock()
+            .unwrap()
+            .get(nrn)
+            .and_then(|versions| versions.get(version))
+            .cloned()
+            .ok_or_else(|| (self.not_found)(nrn, Some(version)))
+    }
+
+    /// Returns the versions of the procedure, from oldest to latest.
+    pub fn versions(&self, nrn: &str) -> Result<Vec<String>, E> {
+        self.double.record("versions", &[&nrn]);
+        self.before_call("versions", nrn)?;
+        let procedures = self.procedures.lock().unwrap();
+        let versions = procedures.get(nrn).ok_or_else(|| (self.not_found)(nrn, None))?;
+        let mut versions: Vec<String> = versions.keys().cloned().collect();
+        versions.sort_by(|a, b| compare_versions(a, b));
+        Ok(versions)
+    }
+
+    /// Stores a new version of the procedure, failing with the conflict error when the version exists.
+    pub fn publish(&self, nrn: &str, version: &str, yaml: &str) -> Result<(), E> {
+        self.double.record("publish", &[&nrn, &version]);
+        self.before_call("publish", nrn)?;
+        let injected = self
+            .conflicts
+            .lock()
+            .unwrap()
+            .get_mut(nrn)
+            .and_then(InjectedFailure::next)
+            .is_some();
+        let mut procedures = self.procedures.lock().unwrap();
+        let versions = procedures.entry(nrn.to_string()).or_default();
+        if injected || versions.contains_key(version) {
+            return Err((self.conflict)(nrn, version));
+        }
+        versions.insert(version.to_string(), yaml.to_string());
+        Ok(())
+    }
+
+    /// Returns a copy of every version of the procedure, for assertions on what a usecase published.
+    pub fn procedure_versions(&self, nrn: &str) -> BTreeMap<String, String> {
+        self.procedures.lock().unwrap().get(nrn).cloned().unwrap_or_default()
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.double.calls()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.double.call_count(method)
+    }
+}
+
+/// Returns the `metadata.nrn` and `metadata.version` of a procedure document.
+fn identify(yaml: &str) -> Option<(String, String)> {
+    let document: Value = serde_yaml::from_str(yaml).ok()?;
+    let metadata = document.get("metadata")?;
+    let field = |name: &str| match metadata.get(name)? {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    };
+    Some((field("nrn")?, field("version")?))
+}
+
+/// Orders versions as semantic versions: by their numeric dot-separated parts, then a release after its
+/// pre-releases.  Parts that are not numbers are ordered as text, after the numbers.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let key = |version: &str| {
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre_release) = version.split_once('-').map_or((version, None), |(core, pre)| (core, Some(pre)));
+        let parts: Vec<(u64, String)> = core
+            .split('.')
+            .map(|part| (part.parse().unwrap_or(u64::MAX), part.to_string()))
+            .collect();
+        (parts, pre_release.is_none(), pre_release.unwrap_or_default().to_string())
+    };
+    key(a).cmp(&key(b))
+}
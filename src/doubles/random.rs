@@ -0,0 +1,195 @@
+use super::id::splitmix64;
+use crate::assertions::report;
+use crate::sync::Mutex;
+use std::collections::VecDeque;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable that fixes the seed of every [`SeededRandom::new`], e.g. `TEST_SEED=42 cargo test`, to
+/// reproduce a failure with the seed it reported.
+pub const TEST_SEED_ENV_VAR: &str = "TEST_SEED";
+
+/// Returns the seed set with [`TEST_SEED_ENV_VAR`], if any.  The test panics when it is not a `u64`.
+pub fn seed_from_env() -> Option<u64> {
+    let seed = env::var(TEST_SEED_ENV_VAR).ok()?;
+    Some(seed.trim().parse().unwrap_or_else(|_| {
+        panic!("The {} environment variable '{}' is not a u64.", TEST_SEED_ENV_VAR, seed)
+    }))
+}
+
+struct State {
+    next: u64,
+    scripted: VecDeque<u64>,
+    drawn: Vec<u64>,
+}
+
+/// A random source for the kernel traits that consume randomness, such as nonce generation and sampling, that yields
+/// the same sequence for the same seed on every run.
+///
+/// A test forwards the trait to the double and wraps its assertions in [`SeededRandom::report_seed_on_failure`], so
+/// a failure reports the seed it needs to be reproduced:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::SeededRandom;
+///
+/// let random = SeededRandom::new();
+/// // run the usecase with a wrapper that implements the randomness trait by forwarding to random.bytes(n)
+///
+/// random.report_seed_on_failure(|| {
+///     assert_eq!(random.draw_count(), 2);
+/// });
+/// ```
+///
+/// Values programmed with [`SeededRandom::script`] are yielded before the seeded sequence, for tests that need a
+/// specific value, such as a nonce that collides.
+pub struct SeededRandom {
+    seed: u64,
+    state: Mutex<State>,
+}
+
+impl Default for SeededRandom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeededRandom {
+    /// Creates a source with the seed of [`TEST_SEED_ENV_VAR`] when it is set, or a seed that differs on every run
+    /// otherwise.
+    pub fn new() -> Self {
+        Self::from_seed(seed_from_env().unwrap_or_else(fresh_seed))
+    }
+
+    /// Creates a source yielding the sequence of the seed.
+    pub fn from_seed(seed: u64) -> Self {
+        SeededRandom {
+            seed,
+            state: Mutex::new(State {
+                next: seed,
+                scripted: VecDeque::new(),
+                drawn: Vec::new(),
+            }),
+        }
+    }
+
+    /// Programs values yielded by [`SeededRandom::next_u64`], in order, before the seeded sequence continues.
+    pub fn script(self, values: impl IntoIterator<Item = u64>) -> Self {
+        self.state.lock().unwrap().scripted.extend(values);
+        self
+    }
+
+    /// Returns the seed, to be logged or set with [`TEST_SEED_ENV_VAR`] to reproduce the run.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the next value of the sequence.  Every other method draws its values from this one.
+    pub fn next_u64(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let value = match state.scripted.pop_front() {
+            Some(value) => value,
+            None => {
+                let value = splitmix64(state.next);
+                state.next = state.next.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                value
+            }
+        };
+        state.drawn.push(value);
+        value
+    }
+
+    /// Returns a number below the bound, which must not be zero.
+    pub fn below(&self, bound: u64) -> u64 {
+        assert!(bound > 0, "The bound of SeededRandom::below must not be zero.");
+        self.next_u64() % bound
+    }
+
+    /// Returns a number in `[0, 1)`.
+    pub fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Fills the buffer with random bytes, e.g. a nonce.
+    pub fn fill_bytes(&self, buffer: &mut [u8]) {
+        for chunk in buffer.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// Returns `len` random bytes.
+    pub fn bytes(&self, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0; len];
+        self.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Returns a random item of the slice, or [`None`] when it is empty.
+    pub fn choose<'a, T>(&self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Shuffles the slice in place.
+    pub fn shuffle<T>(&self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index as u64 + 1) as usize);
+        }
+    }
+
+    /// Returns `count` distinct items of the slice, in random order, or all of them when there are fewer.
+    pub fn sample<T: Clone>(&self, items: &[T], count: usize) -> Vec<T> {
+        let mut items = items.to_vec();
+        self.shuffle(&mut items);
+        items.truncate(count);
+        items
+    }
+
+    /// Returns every value drawn with [`SeededRandom::next_u64`] so far, in order.
+    pub fn drawn(&self) -> Vec<u64> {
+        self.state.lock().unwrap().drawn.clone()
+    }
+
+    /// Returns the number of values drawn so far.
+    pub fn draw_count(&self) -> usize {
+        self.state.lock().unwrap().drawn.len()
+    }
+
+    /// Runs the test, and when it fails adds the seed to the failure so it can be reproduced with
+    /// [`TEST_SEED_ENV_VAR`].
+    ///
+    /// A failed assertion of this crate fails again with the seed in the hint of its report; any other panic is
+    /// preceded by the seed on stderr.
+    pub fn report_seed_on_failure<R>(&self, test: impl FnOnce() -> R) -> R {
+        report::take_last_failure();
+        let panic = match panic::catch_unwind(AssertUnwindSafe(test)) {
+            Ok(value) => return value,
+            Err(panic) => panic,
+        };
+        let seed = format!("\tSeed:\n\t\t{} (rerun with {}={})", self.seed, TEST_SEED_ENV_VAR, self.seed);
+        if let Some(mut failure) = report::take_last_failure() {
+            failure.hint = Some(match failure.hint.take() {
+                Some(hint) => format!("{}\n{}", hint.trim_end(), seed),
+                None => seed,
+            });
+            report::fail(failure);
+        }
+        eprintln!("{}", seed.trim_start());
+        panic::resume_unwind(panic)
+    }
+}
+
+/// Returns a seed that differs between runs and between the sources of one run.
+fn fresh_seed() -> u64 {
+    static CREATED: std::sync::Mutex<u64> = std::sync::Mutex::new(0);
+    let mut created = CREATED.lock().unwrap();
+    *created += 1;
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    splitmix64(nanos ^ splitmix64(*created) ^ u64::from(std::process::id()))
+}
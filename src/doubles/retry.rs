@@ -0,0 +1,192 @@
+use super::FrozenClock;
+use crate::assertions::report::{self, MismatchReport};
+use crate::sync::Mutex;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Observes the attempts of a retry loop on a [`FrozenClock`], so exponential backoff is verified without real
+/// sleeping.
+///
+/// The gateway under test sleeps through the observer, by forwarding the kernel's sleep or time-provider trait to
+/// [`RetryObserver::sleep`], which advances the clock instead of blocking.  Each attempt is recorded with
+/// [`RetryObserver::attempt`], or by wrapping the call that is retried in [`RetryObserver::observe`].  The backoff
+/// before an attempt is the time the clock moved since the previous attempt:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{FailWhen, GitGatewayDouble, RetryObserver};
+/// use std::time::Duration;
+/// # #[derive(Debug, Clone)] struct Error;
+///
+/// let git = GitGatewayDouble::new().fail_when("head_commit", Error, FailWhen::FirstCalls(2));
+/// let observer = RetryObserver::new();
+/// // the gateway calls `observer.observe(|| git.head_commit(path))` and `observer.sleep(backoff)`
+/// observer
+///     .assert_attempts(3)
+///     .assert_backoff_sequence([Duration::from_secs(1), Duration::from_secs(2)]);
+/// ```
+pub struct RetryObserver {
+    clock: Arc<FrozenClock>,
+    attempts: Mutex<Vec<DateTime<Utc>>>,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl Default for RetryObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryObserver {
+    /// Creates an observer with its own [`FrozenClock`].
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(FrozenClock::new()))
+    }
+
+    /// Creates an observer on the clock, e.g. one shared with the usecase's time provider.
+    pub fn with_clock(clock: Arc<FrozenClock>) -> Self {
+        RetryObserver {
+            clock,
+            attempts: Mutex::new(Vec::new()),
+            sleeps: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the clock the observer advances.
+    pub fn clock(&self) -> &FrozenClock {
+        &self.clock
+    }
+
+    /// Records an attempt at the current time of the clock.
+    pub fn attempt(&self) {
+        self.attempts.lock().unwrap().push(self.clock.now());
+    }
+
+    /// Records an attempt, then makes it.
+    pub fn observe<T>(&self, attempt: impl FnOnce() -> T) -> T {
+        self.attempt();
+        attempt()
+    }
+
+    /// Records the sleep and advances the clock by the duration, returning immediately.
+    pub fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        self.clock.advance(duration);
+    }
+
+    /// Returns the number of attempts recorded.
+    pub fn attempts(&self) -> usize {
+        self.attempts.lock().unwrap().len()
+    }
+
+    /// Returns the time the clock moved between each attempt and the next, in order.
+    pub fn backoffs(&self) -> Vec<Duration> {
+        self.attempts
+            .lock()
+            .unwrap()
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).to_std().unwrap_or_default())
+            .collect()
+    }
+
+    /// Returns every sleep, in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+
+    /// Returns the total time slept.
+    pub fn total_sleep(&self) -> Duration {
+        self.sleeps().iter().sum()
+    }
+
+    /// Checks the number of attempts, returning a [`MismatchReport`] instead of panicking.
+    pub fn check_attempts(&self, attempts: usize) -> Result<(), MismatchReport> {
+        if self.attempts() == attempts {
+            return Ok(());
+        }
+        Err(
+            MismatchReport::new("attempts", "The operation was not attempted the expected number of times.")
+                .expected(attempts.to_string())
+                .actual(self.attempts().to_string())
+                .hint(self.render_attempts()),
+        )
+    }
+
+    /// Asserts the number of attempts.
+    pub fn assert_attempts(&self, attempts: usize) -> &Self {
+        report::enforce(self.check_attempts(attempts));
+        self
+    }
+
+    /// Checks the backoff before each retry, returning a [`MismatchReport`] instead of panicking.
+    pub fn check_backoff_sequence(&self, backoffs: impl IntoIterator<Item = Duration>) -> Result<(), MismatchReport> {
+        self.check_backoffs(backoffs, 0.0)
+    }
+
+    /// Asserts the backoff before each retry, e.g. `[1s, 2s, 4s]` for three retries with exponential backoff.
+    pub fn assert_backoff_sequence(&self, backoffs: impl IntoIterator<Item = Duration>) -> &Self {
+        report::enforce(self.check_backoff_sequence(backoffs));
+        self
+    }
+
+    /// Checks the backoff before each retry is within a percentage of the expected backoff, for backoff with
+    /// jitter, returning a [`MismatchReport`] instead of panicking.
+    pub fn check_backoff_sequence_within(
+        &self,
+        backoffs: impl IntoIterator<Item = Duration>,
+        tolerance_pct: f64,
+    ) -> Result<(), MismatchReport> {
+        self.check_backoffs(backoffs, tolerance_pct)
+    }
+
+    /// Asserts the backoff before each retry is within a percentage of the expected backoff, for backoff with
+    /// jitter.
+    pub fn assert_backoff_sequence_within(
+        &self,
+        backoffs: impl IntoIterator<Item = Duration>,
+        tolerance_pct: f64,
+    ) -> &Self {
+        report::enforce(self.check_backoff_sequence_within(backoffs, tolerance_pct));
+        self
+    }
+
+    fn check_backoffs(
+        &self,
+        expected: impl IntoIterator<Item = Duration>,
+        tolerance_pct: f64,
+    ) -> Result<(), MismatchReport> {
+        let expected: Vec<Duration> = expected.into_iter().collect();
+        let actual = self.backoffs();
+        let within = |actual: &Duration, expected: &Duration| {
+            actual == expected
+                || (actual.as_secs_f64() - expected.as_secs_f64()).abs() <= expected.as_secs_f64() * tolerance_pct / 100.0
+        };
+        if actual.len() == expected.len() && actual.iter().zip(&expected).all(|(a, e)| within(a, e)) {
+            return Ok(());
+        }
+        let summary = if tolerance_pct == 0.0 {
+            "The backoff before each retry does not match.".to_string()
+        } else {
+            format!("The backoff before each retry is not within {}% of the expected backoff.", tolerance_pct)
+        };
+        Err(
+            MismatchReport::new("backoffs", summary)
+                .expected(format!("{:?}", expected))
+                .actual(format!("{:?}", actual))
+                .hint(self.render_attempts()),
+        )
+    }
+
+    fn render_attempts(&self) -> String {
+        let attempts = self.attempts.lock().unwrap();
+        let Some(first) = attempts.first() else {
+            return "\tAttempts:\n\t\t<none>".to_string();
+        };
+        let lines: Vec<String> = attempts
+            .iter()
+            .enumerate()
+            .map(|(index, at)| format!("\t\t{} at +{:?}", index + 1, (*at - *first).to_std().unwrap_or_default()))
+            .collect();
+        format!("\tAttempts:\n{}\n\tSleeps:\n\t\t{:?}", lines.join("\n"), self.sleeps())
+    }
+}
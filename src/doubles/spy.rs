@@ -0,0 +1,117 @@
+use super::{Call, verify};
+use crate::sync::Mutex;
+use std::fmt::Debug;
+
+/// Wraps any gateway, real or double, and records the ordered list of method invocations made through it.
+///
+/// The forwarding wrapper that implements the kernel trait calls [`Spy::call`], which records the invocation and
+/// then forwards it to the wrapped gateway:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::Spy;
+/// # #[derive(Debug, Clone)] struct Error;
+/// # trait EvidenceStore { fn store(&self, name: &str) -> Result<(), Error>; }
+/// # struct RealStore;
+/// # impl EvidenceStore for RealStore { fn store(&self, _name: &str) -> Result<(), Error> { Ok(()) } }
+///
+/// struct SpiedStore(Spy<RealStore>);
+///
+/// impl EvidenceStore for SpiedStore {
+///     fn store(&self, name: &str) -> Result<(), Error> {
+///         self.0.call("store", &[&name], |inner| inner.store(name))
+///     }
+/// }
+///
+/// let store = SpiedStore(Spy::new(RealStore));
+/// store.store("evidence.json").unwrap();
+/// store.0.assert_called_once("store");
+/// ```
+pub struct Spy<T> {
+    inner: T,
+    calls: Mutex<Vec<Call>>,
+}
+
+impl<T> Spy<T> {
+    /// Wraps the gateway.
+    pub fn new(inner: T) -> Self {
+        Spy {
+            inner,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the wrapped gateway.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the wrapped gateway, consuming the spy.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Records a call to the method, then forwards it to the wrapped gateway and returns its result.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method being called.
+    /// * `args` - The arguments of the call, recorded with their [`Debug`] rendering.
+    /// * `forward` - Calls the method on the wrapped gateway.
+    ///
+    pub fn call<R>(&self, method: &str, args: &[&dyn Debug], forward: impl FnOnce(&T) -> R) -> R {
+        self.record(method, args);
+        forward(&self.inner)
+    }
+
+    /// Records a call to the method without forwarding it.
+    pub fn record(&self, method: &str, args: &[&dyn Debug]) {
+        self.calls.lock().unwrap().push(Call {
+            method: method.to_string(),
+            args: args.iter().map(|arg| format!("{:?}", arg)).collect(),
+        });
+    }
+
+    /// Returns every recorded call, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Returns the recorded calls to the method, in order.
+    pub fn calls_to(&self, method: &str) -> Vec<Call> {
+        self.calls()
+            .into_iter()
+            .filter(|call| call.method == method)
+            .collect()
+    }
+
+    /// Returns the number of recorded calls to the method.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.calls_to(method).len()
+    }
+
+    /// Asserts the method was called exactly the given number of times.
+    pub fn assert_called_times(&self, method: &str, times: usize) {
+        verify(self).method(method).times(times);
+    }
+
+    /// Asserts the method was called exactly once.
+    pub fn assert_called_once(&self, method: &str) {
+        verify(self).method(method).once();
+    }
+
+    /// Asserts the method was never called.
+    pub fn assert_not_called(&self, method: &str) {
+        verify(self).method(method).never();
+    }
+
+    /// Asserts the methods were called in the given relative order.  Calls to other methods, and further calls to
+    /// the given methods, may occur in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `methods` - The method names in their expected order, e.g. `["fetch", "verify", "store"]`.
+    ///
+    pub fn assert_call_order<'a>(&self, methods: impl IntoIterator<Item = &'a str>) {
+        verify(self).in_order(methods);
+    }
+}
@@ -0,0 +1,224 @@
+use super::{Call, Double, EvidenceRetrievalGatewayDouble, FileSystemGatewayDouble, GitGatewayDouble};
+use super::{FakeCredentialProvider, FakeProcedureCatalog, InMemoryEventBus, InMemoryFileSystem, InMemoryObjectStore};
+use super::Spy;
+use crate::assertions::report::{self, MismatchReport};
+use crate::http_stub::HttpStub;
+use std::fmt::Debug;
+
+/// Anything that records the calls made to it, so its interactions can be verified with [`verify`].
+pub trait RecordsCalls {
+    /// Returns every recorded call, in order.
+    fn recorded_calls(&self) -> Vec<Call>;
+}
+
+macro_rules! records_calls {
+    ($($double:ident<$param:ident>),* $(,)?) => {
+        $(
+            impl<$param: Clone> RecordsCalls for $double<$param> {
+                fn recorded_calls(&self) -> Vec<Call> {
+                    self.calls()
+                }
+            }
+        )*
+    };
+}
+
+records_calls!(
+    Double<E>,
+    FileSystemGatewayDouble<E>,
+    GitGatewayDouble<E>,
+    EvidenceRetrievalGatewayDouble<E>,
+    InMemoryFileSystem<E>,
+    InMemoryObjectStore<E>,
+    InMemoryEventBus<E>,
+    FakeProcedureCatalog<E>,
+    FakeCredentialProvider<E>,
+);
+
+impl<T> RecordsCalls for Spy<T> {
+    fn recorded_calls(&self) -> Vec<Call> {
+        self.calls()
+    }
+}
+
+/// Each request is recorded as a call to its lowercase HTTP method, e.g. `get`, with the path and the body as
+/// arguments.
+impl RecordsCalls for HttpStub {
+    fn recorded_calls(&self) -> Vec<Call> {
+        self.requests()
+            .into_iter()
+            .map(|request| Call {
+                method: request.method.to_ascii_lowercase(),
+                args: vec![format!("{:?}", request.path), format!("{:?}", request.body_text())],
+            })
+            .collect()
+    }
+}
+
+/// Starts verifying the calls recorded by a double, for mock-style interaction tests:
+///
+/// ```no_run
+/// use attestify_test_framework::doubles::{verify, InMemoryFileSystem};
+/// use attestify_test_framework::http_stub::HttpStub;
+/// # #[derive(Debug, Clone)] struct Error;
+///
+/// let filesystem = InMemoryFileSystem::new(|_| Error);
+/// let http = HttpStub::start();
+/// // run the usecase
+/// verify(&filesystem).method("write").times(3);
+/// verify(&http).method("get").never();
+/// ```
+///
+/// The calls are read when `verify` is called, so later calls are not seen.  A failed verification lists every
+/// recorded call.
+pub fn verify<D: RecordsCalls + ?Sized>(double: &D) -> Verification {
+    Verification {
+        calls: double.recorded_calls(),
+    }
+}
+
+/// The calls recorded by a double, created with [`verify`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verification {
+    calls: Vec<Call>,
+}
+
+impl Verification {
+    /// Selects the calls to the method.
+    pub fn method(&self, method: &str) -> MethodVerification {
+        MethodVerification {
+            method: method.to_string(),
+            args: None,
+            calls: self.calls.clone(),
+        }
+    }
+
+    /// Asserts the methods were called in the given relative order.  Calls to other methods, and further calls to
+    /// the given methods, may occur in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `methods` - The method names in their expected order, e.g. `["fetch", "verify", "store"]`.
+    ///
+    pub fn in_order<'a>(&self, methods: impl IntoIterator<Item = &'a str>) {
+        report::record_evaluation();
+        let methods: Vec<&str> = methods.into_iter().collect();
+        let mut remaining = self.calls.iter();
+
+        for method in &methods {
+            if !remaining.any(|call| call.method == *method) {
+                report::fail(
+                    MismatchReport::new(
+                        "calls",
+                        format!(
+                            "The methods were not called in the expected order; '{}' was not called after the methods before it.",
+                            method
+                        ),
+                    )
+                    .expected(methods.join(" -> "))
+                    .hint(render_calls(&self.calls)),
+                );
+            }
+        }
+    }
+
+    /// Asserts no calls were recorded at all.
+    pub fn no_calls(&self) {
+        report::record_evaluation();
+        if !self.calls.is_empty() {
+            report::fail(
+                MismatchReport::new("calls", "No calls were expected, although some were recorded.")
+                    .expected("0 call(s)")
+                    .actual(format!("{} call(s)", self.calls.len()))
+                    .hint(render_calls(&self.calls)),
+            );
+        }
+    }
+}
+
+/// The calls to one method, created with [`Verification::method`].  The terminal methods, such as
+/// [`MethodVerification::times`], fail the test when the number of matching calls is wrong.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodVerification {
+    method: String,
+    args: Option<Vec<String>>,
+    calls: Vec<Call>,
+}
+
+impl MethodVerification {
+    /// Only counts the calls whose arguments have the same [`Debug`] rendering as the given arguments.
+    pub fn with_args(mut self, args: &[&dyn Debug]) -> Self {
+        self.args = Some(args.iter().map(|arg| format!("{:?}", arg)).collect());
+        self
+    }
+
+    /// Returns the number of matching calls.
+    pub fn count(&self) -> usize {
+        self.calls
+            .iter()
+            .filter(|call| call.method == self.method)
+            .filter(|call| self.args.as_ref().is_none_or(|args| *args == call.args))
+            .count()
+    }
+
+    /// Checks the method was called exactly the given number of times, returning a
+    /// [`MismatchReport`] instead of panicking.
+    pub fn check_times(&self, times: usize) -> Result<(), MismatchReport> {
+        self.check(times == self.count(), format!("{} call(s)", times))
+    }
+
+    /// Asserts the method was called exactly the given number of times.
+    pub fn times(&self, times: usize) {
+        report::enforce(self.check_times(times));
+    }
+
+    /// Asserts the method was called exactly once.
+    pub fn once(&self) {
+        self.times(1);
+    }
+
+    /// Asserts the method was never called.
+    pub fn never(&self) {
+        self.times(0);
+    }
+
+    /// Asserts the method was called at least the given number of times.
+    pub fn at_least(&self, times: usize) {
+        report::enforce(self.check(self.count() >= times, format!("at least {} call(s)", times)));
+    }
+
+    /// Asserts the method was called at most the given number of times.
+    pub fn at_most(&self, times: usize) {
+        report::enforce(self.check(self.count() <= times, format!("at most {} call(s)", times)));
+    }
+
+    fn check(&self, matched: bool, expected: String) -> Result<(), MismatchReport> {
+        if matched {
+            return Ok(());
+        }
+        let target = match &self.args {
+            Some(args) => format!("{}({})", self.method, args.join(", ")),
+            None => format!("'{}'", self.method),
+        };
+        Err(
+            MismatchReport::new(
+                self.method.clone(),
+                format!("The method {} was not called the expected number of times.", target),
+            )
+            .expected(expected)
+            .actual(format!("{} call(s)", self.count()))
+            .hint(render_calls(&self.calls)),
+        )
+    }
+}
+
+fn render_calls(calls: &[Call]) -> String {
+    if calls.is_empty() {
+        return "\tCalls:\n\t\t<none>".to_string();
+    }
+    let rendered: Vec<String> = calls
+        .iter()
+        .map(|call| format!("\t\t{}({})", call.method, call.args.join(", ")))
+        .collect();
+    format!("\tCalls:\n{}", rendered.join("\n"))
+}
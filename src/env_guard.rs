@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsString;
+use std::sync::{Condvar, Mutex};
+
+/// The environment variables currently held by an [`EnvGuard`], across the whole process.
+static HELD_VARIABLES: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+static RELEASED: Condvar = Condvar::new();
+
+/// Sets environment variables for the duration of a test, and restores their prior values when dropped.
+///
+/// A guard holds a process-wide lock on each of its variables, so a test running in parallel that guards the same
+/// variable waits until this guard is dropped.  Tests guarding different variables still run in parallel.  All of a
+/// guard's variables are locked at once, so set every variable a test needs with one guard, using
+/// [`EnvGuard::set_all`]; guarding the same variable twice on one thread deadlocks.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::env_guard::EnvGuard;
+///
+/// let _env = EnvGuard::set_all(&[("NAPE_HOME", Some("/tmp/nape")), ("NAPE_PROFILE", None)]);
+/// // NAPE_HOME is set and NAPE_PROFILE is unset until `_env` is dropped.
+/// ```
+#[derive(Debug)]
+pub struct EnvGuard {
+    previous: Vec<(String, Option<OsString>)>,
+}
+
+impl EnvGuard {
+    /// Sets one environment variable.
+    pub fn set(name: &str, value: &str) -> Self {
+        Self::set_all(&[(name, Some(value))])
+    }
+
+    /// Removes one environment variable.
+    pub fn remove(name: &str) -> Self {
+        Self::set_all(&[(name, None)])
+    }
+
+    /// Sets each variable with a value, and removes each variable without one.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - The name of each variable, with its value or [`None`] to remove it.
+    ///
+    pub fn set_all(variables: &[(&str, Option<&str>)]) -> Self {
+        let names: Vec<String> = variables.iter().map(|(name, _)| name.to_string()).collect();
+
+        let mut held = HELD_VARIABLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        while held
+            .as_ref()
+            .is_some_and(|held| names.iter().any(|name| held.contains(name)))
+        {
+            held = RELEASED.wait(held).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        held.get_or_insert_with(HashSet::new).extend(names.iter().cloned());
+        drop(held);
+
+        let previous = variables
+            .iter()
+            .map(|(name, value)| {
+                let previous = env::var_os(name);
+                // SAFETY: the variable is locked by this guard, so no other guard reads or writes it concurrently.
+                unsafe {
+                    match value {
+                        Some(value) => env::set_var(name, value),
+                        None => env::remove_var(name),
+                    }
+                }
+                (name.to_string(), previous)
+            })
+            .collect();
+
+        EnvGuard { previous }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (name, previous) in self.previous.iter().rev() {
+            // SAFETY: the variable is still locked by this guard.
+            unsafe {
+                match previous {
+                    Some(value) => env::set_var(name, value),
+                    None => env::remove_var(name),
+                }
+            }
+        }
+
+        let mut held = HELD_VARIABLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(held) = held.as_mut() {
+            for (name, _) in &self.previous {
+                held.remove(name);
+            }
+        }
+        RELEASED.notify_all();
+    }
+}
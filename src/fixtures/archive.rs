@@ -0,0 +1,409 @@
+//! Tar, tar.gz, and zip evidence bundles, built programmatically and read back without unpacking them by hand.
+//! Requires the `archive` feature.
+//!
+//! The bundles are deterministic: entries are written in the order they are added, with fixed permissions and
+//! modification times, and zip entries are stored uncompressed, so a bundle's digest is the same on every run.
+//! Reading accepts gzip and deflated zip archives written by any tool.
+//!
+//! ```no_run
+//! use attestify_test_framework::assert_archive_contains;
+//! use attestify_test_framework::fixtures::archive::ArchiveBuilder;
+//! use attestify_test_framework::workspace::TestWorkspace;
+//!
+//! let workspace = TestWorkspace::new();
+//! let bundle = ArchiveBuilder::new()
+//!     .file("evidence/report.yaml", "status: passed\n")
+//!     .file("evidence/build-log.json", r#"{"status": "passed"}"#)
+//!     .write(workspace.path_for("bundle.tar.gz"));
+//! assert_archive_contains!(bundle, "evidence/report.yaml", contents = "status: passed\n");
+//! ```
+
+use flate2::Compression;
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use flate2::write::GzEncoder;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+const TAR_BLOCK: usize = 512;
+
+/// The modification time of every entry, 2024-01-01T00:00:00Z, so bundles are the same on every run.
+const MTIME: u64 = 1_704_067_200;
+
+/// The format of an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Returns the format of the path's extension, `.tar`, `.tar.gz` or `.tgz`, or `.zip`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    File { name: String, contents: Vec<u8> },
+    Dir { name: String },
+}
+
+/// Builds an evidence bundle from files and directories, see the [module](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveBuilder {
+    entries: Vec<Entry>,
+}
+
+impl ArchiveBuilder {
+    /// Creates a builder for an empty archive.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The path of the file in the archive, with `/` separators, e.g. `evidence/report.yaml`.
+    /// * `contents` - The contents of the file.
+    ///
+    pub fn file(mut self, name: &str, contents: impl AsRef<[u8]>) -> Self {
+        self.entries.push(Entry::File {
+            name: name.to_string(),
+            contents: contents.as_ref().to_vec(),
+        });
+        self
+    }
+
+    /// Adds a directory, e.g. an empty one.  The directories of files do not have to be added.
+    pub fn dir(mut self, name: &str) -> Self {
+        self.entries.push(Entry::Dir {
+            name: name.trim_end_matches('/').to_string(),
+        });
+        self
+    }
+
+    /// Returns the archive in the format.
+    pub fn build(&self, format: ArchiveFormat) -> Vec<u8> {
+        match format {
+            ArchiveFormat::Tar => self.tar(),
+            ArchiveFormat::TarGz => gzip(&self.tar()),
+            ArchiveFormat::Zip => self.zip(),
+        }
+    }
+
+    /// Writes the archive in the format of the path's extension, creating any parent directories, then returns the
+    /// path.  See [`ArchiveFormat::from_path`].
+    pub fn write(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        let format = ArchiveFormat::from_path(path).unwrap_or_else(|| {
+            panic!("The archive '{}' does not end in .tar, .tar.gz, .tgz, or .zip.", path.display())
+        });
+        self.write_as(path, format)
+    }
+
+    /// Writes the archive in the format, creating any parent directories, then returns the path.
+    pub fn write_as(&self, path: impl AsRef<Path>, format: ArchiveFormat) -> PathBuf {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|_| panic!("Could not create the parent directory for '{}'.", parent.display()));
+        }
+        fs::write(path, self.build(format))
+            .unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+        path.to_path_buf()
+    }
+
+    fn tar(&self) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for entry in &self.entries {
+            match entry {
+                Entry::File { name, contents } => {
+                    tar.extend(tar_header(name, contents.len() as u64, b'0', 0o644));
+                    tar.extend_from_slice(contents);
+                    tar.resize(tar.len().next_multiple_of(TAR_BLOCK), 0);
+                }
+                Entry::Dir { name } => tar.extend(tar_header(&format!("{}/", name), 0, b'5', 0o755)),
+            }
+        }
+        tar.resize(tar.len() + 2 * TAR_BLOCK, 0);
+        tar
+    }
+
+    fn zip(&self) -> Vec<u8> {
+        let mut zip = Vec::new();
+        let mut central = Vec::new();
+        for entry in &self.entries {
+            let (name, contents, attributes) = match entry {
+                Entry::File { name, contents } => (name.clone(), contents.as_slice(), 0o100644 << 16),
+                Entry::Dir { name } => (format!("{}/", name), &[][..], (0o40755 << 16) | 0x10),
+            };
+            let offset = zip.len() as u32;
+            let crc = crc32fast::hash(contents);
+            // Version 2.0, UTF-8 names, stored, at 1980-01-01 00:00, the earliest time zip can represent.
+            let common: Vec<u8> = [
+                &20u16.to_le_bytes()[..],
+                &0x0800u16.to_le_bytes(),
+                &0u16.to_le_bytes(),
+                &0u16.to_le_bytes(),
+                &0x0021u16.to_le_bytes(),
+                &crc.to_le_bytes(),
+                &(contents.len() as u32).to_le_bytes(),
+                &(contents.len() as u32).to_le_bytes(),
+                &(name.len() as u16).to_le_bytes(),
+                &0u16.to_le_bytes(),
+            ]
+            .concat();
+            zip.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+            zip.extend_from_slice(&common);
+            zip.extend_from_slice(name.as_bytes());
+            zip.extend_from_slice(contents);
+
+            central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            central.extend_from_slice(&(0x0300u16 | 20).to_le_bytes());
+            central.extend_from_slice(&common);
+            central.extend_from_slice(&[0; 6]);
+            central.extend_from_slice(&(attributes as u32).to_le_bytes());
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let offset = zip.len() as u32;
+        let count = (self.entries.len() as u16).to_le_bytes();
+        zip.extend_from_slice(&central);
+        zip.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        zip.extend_from_slice(&[0; 4]);
+        zip.extend_from_slice(&count);
+        zip.extend_from_slice(&count);
+        zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&offset.to_le_bytes());
+        zip.extend_from_slice(&[0; 2]);
+        zip
+    }
+}
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(bytes)
+        .and_then(|()| encoder.finish())
+        .expect("Could not compress the archive in memory.")
+}
+
+fn tar_header(name: &str, size: u64, kind: u8, mode: u32) -> Vec<u8> {
+    let mut header = vec![0u8; TAR_BLOCK];
+    let (prefix, name) = split_tar_name(name);
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], u64::from(mode));
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(&mut header[124..136], size);
+    write_octal(&mut header[136..148], MTIME);
+    header[156] = kind;
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+    header[148..156].fill(b' ');
+    let checksum: u32 = header.iter().map(|&byte| u32::from(byte)).sum();
+    header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+    header
+}
+
+/// Splits a name longer than the 100 bytes of the ustar name field into a prefix of up to 155 bytes and a name.
+fn split_tar_name(name: &str) -> (&str, &str) {
+    if name.len() <= 100 {
+        return ("", name);
+    }
+    name.char_indices()
+        .filter(|&(index, c)| c == '/' && index <= 155 && name.len() - index - 1 <= 100)
+        .map(|(index, _)| (&name[..index], &name[index + 1..]))
+        .next()
+        .unwrap_or_else(|| panic!("The name '{}' is too long for a tar archive.", name))
+}
+
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}\0", value, width = field.len() - 1);
+    field.copy_from_slice(digits.as_bytes());
+}
+
+/// Reads the files of a tar, tar.gz, or zip archive, detecting the format from its contents, keyed by their path in
+/// the archive with `/` separators.  Directories and links are not included.
+pub fn read_archive(path: &Path) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    let bytes = fs::read(path)?;
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut tar = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut tar)?;
+        read_tar(&tar)
+    } else if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+        read_zip(&bytes)
+    } else {
+        read_tar(&bytes)
+    }
+}
+
+/// Extracts the files of a tar, tar.gz, or zip archive into the directory, creating it and any subdirectories, then
+/// returns the paths of the files written.  The test fails when an entry would be written outside the directory.
+pub fn extract_archive(path: &Path, dir: &Path) -> Vec<PathBuf> {
+    let files = read_archive(path)
+        .unwrap_or_else(|err| panic!("Could not read the archive '{}':\n\t{}\n", path.display(), err));
+    files
+        .into_iter()
+        .map(|(name, contents)| {
+            if Path::new(&name).components().any(|component| !matches!(component, Component::Normal(_))) {
+                panic!("The archive '{}' has the entry '{}' outside its directory.", path.display(), name);
+            }
+            let target = dir.join(&name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .unwrap_or_else(|_| panic!("Could not create the directory '{}'.", parent.display()));
+            }
+            fs::write(&target, contents)
+                .unwrap_or_else(|_| panic!("Could not write to the file '{}'.", target.display()));
+            target
+        })
+        .collect()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn read_tar(bytes: &[u8]) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    let mut long_name: Option<String> = None;
+    let mut position = 0;
+    while position + TAR_BLOCK <= bytes.len() {
+        let header = &bytes[position..position + TAR_BLOCK];
+        if header.iter().all(|&byte| byte == 0) {
+            break;
+        }
+        let size = read_octal(&header[124..136])
+            .ok_or_else(|| invalid(format!("the tar entry at offset {} has an invalid size", position)))?
+            as usize;
+        let start = position + TAR_BLOCK;
+        let data = bytes
+            .get(start..start + size)
+            .ok_or_else(|| invalid(format!("the tar entry at offset {} is truncated", position)))?;
+        position = start + size.next_multiple_of(TAR_BLOCK);
+
+        match header[156] {
+            // A GNU long name, or a pax extended header, names the entry that follows.
+            b'L' => long_name = Some(field(data)),
+            b'x' => long_name = pax_path(data).or(long_name),
+            b'0' | b'\0' | b'7' => {
+                let name = long_name.take().unwrap_or_else(|| {
+                    let prefix = field(&header[345..500]);
+                    let name = field(&header[..100]);
+                    if header[257..262] == *b"ustar" && !prefix.is_empty() {
+                        format!("{}/{}", prefix, name)
+                    } else {
+                        name
+                    }
+                });
+                files.insert(name.trim_start_matches("./").to_string(), data.to_vec());
+            }
+            _ => long_name = None,
+        }
+    }
+    Ok(files)
+}
+
+fn field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+fn read_octal(bytes: &[u8]) -> Option<u64> {
+    let digits = field(bytes);
+    let digits = digits.trim_matches(|c: char| c == ' ' || c == '\0');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(digits, 8).ok()
+}
+
+/// Returns the `path` record of a pax extended header, whose records are `<length> <key>=<value>\n`.
+fn pax_path(data: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .filter_map(|record| record.split_once(' ').map(|(_, record)| record))
+        .find_map(|record| record.strip_prefix("path=").map(ToString::to_string))
+}
+
+fn read_zip(bytes: &[u8]) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    let u16_at = |at: usize| -> io::Result<usize> {
+        bytes
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(|| invalid("the zip archive is truncated".to_string()))
+    };
+    let u32_at = |at: usize| -> io::Result<usize> {
+        bytes
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(|| invalid("the zip archive is truncated".to_string()))
+    };
+
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&at| bytes[at..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(|| invalid("the zip archive has no end of central directory".to_string()))?;
+    let count = u16_at(end + 10)?;
+    let mut position = u32_at(end + 16)?;
+    if count == 0xffff || position == 0xffff_ffff {
+        return Err(invalid("zip64 archives are not supported".to_string()));
+    }
+
+    let mut files = BTreeMap::new();
+    for _ in 0..count {
+        if u32_at(position)? != 0x0201_4b50 {
+            return Err(invalid(format!("the zip central directory is invalid at offset {}", position)));
+        }
+        let method = u16_at(position + 10)?;
+        let compressed = u32_at(position + 20)?;
+        let name_len = u16_at(position + 28)?;
+        let extra_len = u16_at(position + 30)?;
+        let comment_len = u16_at(position + 32)?;
+        let local = u32_at(position + 42)?;
+        let name = String::from_utf8_lossy(
+            bytes
+                .get(position + 46..position + 46 + name_len)
+                .ok_or_else(|| invalid("the zip archive is truncated".to_string()))?,
+        )
+        .into_owned();
+        position += 46 + name_len + extra_len + comment_len;
+        if name.ends_with('/') {
+            continue;
+        }
+
+        let start = local + 30 + u16_at(local + 26)? + u16_at(local + 28)?;
+        let data = bytes
+            .get(start..start + compressed)
+            .ok_or_else(|| invalid(format!("the zip entry '{}' is truncated", name)))?;
+        let contents = match method {
+            0 => data.to_vec(),
+            8 => {
+                let mut contents = Vec::new();
+                DeflateDecoder::new(data).read_to_end(&mut contents)?;
+                contents
+            }
+            method => {
+                return Err(invalid(format!("the zip entry '{}' uses the unsupported method {}", name, method)));
+            }
+        };
+        files.insert(name, contents);
+    }
+    Ok(files)
+}
@@ -0,0 +1,103 @@
+//! Large synthetic workloads for benchmarks, such as the criterion benches of every attestify repository.
+//!
+//! The content is deterministic, the same on every run and in every repository, so benchmarks of different
+//! components and different revisions measure the same workload.  The workloads are expensive to generate, so
+//! [`large_procedure`] is generated once per process, and a bench generates the evidence store outside its timed
+//! loop:
+//!
+//! ```no_run
+//! use attestify_test_framework::fixtures::bench_fixtures;
+//! # #[derive(Debug, Clone)] struct Error;
+//! # fn parse(yaml: &str) -> usize { yaml.len() }
+//!
+//! let procedure = bench_fixtures::large_procedure();
+//! let store = bench_fixtures::in_memory_evidence_store(bench_fixtures::LARGE_EVIDENCE_STORE_FILES, |_| Error);
+//! // c.bench_function("parse 10k activities", |b| b.iter(|| parse(black_box(&procedure.yaml))));
+//! ```
+
+use super::procedure_fixture::{self, ProcedureDocument};
+use crate::doubles::InMemoryFileSystem;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The number of activities of [`large_procedure`].
+pub const LARGE_PROCEDURE_ACTIVITIES: usize = 10_000;
+
+/// The number of evidence files in the large evidence store of the benchmarks.
+pub const LARGE_EVIDENCE_STORE_FILES: usize = 100_000;
+
+/// The number of evidence files in each procedure directory of an evidence store, so no directory is too large to
+/// list.
+pub const FILES_PER_DIRECTORY: usize = 1_000;
+
+/// The number of files and bytes written by [`write_evidence_store`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvidenceStoreSize {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Returns a fully populated procedure document with [`LARGE_PROCEDURE_ACTIVITIES`] activities, generated on the
+/// first call.
+pub fn large_procedure() -> &'static ProcedureDocument {
+    static PROCEDURE: OnceLock<ProcedureDocument> = OnceLock::new();
+    PROCEDURE.get_or_init(|| procedure_fixture::with_activities(LARGE_PROCEDURE_ACTIVITIES))
+}
+
+/// Returns the relative path and JSON payload of each of `count` evidence files, lazily, in path order.
+///
+/// The files are `procedure-0000/evidence-000000.json`, ..., with [`FILES_PER_DIRECTORY`] files per procedure
+/// directory.  Payloads vary in size from about 140 to 580 bytes, and every tenth evidence has failed.
+pub fn evidence_files(count: usize) -> impl Iterator<Item = (PathBuf, String)> {
+    (0..count).map(|index| {
+        let procedure = index / FILES_PER_DIRECTORY;
+        let path = PathBuf::from(format!("procedure-{:04}", procedure)).join(format!("evidence-{:06}.json", index));
+        let checks: Vec<String> = (0..index % 13)
+            .map(|check| format!("{{\"name\": \"check-{}\", \"passed\": {}}}", check, (index + check) % 10 != 0))
+            .collect();
+        let payload = format!(
+            "{{\"evidence\": \"evidence-{:06}\", \"procedure\": \"procedure-{:04}\", \"collected_at\": \
+             \"2024-01-01T00:00:00Z\", \"status\": \"{}\", \"checks\": [{}]}}",
+            index,
+            procedure,
+            if index % 10 == 0 { "failed" } else { "passed" },
+            checks.join(", ")
+        );
+        (path, payload)
+    })
+}
+
+/// Writes the [`evidence_files`] into the directory, creating the procedure directories, and returns how much was
+/// written.
+pub fn write_evidence_store(dir: &Path, count: usize) -> EvidenceStoreSize {
+    let mut size = EvidenceStoreSize { files: 0, bytes: 0 };
+    for (path, payload) in evidence_files(count) {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|err| panic!("Could not create the directory '{}':\n\t{}\n", parent.display(), err));
+        }
+        fs::write(&path, &payload)
+            .unwrap_or_else(|err| panic!("Could not write the evidence '{}':\n\t{}\n", path.display(), err));
+        size.files += 1;
+        size.bytes += payload.len() as u64;
+    }
+    size
+}
+
+/// Returns an [`InMemoryFileSystem`] holding the [`evidence_files`], for benchmarks that must not measure the disk.
+///
+/// # Arguments
+///
+/// * `count` - The number of evidence files, e.g. [`LARGE_EVIDENCE_STORE_FILES`].
+/// * `not_found` - Builds the error returned when a file does not exist, see [`InMemoryFileSystem::new`].
+///
+pub fn in_memory_evidence_store<E: Clone>(
+    count: usize,
+    not_found: impl Fn(&Path) -> E + Send + Sync + 'static,
+) -> InMemoryFileSystem<E> {
+    evidence_files(count).fold(InMemoryFileSystem::new(not_found), |store, (path, payload)| {
+        store.with_file(path, payload)
+    })
+}
@@ -0,0 +1,209 @@
+//! Deterministic throwaway keypairs for testing evidence signing, and assertions on signatures.
+//!
+//! The Ed25519 keypairs are derived from a fixed seed per index, and the RSA keypairs are 2048-bit keys bundled with
+//! this crate, so no test generates keys at runtime and no repository needs its own private keys.  These keys are
+//! public knowledge; never use them outside of tests.
+//!
+//! ```no_run
+//! use attestify_test_framework::assert_signature_valid;
+//! use attestify_test_framework::fixtures::crypto_fixtures;
+//!
+//! let keypair = crypto_fixtures::ed25519_keypair(0);
+//! let signature = keypair.sign(b"evidence payload");
+//! assert_signature_valid!(b"evidence payload", signature, keypair.public_key());
+//! ```
+//!
+//! Requires the `crypto` feature.
+
+use crate::assertions::report::{self, MismatchReport};
+use rsa::pkcs1v15;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::sha2::{Digest, Sha256};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use std::fmt;
+
+const RSA_PRIMARY_PEM: &str = include_str!("keys/rsa_primary.pem");
+const RSA_SECONDARY_PEM: &str = include_str!("keys/rsa_secondary.pem");
+
+/// A public key that signatures are verified with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublicKey {
+    Ed25519(ed25519_dalek::VerifyingKey),
+    /// An RSA key, verifying PKCS #1 v1.5 signatures over the SHA-256 digest.
+    Rsa(RsaPublicKey),
+}
+
+impl PublicKey {
+    /// Verifies the signature of the payload, returning why it is invalid on failure.
+    pub fn verify(&self, payload: &[u8], signature: &[u8]) -> Result<(), String> {
+        match self {
+            PublicKey::Ed25519(key) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature).map_err(|err| err.to_string())?;
+                key.verify(payload, &signature).map_err(|err| err.to_string())
+            }
+            PublicKey::Rsa(key) => {
+                let signature = pkcs1v15::Signature::try_from(signature).map_err(|err| err.to_string())?;
+                pkcs1v15::VerifyingKey::<Sha256>::new(key.clone())
+                    .verify(payload, &signature)
+                    .map_err(|err| err.to_string())
+            }
+        }
+    }
+
+    /// Returns the key as a PEM encoded SubjectPublicKeyInfo.
+    pub fn to_pem(&self) -> String {
+        match self {
+            PublicKey::Ed25519(key) => key.to_public_key_pem(LineEnding::LF),
+            PublicKey::Rsa(key) => key.to_public_key_pem(LineEnding::LF),
+        }
+        .expect("Could not encode the public key.")
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (algorithm, der) = match self {
+            PublicKey::Ed25519(key) => ("Ed25519", key.to_public_key_der().map(|der| der.into_vec())),
+            PublicKey::Rsa(key) => ("RSA", key.to_public_key_der().map(|der| der.into_vec())),
+        };
+        let fingerprint = der.map(|der| crate::assertions::digest::to_hex(&Sha256::digest(der)[..8]));
+        write!(f, "{} key {}", algorithm, fingerprint.unwrap_or_default())
+    }
+}
+
+/// A throwaway Ed25519 keypair.
+#[derive(Debug, Clone)]
+pub struct Ed25519Keypair {
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl Ed25519Keypair {
+    /// Signs the payload, returning the 64 byte signature.
+    pub fn sign(&self, payload: &[u8]) -> Vec<u8> {
+        self.signing_key.sign(payload).to_bytes().to_vec()
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Ed25519(self.signing_key.verifying_key())
+    }
+
+    /// Returns the private key as a PEM encoded PKCS #8 document, for usecases that load keys from files.
+    pub fn private_key_pem(&self) -> String {
+        self.signing_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("Could not encode the private key.")
+            .to_string()
+    }
+}
+
+/// A throwaway 2048-bit RSA keypair, signing with PKCS #1 v1.5 over the SHA-256 digest.
+#[derive(Debug, Clone)]
+pub struct RsaKeypair {
+    private_key: RsaPrivateKey,
+}
+
+impl RsaKeypair {
+    /// Signs the payload, returning the 256 byte signature.
+    pub fn sign(&self, payload: &[u8]) -> Vec<u8> {
+        pkcs1v15::SigningKey::<Sha256>::new(self.private_key.clone())
+            .sign(payload)
+            .to_vec()
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Rsa(self.private_key.to_public_key())
+    }
+
+    /// Returns the private key as a PEM encoded PKCS #8 document, for usecases that load keys from files.
+    pub fn private_key_pem(&self) -> String {
+        self.private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("Could not encode the private key.")
+            .to_string()
+    }
+}
+
+/// Returns the Ed25519 keypair for the index.  The same index always returns the same keypair, and different
+/// indexes return different keypairs, e.g. for a signer and an impostor.
+pub fn ed25519_keypair(index: u8) -> Ed25519Keypair {
+    let seed: [u8; 32] = Sha256::digest(format!("attestify-test-ed25519-{}", index)).into();
+    Ed25519Keypair {
+        signing_key: ed25519_dalek::SigningKey::from_bytes(&seed),
+    }
+}
+
+/// Returns the bundled RSA keypair used as the signer in most tests.
+pub fn rsa_keypair() -> RsaKeypair {
+    bundled_rsa(RSA_PRIMARY_PEM)
+}
+
+/// Returns a second bundled RSA keypair, for tests where a signature must not verify with the wrong key.
+pub fn other_rsa_keypair() -> RsaKeypair {
+    bundled_rsa(RSA_SECONDARY_PEM)
+}
+
+fn bundled_rsa(pem: &str) -> RsaKeypair {
+    RsaKeypair {
+        private_key: RsaPrivateKey::from_pkcs8_pem(pem).expect("The bundled RSA key is not valid."),
+    }
+}
+
+/// Checks the signature of the payload is valid for the public key.  See
+/// [`check_signature_valid`](crate::check_signature_valid).
+pub fn check_signature_valid(payload: &[u8], signature: &[u8], public_key: &PublicKey) -> Result<(), MismatchReport> {
+    public_key.verify(payload, signature).map_err(|err| {
+        MismatchReport::new("signature", "The signature is not valid for the payload.")
+            .expected(format!("a signature by the {}", public_key))
+            .actual(crate::assertions::digest::to_hex(signature))
+            .hint(format!("\t{}", err))
+    })
+}
+
+/// Asserts the signature of the payload is valid for the public key.  See
+/// [`assert_signature_valid`](crate::assert_signature_valid).
+pub fn assert_signature_valid(payload: &[u8], signature: &[u8], public_key: &PublicKey) {
+    report::enforce(check_signature_valid(payload, signature, public_key));
+}
+
+/// Asserts that a signature of a payload is valid for a public key.
+///
+/// # Arguments
+///
+/// * `$payload` - The signed bytes, as anything that can be referenced as `[u8]`.
+/// * `$signature` - The signature bytes, as anything that can be referenced as `[u8]`.
+/// * `$public_key` - The key to verify with. Should be of type
+///   [`PublicKey`](crate::fixtures::crypto_fixtures::PublicKey).
+///
+#[macro_export]
+macro_rules! assert_signature_valid {
+    ($payload:expr, $signature:expr, $public_key:expr) => {
+        $crate::fixtures::crypto_fixtures::assert_signature_valid(
+            ::std::convert::AsRef::<[u8]>::as_ref(&$payload),
+            ::std::convert::AsRef::<[u8]>::as_ref(&$signature),
+            &$public_key,
+        )
+    };
+}
+
+/// Checks that a signature of a payload is valid for a public key, like
+/// [`assert_signature_valid`](crate::assert_signature_valid), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$payload` - The signed bytes, as anything that can be referenced as `[u8]`.
+/// * `$signature` - The signature bytes, as anything that can be referenced as `[u8]`.
+/// * `$public_key` - The key to verify with. Should be of type
+///   [`PublicKey`](crate::fixtures::crypto_fixtures::PublicKey).
+///
+#[macro_export]
+macro_rules! check_signature_valid {
+    ($payload:expr, $signature:expr, $public_key:expr) => {
+        $crate::fixtures::crypto_fixtures::check_signature_valid(
+            ::std::convert::AsRef::<[u8]>::as_ref(&$payload),
+            ::std::convert::AsRef::<[u8]>::as_ref(&$signature),
+            &$public_key,
+        )
+    };
+}
@@ -0,0 +1,92 @@
+//! Every kernel error classification, for table tests that must handle each [`nape_kernel::error::Kind`] and
+//! [`nape_kernel::error::Audience`].
+//!
+//! The kernel's types are not visible to this crate, so the test crate lists their variants once with
+//! [`error_catalog!`](crate::error_catalog), which generates an `error_catalog` module with `all_kinds()`,
+//! `all_audiences()`, and `all_classifications()`.  It also generates an exhaustive `match` on each type, so when the
+//! kernel adds a variant the test crate stops compiling until the variant is listed, instead of the table tests
+//! silently skipping it:
+//!
+//! ```no_run
+//! use attestify_test_framework::error_catalog;
+//! # #[derive(Debug, Clone, Copy, PartialEq)] enum Kind { InvalidInput, GatewayError, NotFound }
+//! # #[derive(Debug, Clone, Copy, PartialEq)] enum Audience { User, System }
+//! # fn message_for(kind: Kind, audience: Audience) -> String { String::new() }
+//!
+//! error_catalog! {
+//!     kinds: Kind { InvalidInput, GatewayError, NotFound },
+//!     audiences: Audience { User, System },
+//! }
+//!
+//! fn every_classification_has_a_message() {
+//!     for (kind, audience) in error_catalog::all_classifications() {
+//!         assert!(!message_for(kind, audience).is_empty(), "{:?} {:?}", kind, audience);
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! The types must be in scope where the macro is invoked, and their variants must not carry data.  A type marked
+//! `#[non_exhaustive]` cannot be matched exhaustively outside its crate, so the check is only as strong as the
+//! kernel's enums allow.
+
+/// Returns every pair of a kind and an audience, kinds first, in the order given.
+pub fn classifications<K: Clone, A: Clone>(kinds: &[K], audiences: &[A]) -> Vec<(K, A)> {
+    kinds
+        .iter()
+        .flat_map(|kind| audiences.iter().map(move |audience| (kind.clone(), audience.clone())))
+        .collect()
+}
+
+/// Generates an `error_catalog` module listing every variant of the kernel's error kind and audience types, with a
+/// compile-time exhaustive match that fails to compile when a variant is missing from the list.  See
+/// [`error_catalog`](crate::fixtures::error_catalog).
+///
+/// # Arguments
+///
+/// * `kinds: $kind { $variant, ... }` - The kind type, in scope, typically [`nape_kernel::error::Kind`], and every
+///   one of its variants.
+/// * `audiences: $audience { $variant, ... }` - The audience type, in scope, typically
+///   [`nape_kernel::error::Audience`], and every one of its variants.
+///
+#[macro_export]
+macro_rules! error_catalog {
+    (
+        kinds: $kind:ident { $($kind_variant:ident),+ $(,)? },
+        audiences: $audience:ident { $($audience_variant:ident),+ $(,)? } $(,)?
+    ) => {
+        #[allow(dead_code)]
+        pub mod error_catalog {
+            use super::*;
+
+            /// Returns every kind, in the order listed.
+            pub fn all_kinds() -> ::std::vec::Vec<$kind> {
+                ::std::vec![$($kind::$kind_variant),+]
+            }
+
+            /// Returns every audience, in the order listed.
+            pub fn all_audiences() -> ::std::vec::Vec<$audience> {
+                ::std::vec![$($audience::$audience_variant),+]
+            }
+
+            /// Returns every pair of a kind and an audience.
+            pub fn all_classifications() -> ::std::vec::Vec<($kind, $audience)> {
+                $crate::fixtures::error_catalog::classifications(&all_kinds(), &all_audiences())
+            }
+
+            // Fails to compile with "non-exhaustive patterns" when a variant is missing from the catalog.
+            fn exhaustive_kinds(kind: &$kind) {
+                match kind {
+                    $($kind::$kind_variant => {})+
+                }
+            }
+
+            // Fails to compile with "non-exhaustive patterns" when a variant is missing from the catalog.
+            fn exhaustive_audiences(audience: &$audience) {
+                match audience {
+                    $($audience::$audience_variant => {})+
+                }
+            }
+        }
+    };
+}
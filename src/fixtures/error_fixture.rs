@@ -0,0 +1,113 @@
+//! Builds kernel errors for tests without the kernel's constructors.
+//!
+//! The builder serializes the kind, audience, and message into the JSON form of an [`nape_kernel::error::Error`] and
+//! deserializes it into the requested type, so it only relies on the error's serde representation.  Tests that hand
+//! errors to doubles stay unchanged when the kernel's constructors change:
+//!
+//! ```no_run
+//! use attestify_test_framework::fixtures::error_fixture::{error_fixture, user_error};
+//! # struct Kind; #[allow(non_upper_case_globals)] impl Kind { const NotFound: &str = "NotFound"; }
+//! # struct Audience; #[allow(non_upper_case_globals)] impl Audience { const System: &str = "System"; }
+//! # type Error = serde_json::Value;
+//!
+//! let not_found: Error = error_fixture()
+//!     .kind(Kind::NotFound)
+//!     .audience(Audience::System)
+//!     .message("The procedure does not exist.")
+//!     .build();
+//! let rejected: Error = user_error("The procedure name is empty.");
+//! ```
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+/// The kind of errors built by [`user_error`] and by default.
+pub const DEFAULT_USER_KIND: &str = "InvalidInput";
+
+/// The kind of errors built by [`system_error`].
+pub const DEFAULT_SYSTEM_KIND: &str = "GatewayError";
+
+/// The message of errors built without one.
+pub const DEFAULT_MESSAGE: &str = "An error used for testing.";
+
+/// A builder of kernel errors, created with [`error_fixture`].
+///
+/// Defaults to the kind [`DEFAULT_USER_KIND`], the audience `User`, and the message [`DEFAULT_MESSAGE`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorFixture {
+    kind: Value,
+    audience: Value,
+    message: String,
+}
+
+/// Creates an [`ErrorFixture`] with its defaults.
+pub fn error_fixture() -> ErrorFixture {
+    ErrorFixture {
+        kind: Value::from(DEFAULT_USER_KIND),
+        audience: Value::from("User"),
+        message: DEFAULT_MESSAGE.to_string(),
+    }
+}
+
+impl ErrorFixture {
+    /// Sets the kind, typically a [`nape_kernel::error::Kind`].  It must implement [`Serialize`].
+    pub fn kind(mut self, kind: impl Serialize) -> Self {
+        self.kind = to_value(kind, "kind");
+        self
+    }
+
+    /// Sets the audience, typically a [`nape_kernel::error::Audience`].  It must implement [`Serialize`].
+    pub fn audience(mut self, audience: impl Serialize) -> Self {
+        self.audience = to_value(audience, "audience");
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Builds the error, typically an [`nape_kernel::error::Error`], which must implement `Deserialize`.
+    ///
+    /// Panics when the error cannot be deserialized from the kind, audience, and message, such as when the kind is
+    /// not one of the type's kinds.
+    pub fn build<E: DeserializeOwned>(&self) -> E {
+        let document = json!({
+            "kind": self.kind,
+            "audience": self.audience,
+            "message": self.message,
+        });
+        serde_json::from_value(document.clone()).unwrap_or_else(|err| {
+            panic!(
+                "Could not build a {} from the error fixture:\n\t{}\n\tJSON:\t{}\n",
+                std::any::type_name::<E>(),
+                err,
+                document
+            )
+        })
+    }
+
+    /// Builds the error and returns it in an [`Err`], for doubles that return a `Result`.
+    pub fn err<T, E: DeserializeOwned>(&self) -> Result<T, E> {
+        Err(self.build())
+    }
+}
+
+/// Builds an error with the kind [`DEFAULT_USER_KIND`], the audience `User`, and the message.
+pub fn user_error<E: DeserializeOwned>(message: impl Into<String>) -> E {
+    error_fixture().message(message).build()
+}
+
+/// Builds an error with the kind [`DEFAULT_SYSTEM_KIND`], the audience `System`, and the message.
+pub fn system_error<E: DeserializeOwned>(message: impl Into<String>) -> E {
+    error_fixture()
+        .kind(DEFAULT_SYSTEM_KIND)
+        .audience("System")
+        .message(message)
+        .build()
+}
+
+fn to_value(value: impl Serialize, name: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|err| panic!("Could not serialize the error {}:\n\t{}\n", name, err))
+}
@@ -0,0 +1,112 @@
+//! Realistic evidence artifacts written to disk.
+
+use crate::assertions::digest::sha256_hex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The paths and expected digests of the evidence written by an [`EvidenceFixture`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvidenceFiles {
+    /// The JSON payload, `<name>.json`.
+    pub payload: PathBuf,
+    /// The checksum sidecar, `<name>.json.sha256`, in the `sha256sum` format.
+    pub checksum: PathBuf,
+    /// The metadata, `<name>.metadata.yaml`.
+    pub metadata: PathBuf,
+    /// The lowercase hex SHA-256 digest of the payload.
+    pub payload_sha256: String,
+    /// The lowercase hex SHA-256 digest of the metadata.
+    pub metadata_sha256: String,
+}
+
+/// Builds an evidence artifact: a JSON payload, a checksum sidecar, and a metadata document, written into a
+/// directory with [`EvidenceFixture::write_to`].
+///
+/// The content is deterministic, so the returned digests are the same on every run.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::fixtures::evidence::EvidenceFixture;
+/// use attestify_test_framework::workspace::TestWorkspace;
+///
+/// let workspace = TestWorkspace::new();
+/// let evidence = EvidenceFixture::new("build-log")
+///     .with_payload(r#"{"status": "passed"}"#)
+///     .write_to(&workspace.evidence_dir());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvidenceFixture {
+    name: String,
+    payload: String,
+    metadata: BTreeMap<String, String>,
+}
+
+impl EvidenceFixture {
+    /// Creates a fixture for the named evidence, with a small JSON payload describing a passed check and metadata
+    /// collected at 2024-01-01T00:00:00Z.
+    pub fn new(name: &str) -> Self {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("collected_at".to_string(), "2024-01-01T00:00:00Z".to_string());
+        metadata.insert("collector".to_string(), "attestify-test".to_string());
+
+        EvidenceFixture {
+            name: name.to_string(),
+            payload: format!("{{\"evidence\": \"{}\", \"status\": \"passed\"}}", name),
+            metadata,
+        }
+    }
+
+    /// Replaces the JSON payload.  The payload is written as given, and must be valid JSON.
+    pub fn with_payload(mut self, payload: &str) -> Self {
+        serde_json::from_str::<serde_json::Value>(payload)
+            .unwrap_or_else(|err| panic!("The evidence payload is not valid JSON:\n\t{}\n", err));
+        self.payload = payload.to_string();
+        self
+    }
+
+    /// Adds a metadata entry, replacing any existing value for the key.
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Writes the payload, checksum sidecar, and metadata into the directory, creating it if needed, then returns
+    /// their paths and digests.
+    ///
+    /// The metadata records the evidence name, media type, size, and SHA-256 digest of the payload along with the
+    /// configured entries.
+    pub fn write_to(&self, dir: &Path) -> EvidenceFiles {
+        fs::create_dir_all(dir).unwrap_or_else(|_| panic!("Could not create the directory '{}'.", dir.display()));
+
+        let payload_name = format!("{}.json", self.name);
+        let payload = dir.join(&payload_name);
+        let checksum = dir.join(format!("{}.sha256", payload_name));
+        let metadata = dir.join(format!("{}.metadata.yaml", self.name));
+        let payload_sha256 = sha256_hex(self.payload.as_bytes());
+
+        let mut entries = self.metadata.clone();
+        entries.insert("name".to_string(), self.name.clone());
+        entries.insert("media_type".to_string(), "application/json".to_string());
+        entries.insert("size".to_string(), self.payload.len().to_string());
+        entries.insert("sha256".to_string(), payload_sha256.clone());
+        let metadata_contents = serde_yaml::to_string(&entries).expect("Could not serialize the evidence metadata.");
+
+        write(&payload, self.payload.as_bytes());
+        write(&checksum, format!("{}  {}\n", payload_sha256, payload_name).as_bytes());
+        write(&metadata, metadata_contents.as_bytes());
+
+        EvidenceFiles {
+            payload,
+            checksum,
+            metadata,
+            payload_sha256,
+            metadata_sha256: sha256_hex(metadata_contents.as_bytes()),
+        }
+    }
+}
+
+fn write(path: &Path, contents: &[u8]) {
+    fs::write(path, contents).unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+}
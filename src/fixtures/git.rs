@@ -0,0 +1,211 @@
+//! Throwaway git repositories with a programmable history.
+
+use crate::workspace::unique_temp_dir;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The commit date of the first commit, 2024-01-01T00:00:00Z.  Each later commit is one minute later, so the
+/// commit SHAs are the same on every run.
+const FIRST_COMMIT_TIMESTAMP: u64 = 1_704_067_200;
+
+#[derive(Debug, Clone)]
+enum Step {
+    Commit {
+        message: String,
+        files: Vec<(String, String)>,
+    },
+    Tag(String),
+    Branch(String),
+    Checkout(String),
+}
+
+/// Builds a real git repository in a temporary directory with a programmed history of commits, tags, and branches.
+///
+/// The repository is created with the `git` executable, isolated from the user's and system git configuration, and
+/// with a fixed author, committer, and commit dates, so the same history always produces the same commit SHAs.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::fixtures::git::GitRepoFixture;
+///
+/// let repo = GitRepoFixture::new()
+///     .commit("initial", &[("README.md", "hello")])
+///     .tag("v1.0.0")
+///     .branch("feature")
+///     .commit("add evidence", &[("evidence/result.json", "{}")])
+///     .build();
+///
+/// let sha = repo.commit_sha("add evidence");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GitRepoFixture {
+    steps: Vec<Step>,
+}
+
+impl Default for GitRepoFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitRepoFixture {
+    /// Creates a fixture for an empty repository on the `main` branch.
+    pub fn new() -> Self {
+        GitRepoFixture { steps: Vec::new() }
+    }
+
+    /// Writes the files, relative to the repository root, and commits them with the message.
+    pub fn commit(mut self, message: &str, files: &[(&str, &str)]) -> Self {
+        self.steps.push(Step::Commit {
+            message: message.to_string(),
+            files: files
+                .iter()
+                .map(|(path, contents)| (path.to_string(), contents.to_string()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Tags the current commit with a lightweight tag.
+    pub fn tag(mut self, name: &str) -> Self {
+        self.steps.push(Step::Tag(name.to_string()));
+        self
+    }
+
+    /// Creates a branch at the current commit and checks it out.
+    pub fn branch(mut self, name: &str) -> Self {
+        self.steps.push(Step::Branch(name.to_string()));
+        self
+    }
+
+    /// Checks out an existing branch, tag, or commit.
+    pub fn checkout(mut self, reference: &str) -> Self {
+        self.steps.push(Step::Checkout(reference.to_string()));
+        self
+    }
+
+    /// Creates the repository and runs the programmed history.
+    pub fn build(self) -> GitRepo {
+        let repo = GitRepo {
+            path: unique_temp_dir("nape_git_repo"),
+            commits: Vec::new(),
+            tags: HashMap::new(),
+        };
+        repo.git(&["init", "--quiet", "--initial-branch=main"]);
+        self.steps.into_iter().fold(repo, GitRepo::apply)
+    }
+}
+
+/// A git repository created by a [`GitRepoFixture`].  The repository is removed when it is dropped.
+#[derive(Debug)]
+pub struct GitRepo {
+    path: PathBuf,
+    commits: Vec<(String, String)>,
+    tags: HashMap<String, String>,
+}
+
+impl GitRepo {
+    /// Returns the root directory of the repository.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the SHA of every commit, in the order they were made.
+    pub fn commits(&self) -> Vec<String> {
+        self.commits.iter().map(|(_, sha)| sha.clone()).collect()
+    }
+
+    /// Returns the SHA of the most recent commit with the message.
+    pub fn commit_sha(&self, message: &str) -> String {
+        self.commits
+            .iter()
+            .rev()
+            .find(|(commit_message, _)| commit_message == message)
+            .map(|(_, sha)| sha.clone())
+            .unwrap_or_else(|| panic!("No commit was made with the message '{}'.", message))
+    }
+
+    /// Returns the SHA of the commit the tag points to.
+    pub fn tag_sha(&self, name: &str) -> String {
+        self.tags
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("No tag was made with the name '{}'.", name))
+    }
+
+    /// Returns the SHA of the checked out commit.
+    pub fn head(&self) -> String {
+        self.git(&["rev-parse", "HEAD"])
+    }
+
+    fn apply(mut self, step: Step) -> Self {
+        match step {
+            Step::Commit { message, files } => {
+                for (subpath, contents) in files {
+                    let path = self.path.join(&subpath);
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent).unwrap_or_else(|_| {
+                            panic!("Could not create the parent directory for '{}'.", parent.display())
+                        });
+                    }
+                    fs::write(&path, contents)
+                        .unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+                }
+                self.git(&["add", "--all"]);
+                self.git(&["commit", "--quiet", "--allow-empty", "--message", &message]);
+                let sha = self.head();
+                self.commits.push((message, sha));
+            }
+            Step::Tag(name) => {
+                self.git(&["tag", &name]);
+                let sha = self.head();
+                self.tags.insert(name, sha);
+            }
+            Step::Branch(name) => {
+                self.git(&["checkout", "--quiet", "-b", &name]);
+            }
+            Step::Checkout(reference) => {
+                self.git(&["checkout", "--quiet", &reference]);
+            }
+        }
+        self
+    }
+
+    /// Runs git in the repository and returns its trimmed standard output, panicking when it fails.
+    fn git(&self, args: &[&str]) -> String {
+        let date = format!("@{} +0000", FIRST_COMMIT_TIMESTAMP + 60 * self.commits.len() as u64);
+        let output = Command::new("git")
+            .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+            .args(args)
+            .current_dir(&self.path)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_AUTHOR_NAME", "NAPE Test")
+            .env("GIT_AUTHOR_EMAIL", "test@nape.invalid")
+            .env("GIT_AUTHOR_DATE", &date)
+            .env("GIT_COMMITTER_NAME", "NAPE Test")
+            .env("GIT_COMMITTER_EMAIL", "test@nape.invalid")
+            .env("GIT_COMMITTER_DATE", &date)
+            .output()
+            .unwrap_or_else(|err| panic!("Could not run git, is it installed?\n\t{}\n", err));
+
+        if !output.status.success() {
+            panic!(
+                "The git command 'git {}' failed:\n\t{}\n",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+}
+
+impl Drop for GitRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
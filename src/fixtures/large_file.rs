@@ -0,0 +1,184 @@
+//! Large files with deterministic content for testing streaming code, such as hashing and uploading evidence,
+//! for correctness and memory use.
+//!
+//! The content of a file is a function of its [`FilePattern`] and the offset, so a test can compute what a
+//! streaming routine should produce with a [`PatternReader`] instead of holding the file in memory:
+//!
+//! ```no_run
+//! use attestify_test_framework::fixtures::large_file::{ByteSize, FilePattern, PatternReader};
+//! use attestify_test_framework::workspace::TestWorkspace;
+//! # fn hash_stream(reader: impl std::io::Read) -> u64 { 0 }
+//!
+//! let workspace = TestWorkspace::new();
+//! let pattern = FilePattern::Random { seed: 7 };
+//! let evidence = workspace.create_large_file("evidence.bin", 2.gigabytes(), pattern.clone());
+//! let expected = hash_stream(PatternReader::new(pattern, 2.gigabytes()));
+//! assert_eq!(hash_stream(std::fs::File::open(evidence).unwrap()), expected);
+//! ```
+
+use crate::doubles::splitmix64;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// The size of the buffer content is generated into and written from.
+const CHUNK: usize = 1024 * 1024;
+
+/// The number of bytes of content at the start of every [`CHUNK`] of a [`FilePattern::Sparse`] file.
+pub const SPARSE_BLOCK: usize = 4096;
+
+/// Sizes in bytes, e.g. `2.gigabytes()`.  The units are binary, so a kilobyte is 1024 bytes.
+pub trait ByteSize {
+    /// Returns the number of bytes in this many kilobytes.
+    fn kilobytes(self) -> u64;
+    /// Returns the number of bytes in this many megabytes.
+    fn megabytes(self) -> u64;
+    /// Returns the number of bytes in this many gigabytes.
+    fn gigabytes(self) -> u64;
+}
+
+impl ByteSize for u64 {
+    fn kilobytes(self) -> u64 {
+        self * 1024
+    }
+
+    fn megabytes(self) -> u64 {
+        self * 1024 * 1024
+    }
+
+    fn gigabytes(self) -> u64 {
+        self * 1024 * 1024 * 1024
+    }
+}
+
+/// The content of a large file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePattern {
+    /// Every byte is zero.  The file is written sparse, so it takes no disk space where the file system supports
+    /// it.
+    Zeros,
+    /// The bytes repeated from the start of the file.  They must not be empty.
+    Repeat(Vec<u8>),
+    /// Pseudo-random bytes generated from the seed.
+    Random { seed: u64 },
+    /// Pseudo-random bytes in the first [`SPARSE_BLOCK`] bytes of every megabyte, and zeros elsewhere.  The zeros
+    /// are written sparse, so the file takes little disk space but a routine that skips or reorders data still
+    /// produces a different result.
+    Sparse { seed: u64 },
+}
+
+impl FilePattern {
+    /// Fills the buffer with the content at the offset of a file.
+    pub fn fill(&self, offset: u64, buf: &mut [u8]) {
+        match self {
+            FilePattern::Zeros => buf.fill(0),
+            FilePattern::Repeat(bytes) => {
+                assert!(!bytes.is_empty(), "The bytes of FilePattern::Repeat must not be empty.");
+                let len = bytes.len() as u64;
+                for (i, byte) in buf.iter_mut().enumerate() {
+                    *byte = bytes[((offset + i as u64) % len) as usize];
+                }
+            }
+            FilePattern::Random { seed } => fill_random(*seed, offset, buf),
+            FilePattern::Sparse { seed } => {
+                for (i, byte) in buf.iter_mut().enumerate() {
+                    let position = offset + i as u64;
+                    *byte = if position % (CHUNK as u64) < (SPARSE_BLOCK as u64) {
+                        random_byte(*seed, position)
+                    } else {
+                        0
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn random_byte(seed: u64, position: u64) -> u8 {
+    splitmix64(seed ^ splitmix64(position / 8)).to_le_bytes()[(position % 8) as usize]
+}
+
+fn fill_random(seed: u64, offset: u64, buf: &mut [u8]) {
+    let mut position = offset;
+    let mut rest = buf;
+    while !rest.is_empty() {
+        let word = splitmix64(seed ^ splitmix64(position / 8)).to_le_bytes();
+        let start = (position % 8) as usize;
+        let len = (8 - start).min(rest.len());
+        rest[..len].copy_from_slice(&word[start..start + len]);
+        rest = &mut rest[len..];
+        position += len as u64;
+    }
+}
+
+/// Reads the content of a file of the pattern and size without writing it, e.g. to compute the hash a streaming
+/// routine should produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternReader {
+    pattern: FilePattern,
+    size: u64,
+    offset: u64,
+}
+
+impl PatternReader {
+    /// Creates a reader of the content of a file of the pattern and size.
+    pub fn new(pattern: FilePattern, size: u64) -> Self {
+        PatternReader {
+            pattern,
+            size,
+            offset: 0,
+        }
+    }
+}
+
+impl Read for PatternReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = (self.size - self.offset).min(buf.len() as u64) as usize;
+        self.pattern.fill(self.offset, &mut buf[..len]);
+        self.offset += len as u64;
+        Ok(len)
+    }
+}
+
+/// Writes a file of the size with the content of the pattern, creating any parent directories, in chunks so the
+/// file is never held in memory.  An existing file is overwritten.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file.
+/// * `size` - The size of the file in bytes, e.g. `2.gigabytes()`.
+/// * `pattern` - The content of the file.
+///
+pub fn write_large_file(path: &Path, size: u64, pattern: &FilePattern) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|_| panic!("Could not create the parent directory for '{}'.", parent.display()));
+    }
+    write_pattern(path, size, pattern)
+        .unwrap_or_else(|err| panic!("Could not write the large file '{}':\n\t{}\n", path.display(), err));
+}
+
+fn write_pattern(path: &Path, size: u64, pattern: &FilePattern) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    let mut buf = vec![0; CHUNK];
+    let mut offset = 0;
+    while offset < size {
+        let len = (size - offset).min(CHUNK as u64) as usize;
+        match pattern {
+            FilePattern::Zeros => {}
+            FilePattern::Sparse { .. } => {
+                let len = len.min(SPARSE_BLOCK);
+                pattern.fill(offset, &mut buf[..len]);
+                file.seek(SeekFrom::Start(offset))?;
+                file.write_all(&buf[..len])?;
+            }
+            FilePattern::Repeat(_) | FilePattern::Random { .. } => {
+                pattern.fill(offset, &mut buf[..len]);
+                file.write_all(&buf[..len])?;
+            }
+        }
+        offset += len as u64;
+    }
+    // Extends the file to its size without writing, leaving the zeros sparse.
+    file.set_len(size)
+}
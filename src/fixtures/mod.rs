@@ -0,0 +1,16 @@
+//! Fixtures that produce valid test data with sensible defaults.
+
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod bench_fixtures;
+#[cfg(feature = "crypto")]
+pub mod crypto_fixtures;
+pub mod error_catalog;
+pub mod error_fixture;
+pub mod evidence;
+pub mod git;
+pub mod large_file;
+pub mod nrn_fixture;
+pub mod procedure_fixture;
+pub mod report_fixture;
+pub mod values;
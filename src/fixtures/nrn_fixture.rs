@@ -0,0 +1,135 @@
+//! Generated NRNs (NAPE resource names), valid and deliberately invalid, for testing code that parses subject,
+//! procedure, and activity identifiers.
+//!
+//! ```no_run
+//! use attestify_test_framework::fixtures::nrn_fixture::NrnFixture;
+//!
+//! let fixture = NrnFixture::new("subject");
+//! assert_eq!(fixture.build(), "nrn:subject:attestify:test-resource");
+//! for nrn in fixture.valid_variants() {
+//!     // assert the parser accepts nrn
+//! }
+//! for invalid in fixture.invalid_variants() {
+//!     // assert the parser rejects invalid.nrn, which is wrong because of invalid.reason
+//! }
+//! ```
+//!
+//! Every valid variant passes [`assert_valid_nrn`](crate::assert_valid_nrn), and every invalid variant fails it.
+
+use crate::assertions::nrn::NRN_SEGMENTS;
+
+/// Segment shapes that are valid in every position.
+pub const VALID_SEGMENTS: &[(&str, &str)] = &[
+    ("a single letter", "a"),
+    ("a single digit", "7"),
+    ("a leading digit", "1password"),
+    ("hyphens", "access-review-q1"),
+    ("consecutive hyphens", "access--review"),
+    ("a long segment", "a-very-long-segment-name-used-to-check-that-no-length-limit-applies"),
+];
+
+/// Segment shapes that are invalid in every position, with what is wrong with them.
+pub const INVALID_SEGMENTS: &[(&str, &str)] = &[
+    ("an empty segment", ""),
+    ("an uppercase letter", "Attestify"),
+    ("a leading hyphen", "-attestify"),
+    ("an underscore", "access_review"),
+    ("a space", "access review"),
+    ("a slash", "access/review"),
+    ("a dot", "access.review"),
+    ("a non-ASCII letter", "prüfung"),
+];
+
+/// A generated NRN that is not valid, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidNrn {
+    pub nrn: String,
+    /// What is wrong with the NRN, e.g. `the owner segment has an uppercase letter`.
+    pub reason: String,
+}
+
+/// Builds NRNs of a resource type, created with [`NrnFixture::new`].
+///
+/// Defaults to the owner `attestify` and the name `test-resource`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NrnFixture {
+    resource_type: String,
+    owner: String,
+    name: String,
+}
+
+impl NrnFixture {
+    /// Creates a fixture for the resource type, e.g. `subject`, `procedure`, or `activity`.
+    pub fn new(resource_type: &str) -> Self {
+        NrnFixture {
+            resource_type: resource_type.to_string(),
+            owner: "attestify".to_string(),
+            name: "test-resource".to_string(),
+        }
+    }
+
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = owner.to_string();
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Returns the NRN, e.g. `nrn:procedure:attestify:test-resource`.
+    pub fn build(&self) -> String {
+        nrn([&self.resource_type, &self.owner, &self.name])
+    }
+
+    /// Returns valid NRNs where the owner or the name is replaced with each of the [`VALID_SEGMENTS`].  The resource
+    /// type is kept, so every variant is of the fixture's resource type.
+    pub fn valid_variants(&self) -> Vec<String> {
+        let mut variants = Vec::new();
+        for (_, segment) in VALID_SEGMENTS {
+            variants.push(nrn([&self.resource_type, segment, &self.name]));
+            variants.push(nrn([&self.resource_type, &self.owner, segment]));
+        }
+        variants
+    }
+
+    /// Returns invalid NRNs: each segment replaced with each of the [`INVALID_SEGMENTS`], followed by NRNs with a
+    /// wrong structure, such as a missing scheme or too many segments.
+    pub fn invalid_variants(&self) -> Vec<InvalidNrn> {
+        let mut variants = Vec::new();
+        for (position, name) in NRN_SEGMENTS.iter().enumerate() {
+            for (shape, segment) in INVALID_SEGMENTS {
+                let mut segments = [self.resource_type.as_str(), &self.owner, &self.name];
+                segments[position] = segment;
+                variants.push(InvalidNrn {
+                    nrn: nrn(segments),
+                    reason: format!("the {} segment has {}", name, shape),
+                });
+            }
+        }
+
+        let valid = self.build();
+        let structural = [
+            (valid.trim_start_matches("nrn:").to_string(), "the scheme is missing"),
+            (valid.replacen("nrn:", "urn:", 1), "the scheme is not 'nrn'"),
+            (valid.replacen("nrn:", "NRN:", 1), "the scheme is uppercase"),
+            (format!("nrn:{}:{}", self.resource_type, self.name), "a segment is missing"),
+            (format!("{}:extra", valid), "there is an extra segment"),
+            (format!("{}:", valid), "there is a trailing colon"),
+            (format!(" {}", valid), "there is leading whitespace"),
+            (format!("{}\n", valid), "there is a trailing newline"),
+            (String::new(), "the value is empty"),
+        ];
+        variants.extend(
+            structural
+                .into_iter()
+                .map(|(nrn, reason)| InvalidNrn { nrn, reason: reason.to_string() }),
+        );
+        variants
+    }
+}
+
+fn nrn([resource_type, owner, name]: [&str; 3]) -> String {
+    format!("nrn:{}:{}:{}", resource_type, owner, name)
+}
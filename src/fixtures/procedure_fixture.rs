@@ -0,0 +1,211 @@
+//! Generated NAPE procedure definition documents.
+//!
+//! Every function returns a [`ProcedureDocument`] holding the YAML text and the values it was generated from.  A
+//! generated document has this shape:
+//!
+//! ```yaml
+//! kind: Procedure
+//! metadata:
+//!   nrn: nrn:procedure:attestify:test-procedure
+//!   name: test-procedure
+//!   version: 1.0.0
+//!   description: A procedure used for testing.
+//!   labels:
+//!     api: '1.0'
+//! activities:
+//!   - nrn: nrn:activity:attestify:activity-1
+//!     name: activity-1
+//!     description: Activity 1 of the procedure.
+//!     actions:
+//!       - name: action-1
+//!         evidence: evidence-1
+//! ```
+//!
+//! [`minimal`] only has the required fields; `description`, `labels`, and `actions` are optional.
+
+use super::values::{ProcedureFixture, ProcedureValues};
+use serde_yaml::{Mapping, Value};
+
+/// The values of an activity in a generated procedure document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityValues {
+    pub nrn: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// A generated procedure definition document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcedureDocument {
+    /// The YAML text of the document.
+    pub yaml: String,
+    /// The procedure values the document was generated from.
+    pub procedure: ProcedureValues,
+    /// The activity values the document was generated from, in document order.
+    pub activities: Vec<ActivityValues>,
+}
+
+impl ProcedureDocument {
+    /// Passes the YAML text to the parser, typically the kernel's procedure parser, and returns its result.
+    pub fn parse_with<T>(&self, parser: impl FnOnce(&str) -> T) -> T {
+        parser(&self.yaml)
+    }
+}
+
+/// The section of a procedure document made invalid by [`malformed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MalformedSection {
+    /// The `kind` field is missing.
+    MissingKind,
+    /// The `metadata` section is missing.
+    MissingMetadata,
+    /// The `metadata.name` field is missing.
+    MissingName,
+    /// The `metadata.version` field is not a semantic version.
+    InvalidVersion,
+    /// The `metadata.nrn` field is not an NRN.
+    InvalidNrn,
+    /// The `activities` section is a string rather than a list.
+    ActivitiesNotAList,
+    /// Two activities have the same name.
+    DuplicateActivity,
+    /// The document is cut off part way through, so it is not valid YAML.
+    Truncated,
+}
+
+impl MalformedSection {
+    /// Every malformed section, for table tests.
+    pub const ALL: [MalformedSection; 8] = [
+        MalformedSection::MissingKind,
+        MalformedSection::MissingMetadata,
+        MalformedSection::MissingName,
+        MalformedSection::InvalidVersion,
+        MalformedSection::InvalidNrn,
+        MalformedSection::ActivitiesNotAList,
+        MalformedSection::DuplicateActivity,
+        MalformedSection::Truncated,
+    ];
+}
+
+/// Returns a document with only the required fields and a single activity.
+pub fn minimal() -> ProcedureDocument {
+    generate(ProcedureFixture::default().build(), 1, false)
+}
+
+/// Returns a document with every optional field populated and three activities.
+pub fn full() -> ProcedureDocument {
+    with_activities(3)
+}
+
+/// Returns a document with every optional field populated and the given number of activities.
+pub fn with_activities(count: usize) -> ProcedureDocument {
+    generate(ProcedureFixture::default().build(), count, true)
+}
+
+/// Returns a fully populated document generated from the procedure values, with the given number of activities.
+pub fn from_values(procedure: ProcedureValues, activities: usize) -> ProcedureDocument {
+    generate(procedure, activities, true)
+}
+
+/// Returns the [`full`] document with the section made invalid.
+pub fn malformed(section: MalformedSection) -> ProcedureDocument {
+    let mut document = full();
+    let mut root: Value = serde_yaml::from_str(&document.yaml).expect("The generated procedure is not valid YAML.");
+    let root_map = root.as_mapping_mut().expect("The generated procedure is not a mapping.");
+
+    match section {
+        MalformedSection::MissingKind => {
+            root_map.remove("kind");
+        }
+        MalformedSection::MissingMetadata => {
+            root_map.remove("metadata");
+        }
+        MalformedSection::MissingName => {
+            metadata_mut(root_map).remove("name");
+        }
+        MalformedSection::InvalidVersion => {
+            metadata_mut(root_map).insert("version".into(), "not-a-version".into());
+        }
+        MalformedSection::InvalidNrn => {
+            metadata_mut(root_map).insert("nrn".into(), "procedure/test-procedure".into());
+        }
+        MalformedSection::ActivitiesNotAList => {
+            root_map.insert("activities".into(), "activity-1".into());
+        }
+        MalformedSection::DuplicateActivity => {
+            let activities = root_map
+                .get_mut("activities")
+                .and_then(Value::as_sequence_mut)
+                .expect("The generated procedure has no activities.");
+            let first = activities[0].clone();
+            activities.push(first);
+        }
+        MalformedSection::Truncated => {}
+    }
+
+    document.yaml = serde_yaml::to_string(&root).expect("Could not serialize the procedure document.");
+    if section == MalformedSection::Truncated {
+        let cut = document.yaml.find("activities:").unwrap_or(document.yaml.len() / 2);
+        document.yaml = format!("{}activities: [{{name: ", &document.yaml[..cut]);
+    }
+    document
+}
+
+fn metadata_mut(root: &mut Mapping) -> &mut Mapping {
+    root.get_mut("metadata")
+        .and_then(Value::as_mapping_mut)
+        .expect("The generated procedure has no metadata.")
+}
+
+fn generate(procedure: ProcedureValues, activity_count: usize, populated: bool) -> ProcedureDocument {
+    let activities: Vec<ActivityValues> = (1..=activity_count)
+        .map(|number| ActivityValues {
+            nrn: format!("nrn:activity:attestify:activity-{}", number),
+            name: format!("activity-{}", number),
+            description: format!("Activity {} of the procedure.", number),
+        })
+        .collect();
+
+    let mut metadata = Mapping::new();
+    metadata.insert("nrn".into(), procedure.nrn.clone().into());
+    metadata.insert("name".into(), procedure.name.clone().into());
+    metadata.insert("version".into(), procedure.version.clone().into());
+    if populated {
+        metadata.insert("description".into(), procedure.description.clone().into());
+        let labels: Mapping = procedure
+            .metadata
+            .iter()
+            .map(|(key, value)| (Value::from(key.clone()), Value::from(value.clone())))
+            .collect();
+        metadata.insert("labels".into(), Value::Mapping(labels));
+    }
+
+    let activity_values: Vec<Value> = activities
+        .iter()
+        .enumerate()
+        .map(|(index, activity)| {
+            let mut entry = Mapping::new();
+            entry.insert("nrn".into(), activity.nrn.clone().into());
+            entry.insert("name".into(), activity.name.clone().into());
+            if populated {
+                entry.insert("description".into(), activity.description.clone().into());
+                let mut action = Mapping::new();
+                action.insert("name".into(), format!("action-{}", index + 1).into());
+                action.insert("evidence".into(), format!("evidence-{}", index + 1).into());
+                entry.insert("actions".into(), Value::Sequence(vec![Value::Mapping(action)]));
+            }
+            Value::Mapping(entry)
+        })
+        .collect();
+
+    let mut root = Mapping::new();
+    root.insert("kind".into(), "Procedure".into());
+    root.insert("metadata".into(), Value::Mapping(metadata));
+    root.insert("activities".into(), Value::Sequence(activity_values));
+
+    ProcedureDocument {
+        yaml: serde_yaml::to_string(&root).expect("Could not serialize the procedure document."),
+        procedure,
+        activities,
+    }
+}
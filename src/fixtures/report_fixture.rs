@@ -0,0 +1,322 @@
+//! Generated NAPE assurance report documents at the completion states downstream renderer and uploader tests need.
+//!
+//! Every preset of [`ReportFixture`] builds a [`ReportDocument`] holding the canonical YAML text and the values it
+//! was generated from, and [`ReportDocument::deserialize`] turns the YAML into the kernel's report struct.  A
+//! generated document has this shape:
+//!
+//! ```yaml
+//! kind: AssuranceReport
+//! metadata:
+//!   procedure: nrn:procedure:attestify:test-procedure
+//!   procedure_version: 1.0.0
+//!   subject: nrn:subject:attestify:test-subject
+//!   generated_at: 2024-01-01T00:00:00Z
+//! status: Passed
+//! summary:
+//!   activities: 1
+//!   passed: 1
+//!   failed: 0
+//!   pending: 0
+//!   unverifiable: 0
+//! activities:
+//! - nrn: nrn:activity:attestify:activity-1
+//!   name: activity-1
+//!   status: Passed
+//!   evidence:
+//!   - name: evidence-1
+//!     sha256: c81e6cf03ffccb451ced9bbe3505f1698da00ff95762c02b5d2495f81799004e
+//!     status: Verified
+//! ```
+//!
+//! The digest of an evidence is the digest of the default payload of an
+//! [`EvidenceFixture`](super::evidence::EvidenceFixture) with the same name, so the evidence can be written to a
+//! workspace and verified against the report.
+
+use super::values::{ProcedureFixture, ProcedureValues, SubjectFixture, SubjectValues};
+use crate::assertions::digest::sha256_hex;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// When generated reports were generated, unless set with [`ReportFixture::with_generated_at`].
+pub const DEFAULT_GENERATED_AT: &str = "2024-01-01T00:00:00Z";
+
+/// The outcome of an activity in a generated report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum ActivityStatus {
+    /// The activity ran and its evidence was verified.
+    Passed,
+    /// The activity ran and its evidence failed verification.
+    Failed,
+    /// The activity has not run, so it has no evidence.
+    Pending,
+    /// The activity ran, but its evidence could not be verified, e.g. because it has no digest.
+    Unverifiable,
+}
+
+/// The outcome of verifying an evidence in a generated report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum EvidenceStatus {
+    /// The evidence matches its digest.
+    Verified,
+    /// The evidence does not match its digest.
+    Failed,
+    /// The evidence has no digest to verify it against.
+    Unverifiable,
+}
+
+/// The overall status of a generated report, derived from the status of its activities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum ReportStatus {
+    /// No activity has run.
+    NotStarted,
+    /// Some activities have run and none failed.
+    InProgress,
+    /// Every activity passed.
+    Passed,
+    /// An activity failed.
+    Failed,
+    /// Every activity ran and none failed, but some evidence could not be verified.
+    Unverifiable,
+}
+
+/// The values of an evidence in a generated report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EvidenceResult {
+    pub name: String,
+    /// The lowercase hex SHA-256 digest of the evidence, omitted when it is unverifiable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    pub status: EvidenceStatus,
+}
+
+/// The values of an activity in a generated report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityResult {
+    pub nrn: String,
+    pub name: String,
+    pub status: ActivityStatus,
+    pub evidence: Vec<EvidenceResult>,
+}
+
+/// The number of activities of a generated report with each status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ReportSummary {
+    pub activities: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub pending: usize,
+    pub unverifiable: usize,
+}
+
+/// A generated assurance report document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportDocument {
+    /// The canonical YAML text of the document.
+    pub yaml: String,
+    /// The procedure the report is for.
+    pub procedure: ProcedureValues,
+    /// The subject the procedure was run against.
+    pub subject: SubjectValues,
+    /// When the report was generated, in RFC 3339.
+    pub generated_at: String,
+    /// The overall status of the report.
+    pub status: ReportStatus,
+    /// The number of activities with each status.
+    pub summary: ReportSummary,
+    /// The activities, in procedure order.
+    pub activities: Vec<ActivityResult>,
+}
+
+impl ReportDocument {
+    /// Passes the YAML text to the parser, typically the kernel's report parser, and returns its result.
+    pub fn parse_with<T>(&self, parser: impl FnOnce(&str) -> T) -> T {
+        parser(&self.yaml)
+    }
+
+    /// Deserializes the YAML text into the report, typically the kernel's report struct, which must implement
+    /// `Deserialize`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> T {
+        serde_yaml::from_str(&self.yaml).unwrap_or_else(|err| {
+            panic!(
+                "Could not build a {} from the report fixture:\n\t{}\n\tYAML:\t{}\n",
+                std::any::type_name::<T>(),
+                err,
+                self.yaml
+            )
+        })
+    }
+}
+
+/// Builds an assurance report document, starting from one of the presets.
+///
+/// Defaults to the default [`ProcedureFixture`] and [`SubjectFixture`] values, generated at
+/// [`DEFAULT_GENERATED_AT`].  Activity `n` is named `activity-n` and has the evidence `evidence-n`, unless it is
+/// pending:
+///
+/// ```no_run
+/// use attestify_test_framework::fixtures::report_fixture::{ActivityStatus, ReportFixture};
+/// # #[derive(serde::Deserialize)] struct AssuranceReport {}
+///
+/// let document = ReportFixture::with_failures().with_activity(ActivityStatus::Pending).build();
+/// let report: AssuranceReport = document.deserialize();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportFixture {
+    procedure: ProcedureValues,
+    subject: SubjectValues,
+    generated_at: String,
+    activities: Vec<ActivityStatus>,
+}
+
+impl ReportFixture {
+    /// Returns a report with no activities.
+    pub fn empty() -> Self {
+        ReportFixture {
+            procedure: ProcedureFixture::default().build(),
+            subject: SubjectFixture::default().build(),
+            generated_at: DEFAULT_GENERATED_AT.to_string(),
+            activities: Vec::new(),
+        }
+    }
+
+    /// Returns a report of a procedure part way through: two passed activities and one pending.
+    pub fn partially_executed() -> Self {
+        Self::with_statuses(&[ActivityStatus::Passed, ActivityStatus::Passed, ActivityStatus::Pending])
+    }
+
+    /// Returns a report whose three activities passed.
+    pub fn fully_passed() -> Self {
+        Self::with_statuses(&[ActivityStatus::Passed; 3])
+    }
+
+    /// Returns a report whose second of three activities failed.
+    pub fn with_failures() -> Self {
+        Self::with_statuses(&[ActivityStatus::Passed, ActivityStatus::Failed, ActivityStatus::Passed])
+    }
+
+    /// Returns a report whose second of three activities has evidence that could not be verified.
+    pub fn with_unverifiable_evidence() -> Self {
+        Self::with_statuses(&[ActivityStatus::Passed, ActivityStatus::Unverifiable, ActivityStatus::Passed])
+    }
+
+    /// Returns a report with an activity of each status, in order.
+    pub fn with_statuses(statuses: &[ActivityStatus]) -> Self {
+        let mut fixture = Self::empty();
+        fixture.activities = statuses.to_vec();
+        fixture
+    }
+
+    /// Sets the procedure the report is for.
+    pub fn with_procedure(mut self, procedure: ProcedureValues) -> Self {
+        self.procedure = procedure;
+        self
+    }
+
+    /// Sets the subject the procedure was run against.
+    pub fn with_subject(mut self, subject: SubjectValues) -> Self {
+        self.subject = subject;
+        self
+    }
+
+    /// Sets when the report was generated, in RFC 3339.
+    pub fn with_generated_at(mut self, generated_at: &str) -> Self {
+        self.generated_at = generated_at.to_string();
+        self
+    }
+
+    /// Adds an activity with the status after the others.
+    pub fn with_activity(mut self, status: ActivityStatus) -> Self {
+        self.activities.push(status);
+        self
+    }
+
+    /// Generates the document.
+    pub fn build(&self) -> ReportDocument {
+        let activities: Vec<ActivityResult> = self
+            .activities
+            .iter()
+            .enumerate()
+            .map(|(index, status)| activity(index + 1, *status))
+            .collect();
+        let count = |status: ActivityStatus| activities.iter().filter(|activity| activity.status == status).count();
+        let summary = ReportSummary {
+            activities: activities.len(),
+            passed: count(ActivityStatus::Passed),
+            failed: count(ActivityStatus::Failed),
+            pending: count(ActivityStatus::Pending),
+            unverifiable: count(ActivityStatus::Unverifiable),
+        };
+        let status = if summary.failed > 0 {
+            ReportStatus::Failed
+        } else if summary.pending == summary.activities {
+            ReportStatus::NotStarted
+        } else if summary.pending > 0 {
+            ReportStatus::InProgress
+        } else if summary.unverifiable > 0 {
+            ReportStatus::Unverifiable
+        } else {
+            ReportStatus::Passed
+        };
+
+        #[derive(Serialize)]
+        struct Metadata<'a> {
+            procedure: &'a str,
+            procedure_version: &'a str,
+            subject: &'a str,
+            generated_at: &'a str,
+        }
+
+        #[derive(Serialize)]
+        struct Document<'a> {
+            kind: &'a str,
+            metadata: Metadata<'a>,
+            status: ReportStatus,
+            summary: ReportSummary,
+            activities: &'a [ActivityResult],
+        }
+
+        let yaml = serde_yaml::to_string(&Document {
+            kind: "AssuranceReport",
+            metadata: Metadata {
+                procedure: &self.procedure.nrn,
+                procedure_version: &self.procedure.version,
+                subject: &self.subject.nrn,
+                generated_at: &self.generated_at,
+            },
+            status,
+            summary,
+            activities: &activities,
+        })
+        .expect("Could not serialize the report document.");
+
+        ReportDocument {
+            yaml,
+            procedure: self.procedure.clone(),
+            subject: self.subject.clone(),
+            generated_at: self.generated_at.clone(),
+            status,
+            summary,
+            activities,
+        }
+    }
+}
+
+fn activity(number: usize, status: ActivityStatus) -> ActivityResult {
+    let name = format!("evidence-{}", number);
+    let sha256 = sha256_hex(format!("{{\"evidence\": \"{}\", \"status\": \"passed\"}}", name).as_bytes());
+    let evidence = match status {
+        ActivityStatus::Passed => Some((Some(sha256), EvidenceStatus::Verified)),
+        ActivityStatus::Failed => Some((Some(sha256), EvidenceStatus::Failed)),
+        ActivityStatus::Unverifiable => Some((None, EvidenceStatus::Unverifiable)),
+        ActivityStatus::Pending => None,
+    };
+    ActivityResult {
+        nrn: format!("nrn:activity:attestify:activity-{}", number),
+        name: format!("activity-{}", number),
+        status,
+        evidence: evidence
+            .map(|(sha256, status)| EvidenceResult { name, sha256, status })
+            .into_iter()
+            .collect(),
+    }
+}
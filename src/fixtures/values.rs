@@ -0,0 +1,178 @@
+//! Builders for the values of kernel value objects.
+//!
+//! Each fixture starts from valid defaults and lets a test override only the fields it cares about.  `build`
+//! returns the plain values, and `build_with` hands them to a constructor of the kernel value object:
+//!
+//! ```no_run
+//! use attestify_test_framework::fixtures::values::MetaDataFixture;
+//!
+//! let metadata = MetaDataFixture::default().with_key("api", "2.0").build();
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Builds the key/value entries of a kernel `MetaData`.
+///
+/// Defaults to the entries `api: 1.0` and `owner: attestify`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaDataFixture {
+    entries: BTreeMap<String, String>,
+}
+
+impl Default for MetaDataFixture {
+    fn default() -> Self {
+        let mut entries = BTreeMap::new();
+        entries.insert("api".to_string(), "1.0".to_string());
+        entries.insert("owner".to_string(), "attestify".to_string());
+        MetaDataFixture { entries }
+    }
+}
+
+impl MetaDataFixture {
+    /// Creates a fixture with no entries.
+    pub fn empty() -> Self {
+        MetaDataFixture {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the entry, replacing any existing value for the key.
+    pub fn with_key(mut self, key: &str, value: &str) -> Self {
+        self.entries.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Removes the entry for the key.
+    pub fn without_key(mut self, key: &str) -> Self {
+        self.entries.remove(key);
+        self
+    }
+
+    /// Returns the entries.
+    pub fn build(self) -> BTreeMap<String, String> {
+        self.entries
+    }
+
+    /// Passes the entries to the constructor and returns its value.
+    pub fn build_with<T>(self, constructor: impl FnOnce(BTreeMap<String, String>) -> T) -> T {
+        constructor(self.build())
+    }
+}
+
+/// The values of a kernel `Subject`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectValues {
+    pub nrn: String,
+    pub name: String,
+}
+
+/// Builds the values of a kernel `Subject`.
+///
+/// Defaults to the NRN `nrn:subject:attestify:test-subject` and the name `Test Subject`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectFixture {
+    values: SubjectValues,
+}
+
+impl Default for SubjectFixture {
+    fn default() -> Self {
+        SubjectFixture {
+            values: SubjectValues {
+                nrn: "nrn:subject:attestify:test-subject".to_string(),
+                name: "Test Subject".to_string(),
+            },
+        }
+    }
+}
+
+impl SubjectFixture {
+    pub fn with_nrn(mut self, nrn: &str) -> Self {
+        self.values.nrn = nrn.to_string();
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.values.name = name.to_string();
+        self
+    }
+
+    /// Returns the values.
+    pub fn build(self) -> SubjectValues {
+        self.values
+    }
+
+    /// Passes the values to the constructor and returns its value.
+    pub fn build_with<T>(self, constructor: impl FnOnce(SubjectValues) -> T) -> T {
+        constructor(self.build())
+    }
+}
+
+/// The values of a kernel `Procedure`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcedureValues {
+    pub nrn: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Builds the values of a kernel `Procedure`.
+///
+/// Defaults to the NRN `nrn:procedure:attestify:test-procedure`, the name `test-procedure`, version `1.0.0`, a
+/// short description, and the default [`MetaDataFixture`] entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcedureFixture {
+    values: ProcedureValues,
+}
+
+impl Default for ProcedureFixture {
+    fn default() -> Self {
+        ProcedureFixture {
+            values: ProcedureValues {
+                nrn: "nrn:procedure:attestify:test-procedure".to_string(),
+                name: "test-procedure".to_string(),
+                version: "1.0.0".to_string(),
+                description: "A procedure used for testing.".to_string(),
+                metadata: MetaDataFixture::default().build(),
+            },
+        }
+    }
+}
+
+impl ProcedureFixture {
+    pub fn with_nrn(mut self, nrn: &str) -> Self {
+        self.values.nrn = nrn.to_string();
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.values.name = name.to_string();
+        self
+    }
+
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.values.version = version.to_string();
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.values.description = description.to_string();
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: MetaDataFixture) -> Self {
+        self.values.metadata = metadata.build();
+        self
+    }
+
+    /// Returns the values.
+    pub fn build(self) -> ProcedureValues {
+        self.values
+    }
+
+    /// Passes the values to the constructor and returns its value.
+    pub fn build_with<T>(self, constructor: impl FnOnce(ProcedureValues) -> T) -> T {
+        constructor(self.build())
+    }
+}
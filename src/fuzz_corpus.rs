@@ -0,0 +1,254 @@
+//! Seed and mutated YAML procedure documents for fuzzing procedure parsers.
+//!
+//! The seeds are the generated documents of [`crate::fixtures::procedure_fixture`].  Each [`Mutation`] damages a
+//! document in a way a parser must reject gracefully, and every mutation is deterministic for a given seed, so a
+//! failing input can be reproduced.  [`write_corpus`] emits a corpus directory for a cargo-fuzz target, and
+//! [`assert_parser_never_panics`] runs a parser over the corpus in an ordinary test:
+//!
+//! ```no_run
+//! use attestify_test_framework::fuzz_corpus;
+//! # fn parse_procedure(yaml: &str) -> Result<(), String> { Ok(()) }
+//!
+//! let inputs = fuzz_corpus::corpus(7, 50);
+//! fuzz_corpus::assert_parser_never_panics(&inputs, parse_procedure);
+//! ```
+
+use crate::assertions::digest::sha256_hex;
+use crate::assertions::report::{self, MismatchReport};
+use crate::fixtures::procedure_fixture::{self, MalformedSection};
+use crate::panics::catch_panic;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A way of damaging a procedure document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Cuts the document off at an arbitrary character.
+    Truncate,
+    /// Swaps the values of two keys of one mapping, e.g. `name` and `activities`.
+    SwapKeys,
+    /// Replaces one node with a node of another type, e.g. a string with a sequence.
+    CorruptType,
+}
+
+impl Mutation {
+    /// Every mutation, for exhaustive loops.
+    pub const ALL: [Mutation; 3] = [Mutation::Truncate, Mutation::SwapKeys, Mutation::CorruptType];
+}
+
+/// A small deterministic generator, so mutations are reproducible from their seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number below the bound, which must not be zero.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+/// Returns the seed documents: the minimal and full documents, documents with zero to five activities, and every
+/// [`MalformedSection`].
+pub fn seeds() -> Vec<String> {
+    let mut seeds = vec![procedure_fixture::minimal().yaml, procedure_fixture::full().yaml];
+    seeds.extend([0, 1, 5].map(|count| procedure_fixture::with_activities(count).yaml));
+    seeds.extend(MalformedSection::ALL.map(|section| procedure_fixture::malformed(section).yaml));
+    seeds
+}
+
+/// Applies the mutation to the document.  The same document, mutation, and seed always give the same result.
+///
+/// # Arguments
+///
+/// * `document` - The YAML document to damage.
+/// * `mutation` - How to damage it.
+/// * `seed` - Selects where the damage is done.
+///
+pub fn mutate(document: &str, mutation: Mutation, seed: u64) -> String {
+    let mut rng = Rng(seed);
+    match mutation {
+        Mutation::Truncate => {
+            let boundaries: Vec<usize> = document.char_indices().map(|(index, _)| index).collect();
+            if boundaries.is_empty() {
+                return String::new();
+            }
+            document[..boundaries[rng.below(boundaries.len())]].to_string()
+        }
+        Mutation::SwapKeys | Mutation::CorruptType => {
+            let Ok(mut root) = serde_yaml::from_str::<Value>(document) else {
+                return swap_lines(document, &mut rng);
+            };
+            if mutation == Mutation::SwapKeys {
+                let mappings = count_nodes(&root, &|value| value.as_mapping().is_some_and(|mapping| mapping.len() > 1));
+                if mappings == 0 {
+                    return swap_lines(document, &mut rng);
+                }
+                let target = rng.below(mappings);
+                swap_keys(&mut root, &mut 0, target, &mut rng);
+            } else {
+                let target = rng.below(count_nodes(&root, &|_| true));
+                corrupt(&mut root, &mut 0, target, &mut rng);
+            }
+            serde_yaml::to_string(&root).unwrap_or_else(|_| document.to_string())
+        }
+    }
+}
+
+fn swap_lines(document: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<&str> = document.lines().collect();
+    if lines.len() > 1 {
+        let first = rng.below(lines.len());
+        let second = rng.below(lines.len());
+        lines.swap(first, second);
+    }
+    lines.join("\n")
+}
+
+/// Counts the nodes, in depth-first order, that satisfy the predicate.
+fn count_nodes(value: &Value, counted: &dyn Fn(&Value) -> bool) -> usize {
+    let children = match value {
+        Value::Mapping(mapping) => mapping.values().map(|child| count_nodes(child, counted)).sum(),
+        Value::Sequence(items) => items.iter().map(|child| count_nodes(child, counted)).sum(),
+        _ => 0,
+    };
+    children + usize::from(counted(value))
+}
+
+/// Swaps the values of two keys of the mapping at the target position, counting only mappings with more than one
+/// key, in depth-first order.
+fn swap_keys(value: &mut Value, position: &mut usize, target: usize, rng: &mut Rng) -> bool {
+    match value {
+        Value::Mapping(mapping) => {
+            if mapping.len() > 1 {
+                if *position == target {
+                    let keys: Vec<Value> = mapping.keys().cloned().collect();
+                    let first = rng.below(keys.len());
+                    let second = (first + 1 + rng.below(keys.len() - 1)) % keys.len();
+                    let first_value = mapping.get(&keys[first]).cloned().unwrap_or(Value::Null);
+                    let second_value = mapping.get(&keys[second]).cloned().unwrap_or(Value::Null);
+                    mapping.insert(keys[first].clone(), second_value);
+                    mapping.insert(keys[second].clone(), first_value);
+                    return true;
+                }
+                *position += 1;
+            }
+            mapping.values_mut().any(|child| swap_keys(child, position, target, rng))
+        }
+        Value::Sequence(items) => items.iter_mut().any(|child| swap_keys(child, position, target, rng)),
+        _ => false,
+    }
+}
+
+/// Replaces the node at the target position, in depth-first order, with a node of another type.
+fn corrupt(value: &mut Value, position: &mut usize, target: usize, rng: &mut Rng) -> bool {
+    if *position == target {
+        *value = match value {
+            Value::String(_) => match rng.below(3) {
+                0 => Value::Sequence(vec![Value::Null]),
+                1 => Value::Number(rng.next().into()),
+                _ => Value::Bool(true),
+            },
+            Value::Mapping(_) => match rng.below(2) {
+                0 => Value::String("corrupted".to_string()),
+                _ => Value::Sequence(Vec::new()),
+            },
+            Value::Sequence(_) => Value::Mapping(Mapping::new()),
+            _ => Value::Mapping(Mapping::new()),
+        };
+        return true;
+    }
+    *position += 1;
+    match value {
+        Value::Mapping(mapping) => mapping.values_mut().any(|child| corrupt(child, position, target, rng)),
+        Value::Sequence(items) => items.iter_mut().any(|child| corrupt(child, position, target, rng)),
+        _ => false,
+    }
+}
+
+/// Returns the [`seeds`] followed by `mutations_per_seed` mutations of each, cycling through every [`Mutation`].
+///
+/// # Arguments
+///
+/// * `seed` - Selects the mutations; the same seed always gives the same corpus.
+/// * `mutations_per_seed` - How many mutated documents to derive from each seed document.
+///
+pub fn corpus(seed: u64, mutations_per_seed: usize) -> Vec<String> {
+    let seeds = seeds();
+    let mut rng = Rng(seed);
+    let mut corpus = seeds.clone();
+    for document in &seeds {
+        for index in 0..mutations_per_seed {
+            let mutation = Mutation::ALL[index % Mutation::ALL.len()];
+            corpus.push(mutate(document, mutation, rng.next()));
+        }
+    }
+    corpus
+}
+
+/// Writes the [`corpus`] to the directory, one file per document named by its SHA-256 digest, as cargo-fuzz
+/// expects of `fuzz/corpus/<target>`.  Returns the paths written.
+///
+/// # Arguments
+///
+/// * `dir` - The corpus directory, created if it does not exist.
+/// * `seed` - Selects the mutations.
+/// * `mutations_per_seed` - How many mutated documents to derive from each seed document.
+///
+pub fn write_corpus(dir: &Path, seed: u64, mutations_per_seed: usize) -> Vec<PathBuf> {
+    fs::create_dir_all(dir)
+        .unwrap_or_else(|_| panic!("Could not create the corpus directory '{}'.", dir.display()));
+    let mut paths: Vec<PathBuf> = corpus(seed, mutations_per_seed)
+        .iter()
+        .map(|document| {
+            let path = dir.join(sha256_hex(document.as_bytes()));
+            fs::write(&path, document)
+                .unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+            path
+        })
+        .collect();
+    paths.dedup();
+    paths
+}
+
+/// Asserts the parser returns, with an `Ok` or an `Err`, for every input, and never panics.  On failure it lists
+/// every input that panicked with its panic message.
+///
+/// # Arguments
+///
+/// * `inputs` - The documents to parse, e.g. from [`corpus`].
+/// * `parser` - The parser under test, typically returning a [`nape_kernel::error::Error`] on invalid input.
+///
+pub fn assert_parser_never_panics<T, E>(inputs: &[String], parser: impl Fn(&str) -> Result<T, E>) {
+    report::record_evaluation();
+    let panics: Vec<String> = inputs
+        .iter()
+        .filter_map(|input| {
+            catch_panic(|| {
+                let _ = parser(input);
+            })
+            .map(|message| format!("\tInput:\t{:?}\n\tPanic:\t{}", input, message))
+        })
+        .collect();
+
+    if !panics.is_empty() {
+        report::fail(
+            MismatchReport::new(
+                "parser",
+                format!(
+                    "The parser panicked on {} of {} inputs, although it should return an Error.",
+                    panics.len(),
+                    inputs.len()
+                ),
+            )
+            .hint(panics.join("\n\n")),
+        );
+    }
+}
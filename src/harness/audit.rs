@@ -0,0 +1,114 @@
+use crate::assertions::report::MismatchReport;
+use crate::reporting::JUNIT_OUTPUT_ENV_VAR;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// The environment variable that audits the assertions of a suite after it runs when it is set to `1`, see
+/// [`TestHarness::assertion_audit`](super::TestHarness::assertion_audit).
+pub const ASSERTION_AUDIT_ENV_VAR: &str = "TEST_ASSERTION_AUDIT";
+
+/// The environment variable naming the file the audit build of a suite writes the tests that passed to.
+const AUDIT_OUTPUT_ENV_VAR: &str = "TEST_ASSERTION_AUDIT_OUTPUT";
+
+/// The flag that builds the suite with its assertions disabled, see
+/// [`assertions_disabled`](crate::assertions::report::assertions_disabled).
+const AUDIT_CFG: &str = "--cfg attestify_assertion_audit";
+
+/// The test that passed in the audit build, and the number of assertions it evaluated.
+pub(super) type AuditedTest = (String, usize);
+
+/// Returns true when the audit is requested by the [`ASSERTION_AUDIT_ENV_VAR`] environment variable, set to `1` or
+/// `true`, or the `--assertion-audit` argument.
+pub(super) fn requested() -> bool {
+    matches!(env::var(ASSERTION_AUDIT_ENV_VAR).as_deref(), Ok("1" | "true"))
+        || env::args().skip(1).any(|arg| arg == "--assertion-audit")
+}
+
+/// Builds and runs the test binary again with its assertions disabled, and checks every test that still passes
+/// evaluated an assertion, see [`check_passed`].
+///
+/// The binary is rebuilt with `cargo test --test <name>` in the `assertion-audit` directory of the target directory,
+/// so the regular build is kept, and is given the arguments of this run, such as the name filter.
+pub(super) fn run() -> Result<(), MismatchReport> {
+    let failure = |summary: &str, hint: String| Err(MismatchReport::new("assertion audit", summary).hint(hint));
+    let (Ok(binary), Ok(manifest_dir)) = (env::current_exe(), env::var("CARGO_MANIFEST_DIR")) else {
+        return failure("The assertion audit must run under cargo test.", String::new());
+    };
+    let stem = binary.file_stem().unwrap_or_default().to_string_lossy();
+    let target = stem.rsplit_once('-').map_or(&*stem, |(name, _hash)| name);
+    let target_dir = binary.ancestors().nth(3).unwrap_or(Path::new("target")).join("assertion-audit");
+    let output = target_dir.join(format!("{}.audit", target));
+    let _ = fs::remove_file(&output);
+    let rustflags = match env::var("RUSTFLAGS") {
+        Ok(flags) if !flags.trim().is_empty() => format!("{} {}", flags, AUDIT_CFG),
+        _ => AUDIT_CFG.to_string(),
+    };
+
+    let run = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["test", "--manifest-path"])
+        .arg(Path::new(&manifest_dir).join("Cargo.toml"))
+        .args(["--test", target, "--"])
+        .args(env::args().skip(1).filter(|arg| arg != "--assertion-audit"))
+        .env("RUSTFLAGS", rustflags)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env(AUDIT_OUTPUT_ENV_VAR, &output)
+        .env_remove(ASSERTION_AUDIT_ENV_VAR)
+        .env_remove(JUNIT_OUTPUT_ENV_VAR)
+        .output();
+    let run = match run {
+        Ok(run) => run,
+        Err(err) => return failure("Could not run cargo for the assertion audit.", format!("\t{}", err)),
+    };
+    match fs::read_to_string(&output) {
+        Ok(results) => check_passed(&parse(&results)),
+        Err(_) => failure(
+            "The test binary could not be built or run with the assertions disabled.",
+            String::from_utf8_lossy(&run.stderr).into_owned(),
+        ),
+    }
+}
+
+/// Writes the tests that passed in the audit build for the run that started the build.  Does nothing when the build
+/// was run by hand, with `RUSTFLAGS='--cfg attestify_assertion_audit'`.
+pub(super) fn write_results(passed: &[AuditedTest]) {
+    let Ok(path) = env::var(AUDIT_OUTPUT_ENV_VAR) else {
+        return;
+    };
+    let results: String = passed.iter().map(|(name, evaluated)| format!("{}\t{}\n", evaluated, name)).collect();
+    fs::write(&path, results)
+        .unwrap_or_else(|err| panic!("Could not write the assertion audit results '{}':\n\t{}\n", path, err));
+}
+
+fn parse(results: &str) -> Vec<AuditedTest> {
+    results
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(evaluated, name)| (name.to_string(), evaluated.parse().unwrap_or(0)))
+        .collect()
+}
+
+/// Checks every test that passed with the assertions disabled evaluated an assertion.  A test that still passes
+/// without evaluating one asserts nothing the framework could fail it on.
+pub(super) fn check_passed(passed: &[AuditedTest]) -> Result<(), MismatchReport> {
+    let unasserted: Vec<&str> = passed
+        .iter()
+        .filter(|(_, evaluated)| *evaluated == 0)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if unasserted.is_empty() {
+        return Ok(());
+    }
+    Err(MismatchReport::new(
+        "assertions",
+        format!(
+            "{} of {} tests still pass with the assertions disabled, without evaluating an assertion.",
+            unasserted.len(),
+            passed.len()
+        ),
+    )
+    .expected("at least 1 assertion per test")
+    .actual("0 assertions")
+    .hint(format!("\t{}", unasserted.join("\n\t"))))
+}
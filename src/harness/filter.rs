@@ -0,0 +1,97 @@
+use std::env;
+
+/// The environment variable selecting tests by tag, e.g. `TEST_TAGS=network,!slow`, see [`TestFilter::parse_tags`].
+pub const TEST_TAGS_ENV_VAR: &str = "TEST_TAGS";
+
+/// The libtest flags that take a value, which is skipped when looking for the name filter in the arguments.
+const FLAGS_WITH_VALUES: [&str; 6] = ["--test-threads", "--format", "--color", "--logfile", "--skip", "-Z"];
+
+/// Selects the tests a [`TestHarness`](super::TestHarness) runs, by tag and by name.
+///
+/// A test runs when its name contains the name filter, it has at least one of the included tags (or none are
+/// included), and it has none of the excluded tags.  Untagged tests are only excluded by a name filter or an
+/// included tag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestFilter {
+    name: Option<String>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl TestFilter {
+    /// Creates a filter that runs every test.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the filter from the [`TEST_TAGS_ENV_VAR`] environment variable and the test binary's arguments.
+    ///
+    /// The arguments accept `--tags <tags>` or `--tags=<tags>`, which is combined with the environment variable, and
+    /// the first argument that is not a flag as the name filter, like libtest.  Other libtest flags are ignored.
+    pub fn from_env() -> Self {
+        let mut filter = TestFilter::new();
+        if let Ok(tags) = env::var(TEST_TAGS_ENV_VAR) {
+            filter = filter.parse_tags(&tags);
+        }
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == "--tags" {
+                if let Some(tags) = args.next() {
+                    filter = filter.parse_tags(&tags);
+                }
+            } else if let Some(tags) = arg.strip_prefix("--tags=") {
+                filter = filter.parse_tags(tags);
+            } else if FLAGS_WITH_VALUES.contains(&arg.as_str()) {
+                args.next();
+            } else if !arg.starts_with('-') && filter.name.is_none() {
+                filter.name = Some(arg);
+            }
+        }
+        filter
+    }
+
+    /// Only runs tests whose name contains the text.
+    pub fn name(mut self, contains: impl Into<String>) -> Self {
+        self.name = Some(contains.into());
+        self
+    }
+
+    /// Only runs tests with the tag, or with any of the tags included so far.
+    pub fn include(mut self, tag: impl Into<String>) -> Self {
+        self.include.push(tag.into());
+        self
+    }
+
+    /// Does not run tests with the tag.
+    pub fn exclude(mut self, tag: impl Into<String>) -> Self {
+        self.exclude.push(tag.into());
+        self
+    }
+
+    /// Adds the tags from a comma separated list, where a tag prefixed with `!` is excluded and any other tag is
+    /// included, e.g. `network,!slow`.
+    pub fn parse_tags(mut self, tags: &str) -> Self {
+        for tag in tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            self = match tag.strip_prefix('!') {
+                Some(excluded) => self.exclude(excluded),
+                None => self.include(tag),
+            };
+        }
+        self
+    }
+
+    /// Returns true when the test should run.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `tags` - The tags of the test.
+    ///
+    pub fn matches(&self, name: &str, tags: &[String]) -> bool {
+        let has = |tag: &String| tags.contains(tag);
+        self.name.as_ref().is_none_or(|contains| name.contains(contains.as_str()))
+            && (self.include.is_empty() || self.include.iter().any(has))
+            && !self.exclude.iter().any(has)
+    }
+}
@@ -0,0 +1,65 @@
+//! A test runner for `harness = false` test binaries, for suites that share expensive setup across tests.
+//!
+//! libtest runs every test in isolation, so a suite that needs one workspace cloned from a large repository must
+//! either clone it per test or hide it in a global.  [`TestHarness`] builds the shared state once, runs suite and
+//! per-test hooks around it, and reports the results with a [`JUnitReport`](crate::reporting::JUnitReport).
+//!
+//! ```no_run
+//! use attestify_test_framework::harness::TestHarness;
+//! use attestify_test_framework::workspace::TestWorkspace;
+//!
+//! fn main() -> std::process::ExitCode {
+//!     TestHarness::new("git_usecases", TestWorkspace::new)
+//!         .before_each(|workspace| {
+//!             workspace.create_file("notes.txt", "");
+//!         })
+//!         .test("reads_the_notes", |workspace| {
+//!             assert!(workspace.path_for("notes.txt").exists());
+//!         })
+//!         .run()
+//! }
+//! ```
+//!
+//! Tests added with [`TestHarness::tagged_test`] can be selected by tag, so a local run can skip the slow tests
+//! that CI runs: `TEST_TAGS='!slow' cargo test --test git_usecases`, or `cargo test --test git_usecases -- --tags
+//! network`.
+//!
+//! A test can also declare a parameter the harness creates for it alone and tears down when it ends, such as its
+//! own [`TestWorkspace`](crate::workspace::TestWorkspace), with [`TestHarness::test_with`].  When the test fails
+//! its report names the workspace directory, which is kept for inspection when `KEEP_TEST_DIRS=1`.
+//!
+//! A suite runs every test and reports every failure by default.  A long integration suite can instead stop at its
+//! first failure, with `TEST_FAIL_FAST=1` or `cargo test --test git_usecases -- --fail-fast`, see
+//! [`ExecutionMode`].
+//!
+//! A suite can also audit its assertions, with `TEST_ASSERTION_AUDIT=1` or `cargo test --test git_usecases --
+//! --assertion-audit`, see [`TestHarness::assertion_audit`].  After the suite runs, the harness builds the test
+//! binary again with `--cfg attestify_assertion_audit`, which disables every assertion of the framework, and runs
+//! it with the same arguments.  In that build an assertion checks nothing: it counts that the test evaluated it, and
+//! one that fails ends its test as passed.  The audit is reported as a test of its own, `assertion_audit`, which
+//! fails with the tests that still pass without evaluating an assertion, as they assert nothing the framework could
+//! fail them on.  Only the framework's assertions are disabled, so a test that fails with `assert!` or a panic still
+//! fails and is not reported.
+//!
+//! The audit build is kept in the `assertion-audit` directory of the target directory, and is built with the
+//! default features of the package.
+//!
+//! The test binary is declared in `Cargo.toml` with `harness = false`:
+//!
+//! ```toml
+//! [[test]]
+//! name = "git_usecases"
+//! harness = false
+//! ```
+
+mod audit;
+mod filter;
+mod mode;
+mod parameter;
+mod runner;
+
+pub use audit::ASSERTION_AUDIT_ENV_VAR;
+pub use filter::{TestFilter, TEST_TAGS_ENV_VAR};
+pub use mode::{ExecutionMode, FAIL_FAST_ENV_VAR};
+pub use parameter::TestParameter;
+pub use runner::TestHarness;
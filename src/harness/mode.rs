@@ -0,0 +1,35 @@
+use std::env;
+
+/// The environment variable that stops a suite at its first failure when it is set to `1`, see
+/// [`ExecutionMode::from_env`].
+pub const FAIL_FAST_ENV_VAR: &str = "TEST_FAIL_FAST";
+
+/// Whether a [`TestHarness`](super::TestHarness) stops at the first failing test or runs every test.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Runs every test and reports every failure, with its mismatch report, at the end.
+    #[default]
+    CollectAll,
+    /// Stops at the first failing test, skipping the tests after it and the suite assertions, so a long integration
+    /// suite does not keep a CI job busy once it has failed.  `after_all` still runs.
+    FailFast,
+}
+
+impl ExecutionMode {
+    /// Reads the mode from the [`FAIL_FAST_ENV_VAR`] environment variable and the test binary's arguments.
+    ///
+    /// The suite fails fast when the environment variable is `1` or `true`, or the arguments contain `--fail-fast`.
+    /// `--no-fail-fast` in the arguments takes precedence, so a local run can override a CI default.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let has = |flag: &str| args.iter().any(|arg| arg == flag);
+        let requested = matches!(env::var(FAIL_FAST_ENV_VAR).as_deref(), Ok("1" | "true"));
+        if has("--no-fail-fast") {
+            ExecutionMode::CollectAll
+        } else if requested || has("--fail-fast") {
+            ExecutionMode::FailFast
+        } else {
+            ExecutionMode::CollectAll
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::workspace::{self, TestWorkspace};
+
+/// A value a harness test declares as a parameter, with [`TestHarness::test_with`](super::TestHarness::test_with).
+/// The harness creates a fresh one for each test and tears it down, by dropping it, when the test ends.
+pub trait TestParameter {
+    /// Creates the value before the test runs.
+    fn create() -> Self;
+
+    /// Describes the value in the report of a failed test, such as the directory a workspace is in, for post-mortem
+    /// inspection.
+    fn describe(&self) -> String;
+}
+
+impl TestParameter for TestWorkspace {
+    fn create() -> Self {
+        TestWorkspace::new()
+    }
+
+    fn describe(&self) -> String {
+        if workspace::keep_test_dirs_requested() {
+            format!("\tWorkspace:\t{}", self.root().display())
+        } else {
+            format!(
+                "\tWorkspace:\t{} (removed, set {}=1 to keep it)",
+                self.root().display(),
+                workspace::KEEP_TEST_DIRS_ENV_VAR
+            )
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use super::audit::{self, AuditedTest};
+use super::{ExecutionMode, TestFilter, TestParameter};
+use crate::assertions::report::{self, MismatchReport};
+use crate::reporting::{panic_report, run_test, JUnitReport, TestCase, TestOutcome};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+type Hook<S> = Box<dyn FnMut(&mut S)>;
+type Teardown<S> = Box<dyn FnOnce(&mut S)>;
+
+type SuiteAssertion = (String, Box<dyn FnOnce()>);
+
+struct Test<S> {
+    name: String,
+    tags: Vec<String>,
+    body: Body<S>,
+}
+
+enum Body<S> {
+    Once(Box<dyn FnOnce(&mut S)>),
+    Retried(u32, Box<dyn FnMut(&mut S)>),
+}
+
+/// Runs a suite of tests that share state built once, with hooks before and after the suite and each test.
+///
+/// The hooks run in this order:
+///
+/// * `before_all` builds the shared state.  If it panics every test fails with its failure.
+/// * `before_each` runs before each test.  If it panics the test fails without running.
+/// * `after_each` runs after each test, even when the test failed.  If it panics a passing test fails.
+/// * Suite assertions run after every test, before `after_all`, and are reported as tests of their own.
+/// * `after_all` runs after every test, and is reported as a test of its own when it panics.
+///
+/// Tests run one at a time, in the order they were added.  Tests can be tagged, e.g. `slow` or `network`, and
+/// selected by tag and name with a [`TestFilter`], which by default is read from the `TEST_TAGS` environment
+/// variable and the arguments, see [`TestFilter::from_env`].  `before_all` is not run when no test is selected.
+///
+/// Every test runs, even after one fails, unless the [`ExecutionMode`] is [`ExecutionMode::FailFast`], which by
+/// default is read from the `TEST_FAIL_FAST` environment variable and the arguments, see
+/// [`ExecutionMode::from_env`].
+///
+/// A known-flaky test, such as one that fetches evidence over the network, can be added with
+/// [`TestHarness::retried_test`] so it is quarantined without losing its signal: it runs again when it fails, and
+/// when a later attempt passes it is reported as a flaky pass, with the failure of every earlier attempt, instead of
+/// silently passing.
+///
+/// The suite can end with an audit of its assertions, see [`TestHarness::assertion_audit`].
+pub struct TestHarness<S> {
+    name: String,
+    before_all: Box<dyn FnOnce() -> S>,
+    after_all: Option<Teardown<S>>,
+    before_each: Vec<Hook<S>>,
+    after_each: Vec<Hook<S>>,
+    tests: Vec<Test<S>>,
+    suite_assertions: Vec<SuiteAssertion>,
+    filter: Option<TestFilter>,
+    mode: Option<ExecutionMode>,
+    audit: Option<bool>,
+}
+
+impl<S: 'static> TestHarness<S> {
+    /// Creates a suite with no tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the suite, used in the JUnit report.
+    /// * `before_all` - Builds the state shared by every test, such as a [`TestWorkspace`](crate::workspace::TestWorkspace).
+    ///   Use `|| ()` when the suite has no shared state.
+    ///
+    pub fn new(name: impl Into<String>, before_all: impl FnOnce() -> S + 'static) -> Self {
+        TestHarness {
+            name: name.into(),
+            before_all: Box::new(before_all),
+            after_all: None,
+            before_each: Vec::new(),
+            after_each: Vec::new(),
+            tests: Vec::new(),
+            suite_assertions: Vec::new(),
+            filter: None,
+            mode: None,
+            audit: None,
+        }
+    }
+
+    /// Sets the hook run once after every test, typically to release what `before_all` acquired.
+    pub fn after_all(mut self, hook: impl FnOnce(&mut S) + 'static) -> Self {
+        self.after_all = Some(Box::new(hook));
+        self
+    }
+
+    /// Adds a hook run before each test.  Hooks run in the order they were added.
+    pub fn before_each(mut self, hook: impl FnMut(&mut S) + 'static) -> Self {
+        self.before_each.push(Box::new(hook));
+        self
+    }
+
+    /// Adds a hook run after each test.  Hooks run in the order they were added.
+    pub fn after_each(mut self, hook: impl FnMut(&mut S) + 'static) -> Self {
+        self.after_each.push(Box::new(hook));
+        self
+    }
+
+    /// Adds a test.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `body` - The test, given the shared state.  It fails when it panics, so any of the assertions can be used.
+    ///
+    pub fn test(self, name: impl Into<String>, body: impl FnOnce(&mut S) + 'static) -> Self {
+        self.tagged_test(name, &[], body)
+    }
+
+    /// Adds a test with tags, which a [`TestFilter`] can include or exclude.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `tags` - The tags of the test, e.g. `&["slow", "network"]`.
+    /// * `body` - The test, given the shared state.
+    ///
+    pub fn tagged_test(mut self, name: impl Into<String>, tags: &[&str], body: impl FnOnce(&mut S) + 'static) -> Self {
+        self.tests.push(Test {
+            name: name.into(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            body: Body::Once(Box::new(body)),
+        });
+        self
+    }
+
+    /// Adds a test that takes a parameter the harness creates for it alone, such as its own
+    /// [`TestWorkspace`](crate::workspace::TestWorkspace), and tears down when it ends.  When the test fails, the
+    /// parameter's [description](TestParameter::describe), e.g. the workspace directory, is added to its report.
+    ///
+    /// ```no_run
+    /// use attestify_test_framework::harness::TestHarness;
+    /// use attestify_test_framework::workspace::TestWorkspace;
+    ///
+    /// TestHarness::new("evidence_usecases", || ())
+    ///     .test_with("writes_the_report", |_, workspace: &TestWorkspace| {
+    ///         workspace.create_file("report.yaml", "status: Passed");
+    ///     })
+    ///     .run();
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `body` - The test, given the shared state and the parameter.
+    ///
+    pub fn test_with<P: TestParameter + 'static>(
+        self,
+        name: impl Into<String>,
+        body: impl FnOnce(&mut S, &P) + 'static,
+    ) -> Self {
+        self.test(name, move |state| {
+            let parameter = P::create();
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| body(state, &parameter)));
+            let Err(payload) = outcome else {
+                return;
+            };
+            // Failing again with a disabled assertion would turn the failure into a pass.
+            if report::assertions_disabled() {
+                panic::resume_unwind(payload);
+            }
+            let failure = panic_report(payload.as_ref());
+            let hint = match &failure.hint {
+                Some(hint) => format!("{}\n{}", hint.trim_end(), parameter.describe()),
+                None => parameter.describe(),
+            };
+            report::fail(failure.hint(hint));
+        })
+    }
+
+    /// Adds a known-flaky test that runs up to a number of times until it passes.  The hooks run around every
+    /// attempt.  A test that passes after failing is reported as [`TestOutcome::FlakyPassed`], and one that fails
+    /// every attempt with the failure of its last attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `tags` - The tags of the test, e.g. `&["network"]`.
+    /// * `attempts` - The most times the test runs, including the first; `0` runs it once.
+    /// * `body` - The test, given the shared state.
+    ///
+    pub fn retried_test(
+        mut self,
+        name: impl Into<String>,
+        tags: &[&str],
+        attempts: u32,
+        body: impl FnMut(&mut S) + 'static,
+    ) -> Self {
+        self.tests.push(Test {
+            name: name.into(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            body: Body::Retried(attempts.max(1), Box::new(body)),
+        });
+        self
+    }
+
+    /// Adds an assertion about the suite as a whole, run after every test, such as
+    /// [`assert_all_error_kinds_covered`](crate::assert_all_error_kinds_covered).  It is reported as a test of its
+    /// own, and skipped when the filter excluded any test, as the suite did not fully run.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the assertion in the report.
+    /// * `assertion` - The assertion, which fails when it panics.
+    ///
+    pub fn suite_assertion(mut self, name: impl Into<String>, assertion: impl FnOnce() + 'static) -> Self {
+        self.suite_assertions.push((name.into(), Box::new(assertion)));
+        self
+    }
+
+    /// Selects the tests to run with the filter, instead of reading it from the environment and arguments.
+    pub fn filter(mut self, filter: TestFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sets whether the suite stops at the first failure, instead of reading it from the environment and arguments.
+    pub fn mode(mut self, mode: ExecutionMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets whether the suite ends with an audit of its assertions, instead of reading it from the
+    /// [`ASSERTION_AUDIT_ENV_VAR`](super::ASSERTION_AUDIT_ENV_VAR) environment variable and the `--assertion-audit`
+    /// argument.
+    ///
+    /// The audit builds and runs the test binary again with every assertion of the framework disabled, and reports
+    /// the tests that still pass without evaluating one, which assert nothing the framework could fail them on.  It
+    /// is reported as a test of its own, `assertion_audit`, see the [module documentation](super).
+    pub fn assertion_audit(mut self, audit: bool) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Runs the suite, prints the results like libtest, writes the JUnit report when
+    /// [`JUNIT_OUTPUT_ENV_VAR`](crate::reporting::JUNIT_OUTPUT_ENV_VAR) is set, and returns the exit code for `main`.
+    pub fn run(self) -> ExitCode {
+        self.execute().finish()
+    }
+
+    /// Runs the suite and returns the results without printing a summary.
+    pub fn execute(self) -> JUnitReport {
+        let TestHarness {
+            name,
+            before_all,
+            after_all,
+            mut before_each,
+            mut after_each,
+            tests,
+            suite_assertions,
+            filter,
+            mode,
+            audit,
+        } = self;
+        let mut report = JUnitReport::new(name);
+        let filter = filter.unwrap_or_else(TestFilter::from_env);
+        let mode = mode.unwrap_or_else(ExecutionMode::from_env);
+        let total = tests.len();
+        let tests: Vec<Test<S>> = tests
+            .into_iter()
+            .filter(|test| filter.matches(&test.name, &test.tags))
+            .collect();
+        let selected = tests.len();
+        println!("\nrunning {} tests ({} filtered out)", tests.len(), total - tests.len());
+        if tests.is_empty() {
+            return report;
+        }
+
+        let mut state = None;
+        if let Err(failure) = run_test(|| state = Some(before_all())) {
+            for test in tests {
+                println!("test {} ... FAILED", test.name);
+                report.record(test.name, Duration::ZERO, Err(hook_failure("before_all", &failure)));
+            }
+            return report;
+        }
+        let mut state = state.unwrap();
+
+        let mut passed: Vec<AuditedTest> = Vec::new();
+        let mut stopped = false;
+        for test in tests {
+            if stopped {
+                println!("test {} ... ignored", test.name);
+                report.skip(test.name, "An earlier test failed and the suite fails fast.");
+                continue;
+            }
+            let start = Instant::now();
+            let mut attempt = |body: &mut dyn FnMut(&mut S)| {
+                let mut result = run_test(|| before_each.iter_mut().for_each(|hook| hook(&mut state)))
+                    .map_err(|failure| hook_failure("before_each", &failure));
+                let mut evaluated = 0;
+                if result.is_ok() {
+                    report::take_evaluated_assertions();
+                    result = run_test(|| body(&mut state));
+                    evaluated = report::take_evaluated_assertions();
+                }
+                let teardown = run_test(|| after_each.iter_mut().for_each(|hook| hook(&mut state)));
+                if let (Ok(()), Err(failure)) = (&result, teardown) {
+                    result = Err(hook_failure("after_each", &failure));
+                }
+                (result, evaluated)
+            };
+
+            let mut failed_attempts = Vec::new();
+            let (result, evaluated) = match test.body {
+                Body::Once(body) => {
+                    let mut body = Some(body);
+                    attempt(&mut |state| {
+                        if let Some(body) = body.take() {
+                            body(state);
+                        }
+                    })
+                }
+                Body::Retried(attempts, mut body) => loop {
+                    match attempt(&mut body) {
+                        (Err(failure), _) if failed_attempts.len() + 1 < attempts as usize => {
+                            failed_attempts.push(failure)
+                        }
+                        outcome => break outcome,
+                    }
+                },
+            };
+            if result.is_ok() {
+                passed.push((test.name.clone(), evaluated));
+            }
+            stopped = result.is_err() && mode == ExecutionMode::FailFast;
+            match result {
+                Ok(()) if !failed_attempts.is_empty() => {
+                    println!("test {} ... flaky-pass", test.name);
+                    report.add(TestCase {
+                        name: test.name,
+                        classname: report.name().to_string(),
+                        duration: start.elapsed(),
+                        outcome: TestOutcome::FlakyPassed(failed_attempts),
+                    });
+                }
+                result => {
+                    println!("test {} ... {}", test.name, if result.is_ok() { "ok" } else { "FAILED" });
+                    report.record(test.name, start.elapsed(), result);
+                }
+            }
+        }
+
+        let complete = selected == total;
+        for (name, assertion) in suite_assertions {
+            if !complete || stopped {
+                println!("test {} ... ignored", name);
+                let reason = if stopped {
+                    "A test failed and the suite fails fast."
+                } else {
+                    "The filter excluded tests of the suite."
+                };
+                report.skip(name, reason);
+                continue;
+            }
+            let start = Instant::now();
+            let result = run_test(assertion);
+            println!("test {} ... {}", name, if result.is_ok() { "ok" } else { "FAILED" });
+            report.record(name, start.elapsed(), result);
+        }
+
+        if let Some(after_all) = after_all {
+            let start = Instant::now();
+            if let Err(failure) = run_test(|| after_all(&mut state)) {
+                report.add(TestCase {
+                    name: "after_all".to_string(),
+                    classname: report.name().to_string(),
+                    duration: start.elapsed(),
+                    outcome: TestOutcome::Failed(hook_failure("after_all", &failure)),
+                });
+            }
+        }
+
+        // The audit build checks the tests it ran itself, and the regular build starts it.
+        let result = if report::assertions_disabled() {
+            audit::write_results(&passed);
+            Some(audit::check_passed(&passed))
+        } else if audit.unwrap_or_else(audit::requested) {
+            Some(audit::run())
+        } else {
+            None
+        };
+        if let Some(result) = result {
+            println!("test assertion_audit ... {}", if result.is_ok() { "ok" } else { "FAILED" });
+            report.record("assertion_audit", Duration::ZERO, result);
+        }
+        report
+    }
+}
+
+/// Describes the failure of a hook, keeping the hook's report as the hint.
+fn hook_failure(hook: &str, failure: &MismatchReport) -> MismatchReport {
+    MismatchReport::new(hook, format!("The {} hook failed:", hook)).hint(failure.to_string())
+}
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub mod multipart;
+#[cfg(feature = "tls")]
+pub mod tls;
+
+/// The response a [`HttpStub`] sends for a route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub latency: Duration,
+}
+
+impl StubResponse {
+    /// Creates a response with the status, no headers, an empty body, and no latency.
+    pub fn new(status: u16) -> Self {
+        StubResponse {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Adds a header to the response.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the body of the response.
+    pub fn body(mut self, body: impl AsRef<[u8]>) -> Self {
+        self.body = body.as_ref().to_vec();
+        self
+    }
+
+    /// Sets how long the server waits before sending the response.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+}
+
+/// A request received by a [`HttpStub`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The path of the request, without the query string.
+    pub path: String,
+    /// The query string, without the leading `?`, or empty.
+    pub query: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Returns the value of the first header with the name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(&self.headers, name)
+    }
+
+    /// Returns the body as UTF-8 text.  Invalid UTF-8 is replaced, as this is only used for assertions.
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Answers a request that matches no route.
+pub(crate) type Fallback = Arc<dyn Fn(&RecordedRequest) -> StubResponse + Send + Sync>;
+
+#[derive(Default)]
+struct State {
+    routes: Mutex<HashMap<(String, String), StubResponse>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+    fallback: Mutex<Option<Fallback>>,
+}
+
+/// A stub HTTP/1.1 server listening on a random localhost port.
+///
+/// Tests register a [`StubResponse`] per method and path; every request is recorded for later assertions, and a
+/// request with no route gets a `404`.  The server shuts down when it is dropped.  With the `tls` feature,
+/// [`HttpStub::start_tls`] serves HTTPS instead; see [`tls`].
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::http_stub::{HttpStub, StubResponse};
+///
+/// let server = HttpStub::start();
+/// server.route("GET", "/evidence/1", StubResponse::new(200).body("{}"));
+///
+/// let url = format!("{}/evidence/1", server.url());
+/// ```
+pub struct HttpStub {
+    address: SocketAddr,
+    scheme: &'static str,
+    ca_cert_pem: Option<String>,
+    state: Arc<State>,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl HttpStub {
+    /// Starts the server on a random localhost port.
+    pub fn start() -> Self {
+        HttpStub::serve("http", None, |mut stream, state| handle(&mut stream, state))
+    }
+
+    /// Starts the server on a random localhost port, accepting each connection with the function.
+    fn serve(
+        scheme: &'static str,
+        ca_cert_pem: Option<String>,
+        accept: impl Fn(TcpStream, &State) + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind the stub HTTP server.");
+        let address = listener
+            .local_addr()
+            .expect("Could not get the address of the stub HTTP server.");
+        let state = Arc::new(State::default());
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let accept = Arc::new(accept);
+
+        let worker = {
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let state = Arc::clone(&state);
+                        let accept = Arc::clone(&accept);
+                        thread::spawn(move || accept(stream, &state));
+                    }
+                }
+            })
+        };
+
+        HttpStub {
+            address,
+            scheme,
+            ca_cert_pem,
+            state,
+            shutdown,
+            worker: Some(worker),
+        }
+    }
+
+    /// Returns the address the server listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns the base URL of the server, e.g. `http://127.0.0.1:49152`, or `https://127.0.0.1:49152` when it
+    /// serves HTTPS.
+    pub fn url(&self) -> String {
+        format!("{}://{}", self.scheme, self.address)
+    }
+
+    /// Returns the PEM certificate that clients trust to connect to the server, or [`None`] when it serves plain
+    /// HTTP.  This is the certificate of the CA that issued the server's certificate, or the server's certificate
+    /// itself when it is self-signed.
+    pub fn ca_cert_pem(&self) -> Option<&str> {
+        self.ca_cert_pem.as_deref()
+    }
+
+    /// Registers the response for the method and path, replacing any existing route.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method, e.g. `GET`.
+    /// * `path` - The path of the request, without the query string.
+    /// * `response` - The response sent for matching requests.
+    ///
+    pub fn route(&self, method: &str, path: &str, response: StubResponse) {
+        self.state
+            .routes
+            .lock()
+            .unwrap()
+            .insert((method.to_uppercase(), path.to_string()), response);
+    }
+
+    /// Answers every request that matches no route with the function, instead of a `404`.
+    pub(crate) fn fallback(&self, fallback: impl Fn(&RecordedRequest) -> StubResponse + Send + Sync + 'static) {
+        *self.state.fallback.lock().unwrap() = Some(Arc::new(fallback));
+    }
+
+    /// Returns every request received, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the requests received for the method and path, in order.
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method.eq_ignore_ascii_case(method) && request.path == path)
+            .collect()
+    }
+}
+
+impl Drop for HttpStub {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the shutdown flag.
+        let _ = TcpStream::connect(self.address);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn handle(stream: &mut (impl Read + Write), state: &State) {
+    let request = match read_request(stream) {
+        Ok(request) => request,
+        Err(_) => return,
+    };
+
+    let route = state
+        .routes
+        .lock()
+        .unwrap()
+        .get(&(request.method.to_uppercase(), request.path.clone()))
+        .cloned();
+    // The fallback may forward to a real upstream, so it runs without the lock held.
+    let fallback = state.fallback.lock().unwrap().clone();
+    let response = route.unwrap_or_else(|| match fallback {
+        Some(fallback) => fallback(&request),
+        None => StubResponse::new(404).body(format!("No stub route for {} {}", request.method, request.path)),
+    });
+    let request_method = request.method.clone();
+    state.requests.lock().unwrap().push(request);
+
+    if !response.latency.is_zero() {
+        thread::sleep(response.latency);
+    }
+    let _ = write_response(stream, &request_method, &response);
+}
+
+/// Reads one HTTP/1.1 request, with a body given by `Content-Length` or chunked transfer encoding.
+pub(crate) fn read_request(stream: &mut impl Read) -> std::io::Result<RecordedRequest> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (target.to_string(), String::new()),
+    };
+
+    let headers = read_headers(&mut reader)?;
+    let body = read_body(&mut reader, &headers, false)?;
+    Ok(RecordedRequest {
+        method,
+        path,
+        query,
+        headers,
+        body,
+    })
+}
+
+/// Reads the header lines up to the blank line that ends the head of a request or response.
+pub(crate) fn read_headers(reader: &mut impl BufRead) -> std::io::Result<Vec<(String, String)>> {
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    Ok(headers)
+}
+
+/// Reads a body given by chunked transfer encoding or `Content-Length`.  Otherwise the body is empty, or, when
+/// `to_end` is true as for a response, everything until the connection is closed.
+pub(crate) fn read_body(
+    reader: &mut impl BufRead,
+    headers: &[(String, String)],
+    to_end: bool,
+) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    if header(headers, "Transfer-Encoding").is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked")) {
+        loop {
+            let mut size_line = String::new();
+            if reader.read_line(&mut size_line)? == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "The chunked body ended before its last chunk.",
+                ));
+            }
+            let size = size_line.trim().split(';').next().unwrap_or_default();
+            let size = usize::from_str_radix(size.trim(), 16).map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Invalid chunk size line '{}'", size_line.trim_end()),
+                )
+            })?;
+            if size == 0 {
+                // The last chunk is followed by optional trailer fields and a blank line.
+                read_headers(reader)?;
+                break;
+            }
+            let mut chunk = vec![0; size + 2];
+            reader.read_exact(&mut chunk)?;
+            body.extend_from_slice(&chunk[..size]);
+        }
+    } else if let Some(length) = header(headers, "Content-Length").and_then(|length| length.parse().ok()) {
+        body = vec![0; length];
+        reader.read_exact(&mut body)?;
+    } else if to_end {
+        reader.read_to_end(&mut body)?;
+    }
+    Ok(body)
+}
+
+/// Returns the value of the first header with the name, compared case-insensitively.
+pub(crate) fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// The headers that frame a message body, which [`write_response`] writes itself.
+const FRAMING_HEADERS: &[&str] = &["Content-Length", "Transfer-Encoding", "Connection"];
+
+/// Returns true when the response with the status, to a request with the method, has no body whatever its headers
+/// say: the response to a `HEAD`, and a `1xx`, `204`, or `304` response.
+pub(crate) fn bodiless(method: &str, status: u16) -> bool {
+    method.eq_ignore_ascii_case("HEAD") || matches!(status, 100..=199 | 204 | 304)
+}
+
+/// Writes the response to a request with the method.  The stub frames the body itself, so the response's own
+/// `Content-Length`, `Transfer-Encoding`, and `Connection` headers are not written, and a bodiless response has
+/// neither a body nor a length.
+pub(crate) fn write_response(stream: &mut impl Write, method: &str, response: &StubResponse) -> std::io::Result<()> {
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason(response.status));
+    for (name, value) in &response.headers {
+        if !FRAMING_HEADERS.iter().any(|framing| framing.eq_ignore_ascii_case(name)) {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    let bodiless = bodiless(method, response.status);
+    if !bodiless {
+        head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    }
+    head.push_str("Connection: close\r\n\r\n");
+    stream.write_all(head.as_bytes())?;
+    if !bodiless {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
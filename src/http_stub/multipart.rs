@@ -0,0 +1,267 @@
+//! Parsing and assertions for `multipart/form-data` requests, such as the evidence uploads of the gateways.
+//!
+//! ```no_run
+//! use attestify_test_framework::http_stub::HttpStub;
+//! use attestify_test_framework::http_stub::multipart::ExpectedPart;
+//!
+//! let server = HttpStub::start();
+//! // run the gateway that uploads evidence to server.url()
+//!
+//! server.requests_to("POST", "/evidence")[0]
+//!     .assert_part("evidence", ExpectedPart::new().content_type("application/json").body_contains("\"p1\""))
+//!     .assert_part("signature", ExpectedPart::new().filename("evidence.sig"));
+//! ```
+
+use super::{RecordedRequest, header};
+use crate::assertions::report::{self, MismatchReport};
+
+/// A part of a `multipart/form-data` body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    /// The `name` of the `Content-Disposition` header.
+    pub name: String,
+    /// The `filename` of the `Content-Disposition` header, for a file.
+    pub filename: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Part {
+    /// Returns the value of the first header with the name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(&self.headers, name)
+    }
+
+    /// Returns the `Content-Type` header of the part.
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("Content-Type")
+    }
+
+    /// Returns the body as UTF-8 text.  Invalid UTF-8 is replaced, as this is only used for assertions.
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// What [`RecordedRequest::assert_part`] expects of a part.  Only what is set is checked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpectedPart {
+    content_type: Option<String>,
+    filename: Option<String>,
+    body: Option<Vec<u8>>,
+    body_contains: Vec<String>,
+}
+
+impl ExpectedPart {
+    /// Creates an expectation that any part with the name meets.
+    pub fn new() -> Self {
+        ExpectedPart::default()
+    }
+
+    /// Expects the `Content-Type` of the part, compared case-insensitively.  Parameters such as `charset` are only
+    /// compared when the expected content type has them.
+    pub fn content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Expects the part to be a file with the name.
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.filename = Some(filename.to_string());
+        self
+    }
+
+    /// Expects the body of the part to be exactly the bytes.
+    pub fn body(mut self, body: impl AsRef<[u8]>) -> Self {
+        self.body = Some(body.as_ref().to_vec());
+        self
+    }
+
+    /// Expects the body of the part to contain the text.  Can be called more than once.
+    pub fn body_contains(mut self, text: &str) -> Self {
+        self.body_contains.push(text.to_string());
+        self
+    }
+}
+
+impl RecordedRequest {
+    /// Parses the body as `multipart/form-data`, with the boundary of the `Content-Type` header, returning why it
+    /// cannot be parsed on failure.
+    pub fn parts(&self) -> Result<Vec<Part>, String> {
+        let content_type = self
+            .header("Content-Type")
+            .ok_or("The request has no Content-Type header.")?;
+        let (media_type, parameters) = content_type.split_once(';').unwrap_or((content_type, ""));
+        if !media_type.trim().eq_ignore_ascii_case("multipart/form-data") {
+            return Err(format!("The Content-Type '{}' is not multipart/form-data.", content_type));
+        }
+        let boundary = parameter(parameters, "boundary")
+            .ok_or_else(|| format!("The Content-Type '{}' has no boundary.", content_type))?;
+        parse(&self.body, &boundary)
+    }
+
+    /// Returns the first part with the name, or [`None`] when there is none or the body is not multipart.
+    pub fn part(&self, name: &str) -> Option<Part> {
+        self.parts().ok()?.into_iter().find(|part| part.name == name)
+    }
+
+    /// Checks the request has a part with the name that meets the expectation, like
+    /// [`RecordedRequest::assert_part`], returning a [`MismatchReport`] instead of panicking.
+    pub fn check_part(&self, name: &str, expected: &ExpectedPart) -> Result<(), MismatchReport> {
+        let parts = self.parts().map_err(|reason| {
+            MismatchReport::new(name, "The request body is not multipart/form-data.").hint(format!("\t{}", reason))
+        })?;
+        let Some(part) = parts.iter().find(|part| part.name == name) else {
+            return Err(
+                MismatchReport::new(name, format!("The request has no part named '{}'.", name)).hint(list_parts(&parts))
+            );
+        };
+
+        if let Some(content_type) = &expected.content_type
+            && !content_type_matches(part.content_type(), content_type)
+        {
+            return Err(mismatch(name, "content type", part)
+                .expected(content_type)
+                .actual(part.content_type().unwrap_or("<none>")));
+        }
+        if let Some(filename) = &expected.filename
+            && part.filename.as_ref() != Some(filename)
+        {
+            return Err(mismatch(name, "filename", part)
+                .expected(filename)
+                .actual(part.filename.as_deref().unwrap_or("<none>")));
+        }
+        if let Some(body) = &expected.body
+            && &part.body != body
+        {
+            return Err(mismatch(name, "body", part)
+                .expected(format!("{:?}", String::from_utf8_lossy(body)))
+                .actual(format!("{:?}", part.body_text())));
+        }
+        let body = part.body_text();
+        if let Some(text) = expected.body_contains.iter().find(|text| !body.contains(text.as_str())) {
+            return Err(
+                MismatchReport::new(name, format!("The body of the part '{}' does not contain the expected text.", name))
+                    .expected(format!("{:?}", text))
+                    .actual(format!("{:?}", body)),
+            );
+        }
+        Ok(())
+    }
+
+    /// Asserts the request has a part with the name that meets the expectation.  On failure it lists the parts of
+    /// the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The `name` of the part's `Content-Disposition` header.
+    /// * `expected` - What the part must have, e.g. `ExpectedPart::new().content_type("application/json")`.
+    ///
+    pub fn assert_part(&self, name: &str, expected: ExpectedPart) -> &Self {
+        report::enforce(self.check_part(name, &expected));
+        self
+    }
+}
+
+fn mismatch(name: &str, property: &str, part: &Part) -> MismatchReport {
+    MismatchReport::new(
+        format!("{} {}", name, property),
+        format!("The {} of the part '{}' does not match.", property, name),
+    )
+    .hint(format!("\tHeaders:\n{}", render_headers(&part.headers)))
+}
+
+fn content_type_matches(actual: Option<&str>, expected: &str) -> bool {
+    let Some(actual) = actual else {
+        return false;
+    };
+    let actual = if expected.contains(';') {
+        actual
+    } else {
+        actual.split(';').next().unwrap_or_default()
+    };
+    normalize(actual).eq_ignore_ascii_case(&normalize(expected))
+}
+
+fn normalize(content_type: &str) -> String {
+    content_type.split(';').map(str::trim).collect::<Vec<_>>().join("; ")
+}
+
+fn render_headers(headers: &[(String, String)]) -> String {
+    if headers.is_empty() {
+        return "\t\t<none>".to_string();
+    }
+    headers
+        .iter()
+        .map(|(name, value)| format!("\t\t{}: {}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list_parts(parts: &[Part]) -> String {
+    if parts.is_empty() {
+        return "\tParts:\n\t\t<none>".to_string();
+    }
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|part| {
+            format!(
+                "\t\t{} ({}, {} bytes)",
+                part.name,
+                part.content_type().unwrap_or("no content type"),
+                part.body.len()
+            )
+        })
+        .collect();
+    format!("\tParts:\n{}", parts.join("\n"))
+}
+
+/// Returns the value of the parameter from `; name=value` pairs, without quotes.
+fn parameter(parameters: &str, name: &str) -> Option<String> {
+    parameters.split(';').find_map(|parameter| {
+        let (key, value) = parameter.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, String> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut position = find(body, &delimiter, 0)
+        .ok_or_else(|| format!("The body has no boundary '{}'.", boundary))?
+        + delimiter.len();
+
+    let mut parts = Vec::new();
+    loop {
+        if body[position..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        let head_start = find(body, b"\r\n", position).ok_or("A boundary is not followed by a line break.")? + 2;
+        let head_end = find(body, b"\r\n\r\n", head_start).ok_or("A part has no blank line after its headers.")?;
+        let body_end = find(body, &[b"\r\n".as_slice(), &delimiter].concat(), head_end + 4)
+            .ok_or_else(|| format!("The body does not end with the boundary '{}--'.", boundary))?;
+
+        let headers: Vec<(String, String)> = String::from_utf8_lossy(&body[head_start..head_end])
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        let disposition = header(&headers, "Content-Disposition").ok_or("A part has no Content-Disposition header.")?;
+        parts.push(Part {
+            name: parameter(disposition, "name").unwrap_or_default(),
+            filename: parameter(disposition, "filename"),
+            body: body[head_end + 4..body_end].to_vec(),
+            headers,
+        });
+        position = body_end + 2 + delimiter.len();
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|index| from + index)
+}
@@ -0,0 +1,200 @@
+//! HTTPS for the [`HttpStub`], with certificates issued per test by a throwaway CA, so gateways that enforce TLS
+//! are tested offline.
+//!
+//! [`HttpStub::start_tls`] serves a certificate for [`LOCALHOST`] issued by a new [`TestCa`], and clients trust the
+//! CA certificate from [`HttpStub::ca_cert_pem`].  Negative tests start the stub with a certificate the client must
+//! reject: one that is expired, issued for another host, or self-signed.
+//!
+//! ```no_run
+//! use attestify_test_framework::http_stub::HttpStub;
+//! use attestify_test_framework::http_stub::tls::{LOCALHOST, ServerCertificate, TestCa};
+//!
+//! let server = HttpStub::start_tls();
+//! let ca_pem = server.ca_cert_pem().unwrap();
+//! // configure the gateway with server.url() and ca_pem, then assert the request succeeds
+//!
+//! let ca = TestCa::new();
+//! let expired = HttpStub::start_tls_with(ca.expired_server_certificate(LOCALHOST));
+//! let wrong_host = HttpStub::start_tls_with(ca.server_certificate(&["evidence.example.com"]));
+//! let untrusted = HttpStub::start_tls_with(ServerCertificate::self_signed(LOCALHOST));
+//! // configure the gateway to trust ca.cert_pem(), then assert each server is rejected
+//! ```
+//!
+//! Requires the `tls` feature.
+
+use super::{HttpStub, handle};
+use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, Issuer, KeyPair, KeyUsagePurpose, date_time_ymd};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ClientConfig, RootCertStore, ServerConfig, ServerConnection, StreamOwned};
+use std::io::Write;
+use std::sync::Arc;
+
+/// The hosts the stub server is reached at, which certificates are issued for by default.
+pub const LOCALHOST: &[&str] = &["localhost", "127.0.0.1"];
+
+/// A certificate authority with a key generated when it is created, which issues server certificates.
+pub struct TestCa {
+    issuer: Issuer<'static, KeyPair>,
+    cert_pem: String,
+}
+
+impl TestCa {
+    /// Creates a CA named `Attestify Test CA` with a new key.
+    pub fn new() -> Self {
+        let mut params = CertificateParams::default();
+        params.distinguished_name.push(DnType::CommonName, "Attestify Test CA");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let key = KeyPair::generate().expect("Could not generate the key of the test CA.");
+        let cert = params
+            .self_signed(&key)
+            .expect("Could not create the certificate of the test CA.");
+        TestCa {
+            issuer: Issuer::new(params, key),
+            cert_pem: cert.pem(),
+        }
+    }
+
+    /// Returns the PEM certificate of the CA, which clients trust to accept the certificates it issues.
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    /// Returns a client configuration that trusts only this CA, for gateways built on `rustls`.
+    pub fn client_config(&self) -> Arc<ClientConfig> {
+        client_config(&self.cert_pem)
+    }
+
+    /// Issues a valid certificate for the hosts, e.g. [`LOCALHOST`].
+    pub fn server_certificate(&self, hosts: &[&str]) -> ServerCertificate {
+        self.issue(server_params(hosts))
+    }
+
+    /// Issues a certificate for the hosts that expired on 1 January 2001.
+    pub fn expired_server_certificate(&self, hosts: &[&str]) -> ServerCertificate {
+        let mut params = server_params(hosts);
+        params.not_before = date_time_ymd(2000, 1, 1);
+        params.not_after = date_time_ymd(2001, 1, 1);
+        self.issue(params)
+    }
+
+    fn issue(&self, params: CertificateParams) -> ServerCertificate {
+        let key = KeyPair::generate().expect("Could not generate the key of the server certificate.");
+        let cert = params
+            .signed_by(&key, &self.issuer)
+            .expect("Could not issue the server certificate.");
+        ServerCertificate {
+            cert_pem: cert.pem(),
+            key_pem: key.serialize_pem(),
+            ca_cert_pem: self.cert_pem.clone(),
+        }
+    }
+}
+
+impl Default for TestCa {
+    fn default() -> Self {
+        TestCa::new()
+    }
+}
+
+/// A server certificate and its private key, created with a [`TestCa`] or [`ServerCertificate::self_signed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerCertificate {
+    cert_pem: String,
+    key_pem: String,
+    ca_cert_pem: String,
+}
+
+impl ServerCertificate {
+    /// Creates a certificate for the hosts that is signed by its own key, so no CA vouches for it.
+    pub fn self_signed(hosts: &[&str]) -> Self {
+        let key = KeyPair::generate().expect("Could not generate the key of the server certificate.");
+        let cert = server_params(hosts)
+            .self_signed(&key)
+            .expect("Could not create the self-signed server certificate.");
+        ServerCertificate {
+            cert_pem: cert.pem(),
+            key_pem: key.serialize_pem(),
+            ca_cert_pem: cert.pem(),
+        }
+    }
+
+    /// Returns the PEM certificate.
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    /// Returns the PEM PKCS #8 private key.
+    pub fn key_pem(&self) -> &str {
+        &self.key_pem
+    }
+
+    /// Returns the PEM certificate of the CA that issued this certificate, or this certificate when it is
+    /// self-signed.
+    pub fn ca_cert_pem(&self) -> &str {
+        &self.ca_cert_pem
+    }
+
+    fn server_config(&self) -> Arc<ServerConfig> {
+        let cert = CertificateDer::from_pem_slice(self.cert_pem.as_bytes()).expect("The certificate is not valid PEM.");
+        let key = PrivatePkcs8KeyDer::from_pem_slice(self.key_pem.as_bytes()).expect("The key is not valid PEM.");
+        let config = ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .expect("The TLS protocol versions are not supported.")
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], PrivateKeyDer::Pkcs8(key))
+            .expect("Could not configure the stub server's certificate.");
+        Arc::new(config)
+    }
+}
+
+/// Returns a client configuration that trusts only the PEM certificate, e.g. from [`HttpStub::ca_cert_pem`], for
+/// gateways built on `rustls`.
+pub fn client_config(ca_cert_pem: &str) -> Arc<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(ca_cert_pem.as_bytes()) {
+        roots
+            .add(cert.expect("The CA certificate is not valid PEM."))
+            .expect("Could not trust the CA certificate.");
+    }
+    let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .expect("The TLS protocol versions are not supported.")
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Arc::new(config)
+}
+
+fn server_params(hosts: &[&str]) -> CertificateParams {
+    let mut params = CertificateParams::new(hosts.iter().map(|host| host.to_string()).collect::<Vec<_>>())
+        .expect("The hosts are not valid certificate names.");
+    params
+        .distinguished_name
+        .push(DnType::CommonName, hosts.first().copied().unwrap_or("localhost"));
+    params
+}
+
+impl HttpStub {
+    /// Starts the server on a random localhost port, serving HTTPS with a certificate for [`LOCALHOST`] issued by
+    /// a new [`TestCa`].  Clients trust [`HttpStub::ca_cert_pem`].
+    pub fn start_tls() -> Self {
+        HttpStub::start_tls_with(TestCa::new().server_certificate(LOCALHOST))
+    }
+
+    /// Starts the server on a random localhost port, serving HTTPS with the certificate, e.g. an expired one to
+    /// test that a gateway rejects it.  A client that fails the TLS handshake is disconnected, and no request is
+    /// recorded.
+    pub fn start_tls_with(certificate: ServerCertificate) -> Self {
+        let config = certificate.server_config();
+        HttpStub::serve("https", Some(certificate.ca_cert_pem), move |stream, state| {
+            let Ok(connection) = ServerConnection::new(Arc::clone(&config)) else {
+                return;
+            };
+            let mut stream = StreamOwned::new(connection, stream);
+            handle(&mut stream, state);
+            stream.conn.send_close_notify();
+            let _ = stream.flush();
+        })
+    }
+}
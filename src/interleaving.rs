@@ -0,0 +1,130 @@
+//! Exhaustive interleaving tests with [loom](https://docs.rs/loom), for the lock ordering of the kernel's shared
+//! state, such as the evidence cache.
+//!
+//! A model runs its closure once for every interleaving of the threads it spawns with [`thread::spawn`], so a
+//! deadlock or a broken invariant is found deterministically instead of by chance.  The code under test must use
+//! loom's primitives, re-exported as [`sync`], when built with `--cfg loom`, and so do the doubles of this crate:
+//!
+//! ```sh
+//! RUSTFLAGS="--cfg loom" cargo test --release --features attestify_test_framework/loom --test interleavings
+//! ```
+//!
+//! ```no_run
+//! use attestify_test_framework::doubles::InMemoryEventBus;
+//! use attestify_test_framework::interleaving::{assert_interleavings, sync::Arc, thread};
+//! # #[derive(Debug, Clone)] struct Error;
+//! # #[derive(Debug)] struct EvidenceCollected;
+//!
+//! assert_interleavings(|| {
+//!     let bus = Arc::new(InMemoryEventBus::<Error>::new());
+//!     let publisher = {
+//!         let bus = bus.clone();
+//!         thread::spawn(move || bus.publish(EvidenceCollected).unwrap())
+//!     };
+//!     bus.publish(EvidenceCollected).unwrap();
+//!     publisher.join().unwrap();
+//!     bus.assert_published_times::<EvidenceCollected>(2);
+//! });
+//! ```
+//!
+//! Without `--cfg loom` the doubles use std's primitives, which still work inside a model, but which the model
+//! cannot see, so their locks are not part of the explored interleavings.
+
+use crate::assertions::report::{self, MismatchReport};
+use crate::panics::panic_message;
+use std::panic::{self, AssertUnwindSafe};
+
+pub use loom::{sync, thread};
+
+/// Returns true when the doubles use loom's primitives, i.e. the crate was built with `--cfg loom`.
+pub fn doubles_are_modelled() -> bool {
+    cfg!(loom)
+}
+
+/// How much of the interleaving space a model explores.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Exploration {
+    /// The most times a thread may be preempted in one interleaving, or [`None`] for every interleaving.  A bound of
+    /// 2 or 3 finds most bugs in a fraction of the time.
+    pub preemption_bound: Option<usize>,
+    /// The most branches of one interleaving, or [`None`] for loom's default.
+    pub max_branches: Option<usize>,
+}
+
+impl Exploration {
+    /// Explores every interleaving.
+    pub fn exhaustive() -> Self {
+        Exploration::default()
+    }
+
+    /// Explores the interleavings with at most `bound` preemptions per thread.
+    pub fn bounded(bound: usize) -> Self {
+        Exploration {
+            preemption_bound: Some(bound),
+            max_branches: None,
+        }
+    }
+
+    fn builder(&self) -> loom::model::Builder {
+        let mut builder = loom::model::Builder::new();
+        if let Some(bound) = self.preemption_bound {
+            builder.preemption_bound = Some(bound);
+        }
+        if let Some(max_branches) = self.max_branches {
+            builder.max_branches = max_branches;
+        }
+        builder
+    }
+}
+
+/// Checks the model passes in every explored interleaving, like [`assert_interleavings_with`], returning a
+/// [`MismatchReport`] instead of panicking.
+pub fn check_interleavings_with<F>(exploration: &Exploration, model: F) -> Result<(), MismatchReport>
+where
+    F: Fn() + Sync + Send + 'static,
+{
+    let builder = exploration.builder();
+    match panic::catch_unwind(AssertUnwindSafe(|| builder.check(model))) {
+        Ok(()) => Ok(()),
+        Err(payload) => Err(MismatchReport::new(
+            "interleaving",
+            "The model failed in one of its interleavings.",
+        )
+        .actual(panic_message(payload.as_ref()))
+        .hint(format!(
+            "\tReproduce:\n\t\tRerun with LOOM_LOG=trace to trace the failing interleaving{}.",
+            if doubles_are_modelled() {
+                ""
+            } else {
+                ", and with RUSTFLAGS=\"--cfg loom\" so the doubles are modelled too"
+            }
+        ))),
+    }
+}
+
+/// Runs the model once for every interleaving of the threads it spawns, and fails the test with the first
+/// interleaving that panics.
+///
+/// Loom prints the failing interleaving as it panics.  A deadlock cannot be unwound, so loom reports it and aborts
+/// the test process instead.
+///
+/// # Arguments
+///
+/// * `exploration` - How much of the interleaving space to explore, e.g. [`Exploration::bounded`].
+/// * `model` - The scenario, which spawns its threads with [`thread::spawn`] and creates its shared state inside
+///   the closure, as it runs again for every interleaving.
+///
+pub fn assert_interleavings_with<F>(exploration: &Exploration, model: F)
+where
+    F: Fn() + Sync + Send + 'static,
+{
+    report::enforce(check_interleavings_with(exploration, model));
+}
+
+/// Runs the model for every interleaving, see [`assert_interleavings_with`].
+pub fn assert_interleavings<F>(model: F)
+where
+    F: Fn() + Sync + Send + 'static,
+{
+    assert_interleavings_with(&Exploration::exhaustive(), model);
+}
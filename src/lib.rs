@@ -1,2 +1,44 @@
+#[cfg(feature = "allocations")]
+pub mod allocations;
 pub mod assertions;
-pub mod nape_filesystem;
\ No newline at end of file
+#[cfg(unix)]
+pub mod capture;
+pub mod cli;
+pub mod concurrency;
+pub mod contracts;
+pub mod diff;
+pub mod doubles;
+pub mod env_guard;
+pub mod fixtures;
+pub mod fuzz_corpus;
+pub mod harness;
+pub mod http_stub;
+#[cfg(feature = "loom")]
+pub mod interleaving;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod logs;
+pub mod matchers;
+pub mod nape_filesystem;
+pub mod nape_test;
+pub mod panics;
+pub mod perf;
+pub mod reporting;
+pub mod scenario;
+pub mod slow_server;
+pub mod snapshot;
+pub mod soft;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod sync;
+pub mod temp_file;
+pub mod testdata;
+pub mod timing;
+pub mod vcr;
+pub mod workspace;
+
+pub use attestify_test_framework_macros::nape_test;
+
+#[doc(hidden)]
+pub use regex;
+#[doc(hidden)]
+pub use serde_json;
\ No newline at end of file
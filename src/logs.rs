@@ -0,0 +1,295 @@
+//! Capture of `log` and `tracing` events emitted during a test.
+//!
+//! Enable the `log` feature to capture [`log`] records and the `tracing` feature to capture [`tracing`] events.
+//! Capture is per thread: a [`CapturedLogs`] only records events emitted on the thread that started it, so tests
+//! running in parallel do not see each other's events.
+
+use crate::assertions::report::{self, MismatchReport};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The severity of a captured event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Level(u8);
+
+impl Level {
+    pub const ERROR: Level = Level(1);
+    pub const WARN: Level = Level(2);
+    pub const INFO: Level = Level(3);
+    pub const DEBUG: Level = Level(4);
+    pub const TRACE: Level = Level(5);
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match *self {
+            Level::ERROR => "ERROR",
+            Level::WARN => "WARN",
+            Level::INFO => "INFO",
+            Level::DEBUG => "DEBUG",
+            _ => "TRACE",
+        };
+        f.write_str(name)
+    }
+}
+
+/// An event recorded by [`CapturedLogs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedEvent {
+    pub level: Level,
+    /// The target of the event, usually the module path it was emitted from.
+    pub target: String,
+    pub message: String,
+}
+
+impl fmt::Display for CapturedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.level, self.target, self.message)
+    }
+}
+
+type Events = Arc<Mutex<Vec<CapturedEvent>>>;
+
+/// Records the `log` and `tracing` events emitted on the current thread until it is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::logs::{CapturedLogs, Level};
+///
+/// let logs = CapturedLogs::start();
+/// // run the usecase
+/// logs.assert_contains(Level::WARN, "retrying evidence upload");
+/// ```
+pub struct CapturedLogs {
+    events: Events,
+    /// The capture this one interrupted on the thread, resumed when this one is dropped.
+    #[cfg(feature = "log")]
+    previous: Option<Events>,
+    #[cfg(feature = "tracing")]
+    _tracing: tracing::subscriber::DefaultGuard,
+}
+
+impl CapturedLogs {
+    /// Starts capturing the events emitted on the current thread.  A capture started while another is running on the
+    /// thread records the events until it is dropped, after which the other records them again.
+    ///
+    /// With the `log` feature, this installs a global logger the first time it is called; it panics if the test
+    /// binary has already installed a different logger.
+    pub fn start() -> Self {
+        let events: Events = Arc::new(Mutex::new(Vec::new()));
+
+        CapturedLogs {
+            #[cfg(feature = "log")]
+            previous: log_capture::start(Arc::clone(&events)),
+            #[cfg(feature = "tracing")]
+            _tracing: tracing::subscriber::set_default(tracing_capture::CaptureSubscriber {
+                events: Arc::clone(&events),
+            }),
+            events,
+        }
+    }
+
+    /// Returns every event captured so far, in order.
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Returns the messages of the events captured at the level, in order.
+    pub fn messages_at(&self, level: Level) -> Vec<String> {
+        self.events()
+            .into_iter()
+            .filter(|event| event.level == level)
+            .map(|event| event.message)
+            .collect()
+    }
+
+    /// Asserts an event was captured at the level with a message containing the phrase.
+    pub fn assert_contains(&self, level: Level, phrase: &str) {
+        report::record_evaluation();
+        if self.matching(level, phrase).is_empty() {
+            report::fail(
+                MismatchReport::new("logs", format!("No {} event containing the expected phrase was captured.", level))
+                    .expected(format!("{} {:?}", level, phrase))
+                    .actual(render_events(&self.events())),
+            );
+        }
+    }
+
+    /// Asserts no event was captured at the level with a message containing the phrase.
+    pub fn assert_not_contains(&self, level: Level, phrase: &str) {
+        report::record_evaluation();
+        let matching = self.matching(level, phrase);
+        if !matching.is_empty() {
+            report::fail(
+                MismatchReport::new(
+                    "logs",
+                    format!("A {} event containing the phrase was captured, although none was expected.", level),
+                )
+                .expected(format!("no {} {:?}", level, phrase))
+                .actual(render_events(&matching)),
+            );
+        }
+    }
+
+    fn matching(&self, level: Level, phrase: &str) -> Vec<CapturedEvent> {
+        self.events()
+            .into_iter()
+            .filter(|event| event.level == level && event.message.contains(phrase))
+            .collect()
+    }
+}
+
+/// Renders the events on one line, e.g. `[WARN usecase: retrying, INFO usecase: uploaded]`.
+fn render_events(events: &[CapturedEvent]) -> String {
+    format!("[{}]", events.iter().map(ToString::to_string).collect::<Vec<String>>().join(", "))
+}
+
+impl Drop for CapturedLogs {
+    fn drop(&mut self) {
+        #[cfg(feature = "log")]
+        log_capture::restore(self.previous.take());
+    }
+}
+
+#[cfg(feature = "log")]
+mod log_capture {
+    use super::{CapturedEvent, Events, Level};
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static CURRENT: RefCell<Option<Events>> = const { RefCell::new(None) };
+    }
+
+    static INSTALL: Once = Once::new();
+
+    struct CaptureLogger;
+
+    static LOGGER: CaptureLogger = CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            CURRENT.with(|current| current.borrow().is_some())
+        }
+
+        fn log(&self, record: &log::Record) {
+            CURRENT.with(|current| {
+                if let Some(events) = current.borrow().as_ref() {
+                    events.lock().unwrap().push(CapturedEvent {
+                        level: match record.level() {
+                            log::Level::Error => Level::ERROR,
+                            log::Level::Warn => Level::WARN,
+                            log::Level::Info => Level::INFO,
+                            log::Level::Debug => Level::DEBUG,
+                            log::Level::Trace => Level::TRACE,
+                        },
+                        target: record.target().to_string(),
+                        message: record.args().to_string(),
+                    });
+                }
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Captures the events on the current thread in the list, and returns the list capturing them before.
+    pub(super) fn start(events: Events) -> Option<Events> {
+        INSTALL.call_once(|| {
+            log::set_logger(&LOGGER).expect("Could not install the log capture, another logger is already installed.");
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        CURRENT.with(|current| current.borrow_mut().replace(events))
+    }
+
+    /// Captures the events on the current thread in the list again, or stops capturing them.
+    pub(super) fn restore(previous: Option<Events>) {
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_capture {
+    use super::{CapturedEvent, Events, Level};
+    use std::fmt;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    pub(super) struct CaptureSubscriber {
+        pub(super) events: Events,
+    }
+
+    /// Collects the `message` field of an event, followed by its other fields as `name=value`.
+    #[derive(Default)]
+    struct MessageVisitor {
+        message: String,
+        fields: Vec<String>,
+    }
+
+    impl Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.message = format!("{:?}", value);
+            } else {
+                self.fields.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "message" {
+                self.message = value.to_string();
+            } else {
+                self.fields.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+    }
+
+    impl Subscriber for CaptureSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = MessageVisitor::default();
+            event.record(&mut visitor);
+            let mut message = visitor.message;
+            if !visitor.fields.is_empty() {
+                if !message.is_empty() {
+                    message.push(' ');
+                }
+                message.push_str(&visitor.fields.join(" "));
+            }
+
+            let level = *event.metadata().level();
+            self.events.lock().unwrap().push(CapturedEvent {
+                level: if level == tracing::Level::ERROR {
+                    Level::ERROR
+                } else if level == tracing::Level::WARN {
+                    Level::WARN
+                } else if level == tracing::Level::INFO {
+                    Level::INFO
+                } else if level == tracing::Level::DEBUG {
+                    Level::DEBUG
+                } else {
+                    Level::TRACE
+                },
+                target: event.metadata().target().to_string(),
+                message,
+            });
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+}
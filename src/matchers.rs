@@ -0,0 +1,548 @@
+use crate::assertions::report::{self, MismatchReport};
+use std::error::Error;
+use std::fmt::Debug;
+use std::thread;
+
+/// A fluent assertion over an [`nape_kernel::error::Error`], created with the [`assert_kernel_error`] macro.
+///
+/// Each check compares one part of the error and records a mismatch instead of panicking, so only the checks
+/// you chain are applied.  When the assertion is dropped (or [`KernelErrorAssert::assert`] is called) the test
+/// fails once, listing every mismatched field.
+///
+/// [`assert_kernel_error`]: crate::assert_kernel_error
+pub struct KernelErrorAssert<K, A> {
+    kind: K,
+    audience: A,
+    message: String,
+    causes: Vec<(K, A, String)>,
+    mismatches: Vec<MismatchReport>,
+    evaluated: bool,
+}
+
+impl<K: Debug + PartialEq, A: Debug + PartialEq> KernelErrorAssert<K, A> {
+    /// Creates a new assertion over the kind, audience, and message of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The actual error kind.
+    /// * `audience` - The actual error audience.
+    /// * `message` - The actual error message.
+    ///
+    pub fn new(kind: K, audience: A, message: impl Into<String>) -> Self {
+        KernelErrorAssert {
+            kind,
+            audience,
+            message: message.into(),
+            causes: Vec::new(),
+            mismatches: Vec::new(),
+            evaluated: false,
+        }
+    }
+
+    /// Adds an underlying cause of the error, used by [`KernelErrorAssert::caused_by`].  Causes are added in order,
+    /// starting with the direct source of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the cause.
+    /// * `audience` - The audience of the cause.
+    /// * `message` - The message of the cause.
+    ///
+    pub fn with_cause(mut self, kind: K, audience: A, message: impl Into<String>) -> Self {
+        self.causes.push((kind, audience, message.into()));
+        self
+    }
+
+    /// Checks that the error kind equals the expected kind.
+    pub fn kind(mut self, expected: K) -> Self {
+        if self.kind != expected {
+            self.mismatches.push(
+                MismatchReport::new("kind", "Kind does not match.")
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", self.kind)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error audience equals the expected audience.
+    pub fn audience(mut self, expected: A) -> Self {
+        if self.audience != expected {
+            self.mismatches.push(
+                MismatchReport::new("audience", "Audience does not match.")
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", self.audience)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error message equals the expected message.
+    pub fn message(mut self, expected: &str) -> Self {
+        if self.message != expected {
+            self.mismatches.push(
+                MismatchReport::new("message", "The Error Message does not match.")
+                    .hint(crate::diff::render(expected, &self.message)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error message starts with the expected phrase.
+    pub fn message_starts_with(mut self, expected: &str) -> Self {
+        if !self.message.starts_with(expected) {
+            self.mismatches.push(
+                MismatchReport::new("message", "The Error Message does not start with the expected phrase.")
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", self.message)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error message contains the expected phrase.
+    pub fn message_contains(mut self, expected: &str) -> Self {
+        if !self.message.contains(expected) {
+            self.mismatches.push(
+                MismatchReport::new("message", "The Error Message does not contain the expected phrase.")
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", self.message)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error message matches the expected regular expression.
+    pub fn message_matches(mut self, pattern: &str) -> Self {
+        let regex = regex::Regex::new(pattern).unwrap_or_else(|err| {
+            report::fail(
+                MismatchReport::new("message", "The expected pattern is not a valid regular expression.")
+                    .expected("a valid regular expression")
+                    .actual(format!("{:?}", pattern))
+                    .hint(format!("\t{}", err)),
+            )
+        });
+        if !regex.is_match(&self.message) {
+            self.mismatches.push(
+                MismatchReport::new("message", "The Error Message does not match the expected pattern.")
+                    .expected(format!("{:?}", pattern))
+                    .actual(format!("{:?}", self.message)),
+            );
+        }
+        self
+    }
+
+    /// Checks that the error message is not empty.
+    pub fn has_message(mut self) -> Self {
+        if self.message.is_empty() {
+            self.mismatches.push(MismatchReport::new(
+                "message",
+                "The error message is empty.  A populated error message is expected.",
+            ));
+        }
+        self
+    }
+
+    /// Checks that some cause of the error has the expected kind and audience, and its message contains the
+    /// expected phrase.
+    pub fn caused_by(mut self, kind: K, audience: A, message_fragment: &str) -> Self {
+        let found = self.causes.iter().any(|(cause_kind, cause_audience, cause_message)| {
+            *cause_kind == kind && *cause_audience == audience && cause_message.contains(message_fragment)
+        });
+        if !found {
+            self.mismatches.push(
+                MismatchReport::new("causes", "No cause matches the expected values.")
+                    .expected(format!("{:?}, {:?}, {:?}", kind, audience, message_fragment))
+                    .actual(format!("{:?}", self.causes)),
+            );
+        }
+        self
+    }
+
+    /// Returns the mismatches found by the chained checks without failing the test, so they can be collected
+    /// elsewhere, such as by [`SoftAssertions`](crate::soft::SoftAssertions).
+    pub fn into_mismatches(mut self) -> Vec<MismatchReport> {
+        self.evaluated = true;
+        std::mem::take(&mut self.mismatches)
+    }
+
+    /// Evaluates the chained checks immediately, rather than waiting for the assertion to be dropped.
+    pub fn assert(mut self) {
+        self.evaluate();
+    }
+
+    /// Evaluates the chained checks without failing the test, returning a report of every mismatch.
+    pub fn check(mut self) -> Result<(), MismatchReport> {
+        self.evaluated = true;
+        match self.report() {
+            Some(report) => Err(report),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<K, A> KernelErrorAssert<K, A> {
+    fn report(&self) -> Option<MismatchReport> {
+        (!self.mismatches.is_empty()).then(|| {
+            let mismatches: Vec<String> = self.mismatches.iter().map(ToString::to_string).collect();
+            MismatchReport::new(
+                "error",
+                format!(
+                    "The Error does not match the expected values ({} mismatch(es)):",
+                    self.mismatches.len()
+                ),
+            )
+            .hint(format!("\n{}", mismatches.join("\n")))
+        })
+    }
+
+    fn evaluate(&mut self) {
+        self.evaluated = true;
+        report::record_evaluation();
+        if let Some(report) = self.report() {
+            report::fail(report);
+        }
+    }
+}
+
+impl<K, A> Drop for KernelErrorAssert<K, A> {
+    fn drop(&mut self) {
+        if !self.evaluated && !thread::panicking() {
+            self.evaluate();
+        }
+    }
+}
+
+/// Returns the causes of an error, walking its [`Error::source`] chain and keeping every ancestor of the same
+/// type.  The error itself is not included.
+///
+/// # Arguments
+///
+/// * `error` - The error whose causes are returned.
+///
+pub fn error_chain<E: Error + 'static>(error: &E) -> Vec<&E> {
+    let mut chain = Vec::new();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if let Some(cause) = cause.downcast_ref::<E>() {
+            chain.push(cause);
+        }
+        source = cause.source();
+    }
+    chain
+}
+
+/// Starts a fluent [`KernelErrorAssert`] over an [`nape_kernel::error::Error`], so you can chain only the checks
+/// you care about and get one failure message listing every mismatched field.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_kernel_error;
+/// # #[derive(Debug, Clone, PartialEq)] enum Kind { InvalidInput }
+/// # #[derive(Debug, Clone, PartialEq)] enum Audience { User }
+/// # #[derive(Debug)] struct Error { kind: Kind, audience: Audience, message: String }
+/// # let result: Result<(), Error> = Err(Error { kind: Kind::InvalidInput, audience: Audience::User, message: "foo".to_string() });
+///
+/// assert_kernel_error!(result)
+///     .kind(Kind::InvalidInput)
+///     .audience(Audience::User)
+///     .message_contains("foo");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_kernel_error {
+    ($result:expr) => {
+        match $result {
+            Ok(val) => $crate::assertions::report::fail(
+                $crate::assertions::report::MismatchReport::new(
+                    "result",
+                    "An Error was expected, although one was not returned.",
+                )
+                .actual(format!("Ok({:?})", val)),
+            ),
+            Err(e) => {
+                $crate::__observe_error!(e);
+                $crate::matchers::KernelErrorAssert::new(e.kind.clone(), e.audience.clone(), e.message.to_string())
+            }
+        }
+    };
+}
+
+/// Starts a fluent [`KernelErrorAssert`] like [`assert_kernel_error`], and also records the causes of the error
+/// so [`KernelErrorAssert::caused_by`] can be used.
+///
+/// The error must implement [`std::error::Error`]; its causes are found with [`error_chain`], and the `Result`
+/// must be passed by value.
+///
+/// # Arguments
+///
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+///
+/// [`assert_kernel_error`]: crate::assert_kernel_error
+#[macro_export]
+macro_rules! assert_kernel_error_chain {
+    ($result:expr) => {
+        match $result {
+            Ok(val) => $crate::assertions::report::fail(
+                $crate::assertions::report::MismatchReport::new(
+                    "result",
+                    "An Error was expected, although one was not returned.",
+                )
+                .actual(format!("Ok({:?})", val)),
+            ),
+            Err(e) => {
+                $crate::__observe_error!(e);
+                let mut assertion = $crate::matchers::KernelErrorAssert::new(
+                    e.kind.clone(),
+                    e.audience.clone(),
+                    e.message.to_string(),
+                );
+                for cause in $crate::matchers::error_chain(&e) {
+                    assertion = assertion.with_cause(
+                        cause.kind.clone(),
+                        cause.audience.clone(),
+                        cause.message.to_string(),
+                    );
+                }
+                assertion
+            }
+        }
+    };
+}
+
+/// A reusable check of a value that can describe what it expects and why a value did not match.
+///
+/// Downstream crates implement this for their own domain checks, such as `is_signed_evidence()`, and combine them
+/// with [`all_of`], [`any_of`], and [`not`] before asserting with [`assert_that`](crate::assert_that).
+pub trait Matcher<T: ?Sized> {
+    /// Returns true when the value matches.
+    fn matched(&self, actual: &T) -> bool;
+
+    /// Describes what a matching value looks like, e.g. `equal to 3`.
+    fn describe(&self) -> String;
+
+    /// Describes why the value did not match, e.g. `was 4`.
+    fn describe_mismatch(&self, _actual: &T) -> String {
+        format!("did not match {}", self.describe())
+    }
+
+    /// Boxes the matcher, so matchers of different types can be combined.
+    fn boxed<'a>(self) -> Box<dyn Matcher<T> + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T> + ?Sized> Matcher<T> for Box<M> {
+    fn matched(&self, actual: &T) -> bool {
+        (**self).matched(actual)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> String {
+        (**self).describe_mismatch(actual)
+    }
+}
+
+/// Matches values equal to the expected value.
+pub struct EqualTo<T>(T);
+
+/// Returns a matcher for values equal to the expected value.
+pub fn equal_to<T>(expected: T) -> EqualTo<T> {
+    EqualTo(expected)
+}
+
+impl<T: PartialEq<U> + Debug, U: Debug + ?Sized> Matcher<U> for EqualTo<T> {
+    fn matched(&self, actual: &U) -> bool {
+        self.0 == *actual
+    }
+
+    fn describe(&self) -> String {
+        format!("equal to {:?}", self.0)
+    }
+
+    fn describe_mismatch(&self, actual: &U) -> String {
+        format!("was {:?}", actual)
+    }
+}
+
+/// Matches values for which a predicate holds.
+pub struct Predicate<F> {
+    description: String,
+    predicate: F,
+}
+
+/// Returns a matcher for values for which the predicate holds, described by the description.
+///
+/// # Arguments
+///
+/// * `description` - What a matching value looks like, e.g. `a signed evidence record`.
+/// * `predicate` - The check applied to the value.
+///
+pub fn predicate<T: ?Sized, F: Fn(&T) -> bool>(description: &str, predicate: F) -> Predicate<F> {
+    Predicate {
+        description: description.to_string(),
+        predicate,
+    }
+}
+
+impl<T: Debug + ?Sized, F: Fn(&T) -> bool> Matcher<T> for Predicate<F> {
+    fn matched(&self, actual: &T) -> bool {
+        (self.predicate)(actual)
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> String {
+        format!("was {:?}", actual)
+    }
+}
+
+/// Matches values that match every one of the matchers.
+pub struct AllOf<'a, T: ?Sized>(Vec<Box<dyn Matcher<T> + 'a>>);
+
+/// Returns a matcher for values that match every one of the matchers.
+pub fn all_of<'a, T: ?Sized>(matchers: Vec<Box<dyn Matcher<T> + 'a>>) -> AllOf<'a, T> {
+    AllOf(matchers)
+}
+
+impl<T: ?Sized> Matcher<T> for AllOf<'_, T> {
+    fn matched(&self, actual: &T) -> bool {
+        self.0.iter().all(|matcher| matcher.matched(actual))
+    }
+
+    fn describe(&self) -> String {
+        let descriptions: Vec<String> = self.0.iter().map(|matcher| matcher.describe()).collect();
+        format!("({})", descriptions.join(" and "))
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> String {
+        let mismatches: Vec<String> = self
+            .0
+            .iter()
+            .filter(|matcher| !matcher.matched(actual))
+            .map(|matcher| format!("{}: {}", matcher.describe(), matcher.describe_mismatch(actual)))
+            .collect();
+        mismatches.join("; ")
+    }
+}
+
+/// Matches values that match at least one of the matchers.
+pub struct AnyOf<'a, T: ?Sized>(Vec<Box<dyn Matcher<T> + 'a>>);
+
+/// Returns a matcher for values that match at least one of the matchers.
+pub fn any_of<'a, T: ?Sized>(matchers: Vec<Box<dyn Matcher<T> + 'a>>) -> AnyOf<'a, T> {
+    AnyOf(matchers)
+}
+
+impl<T: ?Sized> Matcher<T> for AnyOf<'_, T> {
+    fn matched(&self, actual: &T) -> bool {
+        self.0.iter().any(|matcher| matcher.matched(actual))
+    }
+
+    fn describe(&self) -> String {
+        let descriptions: Vec<String> = self.0.iter().map(|matcher| matcher.describe()).collect();
+        format!("({})", descriptions.join(" or "))
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> String {
+        let mismatches: Vec<String> = self
+            .0
+            .iter()
+            .map(|matcher| matcher.describe_mismatch(actual))
+            .collect();
+        mismatches.join(" and ")
+    }
+}
+
+/// Matches values that do not match the inner matcher.
+pub struct Not<M>(M);
+
+/// Returns a matcher for values that do not match the matcher.
+pub fn not<M>(matcher: M) -> Not<M> {
+    Not(matcher)
+}
+
+impl<T: ?Sized, M: Matcher<T>> Matcher<T> for Not<M> {
+    fn matched(&self, actual: &T) -> bool {
+        !self.0.matched(actual)
+    }
+
+    fn describe(&self) -> String {
+        format!("not {}", self.0.describe())
+    }
+
+    fn describe_mismatch(&self, _actual: &T) -> String {
+        format!("was {}", self.0.describe())
+    }
+}
+
+/// Checks that the value matches the matcher, see [`check_that`](crate::check_that).
+pub fn check_matches<T: ?Sized, M: Matcher<T> + ?Sized>(actual: &T, matcher: &M) -> Result<(), MismatchReport> {
+    if matcher.matched(actual) {
+        return Ok(());
+    }
+    Err(MismatchReport::new("value", "The value does not match.")
+        .expected(matcher.describe())
+        .actual(matcher.describe_mismatch(actual)))
+}
+
+/// Asserts that the value matches the matcher, see [`assert_that`](crate::assert_that).
+pub fn assert_matches<T: ?Sized, M: Matcher<T> + ?Sized>(actual: &T, matcher: &M) {
+    crate::assertions::report::enforce(check_matches(actual, matcher));
+}
+
+/// Asserts that a value matches a [`Matcher`], failing with the matcher's description of what it expected and why
+/// the value did not match.
+///
+/// # Arguments
+///
+/// * `$value` - The value to check.
+/// * `$matcher` - A [`Matcher`] for the value's type.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_that;
+/// use attestify_test_framework::matchers::{all_of, equal_to, not, predicate, Matcher};
+///
+/// let activities = 3;
+/// assert_that!(activities, all_of(vec![
+///     predicate("a positive number", |n: &i32| *n > 0).boxed(),
+///     not(equal_to(4)).boxed(),
+/// ]));
+/// ```
+///
+#[macro_export]
+macro_rules! assert_that {
+    ($value:expr, $matcher:expr) => {{
+        $crate::matchers::assert_matches(&$value, &$matcher)
+    }};
+}
+
+/// Checks that a value matches a [`Matcher`], like [`assert_that`](crate::assert_that), returning a
+/// [`MismatchReport`] instead of panicking.
+///
+/// # Arguments
+///
+/// * `$value` - The value to check.
+/// * `$matcher` - A [`Matcher`] for the value's type.
+///
+#[macro_export]
+macro_rules! check_that {
+    ($value:expr, $matcher:expr) => {{
+        $crate::matchers::check_matches(&$value, &$matcher)
+    }};
+}
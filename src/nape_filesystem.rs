@@ -38,13 +38,9 @@ pub fn remove_nape_testing_dir() {
 	let nape_testing_dir = nape_testing_dir();
 
 	if nape_testing_dir.exists() {
-		fs::remove_dir_all(&nape_testing_dir).expect(
-			format!(
-				"Could not remove the NAPE Testing Root directory '{}'.",
-				nape_testing_dir.display()
-			)
-				.as_str(),
-		);
+		fs::remove_dir_all(&nape_testing_dir).unwrap_or_else(|err| {
+			panic!("Could not remove the NAPE Testing Root directory '{}':\n\t{}\n", nape_testing_dir.display(), err)
+		});
 	}
 }
 
@@ -62,26 +58,18 @@ pub fn create_subpath(subpath: &str) -> PathBuf {
 		if nape_test_root.extension().is_some() {
 			// It's a file
 			if let Some(parent) = nape_test_root.parent() {
-				fs::create_dir_all(parent).expect(
-					format!(
-						"Could not create the parent directory for '{}'.",
-						parent.display()
-					)
-						.as_str(),
-				);
-				fs::File::create(&nape_test_root).expect(
-					format!("Could not create the file '{}'.", nape_test_root.display()).as_str(),
-				);
+				fs::create_dir_all(parent).unwrap_or_else(|err| {
+					panic!("Could not create the parent directory for '{}':\n\t{}\n", parent.display(), err)
+				});
+				fs::File::create(&nape_test_root).unwrap_or_else(|err| {
+					panic!("Could not create the file '{}':\n\t{}\n", nape_test_root.display(), err)
+				});
 			}
 		} else {
 			// It's a directory
-			fs::create_dir_all(&nape_test_root).expect(
-				format!(
-					"Could not create the directory '{}'.",
-					nape_test_root.display()
-				)
-					.as_str(),
-			);
+			fs::create_dir_all(&nape_test_root).unwrap_or_else(|err| {
+				panic!("Could not create the directory '{}':\n\t{}\n", nape_test_root.display(), err)
+			});
 		}
 	}
 
@@ -112,21 +100,13 @@ pub fn generate_nape_testing_file(subpath: &str, contents: &str) -> PathBuf {
 
 	if !nape_test_root.exists() {
 		if let Some(parent) = nape_test_root.parent() {
-			fs::create_dir_all(parent).expect(
-				format!(
-					"Could not create the parent directory for '{}'.",
-					parent.display()
-				)
-					.as_str(),
-			);
+			fs::create_dir_all(parent).unwrap_or_else(|err| {
+				panic!("Could not create the parent directory for '{}':\n\t{}\n", parent.display(), err)
+			});
 		}
-		fs::write(&nape_test_root, contents).expect(
-			format!(
-				"Could not write to the file '{}'.",
-				nape_test_root.display()
-			)
-				.as_str(),
-		);
+		fs::write(&nape_test_root, contents).unwrap_or_else(|err| {
+			panic!("Could not write to the file '{}':\n\t{}\n", nape_test_root.display(), err)
+		});
 	}
 
 	nape_test_root
@@ -138,13 +118,9 @@ pub fn generate_nape_testing_file_write_only(subpath: &str, contents: &str) -> P
 
 	if !nape_test_root.exists() {
 		if let Some(parent) = nape_test_root.parent() {
-			fs::create_dir_all(parent).expect(
-				format!(
-					"Could not create the parent directory for '{}'.",
-					parent.display()
-				)
-					.as_str(),
-			);
+			fs::create_dir_all(parent).unwrap_or_else(|err| {
+				panic!("Could not create the parent directory for '{}':\n\t{}\n", parent.display(), err)
+			});
 		}
 
 		// Create the file with write-only permissions
@@ -152,16 +128,15 @@ pub fn generate_nape_testing_file_write_only(subpath: &str, contents: &str) -> P
 			.write(true)
 			.read(false)
 			.create(true)
+			.truncate(false)
 			.open(&nape_test_root)
-			.expect(format!("Could not create the file '{}'.", nape_test_root.display()).as_str());
+			.unwrap_or_else(|err| {
+				panic!("Could not create the file '{}':\n\t{}\n", nape_test_root.display(), err)
+			});
 
-		file.write_all(contents.as_bytes()).expect(
-			format!(
-				"Could not write to the file '{}'.",
-				nape_test_root.display()
-			)
-				.as_str(),
-		);
+		file.write_all(contents.as_bytes()).unwrap_or_else(|err| {
+			panic!("Could not write to the file '{}':\n\t{}\n", nape_test_root.display(), err)
+		});
 
 		// Set the file permissions to write-only
 		let mut perms = file
@@ -187,17 +162,13 @@ pub fn remove_subpath(subpath: &str) {
 
 	if nape_test_root.exists() {
 		if nape_test_root.is_dir() {
-			fs::remove_dir_all(&nape_test_root).expect(
-				format!(
-					"Could not remove the directory '{}'.",
-					nape_test_root.display()
-				)
-					.as_str(),
-			);
+			fs::remove_dir_all(&nape_test_root).unwrap_or_else(|err| {
+				panic!("Could not remove the directory '{}':\n\t{}\n", nape_test_root.display(), err)
+			});
 		} else {
-			fs::remove_file(&nape_test_root).expect(
-				format!("Could not remove the file '{}'.", nape_test_root.display()).as_str(),
-			);
+			fs::remove_file(&nape_test_root).unwrap_or_else(|err| {
+				panic!("Could not remove the file '{}':\n\t{}\n", nape_test_root.display(), err)
+			});
 		}
 	}
 }
@@ -317,15 +288,36 @@ macro_rules! remove {
 #[macro_export]
 macro_rules! file_contents_eq {
     ($expected_contents:expr, $path:expr) => {{
-        use std::fs;
-        use std::str;
-
-        // Read the file contents
-        let file_contents = fs::read_to_string($path)
-            .expect("Could not read the file.");
+        $crate::assertions::report::enforce($crate::check_file_contents_eq!($expected_contents, $path))
+    }};
+}
 
-        // Assert that the file contents match the expected contents
-        assert_eq!(file_contents, $expected_contents, "{}", format!("The file contents do not match the expected contents.\n\tExpected:\t{}\n\tActual:\t{}", $expected_contents, file_contents));
+/// Compares the contents of a file, given its file path, to the expected contents, like [`file_contents_eq`],
+/// returning a [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.  A file that
+/// cannot be read is also reported.
+///
+/// # Arguments
+///
+/// * `expected_contents` - The expected contents of the file as a [`String`].
+/// * `path` - The path to the file as a [`PathBuf`].
+///
+#[macro_export]
+macro_rules! check_file_contents_eq {
+    ($expected_contents:expr, $path:expr) => {{
+        match std::fs::read_to_string($path) {
+            Err(err) => Err($crate::assertions::report::MismatchReport::new(
+                "file contents",
+                format!("Could not read the file: {}", err),
+            )),
+            Ok(file_contents) if file_contents != $expected_contents => {
+                Err($crate::assertions::report::MismatchReport::new(
+                    "file contents",
+                    "The file contents do not match the expected contents.",
+                )
+                .hint($crate::diff::render(&$expected_contents.to_string(), &file_contents)))
+            }
+            Ok(_) => Ok(()),
+        }
     }};
 }
 
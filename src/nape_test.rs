@@ -0,0 +1,134 @@
+//! The runtime behind the `#[nape_test]` attribute.
+//!
+//! `#[nape_test]` turns a function into a `#[test]` that runs in a fresh [`TestWorkspace`], removed when the test
+//! ends, and prints kernel-error-aware diagnostics when it panics.  The function may take the workspace as its
+//! only argument, may return a `Result` like any test, and may be given a timeout:
+//!
+//! ```no_run
+//! use attestify_test_framework::nape_test;
+//! use attestify_test_framework::workspace::TestWorkspace;
+//!
+//! #[nape_test(timeout = "5s")]
+//! fn writes_the_report(workspace: &TestWorkspace) {
+//!     let report = workspace.report_file();
+//!     // run the usecase
+//! }
+//! ```
+//!
+//! With a timeout the test runs on a watchdog thread, so the function must only capture `Send` values.
+
+use crate::panics::{panics_silenced, wrap_panic_hook};
+use crate::workspace::TestWorkspace;
+use regex::Regex;
+use std::cell::RefCell;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Once;
+use std::time::Duration;
+
+struct TestContext {
+    name: String,
+    workspace: PathBuf,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<TestContext>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Clears the current test context when the test ends, even when it panics.
+struct ContextGuard;
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = None);
+    }
+}
+
+/// Runs a test in a fresh workspace with panic diagnostics.  This is what `#[nape_test]` expands to.
+///
+/// # Arguments
+///
+/// * `name` - The name of the test, used in the diagnostics.
+/// * `test` - The body of the test.
+///
+pub fn run<R>(name: &str, test: impl FnOnce(&TestWorkspace) -> R) -> R {
+    install_diagnostics_hook();
+    let workspace = TestWorkspace::new();
+    CURRENT.with(|current| {
+        *current.borrow_mut() = Some(TestContext {
+            name: name.to_string(),
+            workspace: workspace.root().to_path_buf(),
+        })
+    });
+    let _guard = ContextGuard;
+    test(&workspace)
+}
+
+/// Runs a test like [`run`], failing it when it does not complete within the duration.  This is what
+/// `#[nape_test(timeout = "...")]` expands to.
+///
+/// # Arguments
+///
+/// * `name` - The name of the test, used in the diagnostics.
+/// * `duration` - How long the test may run.
+/// * `test` - The body of the test.
+///
+pub fn run_within<R: Send + 'static>(
+    name: &str,
+    duration: Duration,
+    test: impl FnOnce(&TestWorkspace) -> R + Send + 'static,
+) -> R {
+    let name = name.to_string();
+    crate::timing::complete_within(duration, move || run(&name, test))
+}
+
+/// Installs, once per process, a panic hook that prints the name and workspace of the failing `#[nape_test]`, and
+/// the kind, audience, and message of any kernel error in the panic message, before the previous hook runs.  Panics
+/// silenced by [`catch_panic`](crate::panics::catch_panic) are not printed.
+fn install_diagnostics_hook() {
+    INSTALL_HOOK.call_once(|| {
+        wrap_panic_hook(|previous| {
+            Box::new(move |info| {
+                if panics_silenced() {
+                    return;
+                }
+                let diagnostics =
+                    CURRENT.with(|current| current.borrow().as_ref().map(|context| diagnostics(context, info)));
+                if let Some(diagnostics) = diagnostics {
+                    eprintln!("{}", diagnostics);
+                }
+                previous(info);
+            })
+        });
+    });
+}
+
+fn diagnostics(context: &TestContext, info: &PanicHookInfo) -> String {
+    let mut output = format!(
+        "nape_test '{}' failed.\n\tWorkspace:\t{}\n",
+        context.name,
+        context.workspace.display()
+    );
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    if let Some(kernel_error) = kernel_error_fields(&message) {
+        output.push_str(&kernel_error);
+    }
+    output
+}
+
+/// Finds the fields of a kernel error in its `Debug` rendering, e.g. from an `unwrap` of an `Err`.
+fn kernel_error_fields(message: &str) -> Option<String> {
+    let pattern = Regex::new(r#"kind: "?(\w+)"?, audience: "?(\w+)"?, message: ("(?:[^"\\]|\\.)*")"#).unwrap();
+    let captures = pattern.captures(message)?;
+    Some(format!(
+        "\tKernel error:\n\t\tKind:\t{}\n\t\tAudience:\t{}\n\t\tMessage:\t{}\n",
+        &captures[1], &captures[2], &captures[3]
+    ))
+}
@@ -0,0 +1,164 @@
+use crate::assertions::report::{self, MismatchReport};
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::{Mutex, RwLock};
+use std::thread;
+
+pub(crate) type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+thread_local! {
+    /// How many [`silence_panics`] calls are running on the current thread.
+    static SILENCED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// How many [`silence_panics`] calls are running on any thread, and whether the silencing hook is installed.
+static SILENCING: Mutex<(usize, bool)> = Mutex::new((0, false));
+
+/// The panic hook that was installed before the silencing hook, which the silencing hook calls for the panics that
+/// are not silenced.  It is never locked while the panic hook is swapped, as a panicking thread holds the panic hook
+/// while it reads this one.
+static PREVIOUS_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+
+/// Silences panics on the current thread until it is dropped, including while unwinding from one.
+struct SilenceGuard;
+
+impl SilenceGuard {
+    fn new() -> Self {
+        let mut silencing = SILENCING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !silencing.1 && !thread::panicking() {
+            let previous = panic::take_hook();
+            *PREVIOUS_HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(previous);
+            panic::set_hook(Box::new(|info| {
+                let previous = PREVIOUS_HOOK.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+                if !panics_silenced()
+                    && let Some(previous) = previous.as_ref()
+                {
+                    previous(info);
+                }
+            }));
+            silencing.1 = true;
+        }
+        silencing.0 += 1;
+        SILENCED.with(|depth| depth.set(depth.get() + 1));
+        SilenceGuard
+    }
+}
+
+impl Drop for SilenceGuard {
+    fn drop(&mut self) {
+        SILENCED.with(|depth| depth.set(depth.get() - 1));
+        let mut silencing = SILENCING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        silencing.0 -= 1;
+        // The hook cannot be swapped while this thread unwinds; the next guard to drop restores it instead.
+        if silencing.0 == 0 && silencing.1 && !thread::panicking() {
+            drop(panic::take_hook());
+            let previous = PREVIOUS_HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+            if let Some(previous) = previous {
+                panic::set_hook(previous);
+            }
+            silencing.1 = false;
+        }
+    }
+}
+
+/// Replaces the panic hook with one wrapping it.  While panics are silenced the wrapped hook is the one the
+/// silencing hook calls and later restores, so the new hook outlives the silencing.
+pub(crate) fn wrap_panic_hook(wrap: impl FnOnce(PanicHook) -> PanicHook) {
+    let silencing = SILENCING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if silencing.1 {
+        let mut previous = PREVIOUS_HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(hook) = previous.take() {
+            *previous = Some(wrap(hook));
+        }
+    } else {
+        panic::set_hook(wrap(panic::take_hook()));
+    }
+}
+
+/// Returns the message of a panic payload, which is either a `&str`, a [`String`], or a
+/// [`DisabledAssertion`](report::DisabledAssertion).
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if payload.is::<report::DisabledAssertion>() {
+        return "An assertion failed while the assertions are disabled.".to_string();
+    }
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|message| message.to_string()))
+        .unwrap_or_else(|| "<non-string panic payload>".to_string())
+}
+
+/// Runs the closure and returns the message it panicked with, or [`None`] when it did not panic.
+///
+/// Panics on the current thread are not printed while the closure runs, so the expected panic is not either.  Panics
+/// on other threads are printed as usual.  Calls can be nested, and the closure may run for as long as it needs, as
+/// no lock is held while it runs.
+pub fn catch_panic<R>(work: impl FnOnce() -> R) -> Option<String> {
+    silence_panics(|| panic::catch_unwind(AssertUnwindSafe(work)))
+        .err()
+        .map(|payload| panic_message(payload.as_ref()))
+}
+
+/// Runs the closure without printing the panics on the current thread, and re-raises its panic.  The closure catches
+/// the panics it expects itself; a closure that spawns threads silences them on each of those threads.
+///
+/// The first call installs a panic hook that skips the previous hook while the panicking thread is silenced, and the
+/// previous hook is restored when no call is running on any thread.  The lock guarding the hook is only held while
+/// it is swapped, never while the closure runs, so calls nest.
+pub(crate) fn silence_panics<R>(work: impl FnOnce() -> R) -> R {
+    let _guard = SilenceGuard::new();
+    work()
+}
+
+/// Returns true when the panics on the current thread are silenced by [`silence_panics`].
+pub(crate) fn panics_silenced() -> bool {
+    SILENCED.with(|depth| depth.get() > 0)
+}
+
+/// Asserts the closure panics with a message containing the expected phrase, and returns the message.  See
+/// [`assert_panics_with`](crate::assert_panics_with).
+pub fn assert_panics_with<R>(work: impl FnOnce() -> R, expected: &str) -> String {
+    report::record_evaluation();
+    match catch_panic(work) {
+        None => report::fail(
+            MismatchReport::new("panic", "A panic was expected, although one did not occur.")
+                .expected(format!("{:?}", expected)),
+        ),
+        Some(message) if message.contains(expected) => message,
+        Some(message) => report::fail(
+            MismatchReport::new("panic", "The panic message does not contain the expected phrase.")
+                .expected(format!("{:?}", expected))
+                .actual(format!("{:?}", message)),
+        ),
+    }
+}
+
+/// Asserts that a closure panics, and that the panic message contains a phrase, then returns the message.
+///
+/// The expected panic is not printed, and the previous panic hook is restored afterwards, once no other thread is
+/// silencing its panics.  This is useful for
+/// testing the framework's own assertion macros and other code that intentionally panics.
+///
+/// # Arguments
+///
+/// * `$closure` - The closure that is expected to panic.
+/// * `$expected` - Optional, the phrase the panic message must contain.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::{assert_panics_with, is_ok};
+///
+/// assert_panics_with!(|| is_ok!(Err::<(), _>("boom")), "An Ok was expected");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_panics_with {
+    ($closure:expr) => {
+        $crate::panics::assert_panics_with($closure, "")
+    };
+    ($closure:expr, $expected:expr) => {
+        $crate::panics::assert_panics_with($closure, $expected)
+    };
+}
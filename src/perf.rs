@@ -0,0 +1,214 @@
+//! Lightweight performance gates for regular test runs, which compare a routine's timing with a baseline committed
+//! with the crate.
+//!
+//! The baselines of a crate are kept in one JSON file, `tests/__baselines__/perf.json`, keyed by name.  Run the tests
+//! with `UPDATE_PERF_BASELINES=1` to record them, on the machine class that runs the gate, and commit the file.
+//! Timings depend on the machine and the build profile, so the tolerance should leave room for noise.
+
+use crate::assertions::report::{self, MismatchReport};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The environment variable that, when set to `1`, records the timings as the new baselines instead of comparing
+/// them.
+pub const UPDATE_BASELINES_ENV_VAR: &str = "UPDATE_PERF_BASELINES";
+
+/// The file, relative to the crate root, that baselines are stored in.
+pub const BASELINE_FILE: &str = "tests/__baselines__/perf.json";
+
+/// How many timed runs a measurement takes the median of, after one untimed warm-up run.
+pub const SAMPLES: usize = 9;
+
+/// Serializes reading and writing the baseline file, as tests run in parallel.
+static BASELINE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Returns true when baselines should be recorded, see [`UPDATE_BASELINES_ENV_VAR`].
+pub fn update_requested() -> bool {
+    env::var(UPDATE_BASELINES_ENV_VAR).as_deref() == Ok("1")
+}
+
+/// Returns the path of the baseline file of the crate.
+pub fn baseline_path(manifest_dir: &str) -> PathBuf {
+    Path::new(manifest_dir).join(BASELINE_FILE)
+}
+
+/// The recorded timing of a routine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The median of the timed runs, in nanoseconds.
+    pub median_nanos: u64,
+    /// The number of timed runs.
+    pub samples: usize,
+}
+
+/// The timings of the runs of a routine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    /// The duration of each timed run, sorted from fastest to slowest.
+    pub samples: Vec<Duration>,
+}
+
+impl Timing {
+    /// Returns the median duration.
+    pub fn median(&self) -> Duration {
+        self.samples[self.samples.len() / 2]
+    }
+}
+
+/// Runs the routine once to warm up, then [`SAMPLES`] times timed, returning the value of the last run and the
+/// timings.
+pub fn measure<R>(mut work: impl FnMut() -> R) -> (R, Timing) {
+    let mut value = work();
+    let mut samples = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        value = work();
+        samples.push(start.elapsed());
+    }
+    samples.sort();
+    (value, Timing { samples })
+}
+
+/// Reads the baselines of the crate, which are empty when the file does not exist.
+pub fn read_baselines(manifest_dir: &str) -> BTreeMap<String, Baseline> {
+    let path = baseline_path(manifest_dir);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|err| panic!("The baseline file '{}' is not valid:\n\t{}\n", path.display(), err))
+}
+
+fn write_baseline(manifest_dir: &str, name: &str, baseline: Baseline) {
+    let path = baseline_path(manifest_dir);
+    let mut baselines = read_baselines(manifest_dir);
+    baselines.insert(name.to_string(), baseline);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|_| panic!("Could not create the baseline directory '{}'.", parent.display()));
+    }
+    let contents = serde_json::to_string_pretty(&baselines).expect("Could not serialize the baselines.");
+    fs::write(&path, contents + "\n")
+        .unwrap_or_else(|_| panic!("Could not write the baseline file '{}'.", path.display()));
+}
+
+/// Checks the routine is no slower than its baseline beyond the tolerance, returning the value of its last run.  See
+/// [`check_faster_than_baseline`](crate::check_faster_than_baseline).
+///
+/// When [`update_requested`] is true the timing is recorded as the baseline instead.
+pub fn check_faster_than_baseline<R>(
+    manifest_dir: &str,
+    name: &str,
+    tolerance_pct: f64,
+    work: impl FnMut() -> R,
+) -> Result<R, MismatchReport> {
+    let (value, timing) = measure(work);
+    let _guard = BASELINE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if update_requested() {
+        let baseline = Baseline {
+            median_nanos: timing.median().as_nanos() as u64,
+            samples: timing.samples.len(),
+        };
+        write_baseline(manifest_dir, name, baseline);
+        return Ok(value);
+    }
+
+    let Some(baseline) = read_baselines(manifest_dir).remove(name) else {
+        return Err(MismatchReport::new(
+            name,
+            format!(
+                "The baseline '{}' does not exist in '{}'.  Run the test with {}=1 to record it.",
+                name,
+                baseline_path(manifest_dir).display(),
+                UPDATE_BASELINES_ENV_VAR
+            ),
+        ));
+    };
+    let baseline_median = Duration::from_nanos(baseline.median_nanos);
+    let limit = baseline_median.mul_f64(1.0 + tolerance_pct / 100.0);
+    if timing.median() <= limit {
+        return Ok(value);
+    }
+    Err(MismatchReport::new(
+        name,
+        format!(
+            "The routine is {:.0}% slower than its baseline '{}', beyond the tolerance of {}%.  Run the test with {}=1 \
+             to record a new baseline.",
+            (timing.median().as_secs_f64() / baseline_median.as_secs_f64() - 1.0) * 100.0,
+            name,
+            tolerance_pct,
+            UPDATE_BASELINES_ENV_VAR
+        ),
+    )
+    .expected(format!("at most {:?}, the baseline of {:?} plus {}%", limit, baseline_median, tolerance_pct))
+    .actual(format!("a median of {:?}", timing.median()))
+    .hint(format!(
+        "\tRuns:\t{} timed, from {:?} to {:?}",
+        timing.samples.len(),
+        timing.samples[0],
+        timing.samples[timing.samples.len() - 1]
+    )))
+}
+
+/// Asserts the routine is no slower than its baseline beyond the tolerance, returning the value of its last run.
+/// See [`assert_faster_than_baseline`](crate::assert_faster_than_baseline).
+pub fn assert_faster_than_baseline<R>(
+    manifest_dir: &str,
+    name: &str,
+    tolerance_pct: f64,
+    work: impl FnMut() -> R,
+) -> R {
+    report::enforce(check_faster_than_baseline(manifest_dir, name, tolerance_pct, work))
+}
+
+/// Asserts that a routine is no slower than its committed baseline beyond a tolerance, and returns the value of its
+/// last run.
+///
+/// The routine runs once to warm up and then [`SAMPLES`](crate::perf::SAMPLES) times, and the median of the timed
+/// runs is compared with the baseline named in `tests/__baselines__/perf.json` of the crate running the test.  Run
+/// the tests with `UPDATE_PERF_BASELINES=1` to record the baselines.
+///
+/// # Arguments
+///
+/// * `$name` - The name of the baseline.
+/// * `$tolerance_pct` - How much slower than the baseline the routine may be, in percent, e.g. `25.0`.
+/// * `$closure` - The routine under test, which is called more than once.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_faster_than_baseline;
+/// # fn render_report(activities: usize) -> String { "x".repeat(activities) }
+///
+/// let report = assert_faster_than_baseline!("render_report_100_activities", 50.0, || render_report(100));
+/// ```
+///
+#[macro_export]
+macro_rules! assert_faster_than_baseline {
+    ($name:expr, $tolerance_pct:expr, $closure:expr $(,)?) => {
+        $crate::perf::assert_faster_than_baseline(env!("CARGO_MANIFEST_DIR"), $name, $tolerance_pct, $closure)
+    };
+}
+
+/// Checks that a routine is no slower than its committed baseline beyond a tolerance, like
+/// [`assert_faster_than_baseline`](crate::assert_faster_than_baseline), returning the value of its last run, or a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$name` - The name of the baseline.
+/// * `$tolerance_pct` - How much slower than the baseline the routine may be, in percent.
+/// * `$closure` - The routine under test, which is called more than once.
+///
+#[macro_export]
+macro_rules! check_faster_than_baseline {
+    ($name:expr, $tolerance_pct:expr, $closure:expr $(,)?) => {
+        $crate::perf::check_faster_than_baseline(env!("CARGO_MANIFEST_DIR"), $name, $tolerance_pct, $closure)
+    };
+}
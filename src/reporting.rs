@@ -0,0 +1,335 @@
+use crate::assertions::report::{self, DisabledAssertion, MismatchReport};
+use crate::panics::{panic_message, silence_panics};
+use std::any::Any;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// The environment variable naming the file [`JUnitReport::finish`] writes the JUnit XML to.
+pub const JUNIT_OUTPUT_ENV_VAR: &str = "JUNIT_OUTPUT";
+
+/// How a test ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    /// The test failed, with the report of the assertion that failed.  A panic that did not come from an
+    /// assertion is reported with the field `panic` and the panic message as its summary.
+    Failed(MismatchReport),
+    /// The test was not run, with the reason.
+    Skipped(String),
+    /// The test passed after failing, with the report of each attempt that failed, see
+    /// [`TestHarness::retried_test`](crate::harness::TestHarness::retried_test).
+    FlakyPassed(Vec<MismatchReport>),
+}
+
+/// The result of one test, as it appears in the JUnit XML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    /// The group the test belongs to, which CI dashboards use to nest tests.  Defaults to the suite name.
+    pub classname: String,
+    pub duration: Duration,
+    pub outcome: TestOutcome,
+}
+
+/// Collects test results and renders them as JUnit XML, including the structured details of each failed
+/// assertion, so CI dashboards can show what mismatched without scraping stdout.
+///
+/// It is meant to be driven from a test binary with `harness = false`:
+///
+/// ```no_run
+/// use attestify_test_framework::reporting::JUnitReport;
+/// use attestify_test_framework::kernel_error_eq;
+/// # #[derive(Debug, PartialEq)] enum Kind { InvalidInput }
+/// # #[derive(Debug, PartialEq)] enum Audience { User }
+/// # #[derive(Debug)] struct Error { kind: Kind, audience: Audience, message: String }
+/// # fn parse(_: &str) -> Result<(), Error> { Ok(()) }
+///
+/// fn main() -> std::process::ExitCode {
+///     let mut report = JUnitReport::new("procedure_usecases");
+///     report.run("rejects_empty_procedure", || {
+///         kernel_error_eq!(parse(""), Kind::InvalidInput, Audience::User, "The procedure is empty.");
+///     });
+///     report.finish()
+/// }
+/// ```
+///
+/// Each failure is written as a `<failure>` whose `type` is the mismatched field and whose `message` is the
+/// summary, with the rendered report as its text.  The field, expected, and actual values are also written as
+/// `<properties>` of the test case.  A test that passed after failing is written as passed, with a
+/// `<flakyFailure>` for each failed attempt, like Maven Surefire's reruns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JUnitReport {
+    name: String,
+    cases: Vec<TestCase>,
+}
+
+impl JUnitReport {
+    /// Creates an empty report.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test suite, typically the name of the test binary.
+    ///
+    pub fn new(name: impl Into<String>) -> Self {
+        JUnitReport {
+            name: name.into(),
+            cases: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the test suite.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds the result of a test run elsewhere.
+    pub fn add(&mut self, case: TestCase) {
+        self.cases.push(case);
+    }
+
+    /// Adds a test, in the suite's class, with the outcome of a `check_*` macro or function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `duration` - How long the test took.
+    /// * `result` - The result of the check.
+    ///
+    pub fn record(&mut self, name: impl Into<String>, duration: Duration, result: Result<(), MismatchReport>) {
+        let outcome = match result {
+            Ok(()) => TestOutcome::Passed,
+            Err(report) => TestOutcome::Failed(report),
+        };
+        self.add(TestCase {
+            name: name.into(),
+            classname: self.name.clone(),
+            duration,
+            outcome,
+        });
+    }
+
+    /// Adds a test that was not run.
+    pub fn skip(&mut self, name: impl Into<String>, reason: impl Into<String>) {
+        self.add(TestCase {
+            name: name.into(),
+            classname: self.name.clone(),
+            duration: Duration::ZERO,
+            outcome: TestOutcome::Skipped(reason.into()),
+        });
+    }
+
+    /// Runs a test, which fails when it panics, prints its outcome like libtest, and adds its result.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test.
+    /// * `test` - The test, which may use any of the panicking assertions.
+    ///
+    pub fn run(&mut self, name: impl Into<String>, test: impl FnOnce()) -> &TestOutcome {
+        let name = name.into();
+        let start = Instant::now();
+        let result = run_test(test);
+        println!("test {} ... {}", name, if result.is_ok() { "ok" } else { "FAILED" });
+        self.record(name, start.elapsed(), result);
+        &self.cases.last().unwrap().outcome
+    }
+
+    /// Returns the results added so far, in order.
+    pub fn cases(&self) -> &[TestCase] {
+        &self.cases
+    }
+
+    /// Returns the number of failed tests.
+    pub fn failures(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::Failed(_)))
+    }
+
+    /// Returns the number of tests that passed after failing.
+    pub fn flaky(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::FlakyPassed(_)))
+    }
+
+    fn count(&self, matching: impl Fn(&TestOutcome) -> bool) -> usize {
+        self.cases.iter().filter(|case| matching(&case.outcome)).count()
+    }
+
+    /// Renders the results as a JUnit XML document with one `<testsuite>`.
+    pub fn to_xml(&self) -> String {
+        let total: Duration = self.cases.iter().map(|case| case.duration).sum();
+        let skipped = self.count(|outcome| matches!(outcome, TestOutcome::Skipped(_)));
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"{skipped}\" time=\"{time:.3}\">",
+            name = escape_attribute(&self.name),
+            tests = self.cases.len(),
+            failures = self.failures(),
+            skipped = skipped,
+            time = total.as_secs_f64()
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"{skipped}\" time=\"{time:.3}\">",
+            name = escape_attribute(&self.name),
+            tests = self.cases.len(),
+            failures = self.failures(),
+            skipped = skipped,
+            time = total.as_secs_f64()
+        );
+        for case in &self.cases {
+            write_case(&mut xml, case);
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    /// Writes the JUnit XML to the file, creating its parent directories.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_xml())
+    }
+
+    /// Prints the failures and a summary like libtest, writes the JUnit XML to the file named by
+    /// [`JUNIT_OUTPUT_ENV_VAR`] when it is set, and returns the exit code for the test binary's `main`.
+    pub fn finish(self) -> ExitCode {
+        for case in &self.cases {
+            match &case.outcome {
+                TestOutcome::Failed(report) => println!("\n---- {} ----\n{}", case.name, report),
+                TestOutcome::FlakyPassed(attempts) => {
+                    for (number, report) in attempts.iter().enumerate() {
+                        println!("\n---- {} (flaky-pass, attempt {}) ----\n{}", case.name, number + 1, report);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let failures = self.failures();
+        println!(
+            "\ntest result: {}. {} passed; {} failed; {} skipped; {} flaky",
+            if failures == 0 { "ok" } else { "FAILED" },
+            self.count(|outcome| *outcome == TestOutcome::Passed),
+            failures,
+            self.count(|outcome| matches!(outcome, TestOutcome::Skipped(_))),
+            self.flaky()
+        );
+
+        if let Ok(path) = env::var(JUNIT_OUTPUT_ENV_VAR)
+            && let Err(err) = self.write(&path)
+        {
+            eprintln!("Could not write the JUnit report '{}': {}", path, err);
+            return ExitCode::FAILURE;
+        }
+        if failures == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+    }
+}
+
+/// Runs a test, returning the report of the assertion that failed, or a report of the panic message when the panic
+/// did not come from an assertion.
+///
+/// A test ended by a [disabled assertion](report::assertions_disabled) passes, and the report of the assertion is
+/// left for [`report::take_last_failure`].
+pub fn run_test(test: impl FnOnce()) -> Result<(), MismatchReport> {
+    report::take_last_failure();
+    let Err(payload) = silence_panics(|| panic::catch_unwind(AssertUnwindSafe(test))) else {
+        return Ok(());
+    };
+    if payload.is::<DisabledAssertion>() {
+        return Ok(());
+    }
+    Err(panic_report(payload.as_ref()))
+}
+
+/// Returns the report of the assertion that panicked with the payload, or a report of the panic message when the
+/// panic did not come from an assertion.
+pub(crate) fn panic_report(payload: &(dyn Any + Send)) -> MismatchReport {
+    let message = panic_message(payload);
+    match report::take_last_failure() {
+        Some(failure) if failure.to_string() == message => failure,
+        _ => MismatchReport::new("panic", message.trim_end()),
+    }
+}
+
+fn write_case(xml: &mut String, case: &TestCase) {
+    let _ = write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+        escape_attribute(&case.name),
+        escape_attribute(&case.classname),
+        case.duration.as_secs_f64()
+    );
+    match &case.outcome {
+        TestOutcome::Passed => xml.push_str("/>\n"),
+        TestOutcome::FlakyPassed(attempts) => {
+            xml.push_str(">\n");
+            for report in attempts {
+                let _ = writeln!(
+                    xml,
+                    "      <flakyFailure message=\"{}\" type=\"{}\">{}</flakyFailure>",
+                    escape_attribute(&report.summary),
+                    escape_attribute(&report.field),
+                    escape(report.to_string().trim_end())
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        TestOutcome::Skipped(reason) => {
+            let _ = writeln!(xml, ">\n      <skipped message=\"{}\"/>\n    </testcase>", escape_attribute(reason));
+        }
+        TestOutcome::Failed(report) => {
+            xml.push_str(">\n      <properties>\n");
+            let properties = [
+                ("mismatch.field", Some(&report.field)),
+                ("mismatch.expected", report.expected.as_ref()),
+                ("mismatch.actual", report.actual.as_ref()),
+            ];
+            for (name, value) in properties {
+                if let Some(value) = value {
+                    let _ = writeln!(xml, "        <property name=\"{}\" value=\"{}\"/>", name, escape_attribute(value));
+                }
+            }
+            let _ = writeln!(
+                xml,
+                "      </properties>\n      <failure message=\"{}\" type=\"{}\">{}</failure>\n    </testcase>",
+                escape_attribute(&report.summary),
+                escape_attribute(&report.field),
+                escape(report.to_string().trim_end())
+            );
+        }
+    }
+}
+
+/// Escapes text for an XML attribute or element, dropping the control characters XML cannot represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes text for an XML attribute, keeping its line breaks and tabs, which parsers would otherwise normalize to
+/// spaces.
+fn escape_attribute(text: &str) -> String {
+    escape(text)
+        .replace('\t', "&#9;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+}
@@ -0,0 +1,212 @@
+//! Given/When/Then scenarios for integration tests.
+//!
+//! A [`Scenario`] runs its steps in order against a shared state, and when a step fails, the failure names the step
+//! and lists which steps passed and which were skipped:
+//!
+//! ```no_run
+//! use attestify_test_framework::scenario::Scenario;
+//! use attestify_test_framework::workspace::TestWorkspace;
+//!
+//! fn workspace_with_procedure(name: &'static str) -> impl FnOnce(&mut TestWorkspace) {
+//!     move |workspace| {
+//!         workspace.create_file(&format!("procedures/{}.yaml", name), "");
+//!     }
+//! }
+//!
+//! fn run_usecase(workspace: &mut TestWorkspace) {
+//!     workspace.create_file("report.yaml", "status: passed");
+//! }
+//!
+//! fn expect_report_written(name: &'static str) -> impl FnOnce(&mut TestWorkspace) {
+//!     move |workspace| assert!(workspace.path_for(name).exists())
+//! }
+//!
+//! Scenario::new()
+//!     .given(workspace_with_procedure("p1"))
+//!     .when(run_usecase)
+//!     .then(expect_report_written("report.yaml"))
+//!     .run();
+//! ```
+//!
+//! Steps are named after their function, or after the function that returned them, e.g. `workspace_with_procedure`.
+//! A closure written inline is named after the enclosing function, so give it a name with [`step`].
+
+use crate::assertions::report::{self, MismatchReport};
+use crate::reporting::run_test;
+use std::fmt;
+
+/// The phase of a scenario a step belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Given,
+    When,
+    Then,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Given => "Given",
+            Phase::When => "When",
+            Phase::Then => "Then",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A named step of a scenario, created with [`step`] or from any `FnOnce(&mut S)`.
+pub struct Step<S> {
+    name: String,
+    action: Box<dyn FnOnce(&mut S)>,
+}
+
+/// Creates a step with the given name, for closures written inline.
+///
+/// # Arguments
+///
+/// * `name` - The name shown in the failure output, e.g. `"the procedure is removed"`.
+/// * `action` - The step, which acts on or asserts about the scenario's state.
+///
+pub fn step<S>(name: impl Into<String>, action: impl FnOnce(&mut S) + 'static) -> Step<S> {
+    Step {
+        name: name.into(),
+        action: Box::new(action),
+    }
+}
+
+/// Converts a value into a [`Step`].  Implemented for [`Step`] and for any `FnOnce(&mut S)`, which is named after
+/// its function.
+pub trait IntoStep<S> {
+    fn into_step(self) -> Step<S>;
+}
+
+impl<S> IntoStep<S> for Step<S> {
+    fn into_step(self) -> Step<S> {
+        self
+    }
+}
+
+impl<S, F: FnOnce(&mut S) + 'static> IntoStep<S> for F {
+    fn into_step(self) -> Step<S> {
+        step(step_name(std::any::type_name::<F>()), self)
+    }
+}
+
+/// A sequence of Given/When/Then steps run against a shared state of type `S`, created with [`Scenario::new`].
+#[must_use = "a scenario does nothing until it is run"]
+pub struct Scenario<S> {
+    state: S,
+    steps: Vec<(Phase, Step<S>)>,
+}
+
+impl<S: Default> Scenario<S> {
+    /// Creates a scenario that starts from the default state.
+    pub fn new() -> Self {
+        Scenario::with_state(S::default())
+    }
+}
+
+impl<S: Default> Default for Scenario<S> {
+    fn default() -> Self {
+        Scenario::new()
+    }
+}
+
+impl<S> Scenario<S> {
+    /// Creates a scenario that starts from the given state.
+    pub fn with_state(state: S) -> Self {
+        Scenario { state, steps: Vec::new() }
+    }
+
+    /// Adds a step that arranges the state.
+    pub fn given(self, step: impl IntoStep<S>) -> Self {
+        self.add(Phase::Given, step)
+    }
+
+    /// Adds a step that runs the behaviour under test.
+    pub fn when(self, step: impl IntoStep<S>) -> Self {
+        self.add(Phase::When, step)
+    }
+
+    /// Adds a step that asserts the outcome.
+    pub fn then(self, step: impl IntoStep<S>) -> Self {
+        self.add(Phase::Then, step)
+    }
+
+    /// Adds a step to the same phase as the previous step, or to [`Phase::Given`] when it is the first step.
+    pub fn and(self, step: impl IntoStep<S>) -> Self {
+        let phase = self.steps.last().map_or(Phase::Given, |(phase, _)| *phase);
+        self.add(phase, step)
+    }
+
+    /// Runs the steps in order and returns the final state.  The first step that fails fails the test with a report
+    /// naming the step, and the remaining steps are skipped.
+    pub fn run(self) -> S {
+        report::enforce(self.check())
+    }
+
+    /// Runs the steps in order like [`Scenario::run`], returning the report of the failed step instead of panicking.
+    pub fn check(mut self) -> Result<S, MismatchReport> {
+        let names: Vec<String> = self
+            .steps
+            .iter()
+            .map(|(phase, step)| format!("{} {}", phase, step.name))
+            .collect();
+
+        for (index, (_, step)) in self.steps.into_iter().enumerate() {
+            let state = &mut self.state;
+            if let Err(failure) = run_test(move || (step.action)(state)) {
+                return Err(step_failure(&names, index, failure));
+            }
+        }
+        Ok(self.state)
+    }
+
+    fn add(mut self, phase: Phase, step: impl IntoStep<S>) -> Self {
+        self.steps.push((phase, step.into_step()));
+        self
+    }
+}
+
+fn step_failure(names: &[String], failed: usize, failure: MismatchReport) -> MismatchReport {
+    let steps: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let status = match index.cmp(&failed) {
+                std::cmp::Ordering::Less => "passed",
+                std::cmp::Ordering::Equal => "FAILED",
+                std::cmp::Ordering::Greater => "skipped",
+            };
+            format!("\t\t[{}]\t{}", status, name)
+        })
+        .collect();
+
+    let mut hint = format!("\tFailure:\t{}\n", failure.summary);
+    if let Some(detail) = &failure.hint {
+        hint.push_str(detail.trim_end());
+        hint.push('\n');
+    }
+    hint.push_str("\tSteps:\n");
+    hint.push_str(&steps.join("\n"));
+
+    let mut report = MismatchReport::new(
+        names[failed].clone(),
+        format!("The scenario failed at the step '{}'.", names[failed]),
+    )
+    .hint(hint);
+    report.expected = failure.expected;
+    report.actual = failure.actual;
+    report
+}
+
+/// Returns the name of the function a step's type belongs to, e.g. `run_usecase` for
+/// `my_tests::steps::run_usecase` or `workspace_with_procedure` for
+/// `my_tests::steps::workspace_with_procedure::{{closure}}`.
+fn step_name(type_name: &str) -> String {
+    let mut path = type_name.split('<').next().unwrap_or(type_name);
+    while let Some(parent) = path.strip_suffix("::{{closure}}") {
+        path = parent;
+    }
+    path.rsplit("::").next().unwrap_or(path).to_string()
+}
@@ -0,0 +1,222 @@
+use crate::http_stub::{StubResponse, write_response};
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How a [`SlowServer`] treats every connection it accepts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlowBehavior {
+    /// Reads everything the client sends and never responds, until the server is dropped.
+    Stall,
+    /// Reads the request head, then writes the bytes `chunk_size` at a time, waiting `interval` before each chunk,
+    /// and closes the connection.
+    Drip {
+        bytes: Vec<u8>,
+        chunk_size: usize,
+        interval: Duration,
+    },
+    /// Reads the request head, then resets the connection, so the client gets a connection reset error instead of
+    /// a response.
+    Reset,
+}
+
+#[derive(Default)]
+struct State {
+    connections: AtomicUsize,
+    stalled: Mutex<Vec<TcpStream>>,
+}
+
+/// A TCP server on a random localhost port that misbehaves in a controlled way, so gateway timeouts and retries
+/// can be tested deterministically.  The server shuts down, and closes every stalled connection, when it is
+/// dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::slow_server::SlowServer;
+/// use std::time::Duration;
+///
+/// let server = SlowServer::stall();
+/// // configure the gateway with a 100ms timeout and server.url(), then assert it returns a timeout error
+///
+/// let server = SlowServer::reset();
+/// // assert the gateway retries, then check how many times it connected
+/// assert_eq!(server.connections(), 3);
+/// ```
+pub struct SlowServer {
+    address: SocketAddr,
+    state: Arc<State>,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl SlowServer {
+    /// Starts a server that accepts connections and never responds.
+    pub fn stall() -> Self {
+        SlowServer::start(SlowBehavior::Stall)
+    }
+
+    /// Starts a server that sends the bytes slowly.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Everything the server sends on each connection.
+    /// * `chunk_size` - How many bytes are sent at a time; at least 1.
+    /// * `interval` - How long the server waits before sending each chunk.
+    ///
+    pub fn drip(bytes: impl Into<Vec<u8>>, chunk_size: usize, interval: Duration) -> Self {
+        SlowServer::start(SlowBehavior::Drip {
+            bytes: bytes.into(),
+            chunk_size: chunk_size.max(1),
+            interval,
+        })
+    }
+
+    /// Starts a server that sends the HTTP response slowly, like [`SlowServer::drip`].  The response's own latency
+    /// is ignored.
+    pub fn drip_response(response: &StubResponse, chunk_size: usize, interval: Duration) -> Self {
+        let mut bytes = Vec::new();
+        write_response(&mut bytes, "GET", response).expect("Could not render the response.");
+        SlowServer::drip(bytes, chunk_size, interval)
+    }
+
+    /// Starts a server that resets every connection once the request is received.
+    pub fn reset() -> Self {
+        SlowServer::start(SlowBehavior::Reset)
+    }
+
+    /// Starts a server with the behavior on a random localhost port.
+    pub fn start(behavior: SlowBehavior) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind the slow server.");
+        let address = listener
+            .local_addr()
+            .expect("Could not get the address of the slow server.");
+        let state = Arc::new(State::default());
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let worker = {
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        state.connections.fetch_add(1, Ordering::SeqCst);
+                        // Stalled connections are only closed when the server is dropped.
+                        if behavior == SlowBehavior::Stall
+                            && let Ok(clone) = stream.try_clone()
+                        {
+                            state.stalled.lock().unwrap().push(clone);
+                        }
+                        let behavior = behavior.clone();
+                        let shutdown = Arc::clone(&shutdown);
+                        thread::spawn(move || handle(stream, &behavior, &shutdown));
+                    }
+                }
+            })
+        };
+
+        SlowServer {
+            address,
+            state,
+            shutdown,
+            worker: Some(worker),
+        }
+    }
+
+    /// Returns the address the server listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns the base URL of the server, e.g. `http://127.0.0.1:49152`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Returns how many connections the server has accepted, e.g. to assert how often a gateway retried.
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for SlowServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the shutdown flag.
+        let _ = TcpStream::connect(self.address);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        for stream in self.state.stalled.lock().unwrap().drain(..) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn handle(mut stream: TcpStream, behavior: &SlowBehavior, shutdown: &AtomicBool) {
+    match behavior {
+        SlowBehavior::Stall => {
+            let _ = std::io::copy(&mut stream, &mut std::io::sink());
+        }
+        SlowBehavior::Drip {
+            bytes,
+            chunk_size,
+            interval,
+        } => {
+            read_head(&mut stream);
+            for chunk in bytes.chunks(*chunk_size) {
+                thread::sleep(*interval);
+                if shutdown.load(Ordering::SeqCst) || stream.write_all(chunk).and_then(|_| stream.flush()).is_err() {
+                    return;
+                }
+            }
+        }
+        SlowBehavior::Reset => {
+            read_head(&mut stream);
+            reset(stream);
+        }
+    }
+}
+
+/// Reads until the blank line that ends an HTTP request head, or until the client stops sending.
+fn read_head(stream: &mut TcpStream) {
+    let mut head = Vec::new();
+    let mut byte = [0; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut byte) {
+            Ok(1) => head.push(byte[0]),
+            _ => return,
+        }
+    }
+}
+
+/// Closes the connection with a TCP reset rather than an orderly shutdown, by setting a zero linger time.
+#[cfg(unix)]
+fn reset(stream: TcpStream) {
+    use std::os::fd::AsRawFd;
+
+    let linger = libc::linger { l_onoff: 1, l_linger: 0 };
+    // SAFETY: the socket is open for the duration of the call, and `linger` is a valid `SO_LINGER` option value.
+    unsafe {
+        libc::setsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_LINGER,
+            &linger as *const libc::linger as *const libc::c_void,
+            std::mem::size_of::<libc::linger>() as libc::socklen_t,
+        );
+    }
+    drop(stream);
+}
+
+/// Closes the connection.  Without `SO_LINGER` the client may see an orderly close instead of a reset.
+#[cfg(not(unix))]
+fn reset(stream: TcpStream) {
+    let _ = stream.shutdown(Shutdown::Both);
+}
@@ -0,0 +1,160 @@
+use crate::assertions::report::{self, MismatchReport};
+use serde::Serialize;
+use std::env;
+use std::fmt::{Debug, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The environment variable that regenerates snapshots instead of comparing them, when it is set to `1`.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// The directory, relative to the crate root, that snapshots are stored in.
+pub const SNAPSHOT_DIR: &str = "tests/__snapshots__";
+
+/// Returns true when snapshots should be regenerated, see [`UPDATE_SNAPSHOTS_ENV_VAR`].
+pub fn update_requested() -> bool {
+    env::var(UPDATE_SNAPSHOTS_ENV_VAR).as_deref() == Ok("1")
+}
+
+/// Returns the path of the named snapshot in the crate.
+///
+/// # Arguments
+///
+/// * `manifest_dir` - The root directory of the crate the snapshot belongs to.
+/// * `name` - The name of the snapshot.
+///
+pub fn snapshot_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir)
+        .join(SNAPSHOT_DIR)
+        .join(format!("{}.snap", name))
+}
+
+/// Serializes the value into the YAML text stored in a snapshot.
+pub fn to_snapshot<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_yaml::to_string(value)
+        .unwrap_or_else(|err| panic!("Could not serialize the value for the snapshot:\n\t{}\n", err))
+}
+
+/// Renders the [`Display`] and pretty [`Debug`] output of the value into the text stored in a snapshot, so a change
+/// to either, such as the wording of an error message shown by the CLI, changes the snapshot.
+pub fn to_display_snapshot<T: Display + Debug + ?Sized>(value: &T) -> String {
+    format!("--- Display ---\n{}\n--- Debug ---\n{:#?}\n", value, value)
+}
+
+/// Compares the contents against the named snapshot, failing the test with a diff when they differ.  When
+/// [`update_requested`] is true the snapshot is written with the contents instead.
+///
+/// # Arguments
+///
+/// * `manifest_dir` - The root directory of the crate the snapshot belongs to.
+/// * `name` - The name of the snapshot.
+/// * `contents` - The actual contents.
+///
+pub fn assert_snapshot(manifest_dir: &str, name: &str, contents: &str) {
+    let path = snapshot_path(manifest_dir, name);
+
+    if update_requested() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|_| {
+                panic!("Could not create the snapshot directory '{}'.", parent.display())
+            });
+        }
+        fs::write(&path, contents)
+            .unwrap_or_else(|_| panic!("Could not write the snapshot '{}'.", path.display()));
+        return;
+    }
+
+    report::record_evaluation();
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        report::fail(
+            MismatchReport::new(
+                name,
+                format!(
+                    "The snapshot '{}' does not exist.  Run the test with {}=1 to create it.",
+                    name, UPDATE_SNAPSHOTS_ENV_VAR
+                ),
+            )
+            .expected(format!("a snapshot at '{}'", path.display()))
+            .actual(err.to_string()),
+        )
+    });
+
+    if expected != contents {
+        report::fail(
+            MismatchReport::new(
+                name,
+                format!(
+                    "The value does not match the snapshot '{}'.  Run the test with {}=1 to update it.",
+                    name, UPDATE_SNAPSHOTS_ENV_VAR
+                ),
+            )
+            .hint(crate::diff::render(&expected, contents)),
+        );
+    }
+}
+
+/// Asserts that a value matches a stored snapshot.
+///
+/// The value is serialized to YAML and compared against `tests/__snapshots__/<name>.snap` in the crate running the
+/// test.  A mismatch fails the test with a diff.  Run the tests with `UPDATE_SNAPSHOTS=1` to create or regenerate
+/// the snapshots.
+///
+/// # Arguments
+///
+/// * `$value` - The value to compare, which must implement [`serde::Serialize`].
+/// * `$name` - The name of the snapshot.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_matches_snapshot;
+///
+/// let report = vec!["evidence-1", "evidence-2"];
+/// assert_matches_snapshot!(report, "report_baseline");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_matches_snapshot {
+    ($value:expr, $name:expr) => {{
+        $crate::snapshot::assert_snapshot(
+            env!("CARGO_MANIFEST_DIR"),
+            $name,
+            &$crate::snapshot::to_snapshot(&$value),
+        )
+    }};
+}
+
+/// Asserts that the [`Display`](std::fmt::Display) and [`Debug`](std::fmt::Debug) output of a value matches a stored
+/// snapshot, so accidental changes to user-facing error text are caught.
+///
+/// The value is rendered with [`to_display_snapshot`](crate::snapshot::to_display_snapshot) and compared against
+/// `tests/__snapshots__/<name>.snap` in the crate running the test, like
+/// [`assert_matches_snapshot!`](crate::assert_matches_snapshot).  Run the tests with `UPDATE_SNAPSHOTS=1` to create
+/// or regenerate the snapshots.
+///
+/// # Arguments
+///
+/// * `$value` - The value to compare, typically an [`nape_kernel::error::Error`], which must implement
+///   [`Display`](std::fmt::Display) and [`Debug`](std::fmt::Debug).
+/// * `$name` - The name of the snapshot.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_error_display_stable;
+/// use std::io;
+///
+/// let error = io::Error::new(io::ErrorKind::NotFound, "The procedure 'nrn:procedure:attestify' does not exist.");
+/// assert_error_display_stable!(error, "procedure_not_found_error");
+/// ```
+///
+#[macro_export]
+macro_rules! assert_error_display_stable {
+    ($value:expr, $name:expr) => {{
+        $crate::snapshot::assert_snapshot(
+            env!("CARGO_MANIFEST_DIR"),
+            $name,
+            &$crate::snapshot::to_display_snapshot(&$value),
+        )
+    }};
+}
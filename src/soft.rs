@@ -0,0 +1,171 @@
+use crate::assertions::report::{self, DisabledAssertion, MismatchReport};
+use crate::matchers::KernelErrorAssert;
+use crate::panics::silence_panics;
+use crate::reporting::panic_report;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+/// Collects the outcome of many assertions and fails the test once, at the end, listing every failure.
+///
+/// This is useful when validating large documents such as assurance reports, where stopping at the first mismatched
+/// field hides the rest.  The failures are reported when [`SoftAssertions::assert_all`] is called or the collector is
+/// dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::soft::SoftAssertions;
+///
+/// let mut soft = SoftAssertions::new();
+/// soft.eq("1.0.0", "1.0.1", "procedure version");
+/// soft.is_true(false, "the report is signed");
+/// soft.assert_all();
+/// ```
+#[derive(Debug, Default)]
+pub struct SoftAssertions {
+    failures: Vec<MismatchReport>,
+    evaluated: bool,
+}
+
+impl SoftAssertions {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a failure described by one sentence.
+    pub fn fail(&mut self, failure: impl Into<String>) {
+        self.record(MismatchReport::new("", failure));
+    }
+
+    /// Records a failure reported by one of the `check_*` macros or functions.
+    pub fn record(&mut self, report: MismatchReport) {
+        self.failures.push(report);
+    }
+
+    /// Records a failure when the values are not equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The expected value.
+    /// * `actual` - The actual value.
+    /// * `description` - What is being compared, used in the failure message.
+    ///
+    pub fn eq<T: PartialEq<U> + Debug, U: Debug>(&mut self, expected: T, actual: U, description: &str) {
+        if expected != actual {
+            self.record(
+                MismatchReport::new(description, format!("{} does not match.", description))
+                    .expected(format!("{:?}", expected))
+                    .actual(format!("{:?}", actual)),
+            );
+        }
+    }
+
+    /// Records a failure when the condition is false.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition that should hold.
+    /// * `description` - What the condition means, used in the failure message.
+    ///
+    pub fn is_true(&mut self, condition: bool, description: &str) {
+        if !condition {
+            self.record(MismatchReport::new(description, format!("Expected {}.", description)));
+        }
+    }
+
+    /// Records the mismatches of a fluent kernel error assertion, instead of failing the test immediately.
+    pub fn kernel_error<K: Debug + PartialEq, A: Debug + PartialEq>(&mut self, assertion: KernelErrorAssert<K, A>) {
+        self.failures.extend(assertion.into_mismatches());
+    }
+
+    /// Runs a closure that uses any of the panicking assertion macros, and records the report of the assertion that
+    /// failed, or of its panic message, instead of failing the test immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - What the closure checks, prefixed to the summary of the report.
+    /// * `check` - The closure making the assertions.
+    ///
+    pub fn check(&mut self, description: &str, check: impl FnOnce()) {
+        if let Err(payload) = silence_panics(|| panic::catch_unwind(AssertUnwindSafe(check))) {
+            // A disabled assertion ends the test as passed, as it would have outside the closure.
+            if payload.is::<DisabledAssertion>() {
+                panic::resume_unwind(payload);
+            }
+            let failure = panic_report(payload.as_ref());
+            self.record(MismatchReport {
+                summary: format!("{}: {}", description, failure.summary),
+                ..failure
+            });
+        }
+    }
+
+    /// Returns the failures recorded so far.
+    pub fn failures(&self) -> &[MismatchReport] {
+        &self.failures
+    }
+
+    /// Fails the test when any failure was recorded, listing all of them.
+    pub fn assert_all(mut self) {
+        self.evaluate();
+    }
+
+    fn evaluate(&mut self) {
+        self.evaluated = true;
+        report::record_evaluation();
+        if !self.failures.is_empty() {
+            let failures: Vec<String> = self
+                .failures
+                .iter()
+                .enumerate()
+                .map(|(index, failure)| format!("{}) {}", index + 1, failure.to_string().trim_end()))
+                .collect();
+            report::fail(
+                MismatchReport::new("soft", format!("{} soft assertion(s) failed:", self.failures.len()))
+                    .hint(format!("\n{}", failures.join("\n\n"))),
+            );
+        }
+    }
+}
+
+impl Drop for SoftAssertions {
+    fn drop(&mut self) {
+        if !self.evaluated && !thread::panicking() {
+            self.evaluate();
+        }
+    }
+}
+
+/// Records, in a [`SoftAssertions`], whether an [`nape_kernel::error::Error`] matches the expected kind, audience,
+/// and message, without failing the test immediately.
+///
+/// # Arguments
+///
+/// * `$soft` - The [`SoftAssertions`] collecting the failures.
+/// * `$result` - A `Result` expression that is expected to be an [`nape_kernel::error::Error`].
+/// * `$expected_kind` - The expected error kind. Should be of type [`nape_kernel::error::Kind`].
+/// * `$expected_audience` - The expected error audience. Should be of type [`nape_kernel::error::Audience`].
+/// * `$expected_message` - The expected error message.
+///
+#[macro_export]
+macro_rules! soft_kernel_error_eq {
+    ($soft:expr, $result:expr, $expected_kind:expr, $expected_audience:expr, $expected_message:expr) => {
+        match $result {
+            Ok(val) => $soft.fail(format!(
+                "An Error was expected, although one was not returned:\n\t{:?}",
+                val
+            )),
+            Err(e) => {
+                $crate::__observe_error!(e);
+                $soft.kernel_error(
+                    $crate::matchers::KernelErrorAssert::new(e.kind.clone(), e.audience.clone(), e.message.to_string())
+                        .kind($expected_kind)
+                        .audience($expected_audience)
+                        .message(&$expected_message.to_string()),
+                )
+            }
+        }
+    };
+}
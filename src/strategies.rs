@@ -0,0 +1,108 @@
+//! [`proptest`] strategies for kernel value objects and errors.
+//!
+//! Enable the `proptest` feature to use them.  The kernel types are not visible to this crate, so the kinds and
+//! audiences are drawn from the variants the test passes in, and errors are built with the test's own constructor:
+//!
+//! ```no_run
+//! use attestify_test_framework::strategies;
+//! use proptest::prelude::*;
+//! # #[derive(Debug, Clone)] enum Kind { InvalidInput, GatewayError }
+//! # #[derive(Debug, Clone)] enum Audience { User, System }
+//! # #[derive(Debug, Clone)] struct Error { kind: Kind, audience: Audience, message: String }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn rejects_malformed_nrns(nrn in strategies::malformed_nrn()) {
+//!         // assert the parser returns an InvalidInput error
+//!     }
+//! }
+//!
+//! let errors = strategies::kernel_error(
+//!     &[Kind::InvalidInput, Kind::GatewayError],
+//!     &[Audience::User, Audience::System],
+//!     |kind, audience, message| Error { kind, audience, message },
+//! );
+//! ```
+
+use proptest::prelude::*;
+use proptest::sample::select;
+use std::fmt::Debug;
+
+/// The character set of a single NRN segment, e.g. `attestify` or `test-subject`.
+const SEGMENT: &str = "[a-z][a-z0-9-]{0,15}[a-z0-9]";
+
+/// Draws one of the kinds, e.g. every variant of [`nape_kernel::error::Kind`].
+pub fn error_kind<K: Clone + Debug + 'static>(kinds: &[K]) -> impl Strategy<Value = K> {
+    select(kinds.to_vec())
+}
+
+/// Draws one of the audiences, e.g. every variant of [`nape_kernel::error::Audience`].
+pub fn audience<A: Clone + Debug + 'static>(audiences: &[A]) -> impl Strategy<Value = A> {
+    select(audiences.to_vec())
+}
+
+/// Draws a non-empty, printable error message.
+pub fn error_message() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9][A-Za-z0-9 ,.:'/_-]{0,79}"
+}
+
+/// Draws an error built from a drawn kind, audience, and message.
+///
+/// # Arguments
+///
+/// * `kinds` - The kinds to draw from.
+/// * `audiences` - The audiences to draw from.
+/// * `build` - Builds the error, typically an [`nape_kernel::error::Error`], from the kind, audience, and message.
+///
+pub fn kernel_error<K, A, E>(
+    kinds: &[K],
+    audiences: &[A],
+    build: impl Fn(K, A, String) -> E + Clone + 'static,
+) -> impl Strategy<Value = E>
+where
+    K: Clone + Debug + 'static,
+    A: Clone + Debug + 'static,
+    E: Debug,
+{
+    (error_kind(kinds), audience(audiences), error_message())
+        .prop_map(move |(kind, audience, message)| build(kind, audience, message))
+}
+
+/// Draws a valid NRN of the resource type, e.g. `nrn:subject:attestify:test-subject` for `subject`.
+pub fn nrn(resource_type: &'static str) -> impl Strategy<Value = String> {
+    (SEGMENT, SEGMENT).prop_map(move |(owner, name)| format!("nrn:{}:{}:{}", resource_type, owner, name))
+}
+
+/// Draws a valid subject NRN.
+pub fn subject_nrn() -> impl Strategy<Value = String> {
+    nrn("subject")
+}
+
+/// Draws a valid procedure NRN.
+pub fn procedure_nrn() -> impl Strategy<Value = String> {
+    nrn("procedure")
+}
+
+/// Draws a valid activity NRN.
+pub fn activity_nrn() -> impl Strategy<Value = String> {
+    nrn("activity")
+}
+
+/// Draws a human-readable procedure name, e.g. `Quarterly Access Review 2`.
+pub fn procedure_name() -> impl Strategy<Value = String> {
+    "[A-Z][a-z]{2,11}( [A-Z]?[a-z0-9]{1,11}){0,4}"
+}
+
+/// Draws a string that is not a valid NRN: a missing or wrong scheme, too few or too many segments, empty
+/// segments, or invalid characters.
+pub fn malformed_nrn() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        (SEGMENT, SEGMENT, SEGMENT).prop_map(|(a, b, c)| format!("{}:{}:{}", a, b, c)),
+        (SEGMENT, SEGMENT).prop_map(|(a, b)| format!("urn:subject:{}:{}", a, b)),
+        SEGMENT.prop_map(|a| format!("nrn:subject:{}", a)),
+        (SEGMENT, SEGMENT, SEGMENT).prop_map(|(a, b, c)| format!("nrn:subject:{}:{}:{}", a, b, c)),
+        SEGMENT.prop_map(|a| format!("nrn:subject::{}", a)),
+        (SEGMENT, "[ A-Z!@#$%^&*]{1,4}").prop_map(|(a, b)| format!("nrn:subject:{}:{}", a, b)),
+    ]
+}
@@ -0,0 +1,11 @@
+//! The synchronization primitives of the doubles.
+//!
+//! They are loom's when the crate is built with the `loom` feature and `--cfg loom`, so a
+//! [loom model](crate::interleaving) explores the interleavings of the doubles' locks along with the code under
+//! test, and std's otherwise.  Loom's primitives only work inside a model, so the `loom` feature alone does not
+//! switch them, and the other tests of a crate that enables it keep working.
+
+#[cfg(all(feature = "loom", loom))]
+pub(crate) use loom::sync::Mutex;
+#[cfg(not(all(feature = "loom", loom)))]
+pub(crate) use std::sync::Mutex;
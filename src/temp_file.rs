@@ -0,0 +1,178 @@
+use crate::workspace::{TestWorkspace, unique_temp_dir};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Writes a file from an inline template, substituting `{{variable}}` placeholders, so a test composing a procedure
+/// file that references local paths does not replace them by hand.
+///
+/// The variables are:
+///
+/// * `{{workspace}}` - The root of the workspace the file is written into, or of its own temporary directory.
+/// * `{{now}}` - The current time in RFC 3339 format, e.g. `2024-01-01T00:00:00Z`, or the time set with
+///   [`TempFileBuilder::now`].
+/// * Any variable set with [`TempFileBuilder::var`].
+///
+/// An unknown placeholder fails the test, so a typo is not written into the file.
+///
+/// ```no_run
+/// use attestify_test_framework::temp_file::TempFileBuilder;
+/// use attestify_test_framework::workspace::TestWorkspace;
+///
+/// let workspace = TestWorkspace::new();
+/// let procedure = TempFileBuilder::new("procedures/build.yaml")
+///     .template("evidence_dir: {{workspace}}/evidence\ncollected_at: {{now}}\nowner: {{owner}}\n")
+///     .var("owner", "platform-team")
+///     .write_to(&workspace);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempFileBuilder {
+    name: String,
+    template: String,
+    variables: BTreeMap<String, String>,
+    now: Option<DateTime<Utc>>,
+}
+
+impl TempFileBuilder {
+    /// Creates a builder for an empty file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The path of the file relative to the directory it is written into, e.g. `procedures/build.yaml`.
+    ///
+    pub fn new(name: &str) -> Self {
+        TempFileBuilder {
+            name: name.to_string(),
+            template: String::new(),
+            variables: BTreeMap::new(),
+            now: None,
+        }
+    }
+
+    /// Sets the template the contents are rendered from.
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = template.to_string();
+        self
+    }
+
+    /// Sets a variable, replacing `{{name}}` with the value.  It takes precedence over a built-in variable.
+    pub fn var(mut self, name: &str, value: impl ToString) -> Self {
+        self.variables.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Sets the time `{{now}}` is replaced with, for contents that are the same on every run.
+    pub fn now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Renders the template with the workspace root as `{{workspace}}`.  The test fails on an unknown or
+    /// unterminated placeholder.
+    pub fn render(&self, workspace: &Path) -> String {
+        let now = self.now.unwrap_or_else(|| DateTime::<Utc>::from(SystemTime::now()));
+        let mut rendered = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find("}}") else {
+                panic!(
+                    "The template of '{}' has an unterminated placeholder:\n\t{}\n",
+                    self.name,
+                    &rest[start..]
+                );
+            };
+            let name = rest[start + 2..start + end].trim();
+            match (self.variables.get(name), name) {
+                (Some(value), _) => rendered.push_str(value),
+                (None, "workspace") => rendered.push_str(&workspace.display().to_string()),
+                (None, "now") => rendered.push_str(&now.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                (None, _) => panic!(
+                    "The template of '{}' has the unknown variable '{}'.\n\tVariables:\n\t\t{}\n",
+                    self.name,
+                    name,
+                    self.variable_names().join("\n\t\t")
+                ),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+
+    /// Renders the file into the workspace, creating any parent directories, and returns its path.  An existing
+    /// file is overwritten.
+    pub fn write_to(&self, workspace: &TestWorkspace) -> PathBuf {
+        write(workspace.root(), &self.name, &self.render(workspace.root()))
+    }
+
+    /// Renders the file into a temporary directory of its own, which is removed when the returned [`TempFile`] is
+    /// dropped.
+    pub fn write(&self) -> TempFile {
+        let dir = unique_temp_dir("nape_temp_file");
+        let path = write(&dir, &self.name, &self.render(&dir));
+        TempFile { dir, path }
+    }
+
+    fn variable_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = ["now", "workspace"].iter().map(ToString::to_string).collect();
+        names.extend(self.variables.keys().cloned());
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|_| panic!("Could not create the parent directory for '{}'.", parent.display()));
+    }
+    fs::write(&path, contents).unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+    path
+}
+
+/// A file written by [`TempFileBuilder::write`].  Its directory, and everything in it, is removed when it is
+/// dropped.  It dereferences to the file's path.
+#[derive(Debug)]
+pub struct TempFile {
+    dir: PathBuf,
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the temporary directory the file is in, which `{{workspace}}` was replaced with.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Deref for TempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // Errors are ignored, as in TestWorkspace: panicking in drop during a panic would abort the test binary.
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,156 @@
+//! Loading fixture files from the `tests/data` directory of the crate running the test.
+//!
+//! [`testdata!`](crate::testdata) resolves the directory from the calling crate's `CARGO_MANIFEST_DIR`, so tests
+//! name their fixtures without building paths, and a missing fixture fails with the files that do exist:
+//!
+//! ```no_run
+//! use attestify_test_framework::testdata;
+//! # #[derive(serde::Deserialize)] struct Procedure { kind: String }
+//!
+//! let procedure: Procedure = testdata!().load_yaml("procedures/minimal.yaml");
+//! let payload = testdata!().read_to_string("evidence/build-log.json");
+//! ```
+//!
+//! `testdata!("procedures/minimal.yaml")` embeds the file in the test binary instead, so a missing fixture is a
+//! compile error.
+
+use crate::assertions::structs::type_name;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The directory, relative to the crate root, that test data is read from.
+pub const TESTDATA_DIR: &str = "tests/data";
+
+/// Reads fixture files from a test data directory.  Every method panics with a clear message when the file is
+/// missing or cannot be loaded, as a test cannot continue without its fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestData {
+    dir: PathBuf,
+}
+
+impl TestData {
+    /// Reads from the [`TESTDATA_DIR`] of the crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_dir` - The root directory of the crate, `env!("CARGO_MANIFEST_DIR")`.
+    ///
+    pub fn new(manifest_dir: &str) -> Self {
+        Self::in_dir(Path::new(manifest_dir).join(TESTDATA_DIR))
+    }
+
+    /// Reads from the directory.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        TestData { dir: dir.into() }
+    }
+
+    /// Returns the directory the test data is read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the file, failing the test when it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The path of the file relative to the test data directory, e.g. `procedures/minimal.yaml`.
+    ///
+    pub fn path(&self, name: &str) -> PathBuf {
+        let path = self.dir.join(name);
+        if !path.is_file() {
+            panic!(
+                "The test data '{}' does not exist at '{}'.\n\tAvailable:\n{}\n",
+                name,
+                path.display(),
+                self.available(path.parent().unwrap_or(&self.dir))
+            );
+        }
+        path
+    }
+
+    /// Returns true when the file exists.
+    pub fn exists(&self, name: &str) -> bool {
+        self.dir.join(name).is_file()
+    }
+
+    /// Reads the file.
+    pub fn read(&self, name: &str) -> Vec<u8> {
+        let path = self.path(name);
+        fs::read(&path).unwrap_or_else(|err| panic!("Could not read the test data '{}':\n\t{}\n", path.display(), err))
+    }
+
+    /// Reads the file as UTF-8 text.
+    pub fn read_to_string(&self, name: &str) -> String {
+        let path = self.path(name);
+        fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Could not read the test data '{}':\n\t{}\n", path.display(), err))
+    }
+
+    /// Reads the file and deserializes it from YAML.
+    pub fn load_yaml<T: DeserializeOwned>(&self, name: &str) -> T {
+        serde_yaml::from_str(&self.read_to_string(name)).unwrap_or_else(|err| {
+            panic!("The test data '{}' is not a valid {} in YAML:\n\t{}\n", name, type_name::<T>(), err)
+        })
+    }
+
+    /// Reads the file and deserializes it from JSON.
+    pub fn load_json<T: DeserializeOwned>(&self, name: &str) -> T {
+        serde_json::from_str(&self.read_to_string(name)).unwrap_or_else(|err| {
+            panic!("The test data '{}' is not a valid {} in JSON:\n\t{}\n", name, type_name::<T>(), err)
+        })
+    }
+
+    /// Returns the names of the files in the subdirectory, relative to the test data directory, sorted, e.g. to run
+    /// a test for every fixture of a kind.
+    pub fn files(&self, subdir: &str) -> Vec<String> {
+        let dir = self.dir.join(subdir);
+        let entries = fs::read_dir(&dir).unwrap_or_else(|err| {
+            panic!("Could not list the test data directory '{}':\n\t{}\n", dir.display(), err)
+        });
+        let mut files: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| Path::new(subdir).join(entry.file_name()).to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Lists the files of the directory, or explains why there are none, for the message of a missing fixture.
+    fn available(&self, dir: &Path) -> String {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return format!("\t\t<the directory '{}' does not exist>", dir.display());
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(Result::ok)
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if entry.path().is_dir() { format!("{}/", name) } else { name }
+            })
+            .collect();
+        if names.is_empty() {
+            return "\t\t<none>".to_string();
+        }
+        names.sort();
+        names.iter().map(|name| format!("\t\t{}", name)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Returns a [`TestData`](crate::testdata::TestData) loader for the `tests/data` directory of the crate running the
+/// test, or, given a file name, the file's contents embedded in the test binary as a `&'static str`.
+///
+/// # Arguments
+///
+/// * `$name` - Optional. The path of a file relative to `tests/data`, which must be a string literal.  A missing
+///   file is a compile error.
+///
+#[macro_export]
+macro_rules! testdata {
+    () => {
+        $crate::testdata::TestData::new(env!("CARGO_MANIFEST_DIR"))
+    };
+    ($name:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/", $name))
+    };
+}
@@ -0,0 +1,302 @@
+use crate::assertions::config;
+use crate::assertions::report::{self, MismatchReport};
+use crate::panics::{catch_panic, panics_silenced, silence_panics};
+use std::fmt::Debug;
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Runs the closure on a separate thread and returns its value, failing the test if it does not complete within the
+/// duration.  See [`assert_completes_within`](crate::assert_completes_within).
+///
+/// A panic in the closure is re-raised on the calling thread, and is not printed when the calling thread's panics are
+/// silenced, e.g. inside [`catch_panic`].  When the duration elapses the closure's thread is
+/// left running, as Rust threads cannot be stopped, and the test fails immediately.
+///
+/// # Arguments
+///
+/// * `duration` - How long the closure may run.
+/// * `work` - The work that must complete in time.
+///
+pub fn complete_within<T: Send + 'static>(duration: Duration, work: impl FnOnce() -> T + Send + 'static) -> T {
+    report::record_evaluation();
+    let (sender, receiver) = mpsc::channel();
+    let silenced = panics_silenced();
+    let worker = thread::spawn(move || {
+        let work = panic::AssertUnwindSafe(work);
+        let outcome = if silenced {
+            silence_panics(|| panic::catch_unwind(work))
+        } else {
+            panic::catch_unwind(work)
+        };
+        let _ = sender.send(outcome);
+    });
+
+    match receiver.recv_timeout(duration) {
+        Ok(Ok(value)) => {
+            let _ = worker.join();
+            value
+        }
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(RecvTimeoutError::Timeout) => report::fail(
+            MismatchReport::new(
+                "duration",
+                format!(
+                    "The expression did not complete within {:?}; it may be deadlocked or blocked on a gateway call.",
+                    duration
+                ),
+            )
+            .expected(format!("at most {:?}", duration))
+            .actual(format!("still running after {:?}", duration)),
+        ),
+        Err(RecvTimeoutError::Disconnected) => report::fail(
+            MismatchReport::new("completion", "The expression's thread stopped without completing.")
+                .expected("a value")
+                .actual("no value"),
+        ),
+    }
+}
+
+/// The result of one evaluation of an [`eventually`] condition.
+pub trait Outcome {
+    /// Returns why the condition does not hold yet, or [`None`] when it holds.
+    fn failure(self) -> Option<String>;
+}
+
+impl Outcome for bool {
+    fn failure(self) -> Option<String> {
+        (!self).then(|| "The condition was false.".to_string())
+    }
+}
+
+impl Outcome for () {
+    fn failure(self) -> Option<String> {
+        None
+    }
+}
+
+impl<T, E: Debug> Outcome for Result<T, E> {
+    fn failure(self) -> Option<String> {
+        self.err().map(|err| format!("The condition returned an error: {:?}", err))
+    }
+}
+
+/// Evaluates the condition every interval until it holds, failing the test with the last failure when the timeout
+/// elapses first.  See [`assert_eventually`](crate::assert_eventually).
+///
+/// The condition holds when it returns `true`, `()`, or an [`Ok`], without panicking.  Panics, such as failed
+/// assertions, are caught and count as a failed attempt.
+///
+/// # Arguments
+///
+/// * `timeout` - How long to keep polling.
+/// * `interval` - How long to wait between attempts.
+/// * `description` - What the condition is, used in the failure message.
+/// * `condition` - The condition that must eventually hold.
+///
+pub fn eventually<O: Outcome>(
+    timeout: Duration,
+    interval: Duration,
+    description: &str,
+    mut condition: impl FnMut() -> O,
+) {
+    report::record_evaluation();
+    let start = Instant::now();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let mut outcome = None;
+        let failure = match catch_panic(|| outcome = condition().failure()) {
+            Some(message) => Some(format!("The condition panicked: {}", message)),
+            None => outcome,
+        };
+        let Some(failure) = failure else {
+            return;
+        };
+
+        if start.elapsed() >= timeout {
+            report::fail(
+                MismatchReport::new(
+                    description,
+                    format!("The condition did not hold within {:?} after {} attempts.", timeout, attempts),
+                )
+                .hint(format!("\tCondition:\t{}\n\tLast failure:\t{}", description, failure)),
+            );
+        }
+        thread::sleep(interval.min(timeout.saturating_sub(start.elapsed())));
+    }
+}
+
+/// Asserts that an expression completes within a duration, and returns its value.
+///
+/// The expression runs on a watchdog thread, so a deadlocked usecase fails the test instead of hanging the CI job.
+/// Because it runs on another thread, the expression takes ownership of what it uses, which must be `Send` and
+/// `'static`.
+///
+/// # Arguments
+///
+/// * `$duration` - The [`std::time::Duration`] the expression may run for.
+/// * `$expr` - The expression that must complete in time.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_completes_within;
+/// use std::time::Duration;
+///
+/// let total = assert_completes_within!(Duration::from_secs(5), (1..=10).sum::<u32>());
+/// ```
+///
+#[macro_export]
+macro_rules! assert_completes_within {
+    ($duration:expr, $expr:expr) => {
+        $crate::timing::complete_within($duration, move || $expr)
+    };
+}
+
+/// Asserts that a condition eventually holds, polling it every interval until the timeout elapses.  On failure it
+/// reports the number of attempts and the last failure.
+///
+/// This replaces sleeps in tests that wait on a background task or the stub HTTP server.  The condition holds when
+/// it evaluates to `true`, `()`, or an `Ok` without panicking, so it can be a boolean expression or a block of
+/// assertions.
+///
+/// # Arguments
+///
+/// * `$timeout` - The [`std::time::Duration`] to keep polling for.
+/// * `$interval` - The [`std::time::Duration`] to wait between attempts.
+/// * `$condition` - The condition that must eventually hold.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_eventually;
+/// use attestify_test_framework::http_stub::HttpStub;
+/// use std::time::Duration;
+///
+/// let server = HttpStub::start();
+/// // start the background upload
+/// assert_eventually!(Duration::from_secs(5), Duration::from_millis(50), server.requests().len() == 2);
+/// ```
+///
+#[macro_export]
+macro_rules! assert_eventually {
+    ($timeout:expr, $interval:expr, $condition:expr) => {
+        $crate::timing::eventually($timeout, $interval, stringify!($condition), || $condition)
+    };
+}
+
+/// Renders a duration for a person, in the largest unit under which it is at least one, e.g. `412ms`, `1.25s`, or
+/// `2m 3s`.  Microseconds are `µs`, or `us` when the [assertions config](crate::assertions::config()) is ASCII.
+pub fn humane_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    let trim = |value: f64, unit: &str| {
+        let rendered = format!("{:.2}", value);
+        format!("{}{}", rendered.trim_end_matches('0').trim_end_matches('.'), unit)
+    };
+    match nanos {
+        0..1_000 => format!("{}ns", nanos),
+        1_000..1_000_000 => trim(nanos as f64 / 1e3, if config().unicode { "µs" } else { "us" }),
+        1_000_000..1_000_000_000 => trim(nanos as f64 / 1e6, "ms"),
+        1_000_000_000..60_000_000_000 => trim(nanos as f64 / 1e9, "s"),
+        _ => {
+            let secs = duration.as_secs();
+            let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+            let mut parts = Vec::new();
+            if hours > 0 {
+                parts.push(format!("{}h", hours));
+            }
+            if minutes > 0 {
+                parts.push(format!("{}m", minutes));
+            }
+            if secs > 0 {
+                parts.push(format!("{}s", secs));
+            }
+            parts.join(" ")
+        }
+    }
+}
+
+/// Checks the duration is at least the minimum and at most the maximum.  See
+/// [`check_duration_between`](crate::check_duration_between).
+pub fn check_duration_between(
+    expression: &str,
+    actual: Duration,
+    min: Duration,
+    max: Duration,
+) -> Result<(), MismatchReport> {
+    if min > max {
+        return Err(MismatchReport::new(
+            expression,
+            format!(
+                "The range is empty; the minimum {} is longer than the maximum {}.",
+                humane_duration(min),
+                humane_duration(max)
+            ),
+        ));
+    }
+    let summary = if actual < min {
+        "The duration is shorter than the minimum."
+    } else if actual > max {
+        "The duration is longer than the maximum."
+    } else {
+        return Ok(());
+    };
+    Err(
+        MismatchReport::new(expression, summary)
+            .expected(format!("{}{}{}", humane_duration(min), config().range_separator(), humane_duration(max)))
+            .actual(humane_duration(actual)),
+    )
+}
+
+/// Asserts the duration is at least the minimum and at most the maximum.  See
+/// [`assert_duration_between`](crate::assert_duration_between).
+pub fn assert_duration_between(expression: &str, actual: Duration, min: Duration, max: Duration) {
+    report::enforce(check_duration_between(expression, actual, min, max));
+}
+
+/// Asserts that a duration is within a range, inclusive, for tests that measure elapsed behavior such as cache TTLs
+/// and debouncing.  On failure it reports the range and the duration in human units, e.g.
+/// `expected 100ms–250ms, got 412ms`.
+///
+/// # Arguments
+///
+/// * `$actual` - The [`std::time::Duration`] measured, e.g. `start.elapsed()`.
+/// * `$min` - The shortest [`std::time::Duration`] allowed.
+/// * `$max` - The longest [`std::time::Duration`] allowed.
+///
+/// # Example
+///
+/// ```no_run
+/// use attestify_test_framework::assert_duration_between;
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// // wait for the debounced save
+/// assert_duration_between!(start.elapsed(), Duration::from_millis(100), Duration::from_millis(250));
+/// ```
+///
+#[macro_export]
+macro_rules! assert_duration_between {
+    ($actual:expr, $min:expr, $max:expr $(,)?) => {
+        $crate::timing::assert_duration_between(stringify!($actual), $actual, $min, $max)
+    };
+}
+
+/// Checks that a duration is within a range, inclusive, like
+/// [`assert_duration_between`](crate::assert_duration_between), returning a
+/// [`MismatchReport`](crate::assertions::report::MismatchReport) instead of panicking.
+///
+/// # Arguments
+///
+/// * `$actual` - The [`std::time::Duration`] measured.
+/// * `$min` - The shortest [`std::time::Duration`] allowed.
+/// * `$max` - The longest [`std::time::Duration`] allowed.
+///
+#[macro_export]
+macro_rules! check_duration_between {
+    ($actual:expr, $min:expr, $max:expr $(,)?) => {
+        $crate::timing::check_duration_between(stringify!($actual), $actual, $min, $max)
+    };
+}
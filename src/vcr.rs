@@ -0,0 +1,511 @@
+//! Record and replay of HTTP interactions, so gateways are tested against realistic responses of third-party
+//! evidence sources without calling them in CI.
+//!
+//! A [`VcrServer`] listens on a random localhost port that the gateway under test is configured with.  In record
+//! mode it forwards every request to the real upstream and saves each request and response to a YAML cassette when
+//! it is dropped; in replay mode it answers from the cassette and fails the test on a request that was not
+//! recorded.  [`VcrServer::start`] records when [`RECORD_CASSETTES_ENV_VAR`] is set to `1`, and replays otherwise:
+//!
+//! ```no_run
+//! use attestify_test_framework::vcr::VcrServer;
+//!
+//! let server = VcrServer::start(env!("CARGO_MANIFEST_DIR"), "github_pull_requests", "https://api.github.com");
+//! // configure the gateway with server.url() instead of https://api.github.com, then run it
+//! ```
+//!
+//! Credentials are never written to a cassette: the headers in [`FILTERED_HEADERS`] are not recorded.  Recording
+//! from an HTTPS upstream requires the `tls` feature.
+
+use crate::assertions::report::{self, MismatchReport};
+use crate::http_stub::{HttpStub, RecordedRequest, StubResponse, bodiless, read_body, read_headers};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The environment variable that records cassettes from the real upstream instead of replaying them, when it is
+/// set to `1`.
+pub const RECORD_CASSETTES_ENV_VAR: &str = "RECORD_CASSETTES";
+
+/// The directory, relative to the crate root, that cassettes are stored in.
+pub const CASSETTE_DIR: &str = "tests/__cassettes__";
+
+/// Headers that are neither recorded nor forwarded from a cassette: credentials, and headers that describe the
+/// connection rather than the message.
+pub const FILTERED_HEADERS: &[&str] = &[
+    "Authorization",
+    "Proxy-Authorization",
+    "Cookie",
+    "Set-Cookie",
+    "Host",
+    "Content-Length",
+    "Transfer-Encoding",
+    "Connection",
+];
+
+/// Returns true when cassettes should be recorded, see [`RECORD_CASSETTES_ENV_VAR`].
+pub fn record_requested() -> bool {
+    env::var(RECORD_CASSETTES_ENV_VAR).as_deref() == Ok("1")
+}
+
+/// Returns the path of the named cassette in the crate.
+///
+/// # Arguments
+///
+/// * `manifest_dir` - The root directory of the crate the cassette belongs to.
+/// * `name` - The name of the cassette.
+///
+pub fn cassette_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir)
+        .join(CASSETTE_DIR)
+        .join(format!("{}.yaml", name))
+}
+
+/// The recorded requests and responses, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Reads the cassette from the YAML file.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).unwrap_or_else(|err| {
+            panic!(
+                "Could not read the cassette '{}'; record it with {}=1:\n\t{}\n",
+                path.display(),
+                RECORD_CASSETTES_ENV_VAR,
+                err
+            )
+        });
+        serde_yaml::from_str(&text)
+            .unwrap_or_else(|err| panic!("The cassette '{}' is not valid:\n\t{}\n", path.display(), err))
+    }
+
+    /// Writes the cassette to the YAML file, creating its directory.
+    pub fn save(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|err| {
+                panic!("Could not create the cassette directory '{}':\n\t{}\n", parent.display(), err)
+            });
+        }
+        let text = serde_yaml::to_string(self)
+            .unwrap_or_else(|err| panic!("Could not serialize the cassette:\n\t{}\n", err));
+        fs::write(path, text)
+            .unwrap_or_else(|err| panic!("Could not write the cassette '{}':\n\t{}\n", path.display(), err));
+    }
+}
+
+/// A request and the response the upstream sent for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: CassetteRequest,
+    pub response: CassetteResponse,
+}
+
+/// A recorded request.  Replayed requests match it by method, path, query, and body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    pub body: CassetteBody,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    pub body: CassetteBody,
+}
+
+/// A recorded body: text when it is UTF-8, so cassettes are readable and diffable, and base64 otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CassetteBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+impl CassetteBody {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok("") => CassetteBody::default(),
+            Ok(text) => CassetteBody {
+                body: Some(text.to_string()),
+                body_base64: None,
+            },
+            Err(_) => CassetteBody {
+                body: None,
+                body_base64: Some(STANDARD.encode(bytes)),
+            },
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match (&self.body, &self.body_base64) {
+            (_, Some(encoded)) => STANDARD
+                .decode(encoded)
+                .unwrap_or_else(|err| panic!("The cassette has an invalid base64 body:\n\t{}\n", err)),
+            (Some(text), None) => text.as_bytes().to_vec(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+impl CassetteRequest {
+    fn matches(&self, request: &RecordedRequest) -> bool {
+        self.method.eq_ignore_ascii_case(&request.method)
+            && self.path == request.path
+            && self.query == request.query
+            && self.body.to_bytes() == request.body
+    }
+}
+
+/// Whether a [`VcrServer`] forwards requests to the upstream or answers them from the cassette.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VcrMode {
+    /// Forwards requests to the upstream base URL, and saves the cassette when the server is dropped.
+    Record { upstream: String },
+    Replay,
+}
+
+#[derive(Default)]
+struct Tape {
+    interactions: Vec<Interaction>,
+    /// Whether each interaction has been replayed.
+    played: Vec<bool>,
+    unexpected: Vec<RecordedRequest>,
+}
+
+/// A recording or replaying HTTP server, created with [`VcrServer::start`], [`VcrServer::record`], or
+/// [`VcrServer::replay`].  See the [module documentation](self).
+pub struct VcrServer {
+    stub: HttpStub,
+    mode: VcrMode,
+    path: PathBuf,
+    tape: Arc<Mutex<Tape>>,
+}
+
+impl VcrServer {
+    /// Records the named cassette from the upstream when [`record_requested`] is true, and replays it otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_dir` - The root directory of the crate the cassette belongs to.
+    /// * `name` - The name of the cassette.
+    /// * `upstream` - The base URL of the real service, e.g. `https://api.github.com`.
+    ///
+    pub fn start(manifest_dir: &str, name: &str, upstream: &str) -> Self {
+        let path = cassette_path(manifest_dir, name);
+        if record_requested() {
+            VcrServer::record(path, upstream)
+        } else {
+            VcrServer::replay(path)
+        }
+    }
+
+    /// Forwards every request to the upstream base URL, and saves the cassette to the path when dropped,
+    /// replacing any existing cassette.
+    pub fn record(path: impl Into<PathBuf>, upstream: &str) -> Self {
+        let upstream_url = Upstream::parse(upstream);
+        let server = VcrServer::serve(path.into(), VcrMode::Record {
+            upstream: upstream.to_string(),
+        });
+        let tape = Arc::clone(&server.tape);
+        server.stub.fallback(move |request| {
+            let response = match upstream_url.forward(request) {
+                Ok(response) => response,
+                Err(err) => {
+                    return StubResponse::new(502).body(format!("Could not forward the request to the upstream: {}", err));
+                }
+            };
+            let mut tape = tape.lock().unwrap();
+            tape.interactions.push(Interaction {
+                request: CassetteRequest {
+                    method: request.method.clone(),
+                    path: request.path.clone(),
+                    query: request.query.clone(),
+                    headers: filter_headers(&request.headers),
+                    body: CassetteBody::from_bytes(&request.body),
+                },
+                response: response.clone(),
+            });
+            tape.played.push(true);
+            stub_response(&response)
+        });
+        server
+    }
+
+    /// Answers every request from the cassette at the path.  Each interaction is replayed once, in order, and a
+    /// request that no unplayed interaction matches gets a `500` and fails the test when the server is dropped.
+    pub fn replay(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let cassette = Cassette::load(&path);
+        let server = VcrServer::serve(path, VcrMode::Replay);
+        {
+            let mut tape = server.tape.lock().unwrap();
+            tape.played = vec![false; cassette.interactions.len()];
+            tape.interactions = cassette.interactions;
+        }
+        let tape = Arc::clone(&server.tape);
+        server.stub.fallback(move |request| {
+            let mut tape = tape.lock().unwrap();
+            let Tape {
+                interactions, played, ..
+            } = &mut *tape;
+            match interactions
+                .iter()
+                .zip(played.iter_mut())
+                .find(|(interaction, played)| !**played && interaction.request.matches(request))
+            {
+                Some((interaction, played)) => {
+                    *played = true;
+                    stub_response(&interaction.response)
+                }
+                None => {
+                    tape.unexpected.push(request.clone());
+                    StubResponse::new(500).body(format!(
+                        "The cassette has no unplayed interaction for {} {}",
+                        request.method, request.path
+                    ))
+                }
+            }
+        });
+        server
+    }
+
+    fn serve(path: PathBuf, mode: VcrMode) -> Self {
+        VcrServer {
+            stub: HttpStub::start(),
+            mode,
+            path,
+            tape: Arc::new(Mutex::new(Tape::default())),
+        }
+    }
+
+    /// Returns the base URL of the server, which the gateway under test uses instead of the upstream.
+    pub fn url(&self) -> String {
+        self.stub.url()
+    }
+
+    pub fn mode(&self) -> &VcrMode {
+        &self.mode
+    }
+
+    /// Returns every request received, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.stub.requests()
+    }
+
+    /// Returns the requests that did not match an unplayed interaction of the cassette, in order.
+    pub fn unexpected_requests(&self) -> Vec<RecordedRequest> {
+        self.tape.lock().unwrap().unexpected.clone()
+    }
+
+    /// Checks every request was answered from the cassette, and, when `all_played` is true, that every recorded
+    /// interaction was replayed.
+    pub fn check(&self, all_played: bool) -> Result<(), MismatchReport> {
+        let tape = self.tape.lock().unwrap();
+        if !tape.unexpected.is_empty() {
+            let requests: Vec<String> = tape
+                .unexpected
+                .iter()
+                .map(|request| format!("\t\t{}", render_request(&request.method, &request.path, &request.query)))
+                .collect();
+            return Err(MismatchReport::new(
+                self.path.display().to_string(),
+                format!("The cassette '{}' has no recorded interaction for some requests.", self.path.display()),
+            )
+            .hint(format!(
+                "\tUnexpected requests:\n{}\n\tRecord the cassette again with {}=1.",
+                requests.join("\n"),
+                RECORD_CASSETTES_ENV_VAR
+            )));
+        }
+        if all_played {
+            let unplayed: Vec<String> = tape
+                .interactions
+                .iter()
+                .zip(&tape.played)
+                .filter(|(_, played)| !**played)
+                .map(|(interaction, _)| {
+                    let request = &interaction.request;
+                    format!("\t\t{}", render_request(&request.method, &request.path, &request.query))
+                })
+                .collect();
+            if !unplayed.is_empty() {
+                return Err(MismatchReport::new(
+                    self.path.display().to_string(),
+                    format!("Some interactions of the cassette '{}' were not replayed.", self.path.display()),
+                )
+                .hint(format!("\tUnplayed interactions:\n{}", unplayed.join("\n"))));
+            }
+        }
+        Ok(())
+    }
+
+    /// Asserts every recorded interaction was replayed, and no other request was made.
+    pub fn assert_all_played(&self) {
+        report::enforce(self.check(true));
+    }
+}
+
+impl Drop for VcrServer {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        match self.mode {
+            VcrMode::Record { .. } => Cassette {
+                interactions: self.tape.lock().unwrap().interactions.clone(),
+            }
+            .save(&self.path),
+            VcrMode::Replay => {
+                report::enforce(self.check(false));
+            }
+        }
+    }
+}
+
+fn render_request(method: &str, path: &str, query: &str) -> String {
+    match query {
+        "" => format!("{} {}", method, path),
+        query => format!("{} {}?{}", method, path, query),
+    }
+}
+
+fn filter_headers(headers: &[(String, String)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !FILTERED_HEADERS.iter().any(|filtered| filtered.eq_ignore_ascii_case(name)))
+        .cloned()
+        .collect()
+}
+
+fn stub_response(response: &CassetteResponse) -> StubResponse {
+    let stub = StubResponse::new(response.status).body(response.body.to_bytes());
+    response
+        .headers
+        .iter()
+        .fold(stub, |stub, (name, value)| stub.header(name, value))
+}
+
+/// The scheme, host, port, and base path of the upstream.
+struct Upstream {
+    tls: bool,
+    host: String,
+    port: u16,
+    base_path: String,
+}
+
+impl Upstream {
+    fn parse(url: &str) -> Self {
+        let (tls, rest) = match url.split_once("://") {
+            Some(("http", rest)) => (false, rest),
+            Some(("https", rest)) => (true, rest),
+            _ => panic!("The upstream '{}' is not an http or https URL.", url),
+        };
+        if tls && !cfg!(feature = "tls") {
+            panic!("Recording from the HTTPS upstream '{}' requires the `tls` feature.", url);
+        }
+        let (authority, base_path) = match rest.find('/') {
+            Some(index) => (&rest[..index], rest[index..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .unwrap_or_else(|_| panic!("The upstream '{}' has an invalid port.", url)),
+            ),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+        Upstream {
+            tls,
+            host: host.to_string(),
+            port,
+            base_path: base_path.to_string(),
+        }
+    }
+
+    /// Sends the request to the upstream and reads its response.
+    fn forward(&self, request: &RecordedRequest) -> std::io::Result<CassetteResponse> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        if self.tls {
+            #[cfg(feature = "tls")]
+            return self.exchange(tls_stream(&self.host, stream)?, request);
+        }
+        self.exchange(stream, request)
+    }
+
+    fn exchange(&self, mut stream: impl Read + Write, request: &RecordedRequest) -> std::io::Result<CassetteResponse> {
+        let target = render_request(&request.method, &format!("{}{}", self.base_path, request.path), &request.query);
+        let mut head = format!("{} HTTP/1.1\r\nHost: {}\r\n", target, self.host);
+        for (name, value) in &request.headers {
+            if !["Host", "Content-Length", "Transfer-Encoding", "Connection"]
+                .iter()
+                .any(|skipped| skipped.eq_ignore_ascii_case(name))
+            {
+                head.push_str(&format!("{}: {}\r\n", name, value));
+            }
+        }
+        head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", request.body.len()));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&request.body)?;
+        stream.flush()?;
+
+        let mut reader = BufReader::new(stream);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line)?;
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| std::io::Error::other(format!("Invalid status line '{}'", status_line.trim_end())))?;
+        let headers = read_headers(&mut reader)?;
+        let body = if bodiless(&request.method, status) {
+            Vec::new()
+        } else {
+            read_body(&mut reader, &headers, true)?
+        };
+        Ok(CassetteResponse {
+            status,
+            headers: filter_headers(&headers),
+            body: CassetteBody::from_bytes(&body),
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+fn tls_stream(
+    host: &str,
+    stream: TcpStream,
+) -> std::io::Result<rustls::StreamOwned<rustls::ClientConnection, TcpStream>> {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(std::io::Error::other)?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let name = rustls::pki_types::ServerName::try_from(host.to_string()).map_err(std::io::Error::other)?;
+    let connection = rustls::ClientConnection::new(Arc::new(config), name).map_err(std::io::Error::other)?;
+    Ok(rustls::StreamOwned::new(connection, stream))
+}
@@ -0,0 +1,335 @@
+use crate::assertions::permissions;
+use crate::fixtures::large_file::{self, FilePattern};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable that keeps the directories of workspaces when they are dropped, for post-mortem
+/// inspection, when it is set to `1`.
+pub const KEEP_TEST_DIRS_ENV_VAR: &str = "KEEP_TEST_DIRS";
+
+static WORKSPACE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns true when workspace directories are kept when the workspace is dropped, see [`KEEP_TEST_DIRS_ENV_VAR`].
+pub fn keep_test_dirs_requested() -> bool {
+    std::env::var(KEEP_TEST_DIRS_ENV_VAR).as_deref() == Ok("1")
+}
+
+/// Creates a new, empty directory under the system temporary directory whose name starts with the prefix and is
+/// unique to this process and call.
+pub(crate) fn unique_temp_dir(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let dir = std::env::temp_dir().join(format!(
+        "{}_{}_{}_{}",
+        prefix,
+        process::id(),
+        WORKSPACE_COUNTER.fetch_add(1, Ordering::SeqCst),
+        nanos
+    ));
+
+    fs::create_dir_all(&dir).unwrap_or_else(|_| panic!("Could not create the directory '{}'.", dir.display()));
+    dir
+}
+
+/// An isolated temporary directory laid out like a NAPE assurance procedure workspace:
+///
+/// ```text
+/// <root>/
+///     evidence/
+///     activities/
+///     report.yaml
+/// ```
+///
+/// Every workspace gets its own directory under the system temporary directory, so tests running in parallel do
+/// not share files.  The directory and everything in it is removed when the workspace is dropped, including when
+/// the test panics, unless [`KEEP_TEST_DIRS_ENV_VAR`] is set to `1`.
+#[derive(Debug)]
+pub struct TestWorkspace {
+    root: PathBuf,
+}
+
+impl TestWorkspace {
+    /// Creates the workspace directory, its evidence and activities directories, and an empty report file.
+    pub fn new() -> Self {
+        let workspace = TestWorkspace {
+            root: unique_temp_dir("nape_workspace"),
+        };
+        for dir in [workspace.evidence_dir(), workspace.activity_dir()] {
+            fs::create_dir_all(&dir)
+                .unwrap_or_else(|_| panic!("Could not create the directory '{}'.", dir.display()));
+        }
+        fs::File::create(workspace.report_file()).unwrap_or_else(|_| {
+            panic!("Could not create the file '{}'.", workspace.report_file().display())
+        });
+
+        workspace
+    }
+
+    /// Returns the root directory of the workspace.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the directory evidence is collected into.
+    pub fn evidence_dir(&self) -> PathBuf {
+        self.root.join("evidence")
+    }
+
+    /// Returns the directory activity definitions are stored in.
+    pub fn activity_dir(&self) -> PathBuf {
+        self.root.join("activities")
+    }
+
+    /// Returns the path of the assurance report file.
+    pub fn report_file(&self) -> PathBuf {
+        self.root.join("report.yaml")
+    }
+
+    /// Returns the path to the subpath in the workspace.  This does not create the directory or file.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath relative to the workspace root.
+    ///
+    pub fn path_for(&self, subpath: &str) -> PathBuf {
+        self.root.join(subpath)
+    }
+
+    /// Creates a file in the workspace with the given contents, creating any parent directories, then returns the
+    /// path to the file.  An existing file is overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the file relative to the workspace root.
+    /// * `contents` - The contents of the file.
+    ///
+    pub fn create_file(&self, subpath: &str, contents: &str) -> PathBuf {
+        let path = self.path_for(subpath);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|_| {
+                panic!("Could not create the parent directory for '{}'.", parent.display())
+            });
+        }
+        fs::write(&path, contents).unwrap_or_else(|_| panic!("Could not write to the file '{}'.", path.display()));
+        path
+    }
+
+    /// Creates a directory in the workspace, including any parents, then returns its path.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the directory relative to the workspace root.
+    ///
+    pub fn create_dir(&self, subpath: &str) -> PathBuf {
+        let path = self.path_for(subpath);
+        fs::create_dir_all(&path).unwrap_or_else(|_| panic!("Could not create the directory '{}'.", path.display()));
+        path
+    }
+
+    /// Creates a symbolic link in the workspace, creating any parent directories, then returns the path of the link.
+    /// The target does not have to exist.
+    ///
+    /// On Windows, creating a link needs Developer Mode or the symbolic link privilege, and the link is a directory
+    /// link when the target is an existing directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the link relative to the workspace root.
+    /// * `target` - The path the link points to, which is resolved relative to the directory of the link when it is
+    ///   relative, as the platform does.
+    ///
+    pub fn create_symlink(&self, subpath: &str, target: impl AsRef<Path>) -> PathBuf {
+        let path = self.path_for(subpath);
+        let target = target.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|_| {
+                panic!("Could not create the parent directory for '{}'.", parent.display())
+            });
+        }
+        symlink(target, &path).unwrap_or_else(|err| {
+            panic!("Could not create the link '{}' to '{}':\n\t{}\n", path.display(), target.display(), err)
+        });
+        path
+    }
+
+    /// Creates a symbolic link in the workspace whose target does not exist, then returns the path of the link.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the link relative to the workspace root.
+    ///
+    pub fn create_dangling_symlink(&self, subpath: &str) -> PathBuf {
+        self.create_symlink(subpath, DANGLING_TARGET)
+    }
+
+    /// Creates a chain of nested directories in the workspace, `level-1/level-2/...`, then returns the path of the
+    /// deepest one, e.g. to test paths longer than a platform or gateway limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath, relative to the workspace root, the chain starts in.
+    /// * `depth` - The number of nested directories.
+    ///
+    pub fn create_nested_dirs(&self, subpath: &str, depth: usize) -> PathBuf {
+        let mut path = self.path_for(subpath);
+        for level in 1..=depth {
+            path.push(format!("level-{}", level));
+        }
+        fs::create_dir_all(&path).unwrap_or_else(|err| {
+            panic!("Could not create the nested directories '{}':\n\t{}\n", path.display(), err)
+        });
+        path
+    }
+
+    /// Creates a file whose name is not valid UTF-8 in a directory of the workspace, then returns its path, or
+    /// `None` when the platform or file system does not allow such a name, e.g. APFS on macOS.
+    ///
+    /// On Unix the name contains the byte `0xFF`; on Windows it contains an unpaired UTF-16 surrogate.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the directory relative to the workspace root.
+    /// * `contents` - The contents of the file.
+    ///
+    pub fn create_non_utf8_file(&self, subpath: &str, contents: &str) -> Option<PathBuf> {
+        let name = non_utf8_name()?;
+        let path = self.create_dir(subpath).join(name);
+        fs::write(&path, contents).ok()?;
+        Some(path)
+    }
+
+    /// Creates a large file in the workspace with deterministic content, creating any parent directories, then
+    /// returns its path.  The file is written in chunks, and sparse where the pattern allows, so streaming hashing
+    /// and upload code can be tested with files larger than memory.  See [`large_file`](crate::fixtures::large_file).
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the file relative to the workspace root.
+    /// * `size` - The size of the file in bytes, e.g. `2.gigabytes()`.
+    /// * `pattern` - The content of the file.
+    ///
+    pub fn create_large_file(&self, subpath: &str, size: u64, pattern: FilePattern) -> PathBuf {
+        let path = self.path_for(subpath);
+        large_file::write_large_file(&path, size, &pattern);
+        path
+    }
+
+    /// Makes a file or directory in the workspace read-only, on both Unix and Windows, then returns its path, e.g.
+    /// to test that a gateway fails with a `GatewayError` when writing to a protected location.  The workspace is
+    /// still removed when dropped.
+    ///
+    /// Running as root on Unix bypasses the permissions, see
+    /// [`readonly_enforced`](crate::assertions::permissions::readonly_enforced).  On Windows a read-only directory
+    /// still accepts new files, so make the file itself read-only there.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the file or directory relative to the workspace root.
+    ///
+    pub fn make_readonly(&self, subpath: &str) -> PathBuf {
+        let path = self.path_for(subpath);
+        permissions::make_readonly(&path);
+        path
+    }
+
+    /// Makes a file or directory in the workspace writable again, then returns its path.
+    ///
+    /// # Arguments
+    ///
+    /// * `subpath` - The subpath of the file or directory relative to the workspace root.
+    ///
+    pub fn make_writable(&self, subpath: &str) -> PathBuf {
+        let path = self.path_for(subpath);
+        permissions::make_writable(&path);
+        path
+    }
+}
+
+/// The target of [`TestWorkspace::create_dangling_symlink`], which is never created.
+const DANGLING_TARGET: &str = "does-not-exist";
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    let resolved = link.parent().map_or_else(|| target.to_path_buf(), |parent| parent.join(target));
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "symbolic links are not supported on this platform"))
+}
+
+#[cfg(unix)]
+fn non_utf8_name() -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(OsString::from_vec(b"non-utf8-\xff.txt".to_vec()))
+}
+
+#[cfg(windows)]
+fn non_utf8_name() -> Option<OsString> {
+    use std::os::windows::ffi::OsStringExt;
+    let mut name: Vec<u16> = "non-utf8-".encode_utf16().collect();
+    name.push(0xD800);
+    name.extend(".txt".encode_utf16());
+    Some(OsString::from_wide(&name))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn non_utf8_name() -> Option<OsString> {
+    None
+}
+
+/// Makes the directory and everything in it writable, so a read-only entry does not stop it being removed.
+fn make_tree_writable(path: &Path) {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return;
+    };
+    if metadata.file_type().is_symlink() {
+        return;
+    }
+    if metadata.permissions().readonly() {
+        let _ = permissions::set_writable(path);
+    }
+    if metadata.is_dir()
+        && let Ok(entries) = fs::read_dir(path)
+    {
+        for entry in entries.filter_map(Result::ok) {
+            make_tree_writable(&entry.path());
+        }
+    }
+}
+
+impl Default for TestWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestWorkspace {
+    fn drop(&mut self) {
+        if keep_test_dirs_requested() {
+            return;
+        }
+        // Errors are ignored: panicking in drop while a test is already panicking would abort the test binary.
+        if fs::remove_dir_all(&self.root).is_err() {
+            make_tree_writable(&self.root);
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
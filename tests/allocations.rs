@@ -0,0 +1,21 @@
+//! Counts the bytes a closure allocates, with the counting allocator installed for this binary.
+#![cfg(feature = "allocations")]
+
+mod common;
+
+use attestify_test_framework::{assert_allocates_less_than, install_counting_allocator};
+use common::report_of;
+
+install_counting_allocator!();
+
+#[test]
+fn a_small_allocation_passes_and_returns_the_value() {
+    let bytes = assert_allocates_less_than!(1_000, || vec![0u8; 100]);
+    assert_eq!(bytes.len(), 100);
+}
+
+#[test]
+fn a_large_allocation_reports_the_bytes() {
+    let report = report_of(|| assert_allocates_less_than!(1_000, || vec![0u8; 5_000]));
+    assert!(report.actual.unwrap().starts_with("5000 bytes"));
+}
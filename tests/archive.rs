@@ -0,0 +1,86 @@
+//! Builds tar, tar.gz, and zip bundles and reads them back, along with archives compressed by other tools.
+#![cfg(feature = "archive")]
+
+use attestify_test_framework::assertions::report::take_last_failure;
+use attestify_test_framework::fixtures::archive::{ArchiveBuilder, ArchiveFormat, extract_archive, read_archive};
+use attestify_test_framework::panics::catch_panic;
+use attestify_test_framework::workspace::TestWorkspace;
+use attestify_test_framework::{assert_archive_contains, assert_archive_entries, check_archive_contains};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::fs;
+
+/// A zip with `evidence/report.yaml` deflated, written by Python's `zipfile`.
+const DEFLATED_ZIP: &str = "UEsDBBQAAAAIAAAAIVgDYURqFgAAACwBAAAUAAAAZXZpZGVuY2UvcmVwb3J0LnlhbWwrLkksKS22UihILC5OTeEqHu\
+    Xi5gIAUEsBAhQDFAAAAAgAAAAhWANhRGoWAAAALAEAABQAAAAAAAAAAAAAAIABAAAAAGV2aWRlbmNlL3JlcG9ydC55YW1sUEsFBgAAAAABAAEAQgAA\
+    AEgAAAAAAA==";
+
+/// A tar.gz with `evidence/report.yaml`, written by Python's `tarfile` and `gzip`.
+const PYTHON_TAR_GZ: &str = "H4sIAAAAAAACA+3PPQrCQBDF8ak9xZ4gzoZRwdssZoqAH2F3I3h7N5YWqUQE/79iHu914/dx8OvJt9mnW67dI13O8\
+    mHa7M1e2byn2s4ktmOm2i97jIc+SlD5grnUlEOQP9W+r3M5himV4sOGulIFAAAAAAAAAAAAAAAAAH7IE7ENOaoAKAAA";
+
+fn bundle() -> ArchiveBuilder {
+    ArchiveBuilder::new()
+        .file("evidence/report.yaml", "status: passed\n")
+        .file("evidence/blob.bin", (0..=255u8).cycle().take(70_000).collect::<Vec<u8>>())
+        .dir("evidence/empty")
+}
+
+#[test]
+fn every_format_round_trips() {
+    let workspace = TestWorkspace::new();
+    for name in ["bundle.tar", "bundle.tar.gz", "bundle.zip"] {
+        let path = bundle().write(workspace.path_for(name));
+
+        let files = read_archive(&path).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), ["evidence/blob.bin", "evidence/report.yaml"], "{}", name);
+        assert_eq!(files["evidence/blob.bin"].len(), 70_000, "{}", name);
+        assert_archive_contains!(path, "evidence/report.yaml", contents = "status: passed\n");
+        assert_archive_entries!(path, ["evidence/report.yaml", "evidence/blob.bin"]);
+    }
+}
+
+#[test]
+fn bundles_are_deterministic() {
+    for format in [ArchiveFormat::Tar, ArchiveFormat::TarGz, ArchiveFormat::Zip] {
+        assert_eq!(bundle().build(format), bundle().build(format), "{:?}", format);
+    }
+}
+
+#[test]
+fn reads_archives_compressed_by_other_tools() {
+    let workspace = TestWorkspace::new();
+    for (name, encoded) in [("python.zip", DEFLATED_ZIP), ("python.tar.gz", PYTHON_TAR_GZ)] {
+        let path = workspace.path_for(name);
+        fs::write(&path, STANDARD.decode(encoded).unwrap()).unwrap();
+
+        assert_archive_contains!(path, "evidence/report.yaml", contents = "status: passed\n".repeat(20));
+        let extracted = extract_archive(&path, &workspace.path_for(&format!("{}-extracted", name)));
+        assert_eq!(fs::read_to_string(&extracted[0]).unwrap(), "status: passed\n".repeat(20));
+    }
+}
+
+#[test]
+fn a_corrupt_archive_fails_with_a_report() {
+    let workspace = TestWorkspace::new();
+    let path = workspace.path_for("corrupt.tar.gz");
+    let mut bytes = bundle().build(ArchiveFormat::TarGz);
+    let end = bytes.len() - 8;
+    bytes[end] ^= 0xff;
+    fs::write(&path, bytes).unwrap();
+
+    let report = check_archive_contains!(path, "evidence/report.yaml").unwrap_err();
+    assert_eq!(report.summary, "Could not read the archive.");
+}
+
+#[test]
+fn a_missing_entry_fails_with_the_entries() {
+    let workspace = TestWorkspace::new();
+    let path = bundle().write(workspace.path_for("bundle.zip"));
+
+    assert!(catch_panic(|| assert_archive_contains!(path, "evidence/missing.yaml")).is_some());
+    let report = take_last_failure().unwrap();
+    assert!(report.hint.unwrap().contains("evidence/report.yaml"));
+    assert!(catch_panic(|| assert_archive_contains!(path, "evidence/report.yaml", contents = "other")).is_some());
+    assert!(catch_panic(|| assert_archive_entries!(path, ["evidence/report.yaml"])).is_some());
+}
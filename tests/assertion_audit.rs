@@ -0,0 +1,51 @@
+//! Audits the assertions of a suite, which builds and runs this binary again with the assertions disabled and
+//! reports the tests that still pass without evaluating one.
+
+use attestify_test_framework::assertions::report;
+use attestify_test_framework::harness::{ExecutionMode, TestFilter, TestHarness};
+use attestify_test_framework::reporting::TestOutcome;
+use attestify_test_framework::soft::SoftAssertions;
+use attestify_test_framework::{assert_panics_with, is_ok};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let report = TestHarness::new("assertion_audit", || ())
+        .filter(TestFilter::new())
+        .mode(ExecutionMode::CollectAll)
+        .assertion_audit(true)
+        .test("asserts_with_a_macro", |_| {
+            is_ok!(Ok::<_, ()>(1));
+        })
+        .test("asserts_a_panic", |_| {
+            assert_panics_with!(|| panic!("boom"), "boom");
+        })
+        .test("asserts_softly", |_| {
+            let mut soft = SoftAssertions::new();
+            soft.check("the result", || {
+                is_ok!(Ok::<_, ()>(1));
+            });
+            soft.assert_all();
+        })
+        .test("asserts_nothing", |_| {
+            let _ = "evidence".len();
+        })
+        .test("asserts_with_std_only", |_| assert_eq!("evidence".len(), 8))
+        .execute();
+
+    if report::assertions_disabled() {
+        return report.finish();
+    }
+    let audit = report.cases().iter().find(|case| case.name == "assertion_audit").map(|case| &case.outcome);
+    let Some(TestOutcome::Failed(failure)) = audit else {
+        eprintln!("The assertion audit was expected to fail, although it ended with {:?}.", audit);
+        return ExitCode::FAILURE;
+    };
+    let hint = failure.hint.clone().unwrap_or_default();
+    let reported: Vec<&str> = hint.lines().map(str::trim).collect();
+    if reported != ["asserts_nothing", "asserts_with_std_only"] || report.failures() != 1 {
+        eprintln!("The assertion audit reported the wrong tests:\n{}", failure);
+        return ExitCode::FAILURE;
+    }
+    println!("\nassertion audit reported {:?}", reported);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,35 @@
+//! The async variants of the assertion macros await the future and assert on its result.
+
+mod common;
+
+use attestify_test_framework::{
+    async_assert_kernel_error, async_is_error, async_is_ok, async_kernel_error_contains, async_kernel_error_eq,
+    async_kernel_error_matches,
+};
+use common::{Audience, Error, Kind, block_on, error, report_of};
+
+async fn fetch(found: bool) -> Result<u32, Error> {
+    if found { Ok(7) } else { error(Kind::NotFound, Audience::User, "The evidence 7 is missing.") }
+}
+
+#[test]
+fn awaited_results_that_match_pass() {
+    block_on(async {
+        assert_eq!(async_is_ok!(fetch(true)), 7);
+        assert_eq!(async_is_error!(fetch(false)).kind, Kind::NotFound);
+        async_kernel_error_eq!(fetch(false), Kind::NotFound, Audience::User, "The evidence 7 is missing.");
+        async_kernel_error_contains!(fetch(false), Kind::NotFound, Audience::User, "is missing");
+        async_kernel_error_matches!(fetch(false), Kind::NotFound, Audience::User, r"evidence \d+");
+        async_assert_kernel_error!(fetch(false)).kind(Kind::NotFound);
+    });
+}
+
+#[test]
+fn awaited_results_that_do_not_match_fail() {
+    let report = report_of(|| block_on(async { async_is_ok!(fetch(false)) }));
+    assert_eq!(report.field, "result");
+    let report = report_of(|| {
+        block_on(async { async_kernel_error_eq!(fetch(false), Kind::InvalidInput, Audience::User, "") })
+    });
+    assert_eq!(report.field, "kind");
+}
@@ -0,0 +1,36 @@
+//! Scans the messages of errors for the user for details that should only reach the system audience.
+
+mod common;
+
+use attestify_test_framework::assert_no_system_details_for_user_audience;
+use common::{Audience, Error, Kind, error, report_of};
+
+fn not_found(audience: Audience, message: &str) -> Error {
+    error(Kind::NotFound, audience, message).unwrap_err()
+}
+
+#[test]
+fn user_messages_without_internal_details_pass() {
+    let errors = vec![
+        not_found(Audience::User, "The procedure 'p1' does not exist."),
+        not_found(Audience::System, "Could not read /etc/nape/config.yaml"),
+    ];
+    assert_no_system_details_for_user_audience!(&errors);
+}
+
+#[test]
+fn lists_each_user_message_with_an_internal_detail() {
+    let errors = vec![
+        not_found(Audience::User, "The file evidence/report.yaml is missing."),
+        not_found(Audience::User, "Could not read /etc/nape/config.yaml"),
+        not_found(Audience::User, "Config { path: \"evidence\" }"),
+    ];
+
+    let report = report_of(|| assert_no_system_details_for_user_audience!(&errors));
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("[1] absolute file path") && hint.contains("[2] struct debug dump"), "{}", hint);
+    assert!(!hint.contains("[0]"), "{}", hint);
+    let errors = vec![not_found(Audience::User, "SELECT * FROM evidence")];
+    let report = report_of(|| assert_no_system_details_for_user_audience!(&errors, [r"SELECT .* FROM"]));
+    assert!(report.hint.unwrap().starts_with("\t[0] SELECT .* FROM: "));
+}
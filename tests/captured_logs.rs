@@ -0,0 +1,37 @@
+//! Captures `log` records, including with one capture nested in another.
+#![cfg(feature = "log")]
+
+use attestify_test_framework::assert_panics_with;
+use attestify_test_framework::assertions::report::take_last_failure;
+use attestify_test_framework::logs::{CapturedLogs, Level};
+
+#[test]
+fn captures_the_records_of_the_thread() {
+    let logs = CapturedLogs::start();
+    log::warn!("retrying evidence upload");
+
+    logs.assert_contains(Level::WARN, "retrying evidence upload");
+    logs.assert_not_contains(Level::ERROR, "retrying evidence upload");
+    assert_panics_with!(|| logs.assert_contains(Level::INFO, "uploaded"), "No INFO event");
+    let report = take_last_failure().unwrap();
+    assert_eq!(report.field, "logs");
+    assert_eq!(report.expected.as_deref(), Some("INFO \"uploaded\""));
+    assert_panics_with!(
+        || logs.assert_not_contains(Level::WARN, "retrying"),
+        "although none was expected"
+    );
+}
+
+#[test]
+fn a_nested_capture_resumes_the_outer_one_when_dropped() {
+    let outer = CapturedLogs::start();
+    log::warn!("before");
+    {
+        let inner = CapturedLogs::start();
+        log::warn!("during");
+        assert_eq!(inner.messages_at(Level::WARN), vec!["during"]);
+    }
+    log::warn!("after");
+
+    assert_eq!(outer.messages_at(Level::WARN), vec!["before", "after"]);
+}
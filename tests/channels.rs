@@ -0,0 +1,38 @@
+//! Asserts on the messages a channel receives, waiting for them up to a timeout.
+
+mod common;
+
+use attestify_test_framework::matchers::equal_to;
+use attestify_test_framework::{assert_no_more_messages, assert_received};
+use common::report_of;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn receives_the_matching_messages_in_time() {
+    let (sender, mut receiver) = mpsc::channel();
+    let publisher = thread::spawn(move || {
+        sender.send("evidence collected").unwrap();
+        thread::sleep(Duration::from_millis(20));
+        sender.send("report generated").unwrap();
+    });
+
+    assert_received!(receiver, equal_to("evidence collected"));
+    assert_received!(receiver, equal_to("report generated"), within = Duration::from_secs(5));
+    publisher.join().unwrap();
+    assert_no_more_messages!(receiver);
+}
+
+#[test]
+fn reports_the_messages_that_did_not_match() {
+    let (sender, mut receiver) = mpsc::channel();
+    sender.send("evidence collected").unwrap();
+
+    let within = Duration::from_millis(50);
+    let report = report_of(|| assert_received!(receiver, equal_to("report generated"), within = within));
+    assert!(report.summary.contains("within 50ms"), "{}", report);
+    sender.send("report generated").unwrap();
+    let report = report_of(|| assert_no_more_messages!(receiver, within = Duration::from_millis(10)));
+    assert_eq!(report.actual.as_deref(), Some("\"report generated\""));
+}
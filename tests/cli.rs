@@ -0,0 +1,35 @@
+//! Runs commands through the shell and asserts on their exit code, output, and the files they write.
+#![cfg(unix)]
+
+mod common;
+
+use attestify_test_framework::cli::CliRunner;
+use attestify_test_framework::workspace::TestWorkspace;
+use common::report_of;
+
+#[test]
+fn asserts_on_what_the_command_did() {
+    let workspace = TestWorkspace::new();
+    CliRunner::new("sh")
+        .args(["-c", "cat > report.yaml; echo \"uploaded $BUNDLE\"; echo retrying >&2; exit 3"])
+        .env("BUNDLE", "evidence.zip")
+        .stdin("status: passed\n")
+        .current_dir(workspace.root())
+        .run()
+        .assert_failure()
+        .assert_exit_code(3)
+        .assert_stdout_eq("uploaded evidence.zip\n")
+        .assert_stderr_matches(r"^retry")
+        .assert_file_contains("report.yaml", "status: passed");
+}
+
+#[test]
+fn reports_the_command_with_the_mismatch() {
+    let output = CliRunner::new("sh").args(["-c", "echo uploaded"]).run();
+    output.assert_success();
+
+    let report = report_of(|| output.assert_stdout_contains("failed"));
+    assert_eq!(report.field, "stdout");
+    assert!(report.hint.unwrap().contains("Command:\tsh -c echo uploaded"));
+    assert_eq!(report_of(|| output.assert_exit_code(1)).actual.as_deref(), Some("0"));
+}
@@ -0,0 +1,61 @@
+//! Compares collections regardless of their order, and checks the keys of their elements are unique and in order.
+
+mod common;
+
+use attestify_test_framework::{assert_eq_unordered, assert_precedes, assert_sorted_by, assert_unique_by};
+use std::cmp::Ordering;
+use common::report_of;
+
+#[test]
+fn the_same_elements_in_another_order_are_equal() {
+    let expected = vec!["report.yaml", "evidence.json", "evidence.json"];
+    assert_eq_unordered!(expected, ["evidence.json", "report.yaml", "evidence.json"]);
+}
+
+#[test]
+fn reports_the_missing_and_unexpected_elements() {
+    let report = report_of(|| assert_eq_unordered!(vec!["report.yaml", "evidence.json"], ["report.yaml", "notes.md"]));
+    assert_eq!(report.field, "elements");
+    assert_eq!(report.hint.as_deref(), Some("\tMissing:\n\t\t\"evidence.json\"\n\tUnexpected:\n\t\t\"notes.md\""));
+}
+
+#[derive(Debug)]
+struct Evidence {
+    id: u32,
+    name: &'static str,
+}
+
+fn evidence(ids: &[u32]) -> Vec<Evidence> {
+    ids.iter().map(|&id| Evidence { id, name: "report.yaml" }).collect()
+}
+
+#[test]
+fn unique_keys_pass() {
+    assert_unique_by!(evidence(&[1, 2, 3]), |evidence| evidence.id);
+}
+
+#[test]
+fn reports_the_positions_of_each_duplicated_key() {
+    let report = report_of(|| assert_unique_by!(evidence(&[1, 2, 1, 1]), |evidence| evidence.id));
+    assert_eq!(report.summary, "The collection has duplicated keys.");
+    assert_eq!(report.hint.as_deref(), Some("\tDuplicates:\n\t\t1 at positions 0, 2, 3"));
+    let report = report_of(|| assert_unique_by!(evidence(&[1, 2]), |evidence| evidence.name));
+    assert!(report.hint.unwrap().contains("\"report.yaml\" at positions 0, 1"));
+}
+
+#[test]
+fn elements_in_order_pass() {
+    let evidence = evidence(&[1, 2, 2, 5]);
+    assert_sorted_by!(evidence, |evidence| evidence.id, Ordering::Less);
+    assert_precedes!(evidence, |evidence| evidence.id == 1, |evidence| evidence.id == 5);
+}
+
+#[test]
+fn reports_the_elements_out_of_order() {
+    let evidence = evidence(&[1, 5, 2]);
+    let report = report_of(|| assert_sorted_by!(evidence, |evidence| evidence.id, Ordering::Less));
+    assert_eq!(report.summary, "The elements are not in ascending order of their keys.");
+    assert_eq!(report.hint.as_deref(), Some("\tOut of order:\n\t\t[1] 5 before [2] 2"));
+    let report = report_of(|| assert_precedes!(evidence, |evidence| evidence.id == 2, |evidence| evidence.id == 5));
+    assert!(report.summary.contains("comes after"), "{}", report);
+}
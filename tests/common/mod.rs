@@ -0,0 +1,89 @@
+//! A stand-in for the kernel's error, which the kernel error assertions only reach through its `kind`, `audience`,
+//! and `message` fields, and the helpers the tests share.
+#![allow(dead_code)]
+
+use attestify_test_framework::assertions::report::{MismatchReport, take_last_failure};
+use attestify_test_framework::panics::catch_panic;
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    InvalidInput,
+    NotFound,
+    GatewayError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Audience {
+    User,
+    System,
+}
+
+#[derive(Debug, Clone)]
+pub struct Error {
+    pub kind: Kind,
+    pub audience: Audience,
+    pub message: String,
+    pub cause: Option<Box<Error>>,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.cause.as_deref().map(|cause| cause as _)
+    }
+}
+
+/// Returns the error as the result of a use case.
+pub fn error(kind: Kind, audience: Audience, message: &str) -> Result<u32, Error> {
+    Err(Error {
+        kind,
+        audience,
+        message: message.to_string(),
+        cause: None,
+    })
+}
+
+/// Returns a gateway error caused by a missing evidence file.
+pub fn gateway_error() -> Result<u32, Error> {
+    Err(Error {
+        kind: Kind::GatewayError,
+        audience: Audience::User,
+        message: "Could not retrieve the evidence.".to_string(),
+        cause: Some(Box::new(Error {
+            kind: Kind::NotFound,
+            audience: Audience::System,
+            message: "The file 'evidence/report.yaml' is missing.".to_string(),
+            cause: None,
+        })),
+    })
+}
+
+/// Runs the failing assertion and returns the report it failed with.
+pub fn report_of<R>(assertion: impl FnOnce() -> R) -> MismatchReport {
+    let message = catch_panic(assertion).expect("The assertion was expected to fail.");
+    let report = take_last_failure().expect("The assertion did not fail with a report.");
+    assert_eq!(message, report.to_string());
+    report
+}
+
+/// Polls the future until it is ready, for the tests of the async assertions, which need no runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
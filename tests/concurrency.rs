@@ -0,0 +1,33 @@
+//! Calls a gateway from several threads at once and reports every call that failed.
+
+mod common;
+
+use attestify_test_framework::concurrency::run_concurrently;
+use attestify_test_framework::doubles::InMemoryEventBus;
+use common::{Error, report_of};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Clone)]
+struct EvidenceCollected;
+
+#[test]
+fn every_call_succeeds() {
+    let bus: InMemoryEventBus<Error> = InMemoryEventBus::new();
+    run_concurrently(4, 25, || bus.publish(EvidenceCollected));
+    bus.assert_published_times::<EvidenceCollected>(100);
+}
+
+#[test]
+fn reports_the_panics_and_errors_together() {
+    let calls = AtomicUsize::new(0);
+    let report = report_of(|| {
+        run_concurrently(3, 10, || match calls.fetch_add(1, Ordering::SeqCst) {
+            0 => panic!("The gateway is unavailable."),
+            1 => Err("The upload was rejected."),
+            _ => Ok(()),
+        })
+    });
+    assert_eq!(report.summary, "2 of 30 calls from 3 threads failed.");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("The gateway is unavailable.") && hint.contains("The upload was rejected."), "{}", hint);
+}
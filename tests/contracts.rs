@@ -0,0 +1,97 @@
+//! Runs the contract battery against the in-memory filesystem, and checks a gateway breaking it, or drifting from
+//! its double, is reported.
+
+mod common;
+
+use attestify_test_framework::contract_tests;
+use attestify_test_framework::contracts::{self, ParityHarness, ResourceGateway};
+use attestify_test_framework::doubles::InMemoryFileSystem;
+use common::{Audience, Error, Kind, error};
+
+fn fake() -> InMemoryFileSystem<Error> {
+    InMemoryFileSystem::new(|_| error(Kind::NotFound, Audience::User, "The resource is missing.").unwrap_err())
+}
+
+fn denied() -> InMemoryFileSystem<Error> {
+    let denied = error(Kind::GatewayError, Audience::User, "The resource is read-only.").unwrap_err();
+    fake().fail_on_write(contracts::RESOURCE_KEY, denied)
+}
+
+contract_tests! {
+    name: in_memory_filesystem_contract,
+    gateway: fake,
+    denied_gateway: denied,
+    not_found: (Kind::NotFound, Audience::User),
+    permission_denied: (Kind::GatewayError, Audience::User),
+    large_payload: 64 * 1024,
+}
+
+/// A gateway dropping the last byte of every resource it reads.
+struct Truncating(InMemoryFileSystem<Error>);
+
+impl ResourceGateway for Truncating {
+    type Error = Error;
+
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), Error> {
+        ResourceGateway::write(&self.0, key, contents)
+    }
+
+    fn read(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let mut contents = ResourceGateway::read(&self.0, key)?;
+        contents.pop();
+        Ok(contents)
+    }
+}
+
+#[test]
+fn check_round_trip_reports_a_resource_read_back_changed() {
+    let report = contracts::check_round_trip(&Truncating(fake()), contracts::RESOURCE_KEY, b"status: passed\n")
+        .unwrap_err();
+    assert_eq!(report.field, contracts::RESOURCE_KEY);
+    assert_eq!(report.summary, "The gateway did not read back the resource it wrote.");
+    assert!(report.expected.as_deref().unwrap().starts_with("15 bytes, sha256 1d2e199c"));
+    assert!(report.actual.as_deref().unwrap().starts_with("14 bytes, sha256 "));
+}
+
+#[test]
+fn check_round_trip_reports_a_failed_write() {
+    let report = contracts::check_round_trip(&denied(), contracts::RESOURCE_KEY, contracts::RESOURCE_CONTENTS)
+        .unwrap_err();
+    assert_eq!(report.summary, "The gateway failed to write the resource.");
+    assert!(report.actual.as_deref().unwrap().contains("The resource is read-only."));
+}
+
+type Gateway = dyn ResourceGateway<Error = Error>;
+
+/// A harness whose scenarios read a missing resource and one just written.
+fn parity(real: impl Fn() -> Box<Gateway> + 'static) -> ParityHarness<Gateway> {
+    ParityHarness::<Gateway>::new(|| Box::new(fake()), real)
+        .scenario("read_missing", |gateway| gateway.read(contracts::MISSING_KEY).map_err(|err| err.kind))
+        .scenario("write_then_read", |gateway| {
+            gateway.write(contracts::RESOURCE_KEY, b"passed").map_err(|err| err.kind)?;
+            gateway.read(contracts::RESOURCE_KEY).map_err(|err| err.kind)
+        })
+}
+
+#[test]
+fn parity_harness_passes_when_the_double_and_the_real_implementation_agree() {
+    parity(|| Box::new(fake())).compare_with_real(true).assert_parity();
+}
+
+#[test]
+fn parity_harness_only_runs_the_double_unless_comparing() {
+    let harness = parity(|| panic!("The real implementation must not be created.")).compare_with_real(false);
+    assert!(!harness.compares_with_real());
+    harness.assert_parity();
+}
+
+#[test]
+fn parity_harness_reports_the_diverging_scenarios() {
+    let report = parity(|| Box::new(Truncating(fake()))).compare_with_real(true).check_parity().unwrap_err();
+    assert_eq!(report.field, "parity");
+    assert_eq!(report.summary, "The double and the real implementation diverged in 1 of 2 scenarios.");
+    let hint = report.hint.unwrap();
+    let scenario = "Scenario 'write_then_read', expected from the real implementation, actual from the double:";
+    assert!(hint.contains(scenario));
+    assert!(!hint.contains("read_missing"));
+}
@@ -0,0 +1,28 @@
+//! Checks evidence against its SHA-256 digest, given in hex or base64.
+
+mod common;
+
+use attestify_test_framework::workspace::TestWorkspace;
+use attestify_test_framework::{assert_bytes_digest, assert_file_sha256};
+use common::report_of;
+
+const REPORT: &str = "status: passed\n";
+const REPORT_HEX: &str = "1d2e199c4c7da126ead1b7e8b5ccb52a92ef89a6a4a6ea5b70fe634b8ad32c49";
+const REPORT_BASE64: &str = "HS4ZnEx9oSbq0bfotcy1KpLviaakpupbcP5jS4rTLEk=";
+
+#[test]
+fn a_matching_digest_passes_in_hex_and_base64() {
+    let workspace = TestWorkspace::new();
+    let path = workspace.create_file("evidence/report.yaml", REPORT);
+
+    assert_file_sha256!(path, REPORT_HEX);
+    assert_bytes_digest!(Sha256, REPORT, REPORT_BASE64);
+}
+
+#[test]
+fn a_different_digest_reports_both_encodings() {
+    let report = report_of(|| assert_bytes_digest!(Sha256, "status: failed\n", REPORT_HEX));
+    assert_eq!(report.field, "digest");
+    assert_eq!(report.expected.as_deref(), Some(REPORT_HEX));
+    assert!(report.actual.unwrap().ends_with(" (base64)"));
+}
@@ -0,0 +1,38 @@
+//! Compares directory trees, leaving out the excluded paths.
+
+mod common;
+
+use attestify_test_framework::assert_dir_eq;
+use attestify_test_framework::workspace::TestWorkspace;
+use common::report_of;
+
+fn evidence() -> TestWorkspace {
+    let workspace = TestWorkspace::new();
+    workspace.create_file("evidence/report.yaml", "status: passed\n");
+    workspace
+}
+
+#[test]
+fn equal_trees_match_without_the_excluded_paths() {
+    let expected = evidence();
+    let actual = evidence();
+    actual.create_file("logs/run.log", "started\n");
+
+    assert_dir_eq!(expected.root(), actual.root(), exclude = ["logs"]);
+}
+
+#[test]
+fn reports_the_missing_extra_and_differing_files() {
+    let expected = evidence();
+    expected.create_file("evidence/summary.md", "# Summary\n");
+    let actual = evidence();
+    actual.create_file("evidence/report.yaml", "status: failed\n");
+    actual.create_file("notes.md", "");
+
+    let report = report_of(|| assert_dir_eq!(expected.root(), actual.root()));
+    assert_eq!(report.field, "directory");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("\tMissing:\tevidence/summary.md\n"), "{}", hint);
+    assert!(hint.contains("\tExtra:\tnotes.md\n"), "{}", hint);
+    assert!(hint.contains("\tDiffering:\tevidence/report.yaml\n"), "{}", hint);
+}
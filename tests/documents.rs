@@ -0,0 +1,74 @@
+//! The structural assertions on YAML and JSON documents, each passing on a matching document and failing with the
+//! path of the first difference.
+
+mod common;
+
+use attestify_test_framework::assertions::schema::SchemaKind;
+use attestify_test_framework::fixtures::procedure_fixture;
+use attestify_test_framework::{
+    assert_json_eq, assert_json_includes, assert_serializes_roundtrip, assert_valid_nape_schema,
+    assert_yaml_contains_path, assert_yaml_eq,
+};
+use common::report_of;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Check {
+    id: String,
+    #[serde(skip)]
+    attempts: u32,
+}
+
+#[test]
+fn yaml_documents_are_compared_structurally() {
+    assert_yaml_eq!("status: passed\nchecks: [1, 2]\n", "checks:\n  - 1\n  - 2\nstatus: passed");
+    let evidence = "evidence:\n  files:\n    - name: report.yaml";
+    assert_yaml_contains_path!(evidence, "$.evidence.files[0].name");
+    assert_yaml_contains_path!(evidence, "evidence.files[0]", "name: report.yaml");
+
+    let report = report_of(|| assert_yaml_eq!("checks: [{id: a}]", "checks: [{id: b}]"));
+    assert_eq!(report.field, "$.checks[0].id");
+    let report = report_of(|| assert_yaml_contains_path!("evidence: {files: []}", "evidence.files[0]"));
+    assert!(report.field.contains("files[0]"), "{}", report);
+}
+
+#[test]
+fn json_documents_are_compared_structurally() {
+    assert_json_eq!(r#"{"status":"passed","checks":[1,2]}"#, r#"{"checks":[1,2],"status":"passed"}"#);
+    assert_json_eq!(r#"{"id":1,"status":"passed"}"#, json!({"id": 2, "status": "passed"}), ignore = ["$.id"]);
+    assert_json_includes!(r#"{"status":"passed","checks":[1,2,3]}"#, r#"{"checks":[1,2]}"#);
+
+    let report = report_of(|| assert_json_eq!(r#"{"checks":[1,2]}"#, r#"{"checks":[1,3]}"#));
+    assert_eq!(report.field, "/checks/1");
+    let report = report_of(|| assert_json_includes!(r#"{"status":"passed"}"#, r#"{"owner":"team"}"#));
+    assert_eq!(report.field, "/owner");
+}
+
+#[test]
+fn a_value_survives_a_roundtrip_unless_a_field_is_skipped() {
+    let check = Check {
+        id: "report-exists".to_string(),
+        attempts: 0,
+    };
+    assert_serializes_roundtrip!(check, yaml);
+    assert_serializes_roundtrip!(check, json);
+
+    let check = Check {
+        id: "report-exists".to_string(),
+        attempts: 2,
+    };
+    let report = report_of(|| assert_serializes_roundtrip!(check, json));
+    assert_eq!(report.field, "value");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("attempts: 2"), "{}", hint);
+}
+
+#[test]
+fn a_procedure_is_validated_against_its_schema() {
+    assert_valid_nape_schema!(procedure_fixture::full().yaml, SchemaKind::Procedure);
+
+    let report = report_of(|| assert_valid_nape_schema!("kind: Procedure\nmetadata: {}\n", SchemaKind::Procedure));
+    assert!(report.summary.starts_with("The document does not conform to the Procedure schema"), "{}", report);
+    assert!(report.hint.unwrap().contains("activities"));
+}
@@ -0,0 +1,185 @@
+//! The gateway doubles return what they are programmed to and record their calls, and the verification DSL fails on
+//! calls that were not expected.
+
+mod common;
+
+use attestify_test_framework::doubles::{
+    Double, EvidenceRetrievalGatewayDouble, FileSystemGatewayDouble, InMemoryEventBus, RetryObserver, SeededRandom, Spy,
+    verify,
+};
+use attestify_test_framework::{assert_eq_unordered, is_ok, kernel_error_eq};
+use common::{Audience, Error, Kind, report_of};
+use std::cell::Cell;
+use std::path::Path;
+use std::time::Duration;
+
+fn disk_full() -> Error {
+    Error {
+        kind: Kind::GatewayError,
+        audience: Audience::System,
+        message: "The disk is full.".to_string(),
+        cause: None,
+    }
+}
+
+#[test]
+fn a_double_returns_its_responses_and_records_its_calls() {
+    let filesystem: FileSystemGatewayDouble<Error> =
+        FileSystemGatewayDouble::new().respond_to_read("status: passed").fail("write", disk_full());
+
+    assert_eq!(is_ok!(filesystem.read(Path::new("report.yaml"))), "status: passed");
+    kernel_error_eq!(
+        filesystem.write(Path::new("report.yaml"), "status: failed"),
+        Kind::GatewayError,
+        Audience::System,
+        "The disk is full."
+    );
+    assert_eq!(filesystem.call_count("read"), 1);
+    assert_eq!(filesystem.calls()[1].args, ["\"report.yaml\"", "\"status: failed\""]);
+}
+
+#[test]
+#[should_panic(expected = "No response")]
+fn a_double_without_a_response_fails_the_test() {
+    let evidence: EvidenceRetrievalGatewayDouble<Error> = EvidenceRetrievalGatewayDouble::new();
+    let _ = evidence.retrieve("https://evidence.example/report.yaml");
+}
+
+#[test]
+fn a_spy_forwards_and_records_the_calls() {
+    let spy = Spy::new(vec!["report.yaml"]);
+
+    assert_eq!(spy.call("fetch", &[&0], |files| files[0]), "report.yaml");
+    spy.record("store", &[&"report.yaml"]);
+    spy.assert_called_once("fetch");
+    spy.assert_not_called("delete");
+    spy.assert_call_order(["fetch", "store"]);
+    assert_eq!(spy.calls_to("store")[0].args, ["\"report.yaml\""]);
+}
+
+#[test]
+fn a_spy_reports_calls_out_of_order() {
+    let spy = Spy::new(());
+    spy.record("store", &[]);
+    spy.record("fetch", &[]);
+
+    let report = report_of(|| spy.assert_call_order(["fetch", "store"]));
+    assert_eq!(report.field, "calls");
+    assert!(report.summary.contains("'store' was not called after"), "{}", report.summary);
+    assert_eq!(report_of(|| spy.assert_called_times("fetch", 2)).field, "fetch");
+}
+
+#[test]
+fn verify_checks_the_recorded_calls() {
+    let double: Double<Error> = Double::new();
+    double.record("write", &[&"report.yaml"]);
+    double.record("read", &[&"report.yaml"]);
+    double.record("write", &[&"summary.md"]);
+
+    verify(&double).method("write").times(2);
+    verify(&double).method("write").with_args(&[&"summary.md"]).once();
+    verify(&double).method("delete").never();
+    verify(&double).in_order(["write", "read", "write"]);
+}
+
+#[test]
+fn verify_lists_the_calls_when_the_count_differs() {
+    let double: Double<Error> = Double::new();
+    double.record("write", &[&"report.yaml"]);
+    double.record("read", &[&"report.yaml"]);
+
+    let report = report_of(|| verify(&double).method("write").at_least(2));
+    assert_eq!(report.field, "write");
+    assert_eq!(report.actual.as_deref(), Some("1 call(s)"));
+    assert!(report.hint.unwrap().contains("read(\"report.yaml\")"));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct EvidenceCollected {
+    procedure: &'static str,
+}
+
+#[derive(Debug, Clone)]
+struct ReportGenerated;
+
+#[test]
+fn the_event_bus_records_the_published_events() {
+    let bus: InMemoryEventBus<Error> = InMemoryEventBus::new();
+    bus.publish(EvidenceCollected { procedure: "p1" }).unwrap();
+    bus.publish(ReportGenerated).unwrap();
+
+    bus.assert_published::<EvidenceCollected>(|event| event.procedure == "p1");
+    bus.assert_not_published::<EvidenceCollected>(|event| event.procedure == "p2");
+    bus.assert_published_times::<ReportGenerated>(1);
+    bus.assert_published_in_order(&["EvidenceCollected", "ReportGenerated"]);
+}
+
+#[test]
+fn the_event_bus_lists_the_events_when_one_is_missing() {
+    let bus: InMemoryEventBus<Error> = InMemoryEventBus::new();
+    bus.publish(EvidenceCollected { procedure: "p1" }).unwrap();
+    bus.publish(ReportGenerated).unwrap();
+
+    let report = report_of(|| bus.assert_published::<EvidenceCollected>(|event| event.procedure == "p2"));
+    assert_eq!(report.field, "EvidenceCollected");
+    assert!(report.hint.unwrap().contains("[0] EvidenceCollected { procedure: \"p1\" }"));
+    let report = report_of(|| bus.assert_published_in_order(&["ReportGenerated", "EvidenceCollected"]));
+    assert_eq!(report.expected.as_deref(), Some("ReportGenerated -> EvidenceCollected"));
+}
+
+#[test]
+fn seeded_random_repeats_its_sequence() {
+    let random = SeededRandom::from_seed(7);
+    let mut files = vec!["report.yaml", "evidence.json", "summary.md"];
+    random.shuffle(&mut files);
+
+    let replayed = SeededRandom::from_seed(7);
+    let mut replayed_files = vec!["report.yaml", "evidence.json", "summary.md"];
+    replayed.shuffle(&mut replayed_files);
+    assert_eq!(files, replayed_files);
+    random.report_seed_on_failure(|| assert_eq_unordered!(files, ["summary.md", "report.yaml", "evidence.json"]));
+}
+
+#[test]
+fn seeded_random_adds_its_seed_to_a_failure() {
+    let random = SeededRandom::from_seed(99);
+    let report = report_of(|| random.report_seed_on_failure(|| assert_eq_unordered!(vec![random.below(10)], [10])));
+    assert!(report.hint.unwrap().ends_with("(rerun with TEST_SEED=99)"));
+}
+
+/// Uploads with exponential backoff, failing the first `failures` attempts, and gives up after 4 attempts.
+fn upload_with_retries(observer: &RetryObserver, failures: u32) -> Result<(), &'static str> {
+    let attempts = Cell::new(0);
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        let result = observer.observe(|| {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() > failures { Ok(()) } else { Err("The upload timed out.") }
+        });
+        if result.is_ok() || attempts.get() == 4 {
+            return result;
+        }
+        observer.sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+#[test]
+fn the_retry_observer_records_the_backoff_without_sleeping() {
+    let observer = RetryObserver::new();
+    assert_eq!(upload_with_retries(&observer, 2), Ok(()));
+
+    observer.assert_attempts(3).assert_backoff_sequence([1, 2].map(Duration::from_secs));
+    observer.assert_backoff_sequence_within([1100, 1900].map(Duration::from_millis), 10.0);
+}
+
+#[test]
+fn the_retry_observer_reports_the_attempts_and_backoffs() {
+    let observer = RetryObserver::new();
+    assert!(upload_with_retries(&observer, 4).is_err());
+
+    assert_eq!(report_of(|| observer.assert_attempts(3)).actual.as_deref(), Some("4"));
+    let report = report_of(|| observer.assert_backoff_sequence([1, 2, 3].map(Duration::from_secs)));
+    assert_eq!(report.actual.as_deref(), Some("[1s, 2s, 4s]"));
+    assert!(report.hint.unwrap().contains("\t\t4 at +7s"));
+}
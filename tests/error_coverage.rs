@@ -0,0 +1,80 @@
+//! The assertions that expect a kernel error record its classification in the coverage registry, whichever style
+//! they are written in.
+
+use attestify_test_framework::assertions::coverage::observed_error_kinds;
+use attestify_test_framework::soft::SoftAssertions;
+use attestify_test_framework::{
+    assert_kernel_error, assert_kernel_error_chain, assert_no_error_of_kind, kernel_error_not, soft_kernel_error_eq,
+};
+use std::error::Error as StdError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Fluent,
+    Chain,
+    Negative,
+    Collection,
+    Soft,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Audience {
+    User,
+}
+
+#[derive(Debug)]
+struct Error {
+    kind: Kind,
+    audience: Audience,
+    message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl StdError for Error {}
+
+fn fail(kind: Kind) -> Result<(), Error> {
+    Err(Error {
+        kind,
+        audience: Audience::User,
+        message: "The procedure is empty.".to_string(),
+    })
+}
+
+fn observed(kind: &str) -> bool {
+    observed_error_kinds().contains(&(kind.to_string(), "User".to_string()))
+}
+
+#[test]
+fn fluent_assertion_observes_the_error() {
+    assert_kernel_error!(fail(Kind::Fluent)).kind(Kind::Fluent);
+    assert!(observed("Fluent"));
+}
+
+#[test]
+fn fluent_chain_assertion_observes_the_error() {
+    assert_kernel_error_chain!(fail(Kind::Chain)).kind(Kind::Chain);
+    assert!(observed("Chain"));
+}
+
+#[test]
+fn negative_assertions_observe_the_error() {
+    kernel_error_not!(fail(Kind::Negative), Kind::Other, Audience::User);
+    assert_no_error_of_kind!(vec![fail(Kind::Collection)], Kind::Other);
+    assert!(observed("Negative"));
+    assert!(observed("Collection"));
+}
+
+#[test]
+fn soft_assertion_observes_the_error() {
+    let mut soft = SoftAssertions::new();
+    soft_kernel_error_eq!(soft, fail(Kind::Soft), Kind::Soft, Audience::User, "The procedure is empty.");
+    soft.assert_all();
+    assert!(observed("Soft"));
+}
@@ -0,0 +1,23 @@
+//! Runs a parser over the mutated seed procedures, which it must reject without panicking.
+
+mod common;
+
+use attestify_test_framework::fuzz_corpus::{assert_parser_never_panics, corpus};
+use common::report_of;
+
+#[test]
+fn a_parser_that_returns_errors_passes() {
+    assert_parser_never_panics(&corpus(7, 3), |yaml| serde_yaml::from_str::<serde_yaml::Value>(yaml));
+}
+
+#[test]
+fn a_parser_that_panics_reports_the_inputs() {
+    let report = report_of(|| {
+        assert_parser_never_panics(&corpus(7, 3), |yaml| -> Result<(), ()> {
+            assert!(yaml.contains("kind: Procedure"), "The procedure has no kind.");
+            Ok(())
+        })
+    });
+    assert_eq!(report.field, "parser");
+    assert!(report.hint.unwrap().contains("The procedure has no kind."));
+}
@@ -0,0 +1,170 @@
+//! Runs suites in the harness and checks the outcome it reports for each test.
+
+use attestify_test_framework::harness::{ExecutionMode, TestFilter, TestHarness};
+use attestify_test_framework::reporting::{JUnitReport, TestOutcome};
+use attestify_test_framework::workspace::TestWorkspace;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Creates a suite that runs every test, whatever the environment and arguments of this binary.
+fn suite<S: 'static>(before_all: impl FnOnce() -> S + 'static) -> TestHarness<S> {
+    TestHarness::new("suite", before_all)
+        .filter(TestFilter::new())
+        .mode(ExecutionMode::CollectAll)
+        .assertion_audit(false)
+}
+
+/// Returns the name of each test with its outcome, in order.
+fn outcomes(report: &JUnitReport) -> Vec<(&str, &TestOutcome)> {
+    report.cases().iter().map(|case| (case.name.as_str(), &case.outcome)).collect()
+}
+
+fn failure<'a>(report: &'a JUnitReport, name: &str) -> &'a str {
+    match outcomes(report).into_iter().find(|(case, _)| *case == name) {
+        Some((_, TestOutcome::Failed(failure))) => &failure.field,
+        outcome => panic!("The test '{}' was expected to fail, but was {:?}.", name, outcome),
+    }
+}
+
+#[test]
+fn the_hooks_run_around_each_test() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (before, after, teardown) = (log.clone(), log.clone(), log.clone());
+    let report = suite(|| 0)
+        .before_each(move |count: &mut u32| {
+            *count += 1;
+            before.borrow_mut().push(format!("before_each {}", count));
+        })
+        .after_each(move |count| after.borrow_mut().push(format!("after_each {}", count)))
+        .after_all(move |count| teardown.borrow_mut().push(format!("after_all {}", count)))
+        .test("first", |count| assert_eq!(*count, 1))
+        .test("second", |count| assert_eq!(*count, 2))
+        .execute();
+
+    assert_eq!(report.failures(), 0);
+    let expected = ["before_each 1", "after_each 1", "before_each 2", "after_each 2", "after_all 2"];
+    assert_eq!(*log.borrow(), expected);
+}
+
+#[test]
+fn a_failing_hook_fails_the_tests_it_runs_for() {
+    let ran = Rc::new(RefCell::new(false));
+    let body = ran.clone();
+    let report = suite(|| ())
+        .before_each(|_| panic!("The workspace could not be created."))
+        .after_all(|_| panic!("The workspace could not be removed."))
+        .test("skipped_body", move |_| *body.borrow_mut() = true)
+        .execute();
+
+    assert!(!*ran.borrow());
+    assert_eq!(failure(&report, "skipped_body"), "before_each");
+    assert_eq!(failure(&report, "after_all"), "after_all");
+
+    let report = suite(|| -> u32 { panic!("The gateway is unavailable.") }).test("first", |_| {}).execute();
+    assert_eq!(failure(&report, "first"), "before_all");
+}
+
+#[test]
+fn the_filter_selects_the_tests_by_tag_and_name() {
+    let tagged = |filter: TestFilter| {
+        suite(|| ())
+            .filter(filter)
+            .tagged_test("uploads_evidence", &["network"], |_| {})
+            .tagged_test("retrieves_evidence", &["network", "slow"], |_| panic!("The gateway timed out."))
+            .test("parses_a_procedure", |_| {})
+            .execute()
+    };
+
+    let report = tagged(TestFilter::new().parse_tags("network,!slow"));
+    assert_eq!(outcomes(&report), [("uploads_evidence", &TestOutcome::Passed)]);
+    let report = tagged(TestFilter::new().name("evidence"));
+    assert_eq!(report.cases().len(), 2);
+    assert_eq!(failure(&report, "retrieves_evidence"), "panic");
+}
+
+#[test]
+fn fail_fast_skips_the_tests_after_the_first_failure() {
+    let modes = |mode: ExecutionMode| {
+        suite(|| ())
+            .mode(mode)
+            .test("uploads_evidence", |_| {})
+            .test("retrieves_evidence", |_| panic!("The gateway timed out."))
+            .test("deletes_evidence", |_| panic!("The gateway timed out."))
+            .suite_assertion("evidence_is_retained", || {})
+            .execute()
+    };
+
+    let report = modes(ExecutionMode::CollectAll);
+    assert_eq!(report.failures(), 2);
+    assert_eq!(failure(&report, "deletes_evidence"), "panic");
+
+    let report = modes(ExecutionMode::FailFast);
+    assert_eq!(report.failures(), 1);
+    let skipped_test = TestOutcome::Skipped("An earlier test failed and the suite fails fast.".to_string());
+    let skipped_suite = TestOutcome::Skipped("A test failed and the suite fails fast.".to_string());
+    let expected = [
+        ("uploads_evidence", &TestOutcome::Passed),
+        ("deletes_evidence", &skipped_test),
+        ("evidence_is_retained", &skipped_suite),
+    ];
+    let outcomes = outcomes(&report);
+    assert_eq!(failure(&report, "retrieves_evidence"), "panic");
+    assert_eq!([outcomes[0], outcomes[2], outcomes[3]], expected);
+}
+
+#[test]
+fn a_retried_test_passes_as_flaky_or_fails_with_its_last_attempt() {
+    let mut uploads = 0;
+    let mut retrievals = 0;
+    let report = suite(|| 0)
+        .before_each(|attempts: &mut u32| *attempts += 1)
+        .retried_test("uploads_evidence", &["network"], 3, move |_| {
+            uploads += 1;
+            assert!(uploads == 3, "The gateway timed out on upload {}.", uploads);
+        })
+        .retried_test("retrieves_evidence", &["network"], 2, move |_| {
+            retrievals += 1;
+            panic!("The gateway timed out on retrieval {}.", retrievals);
+        })
+        .retried_test("parses_a_procedure", &[], 3, |_| {})
+        .test("counts_attempts", |attempts| assert_eq!(*attempts, 7))
+        .execute();
+
+    assert_eq!(report.failures(), 1);
+    assert_eq!(report.flaky(), 1);
+    match outcomes(&report)[0] {
+        (_, TestOutcome::FlakyPassed(attempts)) => assert_eq!(attempts.len(), 2),
+        outcome => panic!("The test was expected to pass as flaky, but was {:?}.", outcome),
+    }
+    assert_eq!(failure(&report, "retrieves_evidence"), "panic");
+    assert_eq!(outcomes(&report)[2], ("parses_a_procedure", &TestOutcome::Passed));
+    let xml = report.to_xml();
+    assert_eq!(xml.matches("<flakyFailure").count(), 2);
+    assert!(xml.contains("The gateway timed out on retrieval 2."));
+}
+
+#[test]
+fn test_with_injects_a_fresh_parameter_described_on_failure() {
+    let roots = Rc::new(RefCell::new(Vec::<PathBuf>::new()));
+    let (first, second) = (roots.clone(), roots.clone());
+    let report = suite(|| ())
+        .test_with("writes_evidence", move |_, workspace: &TestWorkspace| {
+            first.borrow_mut().push(workspace.root().to_path_buf());
+            workspace.create_file("evidence/report.json", "{}");
+            assert!(workspace.root().join("evidence/report.json").exists());
+        })
+        .test_with("reads_evidence", move |_, workspace: &TestWorkspace| {
+            second.borrow_mut().push(workspace.root().to_path_buf());
+            assert!(workspace.root().join("evidence/report.json").exists(), "The evidence was not written.");
+        })
+        .execute();
+
+    let roots = roots.borrow();
+    assert_ne!(roots[0], roots[1]);
+    assert!(roots.iter().all(|root| !root.exists()));
+    assert_eq!(outcomes(&report)[0], ("writes_evidence", &TestOutcome::Passed));
+    assert_eq!(failure(&report, "reads_evidence"), "panic");
+    let xml = report.to_xml();
+    assert!(xml.contains(&format!("Workspace:\t{}", roots[1].display())), "{}", xml);
+}
@@ -0,0 +1,64 @@
+//! Runs the helpers that catch panics inside harness tests, and inside each other, which must not deadlock on the
+//! panic hook.
+
+use attestify_test_framework::concurrency::run_concurrently;
+use attestify_test_framework::fuzz_corpus::assert_parser_never_panics;
+use attestify_test_framework::harness::{ExecutionMode, TestFilter, TestHarness};
+use attestify_test_framework::scenario::{Scenario, step};
+use attestify_test_framework::{assert_completes_within, assert_eventually, assert_panics_with};
+use std::process::ExitCode;
+use std::time::Duration;
+
+fn main() -> ExitCode {
+    let report = TestHarness::new("harness_nesting", || ())
+        .filter(TestFilter::new())
+        .mode(ExecutionMode::CollectAll)
+        .test("assert_panics_with", |_| {
+            assert_panics_with!(|| panic!("boom"), "boom");
+        })
+        .test("nested_assert_panics_with", |_| {
+            assert_panics_with!(|| assert_panics_with!(|| (), "boom"), "A panic was expected");
+        })
+        .test("assert_eventually", |_| {
+            let mut attempts = 0;
+            assert_eventually!(Duration::from_secs(5), Duration::from_millis(1), {
+                attempts += 1;
+                assert!(attempts > 2);
+            });
+        })
+        .test("run_concurrently", |_| {
+            run_concurrently(4, 10, || {
+                assert_panics_with!(|| panic!("boom"), "boom");
+            });
+        })
+        .test("assert_parser_never_panics", |_| {
+            let inputs = vec!["a".to_string(), "b".to_string()];
+            assert_parser_never_panics(&inputs, |input| {
+                assert_panics_with!(|| panic!("boom"), "boom");
+                Ok::<_, ()>(input.len())
+            });
+        })
+        .test("scenario", |_| {
+            Scenario::with_state(0)
+                .given(step("a panic is caught", |count: &mut i32| {
+                    assert_panics_with!(|| panic!("boom"), "boom");
+                    *count += 1;
+                }))
+                .then(step("the scenario continues", |count: &mut i32| assert_eq!(*count, 1)))
+                .run();
+        })
+        .test("failing_scenario_step", |_| {
+            assert_panics_with!(
+                || Scenario::with_state(()).when(step("fails", |_: &mut ()| panic!("boom"))).run(),
+                "boom"
+            );
+        })
+        .test("completes_within", |_| {
+            assert_panics_with!(|| assert_completes_within!(Duration::from_secs(5), panic!("boom")), "boom");
+        })
+        .execute();
+
+    let failures = report.failures();
+    let code = report.finish();
+    if failures > 0 { ExitCode::FAILURE } else { code }
+}
@@ -0,0 +1,121 @@
+//! The stub frames its responses itself, reads chunked request bodies strictly, and splits multipart bodies into
+//! their parts.
+
+mod common;
+
+use attestify_test_framework::http_stub::multipart::ExpectedPart;
+use attestify_test_framework::http_stub::{HttpStub, RecordedRequest, StubResponse};
+use common::report_of;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+/// Sends the raw request and returns everything the stub answers before closing the connection.
+fn exchange(server: &HttpStub, request: &str) -> String {
+    let mut stream = TcpStream::connect(server.url().trim_start_matches("http://")).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+fn count(response: &str, header: &str) -> usize {
+    response.lines().filter(|line| line.to_lowercase().starts_with(header)).count()
+}
+
+#[test]
+fn a_route_cannot_override_the_framing_headers() {
+    let server = HttpStub::start();
+    server.route(
+        "GET",
+        "/evidence",
+        StubResponse::new(200).header("Content-Length", "99").header("Connection", "keep-alive").body("{}"),
+    );
+
+    let response = exchange(&server, "GET /evidence HTTP/1.1\r\nHost: stub\r\n\r\n");
+    assert_eq!(count(&response, "content-length:"), 1, "{}", response);
+    assert!(response.contains("Content-Length: 2\r\n"), "{}", response);
+    assert_eq!(count(&response, "connection:"), 1, "{}", response);
+    assert!(response.ends_with("\r\n\r\n{}"), "{}", response);
+}
+
+#[test]
+fn bodiless_responses_have_no_body_or_length() {
+    let server = HttpStub::start();
+    server.route("HEAD", "/evidence", StubResponse::new(200).body("{}"));
+    server.route("DELETE", "/evidence", StubResponse::new(204).body("ignored"));
+    server.route("GET", "/cached", StubResponse::new(304).body("ignored"));
+
+    for request in [
+        "HEAD /evidence HTTP/1.1\r\n\r\n",
+        "DELETE /evidence HTTP/1.1\r\n\r\n",
+        "GET /cached HTTP/1.1\r\n\r\n",
+    ] {
+        let response = exchange(&server, request);
+        assert_eq!(count(&response, "content-length:"), 0, "{}", response);
+        assert!(response.ends_with("\r\n\r\n"), "{}", response);
+    }
+}
+
+#[test]
+fn reads_a_chunked_body_with_trailers() {
+    let server = HttpStub::start();
+    server.route("POST", "/upload", StubResponse::new(201));
+
+    let response = exchange(
+        &server,
+        concat!(
+            "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+            "4;ext=1\r\nevid\r\n4\r\nence\r\n0\r\nDigest: x\r\n\r\n"
+        ),
+    );
+    assert!(response.starts_with("HTTP/1.1 201"), "{}", response);
+    assert_eq!(server.requests()[0].body, b"evidence");
+}
+
+#[test]
+fn rejects_a_malformed_chunk_size() {
+    let server = HttpStub::start();
+    server.route("POST", "/upload", StubResponse::new(201));
+
+    let response = exchange(
+        &server,
+        "POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\nevidence\r\n0\r\n\r\n",
+    );
+    assert_eq!(response, "");
+    assert!(server.requests().is_empty());
+}
+
+/// Uploads a report and a note as `multipart/form-data` and returns the request the stub recorded.
+fn upload(server: &HttpStub) -> RecordedRequest {
+    server.route("POST", "/evidence", StubResponse::new(201));
+    let body = concat!(
+        "--XyZ\r\nContent-Disposition: form-data; name=\"report\"; filename=\"report.json\"\r\n",
+        "Content-Type: application/json\r\n\r\n{\"status\": \"passed\"}\r\n",
+        "--XyZ\r\nContent-Disposition: form-data; name=\"note\"\r\n\r\nretried once\r\n--XyZ--\r\n"
+    );
+    let headers = "POST /evidence HTTP/1.1\r\nContent-Type: multipart/form-data; boundary=XyZ\r\n";
+    exchange(server, &format!("{}Content-Length: {}\r\n\r\n{}", headers, body.len(), body));
+    server.requests().remove(0)
+}
+
+#[test]
+fn asserts_on_the_parts_of_a_multipart_upload() {
+    let server = HttpStub::start();
+    let request = upload(&server);
+
+    request
+        .assert_part("report", ExpectedPart::new().content_type("application/json").filename("report.json"))
+        .assert_part("note", ExpectedPart::new().body("retried once"));
+}
+
+#[test]
+fn a_missing_or_different_part_lists_the_parts() {
+    let server = HttpStub::start();
+    let request = upload(&server);
+
+    let report = report_of(|| request.assert_part("signature", ExpectedPart::new()));
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("report (application/json, 20 bytes)"), "{}", hint);
+    let report = report_of(|| request.assert_part("report", ExpectedPart::new().content_type("text/plain")));
+    assert_eq!(report.field, "report content type");
+}
@@ -0,0 +1,41 @@
+//! Explores the interleavings of a model with loom, failing with the first one that breaks it.
+#![cfg(feature = "loom")]
+
+mod common;
+
+use attestify_test_framework::interleaving::sync::Arc;
+use attestify_test_framework::interleaving::sync::atomic::{AtomicUsize, Ordering};
+use attestify_test_framework::interleaving::{Exploration, assert_interleavings, assert_interleavings_with, thread};
+use common::report_of;
+
+#[test]
+fn an_atomic_increment_holds_in_every_interleaving() {
+    assert_interleavings(|| {
+        let uploads = Arc::new(AtomicUsize::new(0));
+        let counted = uploads.clone();
+        let upload = thread::spawn(move || counted.fetch_add(1, Ordering::SeqCst));
+        uploads.fetch_add(1, Ordering::SeqCst);
+        upload.join().unwrap();
+        assert_eq!(uploads.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn a_lost_update_is_found() {
+    let report = report_of(|| {
+        assert_interleavings_with(&Exploration::bounded(2), || {
+            let uploads = Arc::new(AtomicUsize::new(0));
+            let counted = uploads.clone();
+            let upload = thread::spawn(move || {
+                let count = counted.load(Ordering::SeqCst);
+                counted.store(count + 1, Ordering::SeqCst);
+            });
+            let count = uploads.load(Ordering::SeqCst);
+            uploads.store(count + 1, Ordering::SeqCst);
+            upload.join().unwrap();
+            assert_eq!(uploads.load(Ordering::SeqCst), 2);
+        })
+    });
+    assert_eq!(report.field, "interleaving");
+    assert!(report.actual.unwrap().contains("left: 1"));
+}
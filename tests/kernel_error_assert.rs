@@ -0,0 +1,50 @@
+//! Asserts on kernel errors fluently, reporting every mismatch at once.
+
+mod common;
+
+use attestify_test_framework::{assert_kernel_error, assert_kernel_error_chain};
+use common::{Audience, Kind, error, gateway_error, report_of};
+
+#[test]
+fn passes_when_every_expectation_holds() {
+    assert_kernel_error!(error(Kind::InvalidInput, Audience::User, "The procedure is empty."))
+        .kind(Kind::InvalidInput)
+        .audience(Audience::User)
+        .message_starts_with("The procedure")
+        .message_contains("is empty");
+
+    let result = error(Kind::NotFound, Audience::System, "The file is missing.");
+    assert_kernel_error!(&result).message("The file is missing.").assert();
+}
+
+#[test]
+fn fails_with_every_mismatch() {
+    let report = report_of(|| {
+        assert_kernel_error!(error(Kind::InvalidInput, Audience::User, "The procedure is empty."))
+            .kind(Kind::NotFound)
+            .audience(Audience::System)
+            .message_contains("is empty");
+    });
+    assert!(report.summary.contains("2 mismatch"), "{}", report);
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("NotFound") && hint.contains("System"), "{}", hint);
+}
+
+#[test]
+fn fails_when_no_error_is_returned() {
+    let report = report_of(|| assert_kernel_error!(Ok::<u32, common::Error>(7)));
+    assert_eq!(report.field, "result");
+    assert_eq!(report.actual.as_deref(), Some("Ok(7)"));
+}
+
+#[test]
+fn caused_by_walks_the_source_chain() {
+    assert_kernel_error_chain!(gateway_error())
+        .kind(Kind::GatewayError)
+        .caused_by(Kind::NotFound, Audience::System, "evidence/report.yaml");
+
+    let report = report_of(|| {
+        assert_kernel_error_chain!(gateway_error()).caused_by(Kind::InvalidInput, Audience::System, "missing");
+    });
+    assert!(report.hint.unwrap().contains("No cause"));
+}
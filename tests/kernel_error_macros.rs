@@ -0,0 +1,142 @@
+//! The kernel error assertion macros, each passing on a matching error and failing on a mismatch.
+
+mod common;
+
+use attestify_test_framework::diff::render_with;
+use attestify_test_framework::{
+    assert_audience, assert_kernel_error_json, assert_kind, assert_no_error_of_kind, check_is_ok, check_kernel_error_eq,
+    kernel_error_caused_by, kernel_error_eq, kernel_error_matches, kernel_error_not, table_test,
+};
+use common::{Audience, Kind, error, gateway_error, report_of};
+use serde::Deserialize;
+
+#[test]
+fn kernel_error_matches_a_pattern() {
+    kernel_error_matches!(
+        error(Kind::NotFound, Audience::System, "The file '/tmp/evidence-42.yaml' was deleted at 2024-01-01."),
+        Kind::NotFound,
+        Audience::System,
+        r"^The file '\S+' was deleted at \d{4}-\d{2}-\d{2}\.$"
+    );
+
+    let report = report_of(|| {
+        let result = error(Kind::NotFound, Audience::System, "No id.");
+        kernel_error_matches!(result, Kind::NotFound, Audience::System, r"\d+")
+    });
+    assert_eq!(report.field, "message");
+    assert!(report.summary.contains("does not match the expected pattern"), "{}", report);
+}
+
+#[test]
+fn kernel_error_caused_by_finds_the_root_cause() {
+    kernel_error_caused_by!(gateway_error(), Kind::NotFound, Audience::System, "is missing");
+
+    let report = report_of(|| kernel_error_caused_by!(gateway_error(), Kind::NotFound, Audience::User, "is missing"));
+    assert!(report.summary.contains("No cause"), "{}", report);
+}
+
+#[test]
+fn kernel_error_eq_diffs_the_message() {
+    kernel_error_eq!(error(Kind::NotFound, Audience::System, "abc"), Kind::NotFound, Audience::System, "abc");
+
+    let report = report_of(|| {
+        kernel_error_eq!(error(Kind::NotFound, Audience::System, "abd"), Kind::NotFound, Audience::System, "abc")
+    });
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("- abc") && hint.contains("+ abd"), "{}", hint);
+}
+
+#[test]
+fn the_check_variants_return_the_report_instead_of_failing() {
+    let result = error(Kind::NotFound, Audience::User, "abc");
+    assert!(check_kernel_error_eq!(result, Kind::NotFound, Audience::User, "abc").is_ok());
+    assert_eq!(check_is_ok!(Ok::<u32, common::Error>(7)).unwrap(), 7);
+
+    let report =
+        check_kernel_error_eq!(error(Kind::NotFound, Audience::User, "abc"), Kind::NotFound, Audience::System, "abc")
+            .unwrap_err();
+    assert_eq!(report.field, "audience");
+    assert_eq!(check_is_ok!(error(Kind::NotFound, Audience::User, "abc")).unwrap_err().field, "result");
+}
+
+#[test]
+fn assert_kind_and_assert_audience_check_one_attribute() {
+    assert_kind!(error(Kind::NotFound, Audience::User, "The evidence 7 is missing."), Kind::NotFound);
+    assert_audience!(error(Kind::NotFound, Audience::User, "The evidence 7 is missing."), Audience::User);
+
+    let report = report_of(|| assert_kind!(error(Kind::NotFound, Audience::User, "Missing."), Kind::InvalidInput));
+    assert_eq!(report.field, "kind");
+    let report = report_of(|| assert_audience!(error(Kind::NotFound, Audience::User, "Missing."), Audience::System));
+    assert_eq!(report.actual.as_deref(), Some("User"));
+    let report = report_of(|| assert_kind!(Ok::<u32, common::Error>(7), Kind::NotFound));
+    assert!(report.actual.unwrap().contains("Ok(7)"));
+}
+
+#[test]
+fn kernel_error_not_rejects_one_kind_and_audience() {
+    kernel_error_not!(error(Kind::GatewayError, Audience::System, "Timed out."), Kind::GatewayError, Audience::User);
+    kernel_error_not!(Ok::<u32, common::Error>(7), Kind::GatewayError, Audience::User);
+
+    let report = report_of(|| {
+        kernel_error_not!(error(Kind::GatewayError, Audience::User, "Timed out."), Kind::GatewayError, Audience::User)
+    });
+    assert_eq!(report.expected.as_deref(), Some("not GatewayError for User"));
+}
+
+#[test]
+fn the_diff_marks_the_changed_lines() {
+    let diff = render_with("line one\nline two\nline three", "line one\nline 2\nline three", false);
+    assert!(diff.contains("- line two") && diff.contains("+ line 2"), "{}", diff);
+    assert!(!diff.contains("- line one"), "{}", diff);
+}
+
+#[test]
+fn assert_no_error_of_kind_lists_the_offending_results() {
+    let results = vec![
+        Ok(1),
+        error(Kind::NotFound, Audience::User, "The procedure is missing."),
+        error(Kind::GatewayError, Audience::System, "The gateway timed out."),
+    ];
+    assert_no_error_of_kind!(&results, Kind::InvalidInput);
+
+    let report = report_of(|| assert_no_error_of_kind!(&results, Kind::GatewayError));
+    assert!(report.hint.unwrap().contains("[2] GatewayError, System: \"The gateway timed out.\""));
+}
+
+#[derive(Debug, Deserialize)]
+struct SerializedError {
+    kind: Kind,
+    audience: Audience,
+    message: String,
+}
+
+#[test]
+fn assert_kernel_error_json_deserializes_the_error() {
+    let json = r#"{"kind":"NotFound","audience":"User","message":"The procedure is missing."}"#;
+    assert_kernel_error_json!(json, SerializedError, Kind::NotFound, Audience::User, "The procedure is missing.");
+    assert_kernel_error_json!(json, SerializedError).message_contains("missing");
+
+    let report = report_of(|| {
+        assert_kernel_error_json!(r#"{"kind":"Unknown"}"#, SerializedError, Kind::NotFound, Audience::User, "")
+    });
+    assert_eq!(report.field, "json");
+    assert!(report.to_string().contains("unknown variant"), "{}", report);
+    let report = report_of(|| assert_kernel_error_json!("not json", SerializedError));
+    assert_eq!(report.field, "json");
+}
+
+fn parse(procedure: &str) -> Result<u32, common::Error> {
+    match procedure {
+        "" => error(Kind::InvalidInput, Audience::User, "The procedure is empty."),
+        _ => error(Kind::InvalidInput, Audience::User, "The procedure has no name."),
+    }
+}
+
+table_test! {
+    name: parse_errors,
+    subject: |procedure: &str| parse(procedure),
+    cases: {
+        empty_procedure => ("", Kind::InvalidInput, Audience::User, "The procedure is empty."),
+        unnamed_procedure => ("version: 1", Kind::InvalidInput, Audience::User, "The procedure has no name."),
+    }
+}
@@ -0,0 +1,37 @@
+//! Asserts on the headings, table rows, and links of a rendered Markdown report.
+
+mod common;
+
+use attestify_test_framework::{assert_md_has_heading, assert_md_link, assert_md_table_row};
+use common::report_of;
+
+const REPORT: &str = "# Assurance `Report`
+
+## Evidence
+
+| Evidence | Status |
+|---|---|
+| build-log | **passed** |
+| [tests](tests.html) | failed |
+
+See [the *procedure*](https://example.com/p1).
+";
+
+#[test]
+fn finds_the_rendered_headings_rows_and_links() {
+    assert_md_has_heading!(REPORT, 1, "Assurance Report");
+    assert_md_has_heading!(REPORT, "Evidence");
+    assert_md_table_row!(REPORT, ["build-log", "passed"]);
+    assert_md_table_row!(REPORT, ["tests", "failed"]);
+    assert_md_link!(REPORT, "the procedure", "https://example.com/p1");
+}
+
+#[test]
+fn lists_what_the_report_has_instead() {
+    let report = report_of(|| assert_md_has_heading!(REPORT, 3, "Evidence"));
+    assert!(report.hint.unwrap().contains("Headings:\n\t\t# Assurance Report\n\t\t## Evidence"));
+    let report = report_of(|| assert_md_table_row!(REPORT, ["build-log"]));
+    assert!(report.hint.unwrap().contains("[table 0] | build-log | passed |"));
+    let report = report_of(|| assert_md_link!(REPORT, "the procedure", "https://example.com/p2"));
+    assert!(report.hint.unwrap().contains("https://example.com/p1"));
+}
@@ -0,0 +1,38 @@
+//! Composes matchers, including one defined by the test, and asserts values with them.
+
+mod common;
+
+use attestify_test_framework::assert_that;
+use attestify_test_framework::matchers::{Matcher, all_of, any_of, equal_to, not, predicate};
+use common::report_of;
+
+struct SignedEvidence;
+
+impl Matcher<String> for SignedEvidence {
+    fn matched(&self, actual: &String) -> bool {
+        actual.starts_with("sig:")
+    }
+
+    fn describe(&self) -> String {
+        "signed evidence".to_string()
+    }
+}
+
+#[test]
+fn values_that_match_pass() {
+    assert_that!("sig:abc".to_string(), SignedEvidence);
+    assert_that!("unsigned".to_string(), not(SignedEvidence));
+    assert_that!(3, all_of(vec![predicate("positive", |n: &i32| *n > 0).boxed(), not(equal_to(4)).boxed()]));
+    assert_that!(5, any_of(vec![equal_to(1).boxed(), equal_to(5).boxed()]));
+}
+
+#[test]
+fn a_mismatch_reports_what_was_expected() {
+    let report = report_of(|| assert_that!("unsigned".to_string(), SignedEvidence));
+    assert_eq!(report.expected.as_deref(), Some("signed evidence"));
+
+    let report = report_of(|| {
+        assert_that!(4, all_of(vec![predicate("positive", |n: &i32| *n > 0).boxed(), not(equal_to(4)).boxed()]))
+    });
+    assert!(report.expected.unwrap().contains("positive"));
+}
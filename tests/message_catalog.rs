@@ -0,0 +1,37 @@
+//! Asserts an error's message is the message of a catalog key, for the catalog of the locale under test.
+
+mod common;
+
+use attestify_test_framework::assertions::messages::{MessageCatalog, set_message_catalog};
+use attestify_test_framework::kernel_error_msg_key;
+use common::{Audience, Kind, error, report_of};
+
+const CATALOG: &str = "errors:
+  evidence:
+    missing: \"La preuve '{name}' est introuvable.\"
+  empty: La procédure est vide.
+";
+
+#[test]
+fn a_message_of_the_key_passes() {
+    set_message_catalog(MessageCatalog::from_yaml("fr", CATALOG));
+    let result = error(Kind::NotFound, Audience::User, "La preuve 'report.yaml' est introuvable.");
+    kernel_error_msg_key!(result, Kind::NotFound, Audience::User, "errors.evidence.missing", name = "report.yaml");
+    let result = error(Kind::InvalidInput, Audience::User, "La procédure est vide.");
+    kernel_error_msg_key!(result, Kind::InvalidInput, Audience::User, "errors.empty");
+}
+
+#[test]
+fn another_message_or_an_unknown_key_fails() {
+    set_message_catalog(MessageCatalog::from_yaml("fr", CATALOG));
+    let report = report_of(|| {
+        let result = error(Kind::NotFound, Audience::User, "La preuve 'summary.md' est introuvable.");
+        kernel_error_msg_key!(result, Kind::NotFound, Audience::User, "errors.evidence.missing", name = "report.yaml")
+    });
+    assert!(report.summary.contains("'errors.evidence.missing' of the catalog 'fr'"), "{}", report);
+    let report = report_of(|| {
+        let result = error(Kind::InvalidInput, Audience::User, "La procédure est vide.");
+        kernel_error_msg_key!(result, Kind::InvalidInput, Audience::User, "errors.missing")
+    });
+    assert!(report.hint.unwrap().contains("errors.empty"));
+}
@@ -0,0 +1,27 @@
+//! Validates NAPE resource names, with the fixture's valid and invalid variants.
+
+mod common;
+
+use attestify_test_framework::assert_valid_nrn;
+use attestify_test_framework::fixtures::nrn_fixture::NrnFixture;
+use common::report_of;
+
+#[test]
+fn the_valid_variants_pass() {
+    let fixture = NrnFixture::new("procedure").with_owner("acme");
+    assert_valid_nrn!(fixture.build(), "procedure");
+    for nrn in fixture.valid_variants() {
+        assert_valid_nrn!(nrn, "procedure");
+    }
+}
+
+#[test]
+fn the_invalid_variants_fail_with_the_reason() {
+    for variant in NrnFixture::new("procedure").invalid_variants() {
+        report_of(|| assert_valid_nrn!(variant.nrn));
+    }
+    let report = report_of(|| assert_valid_nrn!("nrn:procedure:Acme:p1"));
+    assert!(report.summary.contains("owner segment \"Acme\" must start with"), "{}", report);
+    let report = report_of(|| assert_valid_nrn!("nrn:subject:acme:p1", "procedure"));
+    assert!(report.summary.contains("resource type \"procedure\""), "{}", report);
+}
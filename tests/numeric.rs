@@ -0,0 +1,24 @@
+//! Compares numbers within an absolute or a relative tolerance.
+
+mod common;
+
+use attestify_test_framework::{assert_approx_eq, assert_approx_eq_pct};
+use common::report_of;
+
+#[test]
+fn numbers_within_the_tolerance_pass() {
+    assert_approx_eq!(0.1 + 0.2, 0.3, 1e-9);
+    assert_approx_eq!(3u32, 3, 0);
+    assert_approx_eq_pct!(82.4, 82.0, 1.0);
+}
+
+#[test]
+fn reports_the_delta_beyond_the_tolerance() {
+    let report = report_of(|| assert_approx_eq!(0.7525, 0.75, 0.001));
+    assert_eq!(report.expected.as_deref(), Some("0.75 ± 0.001"));
+    assert!(report.actual.unwrap().starts_with("0.7525 (delta 0.00249"));
+    let report = report_of(|| assert_approx_eq_pct!(90.0, 80.0, 1));
+    assert_eq!(report.actual.as_deref(), Some("90 (delta 10, 12.5%)"));
+    let report = report_of(|| assert_approx_eq!(f64::NAN, f64::NAN, 1.0));
+    assert!(report.summary.contains("not a number"), "{}", report);
+}
@@ -0,0 +1,46 @@
+//! Renders the failures with the output configuration, which is global, so it is only set by one test.
+
+mod common;
+
+use attestify_test_framework::assertions::{self, OutputConfig};
+use attestify_test_framework::assert_duration_between;
+use attestify_test_framework::diff::render_with_config;
+use common::report_of;
+use std::time::Duration;
+
+#[test]
+fn failures_are_rendered_with_the_configuration() {
+    assertions::set_config(OutputConfig::plain().with_unicode(false).with_max_length(Some(4)));
+    assert_duration_between!(Duration::from_micros(12), Duration::from_micros(10), Duration::from_micros(20));
+
+    let report = report_of(|| {
+        assert_duration_between!(Duration::from_micros(120), Duration::from_micros(10), Duration::from_micros(20))
+    });
+    let rendered = report.to_string();
+    assertions::reset_config();
+    assert_eq!(report.expected.as_deref(), Some("10us-20us"));
+    assert!(rendered.contains("\tExpected:\t10us... (5 more characters)\n"), "{}", rendered);
+    assert_eq!(assertions::config(), OutputConfig::from_env());
+}
+
+#[test]
+fn values_are_truncated_to_the_maximum_length() {
+    let config = OutputConfig::plain().with_unicode(false).with_max_length(Some(4));
+    assert_eq!(config.truncate("abcd"), "abcd");
+    assert_eq!(config.truncate("abcdefghij"), "abcd... (6 more characters)");
+}
+
+#[test]
+fn a_long_diff_is_cut_to_the_changes_and_their_context() {
+    let expected: Vec<String> = (0..1000).map(|line| format!("check {}", line)).collect();
+    let mut actual = expected.clone();
+    actual[500] = "check skipped".to_string();
+    let config = OutputConfig::plain().with_max_diff_lines(Some(50)).with_diff_context(2);
+
+    let diff = render_with_config(&expected.join("\n"), &actual.join("\n"), &config);
+    assert!(diff.contains("\t… 498 unchanged lines\n\t  check 498\n\t  check 499\n\t- check 500\n"), "{}", diff);
+    assert!(diff.ends_with("\t  check 502\n\t… 497 unchanged lines\n"), "{}", diff);
+    assert!(!diff.contains("check 497\n"), "{}", diff);
+    let diff = render_with_config("status: passed\nchecks: 2", "status: passed\nchecks: 3", &config);
+    assert_eq!(diff.lines().filter(|line| line.starts_with("\t…")).count(), 0, "{}", diff);
+}
@@ -0,0 +1,34 @@
+//! The panic hook installed before the panics are silenced is the one installed once no silenced closure is running.
+
+use attestify_test_framework::assert_panics_with;
+use attestify_test_framework::panics::catch_panic;
+use std::panic::{self, PanicHookInfo};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+fn address(hook: &PanicHook) -> *const () {
+    &**hook as *const _ as *const ()
+}
+
+#[test]
+fn the_previous_hook_is_restored() {
+    let increment = 1;
+    let hook: PanicHook = Box::new(move |_| {
+        CALLS.fetch_add(increment, Ordering::SeqCst);
+    });
+    let installed = address(&hook);
+    panic::set_hook(hook);
+
+    assert_eq!(catch_panic(|| panic!("silenced")), Some("silenced".to_string()));
+    assert_panics_with!(|| assert_panics_with!(|| (), "nested"), "A panic was expected");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+    assert!(thread::spawn(|| panic!("reported")).join().is_err());
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    assert_eq!(address(&panic::take_hook()), installed);
+}
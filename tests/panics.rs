@@ -0,0 +1,25 @@
+//! Asserts on the message of a panic, failing when there is no panic or it says something else.
+
+mod common;
+
+use attestify_test_framework::assert_panics_with;
+use common::report_of;
+
+#[test]
+fn returns_the_message_of_a_matching_panic() {
+    let message = assert_panics_with!(|| panic!("The procedure {} is empty.", "p1"), "is empty");
+    assert_eq!(message, "The procedure p1 is empty.");
+}
+
+#[test]
+fn fails_when_nothing_panics() {
+    let report = report_of(|| assert_panics_with!(|| 42, "is empty"));
+    assert_eq!(report.summary, "A panic was expected, although one did not occur.");
+    assert_eq!(report.expected.as_deref(), Some("\"is empty\""));
+}
+
+#[test]
+fn fails_when_the_panic_says_something_else() {
+    let report = report_of(|| assert_panics_with!(|| panic!("The gateway timed out."), "is empty"));
+    assert_eq!(report.actual.as_deref(), Some("\"The gateway timed out.\""));
+}
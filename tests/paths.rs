@@ -0,0 +1,23 @@
+//! Compares paths written with either separator.
+
+mod common;
+
+use attestify_test_framework::{assert_path_ends_with, assert_path_eq};
+use common::report_of;
+use std::path::PathBuf;
+
+#[test]
+fn paths_with_either_separator_are_equal() {
+    assert_path_eq!("evidence/report.yaml", "evidence\\report.yaml");
+    assert_path_eq!("./evidence//report.yaml", PathBuf::from("evidence").join("report.yaml"));
+    assert_path_ends_with!("C:\\work\\evidence\\report.yaml", "evidence/report.yaml");
+}
+
+#[test]
+fn different_paths_report_both() {
+    let report = report_of(|| assert_path_eq!("/evidence/report.yaml", "evidence/report.yaml"));
+    assert_eq!(report.field, "path");
+    assert_eq!(report.actual.as_deref(), Some("evidence/report.yaml"));
+    let report = report_of(|| assert_path_ends_with!("evidence/report.yaml", "port.yaml"));
+    assert_eq!(report.expected.as_deref(), Some("a path ending with port.yaml"));
+}
@@ -0,0 +1,38 @@
+//! Compares the median timing of a routine with its recorded baseline.
+
+mod common;
+
+use attestify_test_framework::perf::{BASELINE_FILE, assert_faster_than_baseline};
+use attestify_test_framework::workspace::TestWorkspace;
+use common::report_of;
+use std::hint::black_box;
+
+/// Creates a crate directory whose baseline `render_report` has the median.
+fn crate_with_baseline(median_nanos: u64) -> TestWorkspace {
+    let workspace = TestWorkspace::new();
+    let baseline = format!("{{\"render_report\": {{\"median_nanos\": {}, \"samples\": 9}}}}", median_nanos);
+    workspace.create_file(BASELINE_FILE, &baseline);
+    workspace
+}
+
+fn render_report() -> String {
+    (0..100).map(|activity| format!("- activity {}\n", black_box(activity))).collect()
+}
+
+#[test]
+fn a_routine_within_its_baseline_passes() {
+    let workspace = crate_with_baseline(60_000_000_000);
+    let report = assert_faster_than_baseline(workspace.root().to_str().unwrap(), "render_report", 10.0, render_report);
+    assert!(report.starts_with("- activity 0\n"));
+}
+
+#[test]
+fn a_routine_slower_than_its_baseline_reports_the_timing() {
+    let workspace = crate_with_baseline(1);
+    let root = workspace.root().to_str().unwrap();
+    let report = report_of(|| assert_faster_than_baseline(root, "render_report", 10.0, render_report));
+    assert_eq!(report.field, "render_report");
+    assert!(report.expected.unwrap().starts_with("at most 1ns"));
+    let report = report_of(|| assert_faster_than_baseline(root, "render_summary", 10.0, render_report));
+    assert!(report.summary.starts_with("The baseline 'render_summary' does not exist"), "{}", report);
+}
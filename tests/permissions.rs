@@ -0,0 +1,29 @@
+//! Asserts on the permissions of the files in a workspace.
+
+mod common;
+
+use attestify_test_framework::assert_file_mode;
+use attestify_test_framework::workspace::TestWorkspace;
+use common::report_of;
+
+#[test]
+fn a_file_made_read_only_is_read_only() {
+    let workspace = TestWorkspace::new();
+    let report = workspace.create_file("evidence/report.yaml", "status: passed\n");
+    assert_file_mode!(report, writable);
+
+    workspace.make_readonly("evidence/report.yaml");
+    assert_file_mode!(report, readonly);
+}
+
+#[test]
+fn a_different_mode_reports_the_permissions() {
+    let workspace = TestWorkspace::new();
+    let report_file = workspace.create_file("evidence/report.yaml", "status: passed\n");
+
+    let report = report_of(|| assert_file_mode!(report_file, readonly));
+    assert_eq!(report.expected.as_deref(), Some("read-only"));
+    assert!(report.actual.unwrap().starts_with("writable"));
+    let report = report_of(|| assert_file_mode!(workspace.path_for("evidence/missing.yaml"), readonly));
+    assert_eq!(report.summary, "The path does not exist.");
+}
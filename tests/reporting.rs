@@ -0,0 +1,40 @@
+//! Collects the outcome of each test and renders the failed assertions as JUnit XML.
+
+mod common;
+
+use attestify_test_framework::kernel_error_eq;
+use attestify_test_framework::reporting::{JUnitReport, TestOutcome};
+use common::{Audience, Kind, error};
+
+#[test]
+fn a_passing_test_is_written_without_a_failure() {
+    let mut report = JUnitReport::new("procedure_usecases");
+    let outcome = report.run("rejects_an_empty_procedure", || {
+        let result = error(Kind::InvalidInput, Audience::User, "The procedure is empty.");
+        kernel_error_eq!(result, Kind::InvalidInput, Audience::User, "The procedure is empty.");
+    });
+
+    assert_eq!(*outcome, TestOutcome::Passed);
+    let xml = report.to_xml();
+    assert!(xml.contains("tests=\"1\" failures=\"0\""), "{}", xml);
+    assert!(!xml.contains("<failure"), "{}", xml);
+}
+
+#[test]
+fn a_failed_assertion_is_written_with_its_details() {
+    let mut report = JUnitReport::new("procedure_usecases");
+    report.run("rejects_an_empty_procedure", || {
+        let result = error(Kind::NotFound, Audience::User, "The procedure is empty.");
+        kernel_error_eq!(result, Kind::InvalidInput, Audience::User, "The procedure is empty.");
+    });
+    report.run("panics", || panic!("The procedure is empty."));
+
+    assert_eq!(report.failures(), 2);
+    let TestOutcome::Failed(failure) = &report.cases()[1].outcome else {
+        panic!("The test was expected to fail.");
+    };
+    assert_eq!(failure.field, "panic");
+    let xml = report.to_xml();
+    assert!(xml.contains("<failure message=\"Kind does not match.\" type=\"kind\">"), "{}", xml);
+    assert!(xml.contains("<property name=\"mismatch.expected\" value=\"InvalidInput\"/>"), "{}", xml);
+}
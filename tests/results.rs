@@ -0,0 +1,43 @@
+//! Asserts on the value of an `Ok` result, returning it for further assertions.
+
+mod common;
+
+use attestify_test_framework::{assert_ok_and, is_ok_eq};
+use common::report_of;
+
+fn activities(count: usize) -> Result<Vec<u32>, String> {
+    match count {
+        0 => Err("The procedure has no activities.".to_string()),
+        count => Ok((1..=count as u32).collect()),
+    }
+}
+
+#[test]
+fn assert_ok_and_returns_the_value_satisfying_the_predicate() {
+    let activities = assert_ok_and!(activities(3), |activities| activities.len() == 3, "3 activities were expected");
+    assert_eq!(activities, [1, 2, 3]);
+}
+
+#[test]
+fn assert_ok_and_reports_the_value_or_the_error() {
+    let report = report_of(|| assert_ok_and!(activities(2), |activities| activities.len() == 3, "{} activities", 3));
+    assert_eq!(report.summary, "3 activities");
+    assert_eq!(report.actual.as_deref(), Some("Ok([1, 2])"));
+    let report = report_of(|| assert_ok_and!(activities(0), |activities| activities.is_empty()));
+    assert_eq!(report.actual.as_deref(), Some("Err(\"The procedure has no activities.\")"));
+}
+
+#[test]
+fn is_ok_eq_returns_the_equal_value() {
+    let activities = is_ok_eq!(activities(2), vec![1, 2]);
+    assert_eq!(activities.len(), 2);
+    is_ok_eq!(Ok::<String, ()>("p1".to_string()), "p1");
+}
+
+#[test]
+fn is_ok_eq_diffs_a_different_value() {
+    let report = report_of(|| is_ok_eq!(activities(2), vec![1, 3]));
+    assert!(report.hint.unwrap().contains("+     2,"));
+    let report = report_of(|| is_ok_eq!(activities(0), vec![1]));
+    assert_eq!(report.summary, "An Ok was expected, although an Error was returned.");
+}
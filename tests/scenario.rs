@@ -0,0 +1,45 @@
+//! Runs given/when/then scenarios, which report the step that failed and the steps around it.
+
+mod common;
+
+use attestify_test_framework::scenario::{Scenario, step};
+use common::report_of;
+
+#[derive(Debug, Default)]
+struct Upload {
+    files: Vec<&'static str>,
+    uploaded: usize,
+}
+
+fn a_bundle(upload: &mut Upload) {
+    upload.files = vec!["report.yaml", "evidence.json"];
+}
+
+fn uploading_it(upload: &mut Upload) {
+    upload.uploaded = upload.files.len();
+}
+
+fn every_file_is_uploaded(upload: &mut Upload) {
+    assert_eq!(upload.uploaded, 2);
+}
+
+#[test]
+fn a_scenario_runs_its_steps_in_order() {
+    let upload = Scenario::new().given(a_bundle).when(uploading_it).then(every_file_is_uploaded).run();
+    assert_eq!(upload.uploaded, 2);
+}
+
+#[test]
+fn a_failed_step_is_reported_with_the_steps_around_it() {
+    let report = report_of(|| {
+        Scenario::<Upload>::new()
+            .given(a_bundle)
+            .then(every_file_is_uploaded)
+            .and(step("nothing else is uploaded", |_: &mut Upload| {}))
+            .run()
+    });
+    assert_eq!(report.field, "Then every_file_is_uploaded");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("[passed]\tGiven a_bundle"), "{}", hint);
+    assert!(hint.contains("[skipped]\tThen nothing else is uploaded"), "{}", hint);
+}
@@ -0,0 +1,62 @@
+//! Compares what an iterator or a stream yields with the expected items, one position at a time.
+
+mod common;
+
+use attestify_test_framework::{assert_iter_yields, assert_stream_yields};
+use common::{block_on, report_of};
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Yields the numbers of the activities up to the count, pending once before each.
+struct Activities {
+    yielded: u32,
+    count: u32,
+    pending: bool,
+}
+
+impl Activities {
+    fn new(count: u32) -> Self {
+        Activities {
+            yielded: 0,
+            count,
+            pending: true,
+        }
+    }
+}
+
+impl Stream for Activities {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<u32>> {
+        if self.pending {
+            self.pending = false;
+            context.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.pending = true;
+        if self.yielded == self.count {
+            return Poll::Ready(None);
+        }
+        self.yielded += 1;
+        Poll::Ready(Some(self.yielded))
+    }
+}
+
+#[test]
+fn the_expected_items_pass() {
+    let files = ["report.yaml", "evidence.json"];
+    assert_iter_yields!(files.into_iter().map(String::from), ["report.yaml", "evidence.json"]);
+    block_on(async { assert_stream_yields!(Activities::new(3), [1, 2, 3]) });
+}
+
+#[test]
+fn reports_the_position_where_the_items_diverge() {
+    let report = report_of(|| assert_iter_yields!([1, 5, 3], [1, 2, 3]));
+    assert_eq!(report.field, "[1]");
+    assert_eq!(report.expected.as_deref(), Some("2"));
+    let report = report_of(|| assert_iter_yields!(1.., [1, 2]));
+    assert!(report.summary.contains("more than the 2 expected items"), "{}", report);
+    let report = report_of(|| block_on(async { assert_stream_yields!(Activities::new(2), [1, 2, 3]) }));
+    assert!(report.summary.starts_with("The sequence ended after 2 items"), "{}", report);
+}
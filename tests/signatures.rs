@@ -0,0 +1,29 @@
+//! Signs evidence with the deterministic test keys and verifies the signatures.
+#![cfg(feature = "crypto")]
+
+mod common;
+
+use attestify_test_framework::assert_signature_valid;
+use attestify_test_framework::fixtures::crypto_fixtures::{ed25519_keypair, rsa_keypair};
+use common::report_of;
+
+const EVIDENCE: &[u8] = b"status: passed\n";
+
+#[test]
+fn a_signature_of_the_evidence_is_valid() {
+    let ed25519 = ed25519_keypair(0);
+    assert_signature_valid!(EVIDENCE, ed25519.sign(EVIDENCE), ed25519.public_key());
+    let rsa = rsa_keypair();
+    assert_signature_valid!(EVIDENCE, rsa.sign(EVIDENCE), rsa.public_key());
+}
+
+#[test]
+fn a_signature_by_another_key_is_not_valid() {
+    let signature = ed25519_keypair(0).sign(EVIDENCE);
+    let report = report_of(|| assert_signature_valid!(EVIDENCE, signature, ed25519_keypair(1).public_key()));
+    assert_eq!(report.field, "signature");
+
+    let public_key = ed25519_keypair(0).public_key();
+    let report = report_of(|| assert_signature_valid!(b"status: failed\n", signature, public_key));
+    assert_eq!(report.summary, "The signature is not valid for the payload.");
+}
@@ -0,0 +1,41 @@
+//! Compares values with the snapshots committed in `tests/__snapshots__`.
+
+mod common;
+
+use attestify_test_framework::snapshot::update_requested;
+use attestify_test_framework::{assert_error_display_stable, assert_matches_snapshot};
+use common::{Audience, Kind, error, report_of};
+
+#[test]
+fn a_value_matches_its_snapshot() {
+    assert_matches_snapshot!(vec!["report.yaml", "evidence.json"], "evidence_files");
+}
+
+#[test]
+fn a_changed_value_fails_with_a_diff() {
+    // Updating the snapshots would overwrite the snapshot with the changed value.
+    if update_requested() {
+        return;
+    }
+    let report = report_of(|| assert_matches_snapshot!(vec!["report.yaml", "evidence.yaml"], "evidence_files"));
+    assert_eq!(report.field, "evidence_files");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("- - evidence.json") && hint.contains("+ - evidence.yaml"), "{}", hint);
+}
+
+#[test]
+fn an_error_displays_as_in_its_snapshot() {
+    let missing = error(Kind::NotFound, Audience::User, "The file 'evidence/report.yaml' is missing.").unwrap_err();
+    assert_error_display_stable!(missing, "missing_evidence_error");
+}
+
+#[test]
+fn a_changed_error_message_fails() {
+    if update_requested() {
+        return;
+    }
+    let missing = error(Kind::NotFound, Audience::User, "The file 'evidence/report.yml' is missing.").unwrap_err();
+    let report = report_of(|| assert_error_display_stable!(missing, "missing_evidence_error"));
+    assert_eq!(report.field, "missing_evidence_error");
+    assert!(report.hint.unwrap().contains("+ The file 'evidence/report.yml' is missing."));
+}
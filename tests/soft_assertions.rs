@@ -0,0 +1,44 @@
+//! Soft assertions collect every failure of a test and report them together.
+
+mod common;
+
+use attestify_test_framework::soft::SoftAssertions;
+use attestify_test_framework::{assert_kernel_error, assert_yaml_eq, soft_kernel_error_eq};
+use common::{Audience, Kind, error, report_of};
+
+#[test]
+fn passing_checks_do_not_fail_the_test() {
+    let mut soft = SoftAssertions::new();
+    soft.eq("passed", "passed", "status");
+    soft.is_true(true, "signed");
+    let missing = error(Kind::NotFound, Audience::User, "Missing.");
+    soft_kernel_error_eq!(soft, missing, Kind::NotFound, Audience::User, "Missing.");
+    soft.check("document", || assert_yaml_eq!("status: passed", "status: passed"));
+    soft.assert_all();
+}
+
+#[test]
+fn every_failure_is_reported_together() {
+    let report = report_of(|| {
+        let mut soft = SoftAssertions::new();
+        soft.eq(1, 2, "count");
+        soft.is_true(false, "signed");
+        let missing = error(Kind::NotFound, Audience::User, "Missing.");
+        soft_kernel_error_eq!(soft, missing, Kind::InvalidInput, Audience::User, "Missing.");
+        soft.check("document", || assert_yaml_eq!("status: passed", "status: failed"));
+        let missing = error(Kind::NotFound, Audience::User, "Missing.");
+        soft.kernel_error(assert_kernel_error!(missing).message_contains("Found"));
+        soft.assert_all();
+    });
+    assert_eq!(report.field, "soft");
+    assert!(report.summary.starts_with("5 soft assertion(s) failed"), "{}", report);
+}
+
+#[test]
+fn dropping_unchecked_failures_fails_the_test() {
+    let report = report_of(|| {
+        let mut soft = SoftAssertions::new();
+        soft.is_true(false, "signed");
+    });
+    assert!(report.summary.starts_with("1 soft assertion(s) failed"), "{}", report);
+}
@@ -0,0 +1,71 @@
+//! Compares serializable values field by field, or against the expected values of some fields, reporting the path
+//! of every field that differs.
+
+mod common;
+
+use attestify_test_framework::assertions::structs::ignore;
+use attestify_test_framework::{assert_struct_eq, assert_struct_matches};
+use common::report_of;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct Evidence {
+    name: &'static str,
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct AssuranceReport {
+    subject: &'static str,
+    evidence: Vec<Evidence>,
+}
+
+fn assurance_report(subject: &'static str, status: &'static str) -> AssuranceReport {
+    AssuranceReport {
+        subject,
+        evidence: vec![
+            Evidence {
+                name: "report.yaml",
+                status: "passed",
+            },
+            Evidence {
+                name: "evidence.json",
+                status,
+            },
+        ],
+    }
+}
+
+#[test]
+fn equal_values_pass() {
+    assert_struct_eq!(assurance_report("nrn:p1", "passed"), assurance_report("nrn:p1", "passed"));
+}
+
+#[test]
+fn reports_every_differing_field() {
+    let expected = assurance_report("nrn:p1", "passed");
+    let report = report_of(|| assert_struct_eq!(expected, assurance_report("nrn:p2", "failed")));
+    assert_eq!(report.field, "$.evidence[1].status");
+    assert!(report.summary.starts_with("The AssuranceReport values differ in 2 field(s)"), "{}", report);
+    assert!(report.hint.unwrap().contains("$.subject\n\t\tExpected:\t\"nrn:p1\"\n\t\tActual:\t\"nrn:p2\""));
+}
+
+#[test]
+fn matching_fields_pass_with_the_rest_ignored() {
+    let report = assurance_report("nrn:p1", "failed");
+    assert_struct_matches!(report, {
+        "subject": "nrn:p1",
+        "evidence[0].status": "passed",
+        "evidence[1].name": ignore()
+    });
+}
+
+#[test]
+fn reports_every_field_that_does_not_match() {
+    let assurance = assurance_report("nrn:p1", "failed");
+    let report = report_of(|| assert_struct_matches!(assurance, { "evidence[*].status": "passed", "owner": "team" }));
+    assert!(report.summary.contains("does not match in 2 field(s)"), "{}", report);
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("$.evidence[1].status"), "{}", hint);
+    assert!(hint.contains("$.owner\n\t\tExpected:\t\"team\"\n\t\tActual:\t<missing>"), "{}", hint);
+}
@@ -0,0 +1,59 @@
+//! Assertions that fail outside the assertion macros still fail through a structured report.
+
+use attestify_test_framework::assertions::directory::compare_dirs;
+use attestify_test_framework::assertions::report::{MismatchReport, take_last_failure};
+use attestify_test_framework::matchers::KernelErrorAssert;
+use attestify_test_framework::panics::catch_panic;
+use attestify_test_framework::snapshot::assert_snapshot;
+use attestify_test_framework::timing::complete_within;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Runs the failing assertion and returns the report it failed with.
+fn report_of(assertion: impl FnOnce()) -> MismatchReport {
+    let message = catch_panic(assertion).expect("The assertion was expected to fail.");
+    let report = take_last_failure().expect("The assertion did not fail with a report.");
+    assert_eq!(message, report.to_string());
+    report
+}
+
+#[test]
+fn complete_within_reports_the_duration() {
+    assert_eq!(complete_within(Duration::from_secs(5), || 42), 42);
+
+    let report = report_of(|| complete_within(Duration::from_millis(10), || thread::sleep(Duration::from_secs(1))));
+    assert_eq!(report.field, "duration");
+    assert_eq!(report.expected.as_deref(), Some("at most 10ms"));
+}
+
+#[test]
+fn a_missing_snapshot_reports_its_path() {
+    let report = report_of(|| assert_snapshot(env!("CARGO_MANIFEST_DIR"), "no_such_snapshot", "contents"));
+    assert_eq!(report.field, "no_such_snapshot");
+    assert!(report.expected.unwrap().contains("no_such_snapshot"));
+    assert!(report.actual.is_some());
+}
+
+#[test]
+fn an_invalid_pattern_reports_the_pattern() {
+    let matching = KernelErrorAssert::new("Kind", "Audience", "The procedure is empty.".to_string())
+        .message_matches("procedure .* empty")
+        .into_mismatches();
+    assert!(matching.is_empty());
+
+    let report = report_of(|| {
+        KernelErrorAssert::new("Kind", "Audience", "The procedure is empty.".to_string()).message_matches("(");
+    });
+    assert_eq!(report.field, "message");
+    assert_eq!(report.actual.as_deref(), Some("\"(\""));
+}
+
+#[test]
+fn a_missing_directory_reports_the_directory() {
+    let report = report_of(|| {
+        compare_dirs(Path::new("no/such/expected"), Path::new("no/such/actual"), &[]);
+    });
+    assert_eq!(report.field, "directory");
+    assert_eq!(report.actual.as_deref(), Some("nothing"));
+}
@@ -0,0 +1,22 @@
+//! Compares texts that differ only in line endings and whitespace.
+
+mod common;
+
+use attestify_test_framework::assert_text_eq_normalized;
+use common::report_of;
+
+#[test]
+fn line_endings_and_trailing_whitespace_are_ignored() {
+    assert_text_eq_normalized!("status: passed\nchecks: 2\n", "status: passed  \r\nchecks: 2\r\n\r\n");
+    assert_text_eq_normalized!("checks:\n  - id: a", "checks:\n    - id: a", ignore_indentation);
+}
+
+#[test]
+fn a_different_text_is_diffed() {
+    let report = report_of(|| assert_text_eq_normalized!("status: passed\n", "status: failed\r\n"));
+    assert_eq!(report.field, "text");
+    let hint = report.hint.unwrap();
+    assert!(hint.contains("- status: passed") && hint.contains("+ status: failed"), "{}", hint);
+    let report = report_of(|| assert_text_eq_normalized!("checks:\n  - id: a", "checks:\n    - id: a"));
+    assert_eq!(report.field, "text");
+}
@@ -0,0 +1,60 @@
+//! The timing assertions, which fail a test that takes too long instead of letting it hang.
+
+mod common;
+
+use attestify_test_framework::{assert_completes_within, assert_duration_between, assert_eventually};
+use attestify_test_framework::panics::catch_panic;
+use common::report_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn assert_completes_within_returns_the_value_in_time() {
+    let checks = [1, 2, 3];
+    assert_eq!(assert_completes_within!(Duration::from_secs(5), checks.iter().sum::<i32>()), 6);
+
+    let start = Instant::now();
+    let report = report_of(|| {
+        assert_completes_within!(Duration::from_millis(50), thread::sleep(Duration::from_secs(5)))
+    });
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert_eq!(report.field, "duration");
+}
+
+#[test]
+fn assert_completes_within_keeps_the_panic_of_the_expression() {
+    let message = catch_panic(|| assert_completes_within!(Duration::from_secs(5), panic!("The gateway failed.")));
+    assert_eq!(message.as_deref(), Some("The gateway failed."));
+}
+
+#[test]
+fn assert_eventually_polls_until_the_condition_holds() {
+    let attempts = AtomicUsize::new(0);
+    assert_eventually!(Duration::from_secs(5), Duration::from_millis(1), attempts.fetch_add(1, Ordering::SeqCst) >= 3);
+}
+
+#[test]
+fn assert_eventually_reports_the_last_failure() {
+    let report = report_of(|| {
+        assert_eventually!(Duration::from_millis(30), Duration::from_millis(5), Err::<(), _>("The upload is pending."))
+    });
+    assert!(report.summary.starts_with("The condition did not hold within 30ms"), "{}", report.summary);
+    let hint = report.hint.unwrap();
+    assert!(hint.ends_with("Last failure:\tThe condition returned an error: \"The upload is pending.\""), "{}", hint);
+}
+
+#[test]
+fn assert_duration_between_accepts_the_bounds() {
+    assert_duration_between!(Duration::from_millis(150), Duration::from_millis(100), Duration::from_millis(250));
+    assert_duration_between!(Duration::from_millis(100), Duration::from_millis(100), Duration::from_millis(100));
+}
+
+#[test]
+fn assert_duration_between_reports_the_range() {
+    let report = report_of(|| {
+        assert_duration_between!(Duration::from_millis(412), Duration::from_millis(100), Duration::from_millis(250))
+    });
+    assert_eq!(report.expected.as_deref(), Some("100ms–250ms"));
+    assert_eq!(report.actual.as_deref(), Some("412ms"));
+}
@@ -0,0 +1,61 @@
+//! Records the interactions with an upstream into a cassette and replays them without the upstream.
+
+mod common;
+
+use attestify_test_framework::http_stub::{HttpStub, StubResponse};
+use attestify_test_framework::vcr::{Cassette, VcrServer};
+use attestify_test_framework::workspace::TestWorkspace;
+use common::report_of;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::fs;
+use std::path::PathBuf;
+
+fn get(url: &str, path: &str) -> String {
+    let mut stream = TcpStream::connect(url.trim_start_matches("http://")).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n", path).unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+/// Records a cassette with the evidence of two procedures in the workspace.
+fn record(workspace: &TestWorkspace) -> PathBuf {
+    let upstream = HttpStub::start();
+    upstream.route("GET", "/evidence/p1", StubResponse::new(200).body("status: passed"));
+    upstream.route("GET", "/evidence/p2", StubResponse::new(200).body("status: failed"));
+    let path = workspace.path_for("cassettes/evidence.yaml");
+    let vcr = VcrServer::record(&path, &upstream.url());
+    get(&vcr.url(), "/evidence/p1");
+    get(&vcr.url(), "/evidence/p2");
+    path
+}
+
+#[test]
+fn replays_the_recorded_interactions() {
+    let workspace = TestWorkspace::new();
+    let path = record(&workspace);
+    assert_eq!(Cassette::load(&path).interactions.len(), 2);
+    assert!(!fs::read_to_string(&path).unwrap().contains("secret"));
+
+    let vcr = VcrServer::replay(&path);
+    assert!(get(&vcr.url(), "/evidence/p1").ends_with("status: passed"));
+    assert!(get(&vcr.url(), "/evidence/p2").ends_with("status: failed"));
+    vcr.assert_all_played();
+}
+
+#[test]
+fn reports_the_unplayed_interactions_and_unexpected_requests() {
+    let workspace = TestWorkspace::new();
+    let path = record(&workspace);
+
+    let vcr = VcrServer::replay(&path);
+    get(&vcr.url(), "/evidence/p1");
+    let report = report_of(|| vcr.assert_all_played());
+    assert!(report.hint.unwrap().contains("Unplayed interactions:\n\t\tGET /evidence/p2"));
+
+    let vcr = VcrServer::replay(&path);
+    assert!(get(&vcr.url(), "/evidence/p3").starts_with("HTTP/1.1 500"));
+    let report = report_of(move || drop(vcr));
+    assert!(report.hint.unwrap().contains("Unexpected requests:\n\t\tGET /evidence/p3"));
+}